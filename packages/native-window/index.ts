@@ -5,17 +5,113 @@
 // This file provides the TypeScript entry point.
 
 import {
-  init,
-  pumpEvents,
+  appendFileSync,
+  closeSync,
+  fdatasyncSync,
+  ftruncateSync,
+  openSync,
+  readFileSync,
+  renameSync,
+  writeFileSync,
+  writeSync,
+} from "node:fs";
+import { writeFile } from "node:fs/promises";
+import { extname } from "node:path";
+import {
+  init as _nativeInit,
+  pumpEvents as _pumpEvents,
+  flush as _nativeFlush,
   NativeWindow as _NativeWindow,
-  checkRuntime,
-  ensureRuntime,
-  loadHtmlOrigin,
+  checkRuntime as _checkRuntime,
+  ensureRuntime as _ensureRuntime,
+  repairRuntimeProfile as _repairRuntimeProfile,
+  onProfileFallback as _onProfileFallback,
+  loadHtmlOrigin as _loadHtmlOrigin,
+  getAccessibilityPreferences as _getAccessibilityPreferences,
+  onAccessibilityPreferencesChanged as _onAccessibilityPreferencesChanged,
+  getAccentColor as _getAccentColor,
+  onAccentColorChanged as _onAccentColorChanged,
+  getSystemIdleTime as _getSystemIdleTime,
+  onIdleStateChanged as _onIdleStateChanged,
+  getDefaultAudioDevice as _getDefaultAudioDevice,
+  onAudioDevicesChanged as _onAudioDevicesChanged,
+  getVirtualDesktops as _getVirtualDesktops,
+  setAsDefaultProtocolClient as _setAsDefaultProtocolClient,
+  removeAsDefaultProtocolClient as _removeAsDefaultProtocolClient,
+  isDefaultProtocolClient as _isDefaultProtocolClient,
+  showNativeAboutPanel as _showNativeAboutPanel,
+  downloadUpdate as _downloadUpdate,
+  relaunch as _relaunch,
+  getCapabilities as _getCapabilities,
+  getDiagnostics as _getDiagnostics,
+  unsupportedWindowOptions,
+  applyLayout as _applyLayout,
 } from "./native-window.js";
 
-export { checkRuntime, ensureRuntime, loadHtmlOrigin };
+// Module-level native functions. Calls are logged by `init({ record })`
+// and answered from the recording during `replay()`.
+const _init = recordedModuleCall("init", _nativeInit);
+const pumpEvents = recordedModuleCall("pumpEvents", _pumpEvents);
+const _flush = recordedModuleCall("flush", _nativeFlush);
+const showNativeAboutPanel = recordedModuleCall("showNativeAboutPanel", _showNativeAboutPanel);
+export const checkRuntime = recordedModuleCall("checkRuntime", _checkRuntime);
+export const ensureRuntime = recordedModuleCall("ensureRuntime", _ensureRuntime);
+export const repairRuntimeProfile = recordedModuleCall("repairRuntimeProfile", _repairRuntimeProfile);
+export const onProfileFallback = recordedModuleCall("onProfileFallback", _onProfileFallback);
+export const loadHtmlOrigin = recordedModuleCall("loadHtmlOrigin", _loadHtmlOrigin);
+export const getAccessibilityPreferences = recordedModuleCall("getAccessibilityPreferences", _getAccessibilityPreferences);
+export const onAccessibilityPreferencesChanged = recordedModuleCall("onAccessibilityPreferencesChanged", _onAccessibilityPreferencesChanged);
+export const getAccentColor = recordedModuleCall("getAccentColor", _getAccentColor);
+export const onAccentColorChanged = recordedModuleCall("onAccentColorChanged", _onAccentColorChanged);
+export const getSystemIdleTime = recordedModuleCall("getSystemIdleTime", _getSystemIdleTime);
+export const onIdleStateChanged = recordedModuleCall("onIdleStateChanged", _onIdleStateChanged);
+export const getDefaultAudioDevice = recordedModuleCall("getDefaultAudioDevice", _getDefaultAudioDevice);
+export const onAudioDevicesChanged = recordedModuleCall("onAudioDevicesChanged", _onAudioDevicesChanged);
+export const getVirtualDesktops = recordedModuleCall("getVirtualDesktops", _getVirtualDesktops);
+export const setAsDefaultProtocolClient = recordedModuleCall("setAsDefaultProtocolClient", _setAsDefaultProtocolClient);
+export const removeAsDefaultProtocolClient = recordedModuleCall("removeAsDefaultProtocolClient", _removeAsDefaultProtocolClient);
+export const isDefaultProtocolClient = recordedModuleCall("isDefaultProtocolClient", _isDefaultProtocolClient);
+export const getCapabilities = recordedModuleCall("getCapabilities", _getCapabilities);
+export const getDiagnostics = recordedModuleCall("getDiagnostics", _getDiagnostics);
+export const applyLayout = recordedModuleCall("applyLayout", _applyLayout);
+export const relaunch = recordedModuleCall("relaunch", _relaunch);
 
-export type { WindowOptions, RuntimeInfo } from "./native-window.js";
+export type {
+  RuntimeInfo,
+  ProfileFallback,
+  InputEvent,
+  LoadHtmlOptions,
+  WindowMenuItem,
+  NetworkConditions,
+  FontOverrides,
+  AccessibilityPreferences,
+  AudioDevice,
+  VirtualDesktop,
+  SnapPosition,
+  ProgressBarState,
+  FullscreenMode,
+  TitleBarStyle,
+  CornerStyle,
+  VibrancyMaterial,
+  WindowTheme,
+  Point,
+  Rect,
+  WindowState,
+  PickedColor,
+  ShareOptions,
+  IframePolicy,
+  PopupAnchor,
+  AboutPanelOptions,
+  Capabilities,
+  Diagnostics,
+  DiagnosticsDisplay,
+  MaskableEvent,
+  WindowLayout,
+  CommandRecord,
+  UpdateDownloadOptions,
+  UpdateProgress,
+  RelaunchOptions,
+} from "./native-window.js";
 
 // ---------------------------------------------------------------------------
 // Auto-init / auto-pump state
@@ -24,16 +120,23 @@ export type { WindowOptions, RuntimeInfo } from "./native-window.js";
 let _pump: ReturnType<typeof setInterval> | null = null;
 let _windowCount = 0;
 
+let _strictOptions = false;
+
+/** Pump interval, and the frame budget idle callbacks run within. */
+const FRAME_MS = 16;
+
 function ensureInit() {
   if (_pump) return;
-  init();
+  _init();
   _pump = setInterval(() => {
+    const frameStart = performance.now();
     try {
       pumpEvents();
     } catch (e) {
       console.error("[native-window] pumpEvents() error:", e);
     }
-  }, 16);
+    runIdleCallbacks(frameStart + FRAME_MS);
+  }, FRAME_MS);
 }
 
 function stopPump() {
@@ -43,6 +146,748 @@ function stopPump() {
   }
 }
 
+// ---------------------------------------------------------------------------
+// Idle callbacks
+// ---------------------------------------------------------------------------
+
+/** Passed to {@link onIdle} callbacks. */
+export interface IdleDeadline {
+  /** Milliseconds left of the callback's budget; `0` once it is used up. */
+  timeRemaining(): number;
+}
+
+interface IdleCallback {
+  callback: (deadline: IdleDeadline) => void;
+  budgetMs: number;
+}
+
+const _idleCallbacks = new Set<IdleCallback>();
+
+/** Run the idle callbacks whose budget fits before `frameEnd`. */
+function runIdleCallbacks(frameEnd: number) {
+  for (const entry of _idleCallbacks) {
+    const start = performance.now();
+    if (frameEnd - start < entry.budgetMs) continue;
+    const end = start + entry.budgetMs;
+    try {
+      entry.callback({ timeRemaining: () => Math.max(0, end - performance.now()) });
+    } catch (e) {
+      console.error("[native-window] onIdle() callback error:", e);
+    }
+  }
+}
+
+/**
+ * Run `callback` after each event pump that finishes with at least
+ * `budgetMs` of its ~16ms frame left, for low-priority work such as
+ * persisting state or collecting metrics. The callback should check
+ * `deadline.timeRemaining()` and yield when it reaches `0`.
+ *
+ * Idle callbacks only run while windows are open (the pump is stopped
+ * otherwise). Returns a function that unregisters the callback.
+ *
+ * @example
+ * ```ts
+ * const stop = onIdle((deadline) => {
+ *   while (deadline.timeRemaining() > 0 && queue.length > 0) {
+ *     persist(queue.shift());
+ *   }
+ * }, 4);
+ * ```
+ */
+export function onIdle(callback: (deadline: IdleDeadline) => void, budgetMs: number): () => void {
+  if (!Number.isFinite(budgetMs) || budgetMs <= 0 || budgetMs > FRAME_MS) {
+    throw new RangeError(`onIdle() budgetMs must be greater than 0 and at most ${FRAME_MS}`);
+  }
+  const entry: IdleCallback = { callback, budgetMs };
+  _idleCallbacks.add(entry);
+  return () => {
+    _idleCallbacks.delete(entry);
+  };
+}
+
+/**
+ * Execute the commands queued by every window now, instead of on the next
+ * event pump. See {@link NativeWindow.flush} to flush a single window.
+ */
+export function flush(): void {
+  if (!_pump) return;
+  _flush();
+}
+
+/**
+ * Configure the native window system. Optional — it initializes itself
+ * when the first window is created — but must run before that window to
+ * affect it. Calling it again replaces the options.
+ *
+ * @example
+ * ```ts
+ * import { init, NativeWindow } from "@fcannizzaro/native-window";
+ *
+ * init({ strictOptions: true });
+ * new NativeWindow({ icon: "./icon.png" }); // throws UnsupportedOptionsError on macOS
+ * ```
+ */
+export function init(options: InitOptions = {}): void {
+  const { record: recordPath, commandJournal, ...nativeOptions } = options;
+  // Started first so the recording includes this `init()`.
+  if (recordPath !== undefined) {
+    startRecording(recordPath);
+  }
+  _init(nativeOptions);
+  _strictOptions = options.strictOptions ?? false;
+  if (commandJournal !== undefined) {
+    openJournal(commandJournal);
+  }
+}
+
+/** Options for {@link init}. */
+export interface InitOptions extends NativeInitOptions {
+  /**
+   * Log every window command and OS event to this file (JSON lines, one
+   * entry per line) for {@link replay}. The file is truncated first.
+   * Recordings include window options, messages and page URLs — ask
+   * before collecting them from users.
+   */
+  record?: string;
+  /**
+   * Journal the layout and navigation commands of windows created with a
+   * {@link WindowOptions.journalKey} to this file, and restore them after a
+   * crash. When the previous run ended without closing its windows — a
+   * hard crash or power loss — each new window re-applies the journaled
+   * commands of the window with the same key. See {@link openJournal}.
+   */
+  commandJournal?: string;
+}
+
+/** Options for the {@link NativeWindow} constructor. */
+export interface WindowOptions extends NativeWindowOptions {
+  /**
+   * Name this window in the {@link InitOptions.commandJournal}. After a
+   * crash, a window created with the same key takes over the journaled
+   * layout and page; windows without a key are not journaled. Keys should
+   * be unique among open windows.
+   */
+  journalKey?: string;
+}
+
+/**
+ * Thrown by the {@link NativeWindow} constructor in strict mode
+ * (`init({ strictOptions: true })`) when options that the current
+ * platform would ignore are set. Use {@link getCapabilities} to check
+ * support up front.
+ */
+export class UnsupportedOptionsError extends Error {
+  /** The unsupported `WindowOptions` fields, e.g. `["icon"]`. */
+  readonly options: string[];
+
+  constructor(options: string[]) {
+    super(`Unsupported window options on this platform: ${options.join(", ")}`);
+    this.name = "UnsupportedOptionsError";
+    this.options = options;
+  }
+}
+
+// ---------------------------------------------------------------------------
+// Record / replay
+// ---------------------------------------------------------------------------
+
+/**
+ * One line of a recording. `t` is milliseconds since the recording started;
+ * `window` is the window's `id`, or {@link MODULE_WINDOW} for module-level
+ * functions such as `applyLayout()`. `call` entries are commands sent to
+ * the native layer, `event` entries are callbacks it fired.
+ */
+type RecordingEntry =
+  | { t: number; type: "create"; window: number; options?: WindowOptions }
+  | { t: number; type: "call"; window: number; method: string; args: unknown[]; result?: unknown }
+  | { t: number; type: "event"; window: number; method: string; args: unknown[] };
+
+/** A recording entry before its timestamp is added. */
+type RecordingLine = RecordingEntry extends infer E
+  ? E extends RecordingEntry
+    ? Omit<E, "t">
+    : never
+  : never;
+
+type NativeHandle = InstanceType<typeof _NativeWindow>;
+type ReplayCallback = (...args: unknown[]) => unknown;
+
+/** The `window` of recorded module-level calls; window IDs start at 1. */
+const MODULE_WINDOW = 0;
+
+/** How often buffered recording lines are written out. */
+const RECORD_FLUSH_MS = 250;
+/** Buffered lines that trigger an early write. */
+const RECORD_FLUSH_LINES = 1000;
+
+let _recordPath: string | null = null;
+let _recordStart = 0;
+let _recordBuffer: string[] = [];
+let _recordTimer: ReturnType<typeof setInterval> | null = null;
+
+/** Truncate `path` and start recording to it. */
+function startRecording(path: string) {
+  flushRecording();
+  writeFileSync(path, "");
+  _recordPath = path;
+  _recordStart = performance.now();
+  if (!_recordTimer) {
+    _recordTimer = setInterval(flushRecording, RECORD_FLUSH_MS);
+    _recordTimer.unref?.();
+    // The buffer is written out synchronously on exit, crashes included.
+    process.once("exit", flushRecording);
+  }
+}
+
+function record(entry: RecordingLine) {
+  if (!_recordPath) return;
+  const line = { t: Math.round(performance.now() - _recordStart), ...entry };
+  _recordBuffer.push(JSON.stringify(line, encodeRecording) + "\n");
+  if (_recordBuffer.length >= RECORD_FLUSH_LINES) flushRecording();
+}
+
+function flushRecording() {
+  if (!_recordPath || _recordBuffer.length === 0) return;
+  const lines = _recordBuffer.join("");
+  _recordBuffer = [];
+  try {
+    appendFileSync(_recordPath, lines);
+  } catch (e) {
+    console.error("[native-window] Failed to write recording:", e);
+    _recordPath = null;
+  }
+}
+
+/** Wrap callbacks passed to the native layer so each call is recorded. */
+function recordCallbacks(window: number, method: string, args: unknown[]): unknown[] {
+  return args.map((arg) =>
+    typeof arg === "function"
+      ? (...eventArgs: unknown[]) => {
+          record({ type: "event", window, method, args: eventArgs });
+          return arg(...eventArgs);
+        }
+      : arg,
+  );
+}
+
+/**
+ * Record calls to a module-level native function and the callbacks it
+ * fires. During {@link replay} the native function is not called; calls
+ * are answered with their recorded results instead.
+ */
+function recordedModuleCall<F extends (...args: never[]) => unknown>(method: string, fn: F): F {
+  return ((...args: unknown[]) => {
+    if (_replay) {
+      const module = _replay.windows.get(MODULE_WINDOW)!;
+      for (const arg of args) {
+        if (typeof arg === "function") module.callbacks.set(method, arg as ReplayCallback);
+      }
+      return _replay.results.get(`${MODULE_WINDOW}:${method}`)?.shift();
+    }
+    if (!_recordPath) return (fn as (...args: unknown[]) => unknown)(...args);
+    const result = (fn as (...args: unknown[]) => unknown)(
+      ...recordCallbacks(MODULE_WINDOW, method, args),
+    );
+    // Callbacks serialize as null; they are re-registered on replay.
+    record({ type: "call", window: MODULE_WINDOW, method, args, result });
+    return result;
+  }) as F;
+}
+
+/** Log every call on a native window, and every callback it fires. */
+function recordNative(native: NativeHandle, options?: WindowOptions): NativeHandle {
+  const window = native.id;
+  record({ type: "create", window, options });
+  return new Proxy(native, {
+    get(target, prop) {
+      const value = Reflect.get(target, prop, target);
+      if (typeof prop !== "string" || typeof value !== "function") return value;
+      return (...args: unknown[]) => {
+        const result = value.apply(target, recordCallbacks(window, prop, args));
+        // Callbacks serialize as null; they are re-registered on replay.
+        record({ type: "call", window, method: prop, args, result });
+        return result;
+      };
+    },
+  });
+}
+
+/** Write binary payloads as `{ type: "Buffer", base64 }`. */
+function encodeRecording(this: unknown, key: string, value: unknown): unknown {
+  // `value` has already been through `Buffer#toJSON`; the holder has the original.
+  const raw = (this as Record<string, unknown>)[key];
+  if (raw instanceof Uint8Array) {
+    return {
+      type: "Buffer",
+      base64: Buffer.from(raw.buffer, raw.byteOffset, raw.byteLength).toString("base64"),
+    };
+  }
+  return value;
+}
+
+/** Revive `Buffer`s written by {@link encodeRecording}, or as `{ type, data }`. */
+function reviveRecording(_key: string, value: unknown): unknown {
+  if (!value || typeof value !== "object" || (value as { type?: unknown }).type !== "Buffer") {
+    return value;
+  }
+  const { base64, data } = value as { base64?: unknown; data?: unknown };
+  if (typeof base64 === "string") return Buffer.from(base64, "base64");
+  if (Array.isArray(data)) return Buffer.from(data as number[]);
+  return value;
+}
+
+interface ReplaySession {
+  /** Recorded windows not yet claimed by a `new NativeWindow()`. */
+  creates: Extract<RecordingEntry, { type: "create" }>[];
+  /** Recorded events not yet fired, in recorded order. */
+  events: Extract<RecordingEntry, { type: "event" }>[];
+  /** Recorded sync call results per window and method, in call order. */
+  results: Map<string, unknown[]>;
+  /** Replayed windows by recorded ID. */
+  windows: Map<
+    number,
+    { createdAt: number; recordedAt: number; callbacks: Map<string, ReplayCallback> }
+  >;
+  nextId: number;
+}
+
+let _replay: ReplaySession | null = null;
+
+/**
+ * A stand-in for a native window during replay: calls are accepted and
+ * answered with their recorded results, and callbacks are kept for the
+ * replayed events. No native window is created.
+ */
+function replayNative(session: ReplaySession): NativeHandle {
+  const created = session.creates.shift();
+  const id = created?.window ?? session.nextId++;
+  if (!created) {
+    console.warn(`[native-window] replay: window ${id} was not in the recording`);
+  }
+  const callbacks = new Map<string, ReplayCallback>();
+  session.windows.set(id, {
+    createdAt: performance.now(),
+    recordedAt: created?.t ?? 0,
+    callbacks,
+  });
+  return new Proxy({} as NativeHandle, {
+    get(_target, prop) {
+      if (prop === "id") return id;
+      if (typeof prop !== "string") return undefined;
+      return (...args: unknown[]) => {
+        for (const arg of args) {
+          if (typeof arg === "function") callbacks.set(prop, arg as ReplayCallback);
+        }
+        return session.results.get(`${id}:${prop}`)?.shift();
+      };
+    },
+  });
+}
+
+/** Fire the recorded events that are due, in recorded order. */
+function replayEvents(session: ReplaySession): boolean {
+  const now = performance.now();
+  while (session.events.length > 0) {
+    const event = session.events[0]!;
+    const window = session.windows.get(event.window);
+    // Events keep their timing relative to their window's creation.
+    if (!window || now - window.createdAt < event.t - window.recordedAt) break;
+    session.events.shift();
+    try {
+      window.callbacks.get(event.method)?.(...event.args);
+    } catch (e) {
+      console.error(`[native-window] replay: ${event.method} callback error:`, e);
+    }
+  }
+  return session.events.length === 0;
+}
+
+/**
+ * Play back a recording made with `init({ record })` at the JS level, to
+ * reproduce how the app reacts to a user's session without their machine.
+ * Native windows are replaced by objects that answer from the recording;
+ * no Rust code runs, so bugs in the native layer itself do not reproduce.
+ *
+ * Call it before creating any window, then run the app's window code as
+ * usual: each `new NativeWindow()` takes the place of the next recorded
+ * window (and its `id`), and the recorded OS events — resizes, focus
+ * changes, messages, closes, query results — are fired at their recorded
+ * times. No native windows are created; commands are accepted and
+ * answered with their recorded results, and so are calls to module-level
+ * functions such as `applyLayout()`.
+ *
+ * Resolves once every recorded event has been fired. Rejects if the
+ * recording cannot be read or a window already exists.
+ *
+ * @example
+ * ```ts
+ * import { replay } from "@fcannizzaro/native-window";
+ *
+ * const done = replay("./bug-1234.jsonl");
+ * startApp(); // creates windows as in the recording
+ * await done;
+ * ```
+ */
+export function replay(path: string): Promise<void> {
+  if (_pump || _replay) {
+    return Promise.reject(new Error("replay() must be called before any window is created"));
+  }
+  let entries: RecordingEntry[];
+  try {
+    entries = readFileSync(path, "utf8")
+      .split("\n")
+      .filter((line) => line.trim() !== "")
+      .map((line) => JSON.parse(line, reviveRecording) as RecordingEntry);
+  } catch (e) {
+    return Promise.reject(e);
+  }
+
+  const session: ReplaySession = {
+    creates: [],
+    events: [],
+    results: new Map(),
+    windows: new Map(),
+    nextId: 1,
+  };
+  // Module-level events keep their timing relative to the replay start.
+  session.windows.set(MODULE_WINDOW, {
+    createdAt: performance.now(),
+    recordedAt: 0,
+    callbacks: new Map(),
+  });
+  for (const entry of entries) {
+    session.nextId = Math.max(session.nextId, entry.window + 1);
+    if (entry.type === "create") {
+      session.creates.push(entry);
+    } else if (entry.type === "event") {
+      session.events.push(entry);
+    } else if (entry.result !== undefined) {
+      const key = `${entry.window}:${entry.method}`;
+      const results = session.results.get(key) ?? [];
+      results.push(entry.result);
+      session.results.set(key, results);
+    }
+  }
+  _replay = session;
+
+  return new Promise((resolve) => {
+    const timer = setInterval(() => {
+      if (replayEvents(session)) {
+        clearInterval(timer);
+        _replay = null;
+        resolve();
+      }
+    }, FRAME_MS);
+  });
+}
+
+// ---------------------------------------------------------------------------
+// Command journal
+// ---------------------------------------------------------------------------
+
+/**
+ * Journaled commands, by the slot they overwrite. A command replaces the
+ * previous one in its slot, so the journal keeps each window's latest
+ * layout and navigation state instead of its whole history.
+ */
+const JOURNAL_SLOTS: Record<string, string> = {
+  loadUrl: "page",
+  loadHtml: "page",
+  setTitle: "title",
+  setTitleTemplate: "titleTemplate",
+  setSize: "size",
+  setMinSize: "minSize",
+  setMaxSize: "maxSize",
+  setAspectRatio: "aspectRatio",
+  setPosition: "position",
+  center: "position",
+  setResizable: "resizable",
+  setDecorations: "decorations",
+  setAlwaysOnTop: "alwaysOnTop",
+  setAlwaysOnBottom: "alwaysOnBottom",
+  setVisibleOnAllWorkspaces: "allWorkspaces",
+  setFullscreen: "fullscreen",
+  maximize: "state",
+  minimize: "state",
+  unmaximize: "state",
+  show: "visibility",
+  hide: "visibility",
+  setTextZoom: "textZoom",
+};
+
+/** Journal lines written before the file is compacted. */
+const MAX_JOURNAL_LINES = 1000;
+
+/** How often buffered journal lines are written and synced to disk. */
+const JOURNAL_FLUSH_MS = 250;
+
+/** One line of a command journal. `window` is the window's `id`. */
+type JournalEntry =
+  | { type: "open"; window: number; key: string }
+  | { type: "call"; window: number; method: string; args: unknown[] }
+  | { type: "close"; window: number }
+  | { type: "claim"; window: number; from: number };
+
+interface JournalCall {
+  method: string;
+  args: unknown[];
+}
+
+/** A journaled window: its `journalKey` and latest command per slot. */
+interface JournalWindow {
+  key: string;
+  /** Latest command per slot, in the order they were sent. */
+  slots: Map<string, JournalCall>;
+}
+
+interface Journal {
+  path: string;
+  fd: number;
+  /** Lines counted towards compaction since the file was last compacted. */
+  lines: number;
+  /** Lines not written to the file yet. */
+  buffer: string[];
+  timer: ReturnType<typeof setInterval>;
+  /**
+   * Open journaled windows. The previous run's windows are kept under
+   * negative IDs until a new window claims them.
+   */
+  windows: Map<number, JournalWindow>;
+  /** IDs of the previous run's windows not yet claimed, by `journalKey`. */
+  restores: Map<string, number>;
+}
+
+let _journal: Journal | null = null;
+let _journalExitHook = false;
+
+/** Rebuild the open windows' state from a journal file's lines. */
+function readJournal(path: string): Map<number, JournalWindow> {
+  const windows = new Map<number, JournalWindow>();
+  let text: string;
+  try {
+    text = readFileSync(path, "utf8");
+  } catch {
+    return windows; // First run
+  }
+  for (const line of text.split("\n")) {
+    let entry: JournalEntry;
+    try {
+      entry = JSON.parse(line, reviveRecording) as JournalEntry;
+    } catch {
+      continue; // Empty, or cut short by a power loss
+    }
+    if (entry.type === "open") {
+      windows.set(entry.window, { key: entry.key, slots: new Map() });
+    } else if (entry.type === "close") {
+      windows.delete(entry.window);
+    } else if (entry.type === "claim") {
+      renameJournalWindow(windows, entry.from, entry.window);
+    } else {
+      const window = windows.get(entry.window);
+      if (window) applyJournalCall(window, entry);
+    }
+  }
+  return windows;
+}
+
+/** Move a window's commands to a new ID. */
+function renameJournalWindow(windows: Map<number, JournalWindow>, from: number, to: number) {
+  const window = windows.get(from);
+  if (!window) return;
+  windows.delete(from);
+  windows.set(to, window);
+}
+
+function applyJournalCall(window: JournalWindow, call: JournalCall) {
+  const slot = JOURNAL_SLOTS[call.method] ?? call.method;
+  // Re-inserted so the slot moves to the end, keeping commands in order.
+  window.slots.delete(slot);
+  window.slots.set(slot, { method: call.method, args: call.args });
+}
+
+/**
+ * Open a command journal for {@link InitOptions.commandJournal}, picking up
+ * the windows the previous run left open. They stay in the journal until
+ * claimed, so a second crash before then does not lose them. Exiting with
+ * code 0 clears the journal; a closed window is removed from it.
+ */
+function openJournal(path: string) {
+  closeJournal(false);
+  try {
+    const windows = new Map<number, JournalWindow>();
+    const restores = new Map<string, number>();
+    let nextId = -1;
+    for (const window of readJournal(path).values()) {
+      // With duplicate keys, the window opened last wins.
+      const previous = restores.get(window.key);
+      if (previous !== undefined) windows.delete(previous);
+      const id = nextId--;
+      windows.set(id, window);
+      restores.set(window.key, id);
+    }
+    const timer = setInterval(() => {
+      if (_journal) flushJournal(_journal);
+    }, JOURNAL_FLUSH_MS);
+    timer.unref?.();
+    const journal: Journal = {
+      path,
+      fd: openSync(path, "a"),
+      lines: 0,
+      buffer: [],
+      timer,
+      windows,
+      restores,
+    };
+    _journal = journal;
+    // Renumbers the previous run's windows before this run adds its own.
+    compactJournal(journal);
+  } catch (e) {
+    console.error("[native-window] Failed to open command journal:", e);
+    closeJournal(false);
+    return;
+  }
+  if (!_journalExitHook) {
+    _journalExitHook = true;
+    // Uncaught exceptions and `process.exit(1)` keep the journal.
+    process.once("exit", (code) => closeJournal(code === 0));
+  }
+}
+
+function closeJournal(clear: boolean) {
+  const journal = _journal;
+  if (!journal) return;
+  clearInterval(journal.timer);
+  if (clear) {
+    journal.buffer = [];
+  } else {
+    flushJournal(journal);
+  }
+  _journal = null;
+  try {
+    if (clear) ftruncateSync(journal.fd, 0);
+    closeSync(journal.fd);
+  } catch {}
+}
+
+/** Buffer a journal line; {@link flushJournal} writes it out. */
+function writeJournal(journal: Journal, entry: JournalEntry) {
+  journal.buffer.push(JSON.stringify(entry) + "\n");
+  journal.lines++;
+}
+
+/**
+ * Write the buffered lines with a single sync, so a window animated every
+ * frame costs one disk flush per interval instead of one per command.
+ * Compacting rewrites the file from memory, buffered changes included.
+ */
+function flushJournal(journal: Journal) {
+  if (journal.buffer.length === 0) return;
+  try {
+    if (journal.lines >= MAX_JOURNAL_LINES) {
+      compactJournal(journal);
+      return;
+    }
+    const lines = journal.buffer.join("");
+    journal.buffer = [];
+    writeSync(journal.fd, lines);
+    // Survive power loss, not just a crashed process.
+    fdatasyncSync(journal.fd);
+  } catch (e) {
+    console.error("[native-window] Failed to write command journal:", e);
+    if (_journal === journal) {
+      journal.buffer = [];
+      closeJournal(false);
+    }
+  }
+}
+
+/**
+ * Rewrite the journal with only the open windows' latest commands,
+ * including the previous run's windows not yet claimed.
+ */
+function compactJournal(journal: Journal) {
+  const lines: string[] = [];
+  for (const [window, { key, slots }] of journal.windows) {
+    lines.push(JSON.stringify({ type: "open", window, key }) + "\n");
+    for (const call of slots.values()) {
+      lines.push(JSON.stringify({ type: "call", window, ...call }) + "\n");
+    }
+  }
+  // Written aside and renamed over the journal, so a crash mid-way
+  // leaves either the old file or the new one.
+  const tmp = `${journal.path}.tmp`;
+  const tmpFd = openSync(tmp, "w");
+  try {
+    writeSync(tmpFd, lines.join(""));
+    fdatasyncSync(tmpFd);
+  } finally {
+    closeSync(tmpFd);
+  }
+  closeSync(journal.fd);
+  renameSync(tmp, journal.path);
+  journal.fd = openSync(journal.path, "a");
+  journal.lines = lines.length;
+  journal.buffer = [];
+}
+
+/**
+ * Journal a native window's layout and navigation commands under `key`. If
+ * the previous run left a window with the same key open, its commands are
+ * re-applied once the code that created this window finishes, so they win
+ * over the app's initial setup.
+ */
+function journalNative(journal: Journal, native: NativeHandle, key: string): NativeHandle {
+  const id = native.id;
+  const from = journal.restores.get(key);
+  const previous = from === undefined ? undefined : journal.windows.get(from);
+  const restore = previous ? [...previous.slots.values()] : undefined;
+  if (from !== undefined) {
+    journal.restores.delete(key);
+    renameJournalWindow(journal.windows, from, id);
+    writeJournal(journal, { type: "claim", window: id, from });
+  } else {
+    journal.windows.set(id, { key, slots: new Map() });
+    writeJournal(journal, { type: "open", window: id, key });
+  }
+  const proxy = new Proxy(native, {
+    get(target, prop) {
+      const value = Reflect.get(target, prop, target);
+      if (typeof prop !== "string" || typeof value !== "function") return value;
+      if (!(prop in JOURNAL_SLOTS)) return value.bind(target);
+      return (...args: unknown[]) => {
+        const result = value.apply(target, args);
+        const window = journal.windows.get(id);
+        if (_journal === journal && window) {
+          applyJournalCall(window, { method: prop, args });
+          writeJournal(journal, { type: "call", window: id, method: prop, args });
+        }
+        return result;
+      };
+    },
+  });
+  if (restore) {
+    queueMicrotask(() => {
+      for (const { method, args } of restore) {
+        try {
+          (Reflect.get(proxy, method) as (...args: unknown[]) => unknown)(...args);
+        } catch (e) {
+          console.error(`[native-window] Failed to restore ${method}() from the journal:`, e);
+        }
+      }
+    });
+  }
+  return proxy;
+}
+
+/** Remove a closed window from the journal, so it is not restored. */
+function journalWindowClosed(window: number) {
+  const journal = _journal;
+  if (!journal || !journal.windows.has(window)) return;
+  journal.windows.delete(window);
+  writeJournal(journal, { type: "close", window });
+}
+
 // ---------------------------------------------------------------------------
 // Unsafe namespace
 // ---------------------------------------------------------------------------
@@ -66,6 +911,15 @@ export interface UnsafeNamespace {
    * script code.
    */
   evaluateJs(script: string): void;
+  /**
+   * Like {@link evaluateJs}, but the script runs with a transient user
+   * activation, so it may call APIs that require a user gesture: clipboard
+   * writes, `requestFullscreen()`, `window.open()`.
+   *
+   * @security Same injection risk as {@link evaluateJs}. The activation also
+   * lets the script bypass gesture-gated browser protections.
+   */
+  evaluateJsWithUserGesture(script: string): void;
 }
 
 // ---------------------------------------------------------------------------
@@ -105,65 +959,604 @@ export interface CookieInfo {
   expires: number;
 }
 
-// ---------------------------------------------------------------------------
-// NativeWindow wrapper – auto-init, auto-pump, auto-stop
-// ---------------------------------------------------------------------------
-
-type WindowOptions = import("./native-window.js").WindowOptions;
+/** A single node in an {@link AccessibilitySnapshot}. */
+export interface AccessibilityNode {
+  /** Identifier unique within the snapshot. */
+  nodeId: string;
+  /** Whether assistive technology skips this node (e.g. `aria-hidden`). */
+  ignored: boolean;
+  /** Computed ARIA role, e.g. `{ value: "button" }`. */
+  role?: { value: string };
+  /** Computed accessible name, e.g. `{ value: "Submit" }`. */
+  name?: { value: string };
+  /** `nodeId`s of child nodes, in document order. */
+  childIds?: string[];
+}
 
 /**
- * A native OS window with an embedded webview.
+ * Accessibility tree of the current page, as returned by
+ * {@link NativeWindow.getAccessibilitySnapshot}.
  *
- * Automatically initializes the native subsystem and starts pumping
- * events on first construction. Stops the pump when all windows close.
+ * On Windows this is the engine's computed tree (CDP
+ * `Accessibility.getFullAXTree`). On macOS and Linux it is derived from the
+ * DOM using ARIA attributes and implicit HTML semantics, in the same shape.
  */
-export class NativeWindow {
-  /** @internal */
-  private _native: InstanceType<typeof _NativeWindow>;
-  /** @internal */
-  private _closed = false;
-  /** @internal */
-  private _unsafe?: UnsafeNamespace;
-
-  constructor(options?: WindowOptions) {
-    ensureInit();
-    _windowCount++;
-    this._native = new _NativeWindow(options);
+export interface AccessibilitySnapshot {
+  nodes: AccessibilityNode[];
+}
 
-    // Register a default close handler to track window count.
-    this._native.onClose(() => this._handleClose());
-  }
+/**
+ * Load performance of the current page, as returned by
+ * {@link NativeWindow.getPagePerformanceMetrics}. Timings are milliseconds
+ * since navigation start; values the engine does not report are `null`.
+ */
+export interface PagePerformanceMetrics {
+  /** Time until the first response byte arrived. */
+  timeToFirstByte: number | null;
+  firstPaint: number | null;
+  firstContentfulPaint: number | null;
+  /** Time until the document was parsed. */
+  domInteractive: number | null;
+  /** Time until `DOMContentLoaded` handlers finished. */
+  domContentLoaded: number | null;
+  /** Time until `load` handlers finished. */
+  load: number | null;
+  /** Bytes transferred for the document itself. */
+  transferSize: number | null;
+  /** Number of subresources loaded so far. */
+  resourceCount: number;
+  /** Number of elements in the document. */
+  domElements: number;
+  /** Bytes used by the JS heap. Chromium engines only. */
+  jsHeapUsed: number | null;
+  /** Bytes allocated for the JS heap. Chromium engines only. */
+  jsHeapTotal: number | null;
+  /** Number of full or partial layouts. Windows only. */
+  layoutCount: number | null;
+  /** Number of style recalculations. Windows only. */
+  recalcStyleCount: number | null;
+  /** Total time spent running scripts, in milliseconds. Windows only. */
+  scriptDuration: number | null;
+  /** Total time spent on main-thread tasks, in milliseconds. Windows only. */
+  taskDuration: number | null;
+}
 
-  /** @internal */
-  private _handleClose() {
-    if (this._closed) return;
-    this._closed = true;
-    _windowCount--;
-    if (_windowCount <= 0) {
-      _windowCount = 0;
-      stopPump();
-    }
-    this._userCloseCallback?.();
-  }
+/**
+ * Main article of the current page, as returned by
+ * {@link NativeWindow.extractReadableContent}.
+ */
+export interface ReadableContent {
+  /** Article title, falling back to the document title. */
+  title: string;
+  /** Article markup, without scripts, styles, forms or navigation. */
+  html: string;
+  /** Plain text of the article, paragraphs separated by blank lines. */
+  text: string;
+}
 
-  /**
-   * Throws if the window has been closed.
-   * @internal
-   */
-  private _ensureOpen(): void {
-    if (this._closed) {
-      throw new Error("Window is closed");
-    }
-  }
+/** Raw result delivered by the native `onPerformanceMetrics` callback. */
+interface PerformanceMetricsPayload {
+  /** Page timings, wrapped in a `Runtime.evaluate` result on Windows. */
+  timing: any;
+  /** Windows: `Performance.getMetrics` result. */
+  metrics: { metrics?: { name: string; value: number }[] } | null;
+}
 
-  // ---- onClose with user callback support ----
+/** Merge page timings and DevTools counters into {@link PagePerformanceMetrics}. */
+function buildPerformanceMetrics(
+  payload: PerformanceMetricsPayload,
+): PagePerformanceMetrics | null {
+  const timing = payload.timing?.result ? payload.timing.result.value : payload.timing;
+  if (!timing) return null;
+  const counters = new Map(
+    (payload.metrics?.metrics ?? []).map(({ name, value }) => [name, value] as const),
+  );
+  const counter = (name: string, scale = 1) => {
+    const value = counters.get(name);
+    return value === undefined ? null : value * scale;
+  };
+  return {
+    timeToFirstByte: timing.timeToFirstByte ?? null,
+    firstPaint: timing.firstPaint ?? null,
+    firstContentfulPaint: timing.firstContentfulPaint ?? null,
+    domInteractive: timing.domInteractive ?? null,
+    domContentLoaded: timing.domContentLoaded ?? null,
+    load: timing.load ?? null,
+    transferSize: timing.transferSize ?? null,
+    resourceCount: timing.resourceCount ?? 0,
+    domElements: timing.domElements ?? 0,
+    // DevTools counters are exact; performance.memory is quantized.
+    jsHeapUsed: counter("JSHeapUsedSize") ?? timing.jsHeapUsed ?? null,
+    jsHeapTotal: counter("JSHeapTotalSize") ?? timing.jsHeapTotal ?? null,
+    layoutCount: counter("LayoutCount"),
+    recalcStyleCount: counter("RecalcStyleCount"),
+    scriptDuration: counter("ScriptDuration", 1000),
+    taskDuration: counter("TaskDuration", 1000),
+  };
+}
+
+/** A name/value pair in a {@link HarEntry}. */
+export interface HarHeader {
+  name: string;
+  value: string;
+}
+
+/**
+ * One request/response pair in a {@link HarJson} log. Timings are in
+ * milliseconds; `-1` marks a phase that does not apply or was not reported.
+ */
+export interface HarEntry {
+  /** ISO 8601 time the request started. */
+  startedDateTime: string;
+  /** Total elapsed time of the request. */
+  time: number;
+  request: {
+    method: string;
+    url: string;
+    httpVersion: string;
+    headers: HarHeader[];
+    queryString: HarHeader[];
+    cookies: [];
+    headersSize: number;
+    bodySize: number;
+  };
+  response: {
+    status: number;
+    statusText: string;
+    httpVersion: string;
+    headers: HarHeader[];
+    cookies: [];
+    content: { size: number; mimeType: string };
+    redirectURL: string;
+    headersSize: number;
+    bodySize: number;
+    /** Network error message, when the request failed. */
+    _error?: string;
+  };
+  cache: {};
+  timings: {
+    blocked: number;
+    dns: number;
+    connect: number;
+    ssl: number;
+    send: number;
+    wait: number;
+    receive: number;
+  };
+}
+
+/**
+ * HTTP Archive (HAR 1.2) log, as returned by
+ * {@link NativeWindow.stopNetworkCapture}.
+ */
+export interface HarJson {
+  log: {
+    version: "1.2";
+    creator: { name: string; version: string };
+    pages: [];
+    entries: HarEntry[];
+  };
+}
+
+/** Raw recording delivered by the native `onNetworkCapture` callback. */
+interface NetworkCapturePayload {
+  startedMs: number;
+  /** Windows: DevTools `Network.*` events in arrival order. */
+  events?: { method: string; params: any }[];
+  /** macOS/Linux: Resource Timing entries. */
+  resources?: {
+    url: string;
+    startedMs: number;
+    duration: number;
+    domainLookupStart: number;
+    domainLookupEnd: number;
+    connectStart: number;
+    connectEnd: number;
+    secureConnectionStart: number;
+    requestStart: number;
+    responseStart: number;
+    responseEnd: number;
+    startTime: number;
+    status: number;
+    protocol: string;
+    transferSize: number;
+    bodySize: number;
+    mimeType: string;
+  }[];
+}
+
+/** Duration between two marks, or `-1` when either is missing. */
+function harSpan(start: number | undefined, end: number | undefined): number {
+  if (start === undefined || end === undefined || start < 0 || end < 0 || end < start) {
+    return -1;
+  }
+  return end - start;
+}
+
+function harHeaders(headers: Record<string, string> | undefined): HarHeader[] {
+  return Object.entries(headers ?? {}).map(([name, value]) => ({
+    name,
+    value: String(value),
+  }));
+}
+
+function harQueryString(url: string): HarHeader[] {
+  try {
+    return [...new URL(url).searchParams].map(([name, value]) => ({ name, value }));
+  } catch {
+    return [];
+  }
+}
+
+function harHttpVersion(protocol: string | undefined): string {
+  if (!protocol) return "";
+  if (protocol === "h2") return "HTTP/2";
+  if (protocol === "h3") return "HTTP/3";
+  return protocol.toUpperCase();
+}
+
+function harEntry(
+  startedMs: number,
+  method: string,
+  url: string,
+  requestHeaders: HarHeader[],
+  timings: HarEntry["timings"],
+): HarEntry {
+  const time = [
+    timings.blocked,
+    timings.dns,
+    timings.connect,
+    timings.send,
+    timings.wait,
+    timings.receive,
+  ].reduce((sum, t) => sum + Math.max(t, 0), 0);
+  return {
+    startedDateTime: new Date(startedMs).toISOString(),
+    time,
+    request: {
+      method,
+      url,
+      httpVersion: "",
+      headers: requestHeaders,
+      queryString: harQueryString(url),
+      cookies: [],
+      headersSize: -1,
+      bodySize: -1,
+    },
+    response: {
+      status: 0,
+      statusText: "",
+      httpVersion: "",
+      headers: [],
+      cookies: [],
+      content: { size: -1, mimeType: "" },
+      redirectURL: "",
+      headersSize: -1,
+      bodySize: -1,
+    },
+    cache: {},
+    timings,
+  };
+}
+
+/** Convert a native network recording into a HAR log. */
+function buildHar(payload: NetworkCapturePayload): HarJson {
+  const entries: HarEntry[] = [];
+
+  if (payload.events) {
+    // DevTools events: correlate by requestId. Redirects reuse the id, so
+    // each hop is completed and emitted when the next one starts.
+    type Pending = { entry: HarEntry; timestamp: number; timing?: any };
+    const pending = new Map<string, Pending>();
+    const noTimings = (): HarEntry["timings"] => ({
+      blocked: -1,
+      dns: -1,
+      connect: -1,
+      ssl: -1,
+      send: -1,
+      wait: -1,
+      receive: -1,
+    });
+    const applyResponse = (p: Pending, response: any) => {
+      const res = p.entry.response;
+      res.status = response.status ?? 0;
+      res.statusText = response.statusText ?? "";
+      res.httpVersion = harHttpVersion(response.protocol);
+      res.headers = harHeaders(response.headers);
+      res.content.mimeType = response.mimeType ?? "";
+      res.redirectURL = response.headers?.location ?? response.headers?.Location ?? "";
+      p.entry.request.httpVersion = res.httpVersion;
+      if (response.requestHeaders) {
+        p.entry.request.headers = harHeaders(response.requestHeaders);
+      }
+      p.timing = response.timing;
+    };
+    const finish = (p: Pending, timestamp: number, size?: number) => {
+      const t = p.timing;
+      const timings = noTimings();
+      if (t) {
+        // DevTools timing marks are milliseconds relative to requestTime (s).
+        timings.blocked = Math.max((t.requestTime - p.timestamp) * 1000, 0);
+        timings.dns = harSpan(t.dnsStart, t.dnsEnd);
+        timings.connect = harSpan(t.connectStart, t.connectEnd);
+        timings.ssl = harSpan(t.sslStart, t.sslEnd);
+        timings.send = harSpan(t.sendStart, t.sendEnd);
+        timings.wait = harSpan(t.sendEnd, t.receiveHeadersEnd);
+        timings.receive = Math.max((timestamp - t.requestTime) * 1000 - t.receiveHeadersEnd, 0);
+      } else {
+        timings.wait = Math.max((timestamp - p.timestamp) * 1000, 0);
+      }
+      const done = harEntry(
+        Date.parse(p.entry.startedDateTime),
+        p.entry.request.method,
+        p.entry.request.url,
+        p.entry.request.headers,
+        timings,
+      );
+      done.request.httpVersion = p.entry.request.httpVersion;
+      done.response = p.entry.response;
+      if (size !== undefined) {
+        done.response.bodySize = size;
+        done.response.content.size = size;
+      }
+      entries.push(done);
+    };
+
+    for (const { method, params } of payload.events) {
+      const id: string = params?.requestId;
+      if (method === "Network.requestWillBeSent") {
+        const previous = pending.get(id);
+        if (previous && params.redirectResponse) {
+          applyResponse(previous, params.redirectResponse);
+          finish(previous, params.timestamp);
+        }
+        const request = params.request ?? {};
+        pending.set(id, {
+          entry: harEntry(
+            (params.wallTime ?? payload.startedMs / 1000) * 1000,
+            request.method ?? "GET",
+            request.url ?? "",
+            harHeaders(request.headers),
+            noTimings(),
+          ),
+          timestamp: params.timestamp,
+        });
+      } else if (method === "Network.responseReceived") {
+        const p = pending.get(id);
+        if (p) applyResponse(p, params.response ?? {});
+      } else if (method === "Network.loadingFinished") {
+        const p = pending.get(id);
+        if (p) {
+          finish(p, params.timestamp, params.encodedDataLength);
+          pending.delete(id);
+        }
+      } else if (method === "Network.loadingFailed") {
+        const p = pending.get(id);
+        if (p) {
+          p.entry.response._error = params.errorText ?? "Failed";
+          finish(p, params.timestamp);
+          pending.delete(id);
+        }
+      }
+    }
+  } else if (payload.resources) {
+    // Resource Timing: marks are milliseconds relative to timeOrigin; zero
+    // means "not reported" (e.g. cross-origin without Timing-Allow-Origin).
+    for (const r of payload.resources) {
+      const mark = (v: number) => (v > 0 ? v : undefined);
+      const timings: HarEntry["timings"] = {
+        blocked: harSpan(r.startTime, mark(r.domainLookupStart)),
+        dns: harSpan(mark(r.domainLookupStart), mark(r.domainLookupEnd)),
+        connect: harSpan(mark(r.connectStart), mark(r.connectEnd)),
+        ssl: harSpan(mark(r.secureConnectionStart), mark(r.connectEnd)),
+        send: 0,
+        wait: harSpan(mark(r.requestStart), mark(r.responseStart)),
+        receive: harSpan(mark(r.responseStart), mark(r.responseEnd)),
+      };
+      if (timings.wait < 0 && timings.receive < 0) {
+        timings.receive = r.duration;
+      }
+      const entry = harEntry(r.startedMs, "GET", r.url, [], timings);
+      entry.time = r.duration;
+      entry.request.httpVersion = harHttpVersion(r.protocol);
+      entry.response.status = r.status;
+      entry.response.httpVersion = entry.request.httpVersion;
+      entry.response.content = { size: r.bodySize, mimeType: r.mimeType };
+      entry.response.bodySize = r.bodySize;
+      entries.push(entry);
+    }
+  }
+
+  entries.sort((a, b) => Date.parse(a.startedDateTime) - Date.parse(b.startedDateTime));
+  return {
+    log: {
+      version: "1.2",
+      creator: { name: "native-window", version: "1.0" },
+      pages: [],
+      entries,
+    },
+  };
+}
+
+// ---------------------------------------------------------------------------
+// NativeWindow wrapper – auto-init, auto-pump, auto-stop
+// ---------------------------------------------------------------------------
+
+type NativeWindowOptions = import("./native-window.js").WindowOptions;
+type InputEvent = import("./native-window.js").InputEvent;
+type LoadHtmlOptions = import("./native-window.js").LoadHtmlOptions;
+type WindowMenuItem = import("./native-window.js").WindowMenuItem;
+type NetworkConditions = import("./native-window.js").NetworkConditions;
+type FontOverrides = import("./native-window.js").FontOverrides;
+type SnapPosition = import("./native-window.js").SnapPosition;
+type ProgressBarState = import("./native-window.js").ProgressBarState;
+type FullscreenMode = import("./native-window.js").FullscreenMode;
+type Point = import("./native-window.js").Point;
+type Rect = import("./native-window.js").Rect;
+type WindowState = import("./native-window.js").WindowState;
+type CommandRecord = import("./native-window.js").CommandRecord;
+type PopupAnchor = import("./native-window.js").PopupAnchor;
+type AboutPanelOptions = import("./native-window.js").AboutPanelOptions;
+type NativeInitOptions = import("./native-window.js").InitOptions;
+type MaskableEvent = import("./native-window.js").MaskableEvent;
+type RuntimeInfo = import("./native-window.js").RuntimeInfo;
+
+/** Why a navigation failed, as reported by {@link NativeWindow.onFallbackShown}. */
+export type FallbackErrorCode =
+  | "disconnected"
+  | "hostNotFound"
+  | "timeout"
+  | "connectionFailed"
+  | "certificateError"
+  | "other";
+
+/** Passed to {@link NativeWindow.onCloseRequested} handlers. */
+export interface CloseRequestedEvent {
+  /** Whether {@link preventDefault} has been called. */
+  readonly defaultPrevented: boolean;
+  /** Keep the window open. */
+  preventDefault(): void;
+}
+
+/**
+ * Return value of an {@link NativeWindow.onCloseRequested} handler. `false`
+ * keeps the window open, like `event.preventDefault()`. A promise defers
+ * the decision: the window closes once it settles, unless it resolves to
+ * `false` or rejects.
+ */
+export type CloseRequestedResult = void | boolean | Promise<void | boolean>;
+
+/**
+ * A native OS window with an embedded webview.
+ *
+ * Automatically initializes the native subsystem and starts pumping
+ * events on first construction. Stops the pump when all windows close.
+ */
+export class NativeWindow {
+  /** @internal */
+  private _native: InstanceType<typeof _NativeWindow>;
+  /** @internal */
+  private _closed = false;
+  /** @internal Set once the native window is gone and close handlers ran. */
+  private _destroyed = false;
+  /** @internal */
+  private _unsafe?: UnsafeNamespace;
+  /** @internal Whether a `startTracing()` session is running. */
+  private _tracing = false;
+
+  constructor(options?: WindowOptions) {
+    if (_strictOptions && options) {
+      const unsupported = unsupportedWindowOptions(options);
+      if (unsupported.length > 0) {
+        throw new UnsupportedOptionsError(unsupported);
+      }
+    }
+    if (!_replay) ensureInit();
+    _windowCount++;
+    if (_replay) {
+      this._native = replayNative(_replay);
+    } else {
+      const native = new _NativeWindow(options);
+      const recorded = _recordPath ? recordNative(native, options) : native;
+      const journalKey = options?.journalKey;
+      this._native =
+        _journal && journalKey !== undefined
+          ? journalNative(_journal, recorded, journalKey)
+          : recorded;
+    }
+
+    // Register a default close handler to track window count.
+    this._native.onClose(() => this._handleClose());
+  }
+
+  /** @internal */
+  private _handleClose() {
+    // `close()` marks the window closed up front; only the native
+    // close event finalizes it, so handlers fire for every close path.
+    if (this._destroyed) return;
+    this._destroyed = true;
+    this._closed = true;
+    journalWindowClosed(this._native.id);
+    _windowCount--;
+    if (_windowCount <= 0) {
+      _windowCount = 0;
+      stopPump();
+    }
+    this._audioSink?.(null, null);
+    this._userCloseCallback?.();
+    // Deferred past the pump iteration that destroyed the window, so the
+    // native side has released its event handlers by the time it runs.
+    const closed = this._userClosedCallback;
+    if (closed) setImmediate(closed);
+  }
+
+  /**
+   * Throws if the window has been closed.
+   * @internal
+   */
+  private _ensureOpen(): void {
+    if (this._closed) {
+      throw new Error("Window is closed");
+    }
+  }
+
+  /** @internal Pending one-shot requests by method, answered in the order they were queued. */
+  private _requestQueues = new Map<string, Array<(response: unknown) => void>>();
+
+  /**
+   * Send a one-shot request answered through a native callback. The
+   * callback is registered once per method: commands run in order, so
+   * responses arrive in request order and each settles the oldest pending
+   * request. `settle` maps the response to the result, or throws to reject.
+   * @internal
+   */
+  private _request<R, T>(
+    method: string,
+    register: (callback: (response: R) => void) => void,
+    send: () => void,
+    timeoutMs: number,
+    settle: (response: R) => T | PromiseLike<T>,
+  ): Promise<T> {
+    let queue = this._requestQueues.get(method);
+    if (!queue) {
+      const created: Array<(response: unknown) => void> = [];
+      this._requestQueues.set(method, created);
+      register((response) => created.shift()?.(response));
+      queue = created;
+    }
+    send();
+    const pending = queue;
+    return new Promise<T>((resolve, reject) => {
+      const timeout = setTimeout(() => {
+        // Left in the queue so later responses stay matched.
+        reject(new Error(`${method}() timed out after ${timeoutMs / 1000} seconds`));
+      }, timeoutMs);
+      pending.push((response) => {
+        clearTimeout(timeout);
+        try {
+          resolve(settle(response as R));
+        } catch (e) {
+          reject(e);
+        }
+      });
+    });
+  }
+
+  // ---- onClose with user callback support ----
 
   private _userCloseCallback?: () => void;
+  private _userClosedCallback?: () => void;
+  private _userCloseRequestedCallback?: (event: CloseRequestedEvent) => CloseRequestedResult;
+  /** @internal Set while an async `onCloseRequested` decision is pending. */
+  private _closeDecisionPending = false;
 
   /**
    * Register a handler for the window close event.
    * The pump is automatically stopped when all windows are closed.
    *
+   * Fires after the native window and webview are destroyed, for every
+   * close path: {@link close}, the OS close button, and garbage collection.
+   *
    * Calling this multiple times replaces the previous handler.
    */
   onClose(callback: () => void): void {
@@ -175,6 +1568,103 @@ export class NativeWindow {
     this._userCloseCallback = callback;
   }
 
+  /**
+   * Register a handler that fires once native teardown has finished: on the
+   * next event-loop turn after {@link onClose}, when the pump iteration that
+   * destroyed the window has returned and its event handlers are released.
+   * Every `onClose` handler from that pump has run by then, so it is the
+   * place to exit the process or open a replacement window.
+   *
+   * Calling this multiple times replaces the previous handler.
+   */
+  onClosed(callback: () => void): void {
+    this._userClosedCallback = callback;
+  }
+
+  /** Fires after {@link restoreFromTray} brings the window back. */
+  onTrayRestore(callback: () => void): void {
+    this._ensureOpen();
+    this._native.onTrayRestore(callback);
+  }
+
+  /**
+   * Register a handler for close requests — {@link close} and the OS
+   * close button, Alt+F4, or Cmd+W — fired before anything is destroyed.
+   * Call `event.preventDefault()` or return `false` to keep the window open;
+   * otherwise it closes and {@link onClose} / {@link onClosed} follow.
+   *
+   * Return a promise to decide asynchronously, e.g. after an "unsaved
+   * changes" prompt: the window stays open until it settles and closes
+   * unless it resolves to `false`. Close requests that arrive meanwhile
+   * are ignored.
+   *
+   * Calling this multiple times replaces the previous handler.
+   *
+   * @example
+   * ```ts
+   * win.onCloseRequested(async () => {
+   *   if (!hasUnsavedChanges()) return;
+   *   return await askToDiscardChanges(); // false keeps the window open
+   * });
+   * ```
+   */
+  onCloseRequested(callback: (event: CloseRequestedEvent) => CloseRequestedResult): void {
+    this._ensureOpen();
+    if (!this._userCloseRequestedCallback) {
+      this._native.onCloseRequested(() => {
+        if (this._closed) return;
+        this._requestClose();
+      });
+    }
+    this._userCloseRequestedCallback = callback;
+  }
+
+  /**
+   * Close the window unless the `onCloseRequested` handler vetoes it,
+   * waiting for its decision if it returns a promise.
+   * @internal
+   */
+  private _requestClose(): void {
+    if (this._closeDecisionPending) return;
+    const decision = this._confirmClose();
+    if (typeof decision === "boolean") {
+      if (decision) this.destroy();
+      return;
+    }
+    this._closeDecisionPending = true;
+    decision.then((allow) => {
+      this._closeDecisionPending = false;
+      if (allow) this.destroy();
+    });
+  }
+
+  /**
+   * Run the `onCloseRequested` handler, if any.
+   * Returns `false` if it prevented the close, or a promise of that.
+   * @internal
+   */
+  private _confirmClose(): boolean | Promise<boolean> {
+    let prevented = false;
+    const result = this._userCloseRequestedCallback?.({
+      get defaultPrevented() {
+        return prevented;
+      },
+      preventDefault() {
+        prevented = true;
+      },
+    });
+    if (result instanceof Promise) {
+      return result.then(
+        (value) => !prevented && value !== false,
+        (e) => {
+          console.error("NativeWindow: onCloseRequested handler rejected; keeping the window open.", e);
+          return false;
+        },
+      );
+    }
+    return !prevented && result !== false;
+  }
+
   // ---- Getters ----
 
   /** Unique window ID */
@@ -197,10 +1687,105 @@ export class NativeWindow {
    * [DOMPurify](https://github.com/cure53/DOMPurify) or
    * [sanitize-html](https://github.com/apostrophecms/sanitize-html) to
    * sanitize untrusted content before embedding it.
+   *
+   * Pass `options.path` to store several documents per window; in-page
+   * links between their paths work like a small multi-page site.
+   *
+   * @example
+   * ```ts
+   * win.loadHtml(settingsHtml, { path: "/settings" });
+   * win.loadHtml(`<a href="/settings">Settings</a>`);
+   * ```
+   */
+  loadHtml(html: string, options?: LoadHtmlOptions): void {
+    this._ensureOpen();
+    this._native.loadHtml(html, options);
+  }
+
+  /**
+   * Serve `script` as the service worker of {@link loadHtml} content, at
+   * `/service-worker.js` under {@link loadHtmlOrigin}. `null` stops serving
+   * it; workers that are already registered keep running until
+   * {@link unregisterServiceWorkers} is called.
+   *
+   * Service workers on the internal origin are supported on Windows. WebKit
+   * (macOS/Linux) does not run service workers for custom schemes, so
+   * registration rejects there.
+   *
+   * @example
+   * ```ts
+   * win.setServiceWorker(`
+   *   self.addEventListener("fetch", (e) => {
+   *     e.respondWith(caches.match(e.request).then((r) => r ?? fetch(e.request)));
+   *   });
+   * `);
+   * win.loadHtml(`<script>navigator.serviceWorker.register("/service-worker.js")</script>`);
+   * ```
+   */
+  setServiceWorker(script: string | null): void {
+    this._ensureOpen();
+    this._native.setServiceWorker(script);
+  }
+
+  /**
+   * Unregister every service worker of the current page's origin and
+   * delete its Cache Storage, resetting an offline-capable app bundle.
+   * Takes effect for pages loaded afterwards.
+   */
+  unregisterServiceWorkers(): void {
+    this._ensureOpen();
+    this._native.unregisterServiceWorkers();
+  }
+
+  /**
+   * Show `html` instead of the engine's error page when a main-frame
+   * navigation fails (offline, DNS, refused connection, certificate
+   * error). The document is served like {@link loadHtml} content, from
+   * {@link loadHtmlOrigin}, and {@link onFallbackShown} fires. `null`
+   * restores the default behavior.
+   *
+   * @example
+   * ```ts
+   * win.setFallbackHtml(`<h1>You're offline</h1><button onclick="history.back()">Retry</button>`);
+   * win.loadUrl("https://app.example.com");
+   * ```
+   */
+  setFallbackHtml(html: string | null): void {
+    this._ensureOpen();
+    this._native.setFallbackHtml(html);
+  }
+
+  /**
+   * Warm up DNS, TCP and TLS connections to `origins` the next page will
+   * load from, to cut its perceived load time. Accepts http(s) URLs; only
+   * their origin is used.
+   *
+   * Hints are added to the current document, so call this after it has
+   * started loading.
+   */
+  preconnect(origins: string[]): void {
+    this._ensureOpen();
+    this._native.preconnect(origins);
+  }
+
+  /**
+   * Fetch `urls` into the HTTP cache ahead of an upcoming navigation, e.g.
+   * the next page of a wizard.
+   *
+   * Uses `<link rel="prefetch">` where supported (Windows); WebKit
+   * (macOS/Linux) falls back to a low-priority `fetch()`, which only
+   * helps for cacheable responses.
+   *
+   * @example
+   * ```ts
+   * win.prefetch(["https://app.example.com/step-2", "https://app.example.com/step-2.js"]);
+   * // later
+   * win.loadUrl("https://app.example.com/step-2");
+   * ```
    */
-  loadHtml(html: string): void {
+  prefetch(urls: string[]): void {
     this._ensureOpen();
-    this._native.loadHtml(html);
+    this._native.prefetch(urls);
   }
 
   postMessage(message: string): void {
@@ -208,6 +1793,27 @@ export class NativeWindow {
     this._native.postMessage(message);
   }
 
+  /**
+   * Synthesize a mouse, key, or wheel event into the webview, for scripted
+   * walkthroughs and smoke tests without an external driver.
+   *
+   * - **Windows**: dispatched through the DevTools `Input` domain (trusted events).
+   * - **macOS**: mouse and key events are posted as native `NSEvent`s;
+   *   wheel events are synthesized in the DOM.
+   * - **Linux**: synthesized in the DOM (`isTrusted === false`).
+   *
+   * @example
+   * ```ts
+   * win.sendInputEvent({ type: "mouse", x: 120, y: 48 });
+   * win.sendInputEvent({ type: "key", key: "Enter" });
+   * win.sendInputEvent({ type: "wheel", x: 200, y: 200, deltaY: 400 });
+   * ```
+   */
+  sendInputEvent(event: InputEvent): void {
+    this._ensureOpen();
+    this._native.sendInputEvent(event);
+  }
+
   // ---- Unsafe operations ----
 
   /**
@@ -226,6 +1832,10 @@ export class NativeWindow {
           this._ensureOpen();
           this._native.evaluateJs(script);
         },
+        evaluateJsWithUserGesture: (script: string): void => {
+          this._ensureOpen();
+          this._native.evaluateJsWithUserGesture(script);
+        },
       };
     }
     return this._unsafe;
@@ -233,11 +1843,46 @@ export class NativeWindow {
 
   // ---- Window control ----
 
+  /**
+   * Execute this window's queued commands now instead of on the next
+   * event pump (~16ms later), e.g. to resize right before a capture.
+   * Commands still run in order; other windows' commands stay queued.
+   * Events and callbacks are still delivered by the pump.
+   *
+   * @example
+   * ```ts
+   * win.setSize(1280, 720);
+   * win.flush();
+   * const png = await win.captureWindow();
+   * ```
+   */
+  flush(): void {
+    this._ensureOpen();
+    if (_replay) return;
+    _flush(this.id);
+  }
+
   setTitle(title: string): void {
     this._ensureOpen();
     this._native.setTitle(title);
   }
 
+  /**
+   * Compose the native title from the page's `document.title`. Every
+   * `{{pageTitle}}` in the template is replaced with the current title, and
+   * the window is retitled natively on each change, without a round-trip
+   * through JS. Pass `null` to stop; the current title is kept.
+   *
+   * @example
+   * ```ts
+   * win.setTitleTemplate("{{pageTitle}} — MyApp");
+   * ```
+   */
+  setTitleTemplate(template: string | null): void {
+    this._ensureOpen();
+    this._native.setTitleTemplate(template);
+  }
+
   setSize(width: number, height: number): void {
     this._ensureOpen();
     this._native.setSize(width, height);
@@ -253,239 +1898,2228 @@ export class NativeWindow {
     this._native.setMaxSize(width, height);
   }
 
-  setPosition(x: number, y: number): void {
+  /**
+   * Keep the content area at `width`:`height` (e.g. `16, 9`) while the user
+   * resizes the window. Programmatic resizes are not constrained.
+   * `setAspectRatio(0, 0)` removes the constraint.
+   *
+   * On Linux this is a window manager hint that replaces the
+   * {@link setMinSize}/{@link setMaxSize} hints, and vice versa.
+   */
+  setAspectRatio(width: number, height: number): void {
     this._ensureOpen();
-    this._native.setPosition(x, y);
+    this._native.setAspectRatio(width, height);
   }
 
-  setResizable(resizable: boolean): void {
+  setPosition(x: number, y: number): void {
     this._ensureOpen();
-    this._native.setResizable(resizable);
+    this._native.setPosition(x, y);
   }
 
-  setDecorations(decorations: boolean): void {
+  /**
+   * Tile the window to a half or quarter of a display's work area (the
+   * screen minus taskbar, dock, and menu bar), or `"fill"` it entirely.
+   * Unlike {@link maximize}, the window stays in its normal state.
+   *
+   * @param display Monitor index in OS enumeration order. Defaults to the
+   *   monitor the window is currently on.
+   *
+   * @example
+   * ```ts
+   * editor.snapTo("left");
+   * preview.snapTo("right");
+   * ```
+   */
+  snapTo(position: SnapPosition, display?: number): void {
     this._ensureOpen();
-    this._native.setDecorations(decorations);
+    this._native.snapTo(position, display);
   }
 
-  setAlwaysOnTop(alwaysOnTop: boolean): void {
+  /**
+   * Center the window in a display's work area (excluding the taskbar,
+   * dock and menu bar). `display` is a monitor index; defaults to the
+   * monitor the window is on.
+   */
+  center(display?: number): void {
     this._ensureOpen();
-    this._native.setAlwaysOnTop(alwaysOnTop);
+    this._native.center(display);
   }
 
   /**
-   * Set the window icon from a PNG or ICO file path.
-   * On macOS this is silently ignored (macOS doesn't support per-window icons).
-   * Relative paths resolve from the working directory.
+   * Move the window to another display, keeping its place in the work
+   * area. `display` is a monitor index. Maximized and fullscreen windows
+   * are maximized or made fullscreen on that display; on macOS, a window in
+   * native (Spaces) fullscreen must leave fullscreen first.
+   *
+   * @example
+   * ```ts
+   * win.moveToDisplay(1); // second monitor
+   * ```
    */
-  setIcon(path: string): void {
+  moveToDisplay(display: number): void {
     this._ensureOpen();
-    this._native.setIcon(path);
+    this._native.moveToDisplay(display);
   }
 
-  // ---- Window state ----
+  setResizable(resizable: boolean): void {
+    this._ensureOpen();
+    this._native.setResizable(resizable);
+  }
 
-  show(): void {
+  /**
+   * Enable or disable the close button. The window can still be closed
+   * with {@link close}. On Linux the window manager may ignore this for a
+   * window that is already visible.
+   */
+  setClosable(closable: boolean): void {
     this._ensureOpen();
-    this._native.show();
+    this._native.setClosable(closable);
   }
 
-  hide(): void {
+  /** Enable or disable the minimize button. Not supported on Linux. */
+  setMinimizable(minimizable: boolean): void {
     this._ensureOpen();
-    this._native.hide();
+    this._native.setMinimizable(minimizable);
   }
 
-  close(): void {
+  /**
+   * Enable or disable the maximize button. On macOS this is the zoom
+   * button, which also enters fullscreen. Not supported on Linux.
+   */
+  setMaximizable(maximizable: boolean): void {
     this._ensureOpen();
-    this._closed = true;
-    this._native.close();
+    this._native.setMaximizable(maximizable);
   }
 
-  focus(): void {
+  setDecorations(decorations: boolean): void {
     this._ensureOpen();
-    this._native.focus();
+    this._native.setDecorations(decorations);
   }
 
-  maximize(): void {
+  setAlwaysOnTop(alwaysOnTop: boolean): void {
     this._ensureOpen();
-    this._native.maximize();
+    this._native.setAlwaysOnTop(alwaysOnTop);
   }
 
-  minimize(): void {
+  /**
+   * Keep the window below all normal windows, for desktop widgets.
+   * Turning it on clears always-on-top, and vice versa.
+   */
+  setAlwaysOnBottom(alwaysOnBottom: boolean): void {
     this._ensureOpen();
-    this._native.minimize();
+    this._native.setAlwaysOnBottom(alwaysOnBottom);
   }
 
-  unmaximize(): void {
+  /**
+   * Place this window directly above another window of the app, e.g. to
+   * keep a tool palette over its document without going always-on-top.
+   *
+   * @example
+   * ```ts
+   * palette.moveAbove(editor.id);
+   * ```
+   */
+  moveAbove(otherWindowId: number): void {
     this._ensureOpen();
-    this._native.unmaximize();
+    this._native.moveAbove(otherWindowId);
   }
 
-  reload(): void {
+  /** Place this window directly below another window of the app. */
+  moveBelow(otherWindowId: number): void {
     this._ensureOpen();
-    this._native.reload();
+    this._native.moveBelow(otherWindowId);
   }
 
-  // ---- Event handlers ----
+  /**
+   * Show the window on every macOS Space / Linux workspace / Windows
+   * virtual desktop, so it follows the user when they switch.
+   */
+  setVisibleOnAllWorkspaces(visible: boolean): void {
+    this._ensureOpen();
+    this._native.setVisibleOnAllWorkspaces(visible);
+  }
 
   /**
-   * Register a handler for messages from the webview.
-   *
-   * @security **No origin filtering.** The raw `onMessage` API does not
-   * enforce origin restrictions. If your webview navigates to untrusted
-   * URLs, validate the `sourceUrl` parameter before processing messages.
-   * For automatic origin filtering, use `createChannel()` with the
-   * `trustedOrigins` option from `native-window-ipc`.
-   *
-   * @security **No rate limiting.** Messages from the webview are delivered
-   * without throttling. A malicious page can flood the host with messages.
-   * Consider implementing application-level rate limiting if loading
-   * untrusted content.
+   * Switch between light and dark native chrome (title bar, context menus,
+   * scroll bars), or follow the OS with `"system"`. On Windows the page's
+   * `prefers-color-scheme` follows as well; macOS and Linux apply the theme
+   * to the whole app.
    */
-  onMessage(callback: (message: string, sourceUrl: string) => void): void {
+  setTheme(theme: WindowTheme): void {
     this._ensureOpen();
-    this._native.onMessage(callback);
+    this._native.setTheme(theme);
   }
 
-  onResize(callback: (width: number, height: number) => void): void {
+  /**
+   * Keep the window on every Windows virtual desktop. Same as
+   * {@link setVisibleOnAllWorkspaces}, named after the Task View action.
+   */
+  pinToAllDesktops(pin: boolean): void {
+    this.setVisibleOnAllWorkspaces(pin);
+  }
+
+  /**
+   * Move the window to a Windows virtual desktop, by the `id` from
+   * {@link getVirtualDesktops}. Throws on other platforms.
+   */
+  moveToVirtualDesktop(desktopId: string): void {
     this._ensureOpen();
-    this._native.onResize(callback);
+    this._native.moveToVirtualDesktop(desktopId);
   }
 
-  onMove(callback: (x: number, y: number) => void): void {
+  /**
+   * Keep the window out of the taskbar, e.g. for utility and palette
+   * windows. On macOS it is excluded from window cycling (Cmd+`); the Dock
+   * icon and Cmd+Tab entry belong to the app and stay visible.
+   */
+  setSkipTaskbar(skip: boolean): void {
     this._ensureOpen();
-    this._native.onMove(callback);
+    this._native.setSkipTaskbar(skip);
   }
 
-  onFocus(callback: () => void): void {
+  /**
+   * Move the window with the mouse, as if its title bar were dragged — for
+   * custom title bars. Call while the primary mouse button is held down,
+   * e.g. from a `mousedown` message. Elements with the
+   * `data-native-drag-region` attribute do this without any IPC.
+   */
+  startDragging(): void {
     this._ensureOpen();
-    this._native.onFocus(callback);
+    this._native.startDragging();
   }
 
-  onBlur(callback: () => void): void {
+  /**
+   * Let mouse input pass through the window to whatever is underneath, for
+   * transparent overlays. The window no longer receives clicks, hover or
+   * scrolling until this is turned off again; keyboard input is unaffected.
+   */
+  setIgnoreCursorEvents(ignore: boolean): void {
     this._ensureOpen();
-    this._native.onBlur(callback);
+    this._native.setIgnoreCursorEvents(ignore);
   }
 
-  onPageLoad(callback: (event: "started" | "finished", url: string) => void): void {
+  /**
+   * Enter or leave fullscreen. `mode` overrides the window's
+   * `fullscreenMode` option and is remembered for later calls.
+   */
+  setFullscreen(fullscreen: boolean, mode?: FullscreenMode): void {
     this._ensureOpen();
-    this._native.onPageLoad(callback);
+    this._native.setFullscreen(fullscreen, mode);
   }
 
-  onTitleChanged(callback: (title: string) => void): void {
+  /**
+   * Set the window icon from a PNG or ICO file path.
+   * On macOS this is silently ignored (macOS doesn't support per-window icons).
+   * Relative paths resolve from the working directory.
+   */
+  setIcon(path: string): void {
     this._ensureOpen();
-    this._native.onTitleChanged(callback);
+    this._native.setIcon(path);
   }
 
-  onReload(callback: () => void): void {
+  /**
+   * Set the window icon from an in-memory PNG or ICO image, or from raw
+   * RGBA pixels when `width` and `height` are given, so bundled or
+   * generated icons need no temporary file. Throws if the data cannot be
+   * decoded. On macOS this is silently ignored.
+   */
+  setIconFromBuffer(data: Buffer, width?: number, height?: number): void {
     this._ensureOpen();
-    this._native.onReload(callback);
+    this._native.setIconFromBuffer(data, width, height);
   }
 
   /**
-   * Register a handler for blocked navigation events.
-   * Fired when a navigation is blocked by the {@link WindowOptions.allowedHosts}
-   * restriction. Receives the URL that was blocked.
+   * Show a count badge on the window's taskbar button, or clear it with
+   * `null` or `0`. Counts above 99 are shown as "99+".
+   *
+   * - **Windows**: an overlay icon on this window's taskbar button.
+   * - **macOS**: the Dock badge. The Dock icon is shared by all windows, so
+   *   the last call wins.
+   * - **Linux**: the Unity launcher count, shown by docks that support it.
+   */
+  setTaskbarBadge(count: number | null): void {
+    this._ensureOpen();
+    if (count !== null && (!Number.isInteger(count) || count < 0)) {
+      throw new TypeError("setTaskbarBadge() count must be a non-negative integer or null");
+    }
+    this._native.setTaskbarBadge(count);
+  }
+
+  /**
+   * Show a small icon (PNG or ICO path) over the window's taskbar button,
+   * e.g. a status or unread indicator, or clear it with `null`.
+   * `description` is announced by screen readers. Replaces the badge drawn
+   * by {@link setTaskbarBadge}.
+   *
+   * **Windows only**; ignored on macOS and Linux.
+   */
+  setOverlayIcon(path: string | null, description?: string): void {
+    this._ensureOpen();
+    this._native.setOverlayIcon(path, description);
+  }
+
+  /**
+   * Show free-form text, such as an unread count or `"!"`, on the Dock
+   * icon badge, or clear it with `null` or `""`. The Dock icon is shared by
+   * all windows, so the last call wins; replaces {@link setTaskbarBadge}.
+   *
+   * **macOS only**; ignored on Windows and Linux.
+   */
+  setBadgeLabel(text: string | null): void {
+    this._ensureOpen();
+    this._native.setBadgeLabel(text);
+  }
+
+  /**
+   * Show the progress of a long-running task (0–1) on the window's taskbar
+   * button, or clear it with `null`.
+   *
+   * - **Windows**: the taskbar button's progress bar. `"indeterminate"`,
+   *   `"paused"` (yellow) and `"error"` (red) change its style.
+   * - **macOS / Linux**: the app-wide Dock or Unity launcher progress, shared
+   *   by all windows. Every state is drawn as `"normal"`.
    *
    * @example
    * ```ts
-   * win.onNavigationBlocked((url) => {
-   *   console.log("Blocked navigation to:", url);
-   * });
+   * win.setProgressBar(received / total);
+   * win.setProgressBar(null); // done
    * ```
    */
-  onNavigationBlocked(callback: (url: string) => void): void {
+  setProgressBar(value: number | null, state?: ProgressBarState): void {
     this._ensureOpen();
-    this._native.onNavigationBlocked(callback);
+    this._native.setProgressBar(value, state);
   }
 
-  // ---- Cookie access ----
+  /**
+   * Attach a classic menu bar to this window, or remove it with `null`.
+   * Choosing an item fires {@link onMenuItemClick} with its `id`.
+   *
+   * Windows and Linux only. On macOS the menu bar is app-wide and this
+   * is ignored.
+   *
+   * @example
+   * ```ts
+   * win.setWindowMenu([
+   *   { label: "&File", submenu: [
+   *     { label: "&Open…", id: "open" },
+   *     { type: "separator" },
+   *     { label: "E&xit", id: "exit" },
+   *   ]},
+   * ]);
+   * win.onMenuItemClick((id) => { if (id === "exit") win.close(); });
+   * ```
+   */
+  setWindowMenu(menu: WindowMenuItem[] | null): void {
+    this._ensureOpen();
+    this._native.setWindowMenu(menu);
+  }
 
   /**
-   * Validate and parse a raw cookies JSON array from the native layer.
-   * Returns a cleaned {@link CookieInfo} array or `null` if the payload
-   * is malformed.
+   * Hide the menu bar until Alt is pressed and released on its own; it
+   * hides again after a menu is used (Windows/Linux).
+   */
+  setAutoHideMenuBar(hide: boolean): void {
+    this._ensureOpen();
+    this._native.setAutoHideMenuBar(hide);
+  }
+
+  /**
+   * Lower the scheduling priority of this window's webview content process
+   * while it is in the background, reducing the battery impact of apps with
+   * many open windows. `"normal"` restores it.
    *
-   * @internal
+   * - **Windows**: below-normal priority and EcoQoS for the renderer
+   *   process, plus a low WebView2 memory usage target.
+   * - **macOS**: the WebContent process moves to the Darwin background band.
+   * - **Linux**: no-op; WebKitGTK does not expose its web process.
+   *
+   * @example
+   * ```ts
+   * win.onBlur(() => win.setPriority("background"));
+   * win.onFocus(() => win.setPriority("normal"));
+   * ```
    */
-  private _validateCookies(raw: string): CookieInfo[] | null {
-    let parsed: unknown;
-    try {
-      parsed = JSON.parse(raw);
-    } catch {
-      return null;
-    }
-    if (!Array.isArray(parsed)) return null;
+  setPriority(priority: "normal" | "background"): void {
+    this._ensureOpen();
+    this._native.setPriority(priority);
+  }
 
-    const cookies: CookieInfo[] = [];
-    for (const item of parsed) {
-      if (typeof item !== "object" || item === null) continue;
-      const obj = item as Record<string, unknown>;
-      if (typeof obj.name !== "string" || typeof obj.value !== "string") {
-        continue;
-      }
-      if (typeof obj.domain !== "string" || typeof obj.path !== "string") {
-        continue;
-      }
-      if (typeof obj.httpOnly !== "boolean" || typeof obj.secure !== "boolean") {
-        continue;
-      }
-      if (typeof obj.sameSite !== "string" || typeof obj.expires !== "number") {
-        continue;
-      }
-      cookies.push({
-        name: obj.name as string,
-        value: obj.value as string,
-        domain: obj.domain as string,
-        path: obj.path as string,
-        httpOnly: obj.httpOnly as boolean,
-        secure: obj.secure as boolean,
-        sameSite: obj.sameSite as string,
-        expires: obj.expires as number,
-      });
-    }
-    return cookies;
+  // ---- Coordinates ----
+
+  /**
+   * Convert screen coordinates (logical pixels, as used by
+   * {@link setPosition}) to client coordinates relative to the webview's
+   * top-left corner (CSS pixels at 100% zoom).
+   *
+   * Throws if the native window has not been created yet — wait for the
+   * first event (e.g. `onPageLoad`) before querying.
+   */
+  screenToClient(x: number, y: number): Point {
+    this._ensureOpen();
+    return this._native.screenToClient(x, y);
   }
 
   /**
-   * Query cookies from the native cookie store.
+   * Convert client coordinates (CSS pixels relative to the webview) to
+   * screen coordinates, e.g. to position a popup under a DOM element.
    *
-   * Returns a Promise that resolves with validated {@link CookieInfo} objects,
-   * including `HttpOnly` cookies that are invisible to `document.cookie`.
+   * @example
+   * ```ts
+   * // rect from element.getBoundingClientRect(), sent over IPC
+   * const pos = win.clientToScreen(rect.left, rect.bottom);
+   * popup.setPosition(pos.x, pos.y);
+   * ```
+   */
+  clientToScreen(x: number, y: number): Point {
+    this._ensureOpen();
+    return this._native.clientToScreen(x, y);
+  }
+
+  /**
+   * Physical pixels per logical pixel on the display showing the window,
+   * e.g. `2` on a Retina display or `1.5` at 150% scaling.
+   */
+  getScaleFactor(): number {
+    this._ensureOpen();
+    return this._native.getScaleFactor();
+  }
+
+  /**
+   * Fires with the new scale factor when it changes — the window moved to
+   * a display with a different DPI, or the user changed display scaling —
+   * so canvases can be re-rendered at the new resolution.
    *
-   * - **macOS**: Uses `WKHTTPCookieStore.getAllCookies` with client-side
-   *   URL filtering (domain + path match).
-   * - **Windows**: Uses `ICoreWebView2CookieManager.GetCookies` which
-   *   filters by URI natively.
+   * @example
+   * ```ts
+   * win.onScaleFactorChanged((scale) => win.postMessage(JSON.stringify({ scale })));
+   * ```
+   */
+  onScaleFactorChanged(callback: (scale: number) => void): void {
+    this._ensureOpen();
+    this._native.onScaleFactorChanged(callback);
+  }
+
+  /** Convert device-independent (logical) pixels to physical pixels using this window's scale factor. */
+  dipToPhysical(x: number, y: number): Point {
+    this._ensureOpen();
+    return this._native.dipToPhysical(x, y);
+  }
+
+  /** Convert physical pixels to device-independent (logical) pixels using this window's scale factor. */
+  physicalToDip(x: number, y: number): Point {
+    this._ensureOpen();
+    return this._native.physicalToDip(x, y);
+  }
+
+  // ---- Diagnostics ----
+
+  /**
+   * The last `limit` commands processed for this window (default: all that
+   * are kept, up to 200), oldest first, with timestamps and outcomes.
    *
-   * @param url  If provided, only cookies matching this URL are returned.
-   *             If omitted, all cookies in the webview's cookie store are returned.
+   * Useful for diagnosing calls that seem to have no effect: a command
+   * that never ran is missing, one that ran before the native window
+   * existed (or after it closed) has outcome `"windowNotFound"`.
    *
    * @example
    * ```ts
-   * const cookies = await win.getCookies("https://example.com");
-   * const session = cookies.find((c) => c.name === "session_id");
-   * if (session) console.log("Session:", session.value, "HttpOnly:", session.httpOnly);
+   * for (const { command, timestamp, outcome, error } of win.getCommandHistory(20)) {
+   *   console.log(new Date(timestamp).toISOString(), command, outcome, error ?? "");
+   * }
    * ```
    */
-  getCookies(url?: string): Promise<CookieInfo[]> {
+  getCommandHistory(limit?: number): CommandRecord[] {
     this._ensureOpen();
-    return new Promise((resolve, reject) => {
-      const timeout = setTimeout(() => {
-        reject(new Error("getCookies() timed out after 10 seconds"));
-      }, 10_000);
-      this._native.onCookies((raw: string) => {
-        clearTimeout(timeout);
-        const validated = this._validateCookies(raw);
-        if (validated) {
-          resolve(validated);
-        } else {
-          reject(new Error("Failed to parse cookie response"));
-        }
-      });
-      this._native.getCookies(url);
-    });
+    return this._native.getCommandHistory(limit);
   }
+
+  // ---- Geometry ----
+
+  /**
+   * Read the window's current outer position and content size in logical
+   * pixels — the same spaces as {@link setPosition} and {@link setSize}.
+   *
+   * The query runs on the next event pump, after any commands queued
+   * before it, so it reflects the real native state including changes
+   * made by the user or the window manager. Positions are `0` on Wayland,
+   * which does not report them.
+   *
+   * @example
+   * ```ts
+   * win.setSize(1280, 720);
+   * const { width, height } = await win.getBounds();
+   * ```
+   */
+  getBounds(): Promise<Rect> {
+    this._ensureOpen();
+    return this._request(
+      "getBounds",
+      (cb: (bounds: Rect) => void) => this._native.onBounds(cb),
+      () => this._native.getBounds(),
+      10_000,
+      (bounds) => bounds,
+    );
+  }
+
+  /** Read the window's content size in logical pixels. See {@link getBounds}. */
+  async getSize(): Promise<{ width: number; height: number }> {
+    const { width, height } = await this.getBounds();
+    return { width, height };
+  }
+
+  /** Read the window's outer position in logical pixels. See {@link getBounds}. */
+  async getPosition(): Promise<Point> {
+    const { x, y } = await this.getBounds();
+    return { x, y };
+  }
+
+  // ---- Window state ----
+
+  /**
+   * Read the window's maximized, minimized, visible and focused state from
+   * the OS. Like {@link getBounds}, the query runs on the next event pump,
+   * so it also reflects changes made outside the app (e.g. the user
+   * double-clicking the title bar).
+   */
+  getWindowState(): Promise<WindowState> {
+    this._ensureOpen();
+    return this._request(
+      "getWindowState",
+      (cb: (state: WindowState) => void) => this._native.onWindowState(cb),
+      () => this._native.getWindowState(),
+      10_000,
+      (state) => state,
+    );
+  }
+
+  /** Whether the window is maximized. See {@link getWindowState}. */
+  async isMaximized(): Promise<boolean> {
+    return (await this.getWindowState()).maximized;
+  }
+
+  /** Whether the window is minimized. See {@link getWindowState}. */
+  async isMinimized(): Promise<boolean> {
+    return (await this.getWindowState()).minimized;
+  }
+
+  /** Whether the window is shown. A minimized window still counts as visible. */
+  async isVisible(): Promise<boolean> {
+    return (await this.getWindowState()).visible;
+  }
+
+  /** Whether the window has keyboard focus. See {@link getWindowState}. */
+  async isFocused(): Promise<boolean> {
+    return (await this.getWindowState()).focused;
+  }
+
+  /**
+   * Where the window is among macOS Spaces / Linux workspaces / Windows
+   * virtual desktops, e.g. to
+   * avoid showing a notification window on a Space the user is not on.
+   * See {@link getWindowState}.
+   */
+  async getWindowWorkspace(): Promise<WindowWorkspace> {
+    const state = await this.getWindowState();
+    return {
+      onActiveWorkspace: state.onActiveWorkspace,
+      visibleOnAllWorkspaces: state.visibleOnAllWorkspaces,
+    };
+  }
+
+  show(): void {
+    this._ensureOpen();
+    this._native.show();
+  }
+
+  hide(): void {
+    this._ensureOpen();
+    this._native.hide();
+  }
+
+  /**
+   * Close the window. If an {@link onCloseRequested} handler is registered
+   * it runs first and may cancel or defer the close.
+   * Use {@link destroy} to close unconditionally.
+   */
+  close(): void {
+    this._ensureOpen();
+    this._requestClose();
+  }
+
+  /**
+   * Destroy the window immediately, without running
+   * {@link onCloseRequested}. `onClose` / `onClosed` still fire.
+   * Does nothing if the window is already closed.
+   */
+  destroy(): void {
+    if (this._closed) return;
+    this._closed = true;
+    this._native.close();
+  }
+
+  /**
+   * Keep the window open even if this object is garbage collected, e.g.
+   * for a window that should live on after the code that created it is
+   * done with it. Same as the `persistAfterGc` option.
+   *
+   * Registered event handlers keep working; the window lives until it is
+   * closed by the user or the app exits.
+   */
+  detach(): void {
+    this._ensureOpen();
+    this._native.detach();
+  }
+
+  /**
+   * Destroy the window now, like {@link destroy}, and release the native
+   * object's hold on it so garbage collection has nothing left to close.
+   * Does nothing if the window is already closed.
+   */
+  dispose(): void {
+    if (this._closed) return;
+    this._closed = true;
+    this._native.dispose();
+  }
+
+  focus(): void {
+    this._ensureOpen();
+    this._native.focus();
+  }
+
+  /**
+   * Draw the user's attention to a background window: flash its taskbar
+   * button (Windows), bounce the Dock icon (macOS) or mark it urgent
+   * (Linux). With `critical`, it keeps going until the window is focused;
+   * otherwise it flashes briefly (on macOS, bounces once). Does nothing
+   * if the window is already focused.
+   */
+  requestAttention(critical = false): void {
+    this._ensureOpen();
+    this._native.requestAttention(critical);
+  }
+
+  /**
+   * Move keyboard focus into the webview so key input reaches the page.
+   * Focusing the window does not guarantee this — notably right after
+   * creation on Windows, where focus stays on the host window.
+   */
+  focusWebview(): void {
+    this._ensureOpen();
+    this._native.focusWebview();
+  }
+
+  /**
+   * Open the system emoji picker for the page's focused input — the emoji
+   * panel (Win+.) on Windows, the character palette (Cmd+Ctrl+Space) on
+   * macOS — e.g. from an emoji button in a chat UI. Focuses the window and
+   * webview first. Throws on Linux.
+   */
+  showEmojiPicker(): void {
+    this._ensureOpen();
+    this._native.showEmojiPicker();
+  }
+
+  /** Move keyboard focus from the webview back to the window. */
+  blurWebview(): void {
+    this._ensureOpen();
+    this._native.blurWebview();
+  }
+
+  /**
+   * Move keyboard focus into the webview at the first or last element of
+   * the page's tab order — what Tab (`"first"`) or Shift+Tab (`"last"`)
+   * from adjacent native UI should do.
+   */
+  moveFocusToWebview(edge: "first" | "last"): void {
+    this._ensureOpen();
+    this._native.moveFocusToWebview(edge);
+  }
+
+  /**
+   * Bring back a window hidden by the `minimizeToTray` option: show,
+   * un-minimize and focus it, then fire {@link onTrayRestore}. Call this
+   * from your tray icon's click handler.
+   */
+  restoreFromTray(): void {
+    this._ensureOpen();
+    this._native.restoreFromTray();
+  }
+
+  maximize(): void {
+    this._ensureOpen();
+    this._native.maximize();
+  }
+
+  minimize(): void {
+    this._ensureOpen();
+    this._native.minimize();
+  }
+
+  unmaximize(): void {
+    this._ensureOpen();
+    this._native.unmaximize();
+  }
+
+  reload(): void {
+    this._ensureOpen();
+    this._native.reload();
+  }
+
+  // ---- Event handlers ----
+
+  /**
+   * Deliver only the listed events for this window, or every event with
+   * `null`. Filtered events are dropped in the native event loop, so they
+   * cost nothing to ignore. Close, page-load and ready-to-show events are
+   * always delivered.
+   *
+   * @example
+   * ```ts
+   * // Only IPC messages; skip resize/move/focus traffic.
+   * win.setEventMask(["message"]);
+   * ```
+   */
+  setEventMask(events: MaskableEvent[] | null): void {
+    this._ensureOpen();
+    this._native.setEventMask(events);
+  }
+
+  /**
+   * Register a handler for messages from the webview.
+   *
+   * @security **No origin filtering.** The raw `onMessage` API does not
+   * enforce origin restrictions. If your webview navigates to untrusted
+   * URLs, validate the `sourceUrl` parameter before processing messages.
+   * For automatic origin filtering, use `createChannel()` with the
+   * `trustedOrigins` option from `native-window-ipc`.
+   *
+   * @security **No rate limiting.** Messages from the webview are delivered
+   * without throttling. A malicious page can flood the host with messages.
+   * Consider implementing application-level rate limiting if loading
+   * untrusted content.
+   */
+  onMessage(callback: (message: string, sourceUrl: string) => void): void {
+    this._ensureOpen();
+    this._native.onMessage(callback);
+  }
+
+  onResize(callback: (width: number, height: number) => void): void {
+    this._ensureOpen();
+    this._native.onResize(callback);
+  }
+
+  onMove(callback: (x: number, y: number) => void): void {
+    this._ensureOpen();
+    this._native.onMove(callback);
+  }
+
+  /**
+   * Fires once a resize is over — when the user lets go of the drag, or
+   * right after a programmatic resize — with the final size. Unlike
+   * {@link onResize}, it fires once per drag, so geometry can be persisted
+   * without debouncing.
+   *
+   * @example
+   * ```ts
+   * win.onResizeEnd((width, height) => settings.set("size", { width, height }));
+   * ```
+   */
+  onResizeEnd(callback: (width: number, height: number) => void): void {
+    this._ensureOpen();
+    this._native.onResizeEnd(callback);
+  }
+
+  /** Fires once a move is over, with the final position. See {@link onResizeEnd}. */
+  onMoveEnd(callback: (x: number, y: number) => void): void {
+    this._ensureOpen();
+    this._native.onMoveEnd(callback);
+  }
+
+  /**
+   * Fires when the window ended up entirely outside every display (e.g.
+   * after a monitor was disconnected) and was moved back to its last
+   * visible position, or centered on the primary display.
+   */
+  onWindowRescued(callback: (x: number, y: number) => void): void {
+    this._ensureOpen();
+    this._native.onWindowRescued(callback);
+  }
+
+  /**
+   * Fires when the cursor moves anywhere on screen, not just over this
+   * window, with logical screen coordinates. Checked at most once every
+   * `throttleMs` (and once per event pump); the cursor is only polled
+   * after a handler is registered.
+   */
+  onGlobalMouseMove(throttleMs: number, callback: (x: number, y: number) => void): void {
+    this._ensureOpen();
+    this._native.onGlobalMouseMove(throttleMs, callback);
+  }
+
+  onFocus(callback: () => void): void {
+    this._ensureOpen();
+    this._native.onFocus(callback);
+  }
+
+  onBlur(callback: () => void): void {
+    this._ensureOpen();
+    this._native.onBlur(callback);
+  }
+
+  /**
+   * Register a handler for the webview gaining (`true`) or losing
+   * (`false`) keyboard focus. Unlike {@link onFocus}/{@link onBlur}, this
+   * tracks the page itself, not the window.
+   */
+  onWebviewFocusChanged(callback: (focused: boolean) => void): void {
+    this._ensureOpen();
+    this._native.onWebviewFocusChanged(callback);
+  }
+
+  /**
+   * Register a handler for the window entering (`true`) or leaving
+   * (`false`) the active macOS Space or Windows virtual desktop, e.g. when
+   * the user switches. Never fires on Linux.
+   */
+  onWorkspaceChanged(callback: (onActiveWorkspace: boolean) => void): void {
+    this._ensureOpen();
+    this._native.onWorkspaceChanged(callback);
+  }
+
+  /**
+   * Register a handler for the window's theme switching between light and
+   * dark, e.g. when the OS appearance changes while `theme` is `"system"`.
+   * Use it to keep page styles in step with the native chrome.
+   */
+  onThemeChanged(callback: (theme: "light" | "dark") => void): void {
+    this._ensureOpen();
+    this._native.onThemeChanged(callback);
+  }
+
+  /**
+   * Register a handler for Tab (`"next"`) or Shift+Tab (`"previous"`)
+   * moving keyboard focus out of the page, so the app can pass focus to
+   * the adjacent native control or webview. WebView2 only; WebKit keeps
+   * focus inside the page.
+   *
+   * @example
+   * ```ts
+   * left.onFocusExitRequested((direction) => {
+   *   if (direction === "next") right.moveFocusToWebview("first");
+   * });
+   * ```
+   */
+  onFocusExitRequested(callback: (direction: "next" | "previous") => void): void {
+    this._ensureOpen();
+    this._native.onFocusExitRequested(callback);
+  }
+
+  onPageLoad(callback: (event: "started" | "finished", url: string) => void): void {
+    this._ensureOpen();
+    this._native.onPageLoad(callback);
+  }
+
+  /**
+   * Fires once, when the first page load finishes. Create the window with
+   * `visible: false` and call {@link show} here to avoid a blank flash.
+   */
+  onReadyToShow(callback: () => void): void {
+    this._ensureOpen();
+    this._native.onReadyToShow(callback);
+  }
+
+  onTitleChanged(callback: (title: string) => void): void {
+    this._ensureOpen();
+    this._native.onTitleChanged(callback);
+  }
+
+  /** Fires with the item's `id` when a menu bar item is chosen. */
+  onMenuItemClick(callback: (id: string) => void): void {
+    this._ensureOpen();
+    this._native.onMenuItemClick(callback);
+  }
+
+  onReload(callback: () => void): void {
+    this._ensureOpen();
+    this._native.onReload(callback);
+  }
+
+  /**
+   * Register a handler for blocked navigation events.
+   * Fired when a navigation is blocked by the {@link WindowOptions.allowedHosts}
+   * restriction. Receives the URL that was blocked.
+   *
+   * @example
+   * ```ts
+   * win.onNavigationBlocked((url) => {
+   *   console.log("Blocked navigation to:", url);
+   * });
+   * ```
+   */
+  onNavigationBlocked(callback: (url: string) => void): void {
+    this._ensureOpen();
+    this._native.onNavigationBlocked(callback);
+  }
+
+  /**
+   * Register a handler for iframes loading their first document. `frameId`
+   * is unique within the process and matches the later
+   * {@link onFrameDestroyed} call.
+   *
+   * On macOS and Linux every document counts as a frame, so an iframe that
+   * navigates is reported as destroyed and created again. On Windows only
+   * iframes of the top-level page are reported.
+   *
+   * @example
+   * ```ts
+   * win.onFrameCreated((frameId, url) => {
+   *   console.log(`Frame ${frameId} loaded ${url}`);
+   * });
+   * ```
+   */
+  onFrameCreated(callback: (frameId: number, url: string) => void): void {
+    this._ensureOpen();
+    this._native.onFrameCreated(callback);
+  }
+
+  /**
+   * Register a handler for iframes that were removed or unloaded.
+   * See {@link onFrameCreated}.
+   */
+  onFrameDestroyed(callback: (frameId: number) => void): void {
+    this._ensureOpen();
+    this._native.onFrameDestroyed(callback);
+  }
+
+  /**
+   * Register a handler for throttled navigation events.
+   * Fired when a navigation is blocked because the window exceeded
+   * {@link WindowOptions.maxNavigationsPerMinute}. Receives the URL that was
+   * blocked.
+   */
+  onNavigationThrottled(callback: (url: string) => void): void {
+    this._ensureOpen();
+    this._native.onNavigationThrottled(callback);
+  }
+
+  /**
+   * Register a handler fired when the {@link setFallbackHtml} document
+   * replaced a page that failed to load. Receives the URL that failed and
+   * an error code: `"disconnected"`, `"hostNotFound"`, `"timeout"`,
+   * `"connectionFailed"`, `"certificateError"` or `"other"`.
+   */
+  onFallbackShown(callback: (originalUrl: string, errorCode: FallbackErrorCode) => void): void {
+    this._ensureOpen();
+    this._native.onFallbackShown(callback as (originalUrl: string, errorCode: string) => void);
+  }
+
+  // ---- Cookie access ----
+
+  /**
+   * Validate and parse a raw cookies JSON array from the native layer.
+   * Returns a cleaned {@link CookieInfo} array or `null` if the payload
+   * is malformed.
+   *
+   * @internal
+   */
+  private _validateCookies(raw: string): CookieInfo[] | null {
+    let parsed: unknown;
+    try {
+      parsed = JSON.parse(raw);
+    } catch {
+      return null;
+    }
+    if (!Array.isArray(parsed)) return null;
+
+    const cookies: CookieInfo[] = [];
+    for (const item of parsed) {
+      if (typeof item !== "object" || item === null) continue;
+      const obj = item as Record<string, unknown>;
+      if (typeof obj.name !== "string" || typeof obj.value !== "string") {
+        continue;
+      }
+      if (typeof obj.domain !== "string" || typeof obj.path !== "string") {
+        continue;
+      }
+      if (typeof obj.httpOnly !== "boolean" || typeof obj.secure !== "boolean") {
+        continue;
+      }
+      if (typeof obj.sameSite !== "string" || typeof obj.expires !== "number") {
+        continue;
+      }
+      cookies.push({
+        name: obj.name as string,
+        value: obj.value as string,
+        domain: obj.domain as string,
+        path: obj.path as string,
+        httpOnly: obj.httpOnly as boolean,
+        secure: obj.secure as boolean,
+        sameSite: obj.sameSite as string,
+        expires: obj.expires as number,
+      });
+    }
+    return cookies;
+  }
+
+  /**
+   * Query cookies from the native cookie store.
+   *
+   * Returns a Promise that resolves with validated {@link CookieInfo} objects,
+   * including `HttpOnly` cookies that are invisible to `document.cookie`.
+   *
+   * - **macOS**: Uses `WKHTTPCookieStore.getAllCookies` with client-side
+   *   URL filtering (domain + path match).
+   * - **Windows**: Uses `ICoreWebView2CookieManager.GetCookies` which
+   *   filters by URI natively.
+   *
+   * @param url  If provided, only cookies matching this URL are returned.
+   *             If omitted, all cookies in the webview's cookie store are returned.
+   *
+   * @example
+   * ```ts
+   * const cookies = await win.getCookies("https://example.com");
+   * const session = cookies.find((c) => c.name === "session_id");
+   * if (session) console.log("Session:", session.value, "HttpOnly:", session.httpOnly);
+   * ```
+   */
+  getCookies(url?: string): Promise<CookieInfo[]> {
+    this._ensureOpen();
+    return new Promise((resolve, reject) => {
+      const timeout = setTimeout(() => {
+        reject(new Error("getCookies() timed out after 10 seconds"));
+      }, 10_000);
+      this._native.onCookies((raw: string) => {
+        clearTimeout(timeout);
+        const validated = this._validateCookies(raw);
+        if (validated) {
+          resolve(validated);
+        } else {
+          reject(new Error("Failed to parse cookie response"));
+        }
+      });
+      this._native.getCookies(url);
+    });
+  }
+
+  // ---- Text search ----
+
+  /**
+   * Count case-insensitive occurrences of `text` in the rendered page.
+   *
+   * Unlike find-in-page, this never scrolls the viewport or changes the
+   * selection, so it can be called on every keystroke to build
+   * filter-as-you-type experiences; overlapping calls each get their own
+   * result. `<script>` and `<style>` contents are not counted. Rejects if
+   * the page cannot be searched.
+   *
+   * @example
+   * ```ts
+   * const count = await win.countMatches("invoice");
+   * console.log(`${count} matches`);
+   * ```
+   */
+  countMatches(text: string): Promise<number> {
+    this._ensureOpen();
+    return this._request(
+      "countMatches",
+      (cb: (count: number | null) => void) => this._native.onMatchCount(cb),
+      () => this._native.countMatches(text),
+      10_000,
+      (count) => {
+        if (count === null) {
+          throw new Error("countMatches() failed: the page could not be searched");
+        }
+        return count;
+      },
+    );
+  }
+
+  // ---- Accessibility ----
+
+  /**
+   * Force the engine to build and maintain its accessibility tree even when
+   * no screen reader is running, so tools and automated audits can query it.
+   * Has no effect on macOS/Linux, where the tree is built on demand.
+   */
+  setAccessibilityEnabled(enabled: boolean): void {
+    this._ensureOpen();
+    this._native.setAccessibilityEnabled(enabled);
+  }
+
+  /**
+   * Capture the accessibility tree of the current page.
+   * Rejects if the page cannot be inspected or the engine does not respond
+   * within 10 seconds.
+   *
+   * @example
+   * ```ts
+   * const { nodes } = await win.getAccessibilitySnapshot();
+   * const buttons = nodes.filter((n) => n.role?.value === "button");
+   * ```
+   */
+  getAccessibilitySnapshot(): Promise<AccessibilitySnapshot> {
+    this._ensureOpen();
+    return this._request(
+      "getAccessibilitySnapshot",
+      (cb: (snapshot: string) => void) => this._native.onAccessibilitySnapshot(cb),
+      () => this._native.getAccessibilitySnapshot(),
+      10_000,
+      (snapshot) => {
+        const parsed = JSON.parse(snapshot) as AccessibilitySnapshot | null;
+        if (!parsed || !Array.isArray(parsed.nodes)) {
+          throw new Error("Accessibility snapshot unavailable");
+        }
+        return parsed;
+      },
+    );
+  }
+
+  // ---- Performance ----
+
+  /**
+   * Read load timings and runtime counters for the current page, e.g. to
+   * monitor the real-world load performance of a webview UI.
+   *
+   * Timings come from the Navigation and Paint Timing APIs on every
+   * platform. On Windows, DevTools performance metrics add exact JS heap
+   * sizes, layout/style counts and script/task durations; elsewhere those
+   * fields are `null` (JS heap sizes too, as WebKit does not expose them).
+   *
+   * @example
+   * ```ts
+   * win.onPageLoad(async (event) => {
+   *   if (event !== "finished") return;
+   *   const { firstContentfulPaint, load } = await win.getPagePerformanceMetrics();
+   *   console.log({ firstContentfulPaint, load });
+   * });
+   * ```
+   */
+  getPagePerformanceMetrics(): Promise<PagePerformanceMetrics> {
+    this._ensureOpen();
+    return this._request(
+      "getPagePerformanceMetrics",
+      (cb: (json: string) => void) => this._native.onPerformanceMetrics(cb),
+      () => this._native.getPagePerformanceMetrics(),
+      10_000,
+      (json) => {
+        const payload = JSON.parse(json) as PerformanceMetricsPayload | null;
+        const metrics = payload && buildPerformanceMetrics(payload);
+        if (!metrics) {
+          throw new Error("Page performance metrics unavailable");
+        }
+        return metrics;
+      },
+    );
+  }
+
+  // ---- Reader mode ----
+
+  /**
+   * Extract the main article of the current page, e.g. for a preview pane
+   * or to share a third-party page as text. The extraction runs in an
+   * isolated script world, out of reach of the page's own scripts.
+   * Rejects if no article is found or the engine does not respond within
+   * 10 seconds.
+   *
+   * @example
+   * ```ts
+   * const { title, text } = await win.extractReadableContent();
+   * preview.postMessage(JSON.stringify({ title, text }));
+   * ```
+   */
+  extractReadableContent(): Promise<ReadableContent> {
+    this._ensureOpen();
+    return this._request(
+      "extractReadableContent",
+      (cb: (json: string) => void) => this._native.onReadableContent(cb),
+      () => this._native.extractReadableContent(),
+      10_000,
+      (json) => {
+        const content = JSON.parse(json) as ReadableContent | null;
+        if (!content || typeof content.html !== "string") {
+          throw new Error("No readable content found");
+        }
+        return content;
+      },
+    );
+  }
+
+  // ---- Emulation ----
+
+  /**
+   * Render dates and times as if the host were in `timezone`, an IANA ID
+   * such as `"America/New_York"`. `null` restores the host time zone.
+   *
+   * Uses DevTools emulation on Windows. On macOS/Linux a script shim
+   * covers `Intl.DateTimeFormat`, `Date#toLocale*String` and
+   * `getTimezoneOffset()`, and is applied after each page finishes loading.
+   */
+  setEmulatedTimezone(timezone: string | null): void {
+    this._ensureOpen();
+    this._native.setEmulatedTimezone(timezone);
+  }
+
+  /**
+   * Format dates, numbers and `navigator.language` with `locale`, a BCP 47
+   * tag such as `"de-DE"`. `null` restores the host locale. Same engine
+   * support as {@link setEmulatedTimezone}.
+   */
+  setEmulatedLocale(locale: string | null): void {
+    this._ensureOpen();
+    this._native.setEmulatedLocale(locale);
+  }
+
+  // ---- Fonts ----
+
+  /**
+   * Override the font used when page CSS names none, and the smallest font
+   * size text is drawn at, for accessibility policies on pages whose CSS
+   * the app cannot change. `null` restores the engine defaults.
+   *
+   * WebView2 has no minimum font size, so on Windows `minimumFontSize`
+   * raises the default font sizes instead: text sized in `em`/`rem` or left
+   * at the default grows, text sized in `px` does not.
+   *
+   * @example
+   * ```ts
+   * win.setFontOverrides({ standardFamily: "Atkinson Hyperlegible", minimumFontSize: 14 });
+   * ```
+   */
+  setFontOverrides(overrides: FontOverrides | null): void {
+    this._ensureOpen();
+    this._native.setFontOverrides(overrides);
+  }
+
+  /**
+   * Scale the page's text to `percent` (25–500) of its size; `100` restores
+   * it. Linux (WebKitGTK) zooms text only; Windows and macOS have no
+   * text-only zoom and zoom the whole page.
+   */
+  setTextZoom(percent: number): void {
+    this._ensureOpen();
+    this._native.setTextZoom(percent);
+  }
+
+  /**
+   * Emulate a slow or missing network, e.g. to test offline UX from an
+   * automated suite. `null` restores the real network.
+   *
+   * Windows uses DevTools network emulation (all four fields). On
+   * macOS/Linux a script shim covers `offline` and `latencyMs` for
+   * `fetch()`/XHR and `navigator.onLine`; throughput is ignored.
+   *
+   * @example
+   * ```ts
+   * win.setNetworkConditions({ offline: true });
+   * win.setNetworkConditions({ latencyMs: 300, downloadThroughput: 50_000 });
+   * win.setNetworkConditions(null);
+   * ```
+   */
+  setNetworkConditions(conditions: NetworkConditions | null): void {
+    this._ensureOpen();
+    this._native.setNetworkConditions(conditions);
+  }
+
+  // ---- Network capture ----
+
+  /**
+   * Start recording the webview's network requests. Call
+   * {@link stopNetworkCapture} to end the recording and get it as a HAR
+   * log. Starting again while a capture is running discards it.
+   *
+   * Windows records every request via DevTools network events, including
+   * headers and detailed timings. On macOS/Linux the recording is read from
+   * the page's Resource Timing entries: request headers and methods are not
+   * available, and only resources of the current document are included.
+   */
+  startNetworkCapture(): void {
+    this._ensureOpen();
+    this._native.startNetworkCapture();
+  }
+
+  /**
+   * Stop the running network capture and resolve with its HAR 1.2 log,
+   * e.g. for attaching to bug reports. Rejects when no capture is running.
+   *
+   * @example
+   * ```ts
+   * win.startNetworkCapture();
+   * win.loadUrl("https://example.com");
+   * // ...
+   * const har = await win.stopNetworkCapture();
+   * await Bun.write("session.har", JSON.stringify(har));
+   * ```
+   */
+  stopNetworkCapture(): Promise<HarJson> {
+    this._ensureOpen();
+    return new Promise((resolve, reject) => {
+      const timeout = setTimeout(() => {
+        reject(new Error("stopNetworkCapture() timed out after 10 seconds"));
+      }, 10_000);
+      this._native.onNetworkCapture((json: string | null) => {
+        clearTimeout(timeout);
+        if (json === null) {
+          reject(new Error("No network capture is running"));
+          return;
+        }
+        try {
+          resolve(buildHar(JSON.parse(json)));
+        } catch (err) {
+          reject(err);
+        }
+      });
+      this._native.stopNetworkCapture();
+    });
+  }
+
+  // ---- Tracing ----
+
+  /**
+   * Start recording a Chrome trace of the webview, for diagnosing jank in
+   * complex UIs. `categories` selects trace categories such as
+   * `"devtools.timeline"` or `"v8"`; omit it for the engine's defaults.
+   *
+   * Tracing uses the DevTools protocol and is only available on Windows
+   * (WebView2). Throws on macOS/Linux, or if a trace is already running.
+   *
+   * @example
+   * ```ts
+   * win.startTracing(["devtools.timeline", "v8.execute"]);
+   * // ... reproduce the jank ...
+   * await win.stopTracing("trace.json"); // open in chrome://tracing or Perfetto
+   * ```
+   */
+  startTracing(categories: string[] = []): void {
+    this._ensureOpen();
+    if (this._tracing) {
+      throw new Error("A trace is already running");
+    }
+    this._native.startTracing(categories);
+    this._tracing = true;
+  }
+
+  /**
+   * Stop the running trace and write it to `path` in the Chrome trace
+   * event format. Rejects if no trace is running.
+   */
+  stopTracing(path: string): Promise<void> {
+    this._ensureOpen();
+    this._tracing = false;
+    return new Promise((resolve, reject) => {
+      // Flushing a trace can take a while for busy pages.
+      const timeout = setTimeout(() => {
+        reject(new Error("stopTracing() timed out after 30 seconds"));
+      }, 30_000);
+      this._native.onTrace((json: string | null) => {
+        clearTimeout(timeout);
+        if (json === null) {
+          reject(new Error("No trace is running"));
+          return;
+        }
+        try {
+          const batches = JSON.parse(json) as { value?: unknown[] }[];
+          const traceEvents = batches.flatMap((batch) => batch.value ?? []);
+          writeFile(path, JSON.stringify({ traceEvents })).then(resolve, reject);
+        } catch (e) {
+          reject(e);
+        }
+      });
+      this._native.stopTracing();
+    });
+  }
+
+  // ---- Heap snapshots ----
+
+  /** @internal The last heap snapshot requested, which the next one waits for. */
+  private _heapSnapshot: Promise<void> | null = null;
+
+  /**
+   * Capture a V8 heap snapshot of the page and write it to `path`, so memory
+   * leaks in long-running windows can be investigated without attaching
+   * DevTools. Load the file in the DevTools Memory panel.
+   *
+   * Heap snapshots use the DevTools protocol and are only available on
+   * Windows (WebView2). Throws on macOS/Linux.
+   *
+   * @example
+   * ```ts
+   * await win.takeHeapSnapshot(`kiosk-${Date.now()}.heapsnapshot`);
+   * ```
+   */
+  takeHeapSnapshot(path: string): Promise<void> {
+    this._ensureOpen();
+    // Snapshots of large heaps take a while to serialize.
+    const take = () =>
+      this._request(
+        "takeHeapSnapshot",
+        (cb: (json: string | null) => void) => this._native.onHeapSnapshot(cb),
+        () => this._native.takeHeapSnapshot(),
+        60_000,
+        (json) => {
+          if (json === null) {
+            throw new Error("Heap snapshot failed");
+          }
+          const chunks = JSON.parse(json) as { chunk: string }[];
+          return writeFile(path, chunks.map((c) => c.chunk).join(""));
+        },
+      );
+    // Chunks arrive as DevTools events seen by every snapshot in flight,
+    // so a snapshot waits for the previous one to settle.
+    const snapshot = this._heapSnapshot ? this._heapSnapshot.then(take, take) : take();
+    this._heapSnapshot = snapshot;
+    return snapshot;
+  }
+
+  // ---- Screenshots ----
+
+  /**
+   * Capture the entire window — native title bar and borders included —
+   * as PNG bytes, e.g. for attaching to bug reports.
+   *
+   * Uses `CGWindowListCreateImage` on macOS and `PrintWindow` on Windows.
+   * On Linux the frame is read back from the screen, which requires X11
+   * and a window that is visible and unobscured.
+   *
+   * @example
+   * ```ts
+   * const png = await win.captureWindow();
+   * await Bun.write("window.png", png);
+   * ```
+   */
+  captureWindow(): Promise<Buffer> {
+    this._ensureOpen();
+    return this._request(
+      "captureWindow",
+      (cb: (png: Buffer | null) => void) => this._native.onWindowCapture(cb),
+      () => this._native.captureWindow(),
+      10_000,
+      (png) => {
+        if (!png) {
+          throw new Error("Window capture failed");
+        }
+        return png;
+      },
+    );
+  }
+
+  /**
+   * Capture the visible area of the page as PNG bytes, without the native
+   * frame. Unlike {@link captureWindow}, the snapshot comes from the
+   * engine, so it also works for windows that are off-screen or covered.
+   *
+   * @example
+   * ```ts
+   * const png = await win.capturePage();
+   * ```
+   */
+  capturePage(): Promise<Buffer> {
+    this._ensureOpen();
+    return this._request(
+      "capturePage",
+      (cb: (png: Buffer | null) => void) => this._native.onPageCapture(cb),
+      () => this._native.capturePage(),
+      10_000,
+      (png) => {
+        if (!png) {
+          throw new Error("Page capture failed");
+        }
+        return png;
+      },
+    );
+  }
+
+  /**
+   * Capture the window like {@link captureWindow}, scaled down to fit
+   * `maxWidth` x `maxHeight` physical pixels with its aspect ratio kept —
+   * for window switchers and dashboards. Works while the window is covered
+   * by other windows on macOS and Windows, but not while minimized.
+   *
+   * @example
+   * ```ts
+   * const thumbs = await Promise.all(windows.map((w) => w.getThumbnail(320, 200)));
+   * ```
+   */
+  getThumbnail(maxWidth: number, maxHeight: number): Promise<Buffer> {
+    this._ensureOpen();
+    return this._request(
+      "getThumbnail",
+      (cb: (png: Buffer | null) => void) => this._native.onThumbnail(cb),
+      () => this._native.getThumbnail(maxWidth, maxHeight),
+      10_000,
+      (png) => {
+        if (!png) {
+          throw new Error("Window thumbnail capture failed");
+        }
+        return png;
+      },
+    );
+  }
+
+  // ---- Color picking ----
+
+  /**
+   * Let the user pick a color anywhere on screen: the system eyedropper on
+   * macOS, a magnifier over a frozen screenshot on Windows (click to pick,
+   * Escape or right-click to cancel). Resolves with `null` when the user
+   * cancels. Throws on Linux (see `getCapabilities().supportsColorPicker`).
+   *
+   * @example
+   * ```ts
+   * const color = await win.pickColorFromScreen();
+   * if (color) setFill(`rgb(${color.r} ${color.g} ${color.b})`);
+   * ```
+   */
+  pickColorFromScreen(): Promise<PickedColor | null> {
+    this._ensureOpen();
+    return new Promise((resolve) => {
+      // No timeout: the user may take as long as they like.
+      this._native.onColorPicked(resolve);
+      this._native.pickColorFromScreen();
+    });
+  }
+
+  // ---- Sharing ----
+
+  /**
+   * Open the native share sheet anchored to this window:
+   * `NSSharingServicePicker` on macOS, the Share UI on Windows. At least one
+   * of `text`, `url` or `files` is required; file paths must be absolute.
+   * `title` is only shown on Windows. Throws on Linux (see
+   * `getCapabilities().supportsShare`).
+   *
+   * @example
+   * ```ts
+   * win.onMessage((msg) => {
+   *   const { type, url } = JSON.parse(msg);
+   *   if (type === "share") win.share({ title: "Check this out", url });
+   * });
+   * ```
+   */
+  share(options: ShareOptions): void {
+    this._ensureOpen();
+    this._native.share(options);
+  }
+
+  // ---- Frame capture ----
+
+  /**
+   * Stream composited frames of the page to the {@link onFrame} handler,
+   * e.g. for preview thumbnails, remote mirroring, or recording. Frames are
+   * only produced when the page repaints, so a static page yields few.
+   * Restarting replaces the running capture's settings.
+   *
+   * Frame capture uses the DevTools protocol (`Page.startScreencast`) and
+   * is only available on Windows (WebView2). Throws on macOS/Linux.
+   *
+   * @example
+   * ```ts
+   * win.onFrame((jpeg, width, height) => mirror.send(jpeg));
+   * win.startFrameCapture({ fps: 15 });
+   * ```
+   */
+  startFrameCapture(options: FrameCaptureOptions = {}): void {
+    this._ensureOpen();
+    this._native.startFrameCapture(options.fps ?? 30, options.format);
+  }
+
+  /** Stop the running frame capture. Does nothing if none is running. */
+  stopFrameCapture(): void {
+    this._ensureOpen();
+    this._native.stopFrameCapture();
+  }
+
+  /**
+   * Register a handler for frames from {@link startFrameCapture}. Receives
+   * the encoded image and the page viewport size in CSS pixels; the image
+   * itself is in device pixels.
+   */
+  onFrame(callback: (frame: Buffer, width: number, height: number) => void): void {
+    this._ensureOpen();
+    this._native.onFrame((json: string) => {
+      const { data, metadata } = JSON.parse(json) as {
+        data: string;
+        metadata: { deviceWidth: number; deviceHeight: number };
+      };
+      callback(Buffer.from(data, "base64"), metadata.deviceWidth, metadata.deviceHeight);
+    });
+  }
+
+  // ---- Audio capture ----
+
+  /** @internal Consumer of the running `startAudioCapture()` session. */
+  private _audioSink: ((pcm: Buffer | null, error: string | null) => void) | null = null;
+  /** @internal Ends still to arrive for captures stopped from JS. */
+  private _audioEndsPending = 0;
+  /** @internal */
+  private _audioHandlerRegistered = false;
+
+  /**
+   * Record the audio played by the page as 16-bit little-endian stereo PCM
+   * at 48 kHz. Breaking out of the loop stops the capture; closing the
+   * window ends it. Rejects on platforms without audio capture (see
+   * `getCapabilities().supportsAudioCapture`) or when capture fails.
+   *
+   * On Windows, audio of every webview of the app is captured, since
+   * WebView2 plays it from a shared process.
+   *
+   * @example
+   * ```ts
+   * for await (const pcm of win.startAudioCapture()) {
+   *   visualizer.push(pcm);
+   * }
+   * ```
+   */
+  startAudioCapture(): AsyncIterableIterator<Buffer> {
+    this._ensureOpen();
+    if (this._audioSink) {
+      throw new Error("Audio capture is already running");
+    }
+    if (!this._audioHandlerRegistered) {
+      this._audioHandlerRegistered = true;
+      this._native.onAudioCapture((pcm: Buffer | null, error: string | null) => {
+        // Output of a capture already stopped from JS, up to its end.
+        if (this._audioEndsPending > 0) {
+          if (pcm === null) this._audioEndsPending--;
+          return;
+        }
+        this._audioSink?.(pcm, error);
+      });
+    }
+
+    const queue: Buffer[] = [];
+    let waiter: {
+      resolve: (result: IteratorResult<Buffer>) => void;
+      reject: (err: Error) => void;
+    } | null = null;
+    let ended = false;
+    let failure: Error | null = null;
+    const finish = (err: Error | null) => {
+      ended = true;
+      failure = err;
+      this._audioSink = null;
+      const w = waiter;
+      waiter = null;
+      if (w && err) w.reject(err);
+      else if (w) w.resolve({ value: undefined, done: true });
+    };
+    this._audioSink = (pcm, error) => {
+      if (pcm === null) {
+        finish(error === null ? null : new Error(`Audio capture failed: ${error}`));
+      } else if (waiter) {
+        const w = waiter;
+        waiter = null;
+        w.resolve({ value: pcm, done: false });
+      } else {
+        queue.push(pcm);
+      }
+    };
+
+    const iterator: AsyncIterableIterator<Buffer> = {
+      next: () => {
+        const pcm = queue.shift();
+        if (pcm) return Promise.resolve({ value: pcm, done: false });
+        if (ended) {
+          const err = failure;
+          failure = null;
+          return err ? Promise.reject(err) : Promise.resolve({ value: undefined, done: true });
+        }
+        return new Promise((resolve, reject) => {
+          waiter = { resolve, reject };
+        });
+      },
+      return: () => {
+        if (!ended) {
+          finish(null);
+          if (!this._closed) {
+            this._audioEndsPending++;
+            this._native.stopAudioCapture();
+          }
+        }
+        queue.length = 0;
+        return Promise.resolve({ value: undefined, done: true });
+      },
+      [Symbol.asyncIterator]() {
+        return iterator;
+      },
+    };
+    this._native.startAudioCapture();
+    return iterator;
+  }
+}
+
+// ---------------------------------------------------------------------------
+// Popups
+// ---------------------------------------------------------------------------
+
+/** Result of {@link NativeWindow.getWindowWorkspace}. */
+export interface WindowWorkspace {
+  /** On the macOS Space / Windows virtual desktop the user is looking at. Always `true` on Linux. */
+  onActiveWorkspace: boolean;
+  /** Shown on every Space / workspace / virtual desktop. */
+  visibleOnAllWorkspaces: boolean;
+}
+
+/** Options for {@link NativeWindow.startFrameCapture}. */
+export interface FrameCaptureOptions {
+  /** Maximum frames per second delivered to `onFrame`. Default: 30. */
+  fps?: number;
+  /** Image encoding of frames. Default: `"jpeg"`. */
+  format?: "jpeg" | "png";
+}
+
+/** Options for {@link openPopup}. */
+export interface OpenPopupOptions {
+  /** Window and rectangle the popup is attached to. */
+  anchor: PopupAnchor;
+  /** Window options for the popup. Position is derived from `anchor`. */
+  options?: Omit<WindowOptions, "anchor" | "display" | "x" | "y">;
+}
+
+/**
+ * Open a borderless popup window positioned against a rectangle inside
+ * another window — for dropdowns, menus, and tooltips that must extend past
+ * the parent window's bounds.
+ *
+ * The popup does not take focus when it opens, flips to the other side of
+ * the anchor near screen edges, and closes itself (firing `onClose`) on a
+ * click outside it, when the anchor window moves or closes, or when the
+ * app loses focus.
+ *
+ * @example
+ * ```ts
+ * // rect from button.getBoundingClientRect(), sent over IPC
+ * const menu = openPopup({
+ *   anchor: { windowId: win.id, rect },
+ *   options: { width: 220, height: 300 },
+ * });
+ * menu.loadHtml(menuHtml);
+ * ```
+ */
+export function openPopup({ anchor, options }: OpenPopupOptions): NativeWindow {
+  return new NativeWindow({ ...options, anchor });
+}
+
+/** Options for {@link showTooltipWindow}. */
+export interface TooltipWindowOptions {
+  /** Screen X position (logical pixels) the tooltip points at. */
+  x: number;
+  /** Screen Y position (logical pixels) the tooltip points at. */
+  y: number;
+  /** Auto-close delay in milliseconds. Default: 3000 */
+  timeoutMs?: number;
+  /** Tooltip width in logical pixels. Default: 240 */
+  width?: number;
+  /** Tooltip height in logical pixels. Default: 80 */
+  height?: number;
+  /** Preferred side of the point. Default: "below" */
+  placement?: PopupAnchor["placement"];
+}
+
+/**
+ * Show a small, self-destroying HTML bubble at a screen position — e.g. a
+ * status message next to the tray icon. Built on {@link openPopup}: it
+ * flips away from screen edges, never takes focus, and closes on an outside
+ * click or after `timeoutMs`.
+ *
+ * Returns the underlying window so it can be closed early.
+ *
+ * @example
+ * ```ts
+ * showTooltipWindow("<p>Sync complete</p>", { x: 1200, y: 40, timeoutMs: 2000 });
+ * ```
+ */
+export function showTooltipWindow(
+  html: string,
+  options: TooltipWindowOptions,
+): NativeWindow {
+  const { x, y, timeoutMs = 3000, width = 240, height = 80, placement } =
+    options;
+  const tip = openPopup({
+    anchor: { rect: { x, y, width: 0, height: 0 }, placement },
+    options: { width, height, resizable: false },
+  });
+  tip.loadHtml(html);
+  setTimeout(() => {
+    try {
+      tip.close();
+    } catch {
+      // Already closed (outside click or by the caller)
+    }
+  }, timeoutMs);
+  return tip;
+}
+
+// ---------------------------------------------------------------------------
+// Splash screen
+// ---------------------------------------------------------------------------
+
+/** Duration of the splash fade-out, in milliseconds. */
+const SPLASH_FADE_MS = 200;
+
+const SPLASH_IMAGE_TYPES: Record<string, string> = {
+  ".png": "image/png",
+  ".jpg": "image/jpeg",
+  ".jpeg": "image/jpeg",
+  ".gif": "image/gif",
+  ".svg": "image/svg+xml",
+  ".webp": "image/webp",
+};
+
+/** Options for {@link showSplash}. Provide exactly one of `html` or `imagePath`. */
+export interface SplashOptions {
+  /** HTML content of the splash screen. */
+  html?: string;
+  /** Path to an image (PNG, JPEG, GIF, SVG, or WebP) shown scaled to fit. */
+  imagePath?: string;
+  /** Splash width in logical pixels. Default: 480 */
+  width?: number;
+  /** Splash height in logical pixels. Default: 320 */
+  height?: number;
+  /** Minimum time the splash stays on screen. Default: 0 */
+  minDurationMs?: number;
+}
+
+/** Handle returned by {@link showSplash}. */
+export interface Splash {
+  /** The splash window. */
+  readonly window: NativeWindow;
+  /**
+   * Show `main` and fade the splash out once `main` is ready to show
+   * (see {@link NativeWindow.onReadyToShow}) and `minDurationMs` has passed.
+   * Create `main` with `visible: false`.
+   */
+  closeWhenReady(main: NativeWindow): void;
+  /** Fade the splash out and close it now. */
+  close(): void;
+}
+
+/**
+ * Show a frameless splash window centered on the primary display while the
+ * main window's webview starts up.
+ *
+ * @example
+ * ```ts
+ * const splash = showSplash({ imagePath: "./assets/splash.png", minDurationMs: 800 });
+ * const win = new NativeWindow({ visible: false });
+ * win.loadUrl("https://app.example.com");
+ * splash.closeWhenReady(win);
+ * ```
+ */
+export function showSplash(options: SplashOptions): Splash {
+  const { html, imagePath, width = 480, height = 320, minDurationMs = 0 } =
+    options;
+  if ((html === undefined) === (imagePath === undefined)) {
+    throw new Error("showSplash() requires exactly one of `html` or `imagePath`");
+  }
+
+  let content = html;
+  if (imagePath !== undefined) {
+    const type = SPLASH_IMAGE_TYPES[extname(imagePath).toLowerCase()];
+    if (!type) {
+      throw new Error(`Unsupported splash image type: ${imagePath}`);
+    }
+    const data = readFileSync(imagePath).toString("base64");
+    content =
+      `<!doctype html><html><body style="margin:0;height:100vh;display:flex;` +
+      `align-items:center;justify-content:center;background:transparent">` +
+      `<img src="data:${type};base64,${data}" style="max-width:100%;max-height:100%">` +
+      `</body></html>`;
+  }
+
+  const shownAt = Date.now();
+  const win = new NativeWindow({
+    width,
+    height,
+    display: "primary",
+    decorations: false,
+    resizable: false,
+    transparent: true,
+    alwaysOnTop: true,
+  });
+  win.loadHtml(content!);
+
+  let closing = false;
+  const close = () => {
+    if (closing) return;
+    closing = true;
+    try {
+      win.unsafe.evaluateJs(
+        `document.documentElement.style.transition = "opacity ${SPLASH_FADE_MS}ms";` +
+          `document.documentElement.style.opacity = "0";`,
+      );
+    } catch {
+      // Already closed
+    }
+    setTimeout(() => {
+      try {
+        win.close();
+      } catch {
+        // Already closed
+      }
+    }, SPLASH_FADE_MS);
+  };
+
+  return {
+    window: win,
+    close,
+    closeWhenReady(main: NativeWindow) {
+      main.onReadyToShow(() => {
+        const remaining = Math.max(0, minDurationMs - (Date.now() - shownAt));
+        setTimeout(() => {
+          main.show();
+          close();
+        }, remaining);
+      });
+    },
+  };
+}
+
+// ---------------------------------------------------------------------------
+// About panel
+// ---------------------------------------------------------------------------
+
+const ABOUT_ICON_TYPES: Record<string, string> = {
+  ".png": "image/png",
+  ".ico": "image/x-icon",
+};
+
+function escapeHtml(text: string): string {
+  return text
+    .replace(/&/g, "&amp;")
+    .replace(/</g, "&lt;")
+    .replace(/>/g, "&gt;")
+    .replace(/"/g, "&quot;");
+}
+
+/**
+ * Show a standard About panel with the app's name, version, copyright
+ * and icon.
+ *
+ * - **macOS**: the native About panel. Returns `null`.
+ * - **Windows/Linux**: a small, non-resizable window built from the same
+ *   fields, centered on the primary display. Returns that window.
+ *
+ * @example
+ * ```ts
+ * showAboutPanel({
+ *   appName: "Notes",
+ *   version: "1.4.2",
+ *   copyright: "© 2026 Example Inc.",
+ *   iconPath: "./assets/icon.png",
+ * });
+ * ```
+ */
+export function showAboutPanel(options: AboutPanelOptions): NativeWindow | null {
+  const { appName, version, copyright, iconPath } = options;
+  if (iconPath !== undefined && !ABOUT_ICON_TYPES[extname(iconPath).toLowerCase()]) {
+    throw new Error(`Unsupported about panel icon type: ${iconPath}`);
+  }
+  if (showNativeAboutPanel(options)) {
+    return null;
+  }
+
+  let icon = "";
+  if (iconPath !== undefined) {
+    const type = ABOUT_ICON_TYPES[extname(iconPath).toLowerCase()];
+    const data = readFileSync(iconPath).toString("base64");
+    icon = `<img src="data:${type};base64,${data}" width="64" height="64" alt="">`;
+  }
+  const line = (text: string | undefined, style: string) =>
+    text === undefined ? "" : `<div style="${style}">${escapeHtml(text)}</div>`;
+
+  const win = new NativeWindow({
+    title: appName === undefined ? "About" : `About ${appName}`,
+    width: 320,
+    height: 240,
+    display: "primary",
+    resizable: false,
+    icon: iconPath,
+  });
+  win.loadHtml(
+    `<!doctype html><html><body style="margin:0;height:100vh;display:flex;` +
+      `flex-direction:column;align-items:center;justify-content:center;gap:6px;` +
+      `font:13px system-ui,sans-serif;text-align:center;user-select:none;` +
+      `color-scheme:light dark">` +
+      icon +
+      line(appName, "font-size:16px;font-weight:600;margin-top:8px") +
+      line(version === undefined ? undefined : `Version ${version}`, "") +
+      line(copyright, "opacity:.6;font-size:11px;margin-top:8px") +
+      `</body></html>`,
+  );
+  return win;
+}
+
+// ---------------------------------------------------------------------------
+// Updates
+// ---------------------------------------------------------------------------
+
+/** Options for {@link downloadUpdate}. */
+export interface DownloadUpdateOptions extends UpdateDownloadOptions {
+  /** Called as the file downloads, about five times a second. */
+  onProgress?: (progress: UpdateProgress) => void;
+}
+
+/**
+ * Download an installer or archive and verify it by `sha256`, by the
+ * platform code signature (`signer`), or both. Resolves with the path of
+ * the verified file; a file that fails verification is deleted and the
+ * promise rejects. Hand the file to {@link relaunch} to install it.
+ *
+ * @example
+ * ```ts
+ * const installer = await downloadUpdate({
+ *   url: "https://example.com/releases/app-1.5.0-setup.exe",
+ *   signer: "Example Inc.",
+ *   onProgress: ({ received, total }) => bar.set(total ? received / total : 0),
+ * });
+ * relaunch({ execPath: installer, args: ["/S"] });
+ * process.exit(0);
+ * ```
+ */
+export function downloadUpdate(options: DownloadUpdateOptions): Promise<string> {
+  const { onProgress, ...download } = options;
+  return new Promise((resolve, reject) => {
+    _downloadUpdate(
+      download,
+      (progress) => onProgress?.(progress),
+      (result) => {
+        if (result.path !== undefined) {
+          resolve(result.path);
+        } else {
+          reject(new Error(`downloadUpdate() failed: ${result.error}`));
+        }
+      },
+    );
+  });
+}
+
+// ---------------------------------------------------------------------------
+// HTML rendering
+// ---------------------------------------------------------------------------
+
+/** Options for {@link renderHtmlToImage}. */
+export interface RenderHtmlToImageOptions {
+  /** Image width in logical pixels. */
+  width: number;
+  /** Image height in logical pixels. */
+  height: number;
+  /** Time allowed for the page to load and paint. Default: 10000 */
+  timeoutMs?: number;
+}
+
+const RENDER_READY_MESSAGE = "__native_render_ready__";
+
+/** Posts once web fonts are loaded and the next frame has painted. */
+const RENDER_READY_SCRIPT =
+  `<script>(document.fonts ? document.fonts.ready : Promise.resolve()).then(function () {` +
+  `requestAnimationFrame(function () { requestAnimationFrame(function () {` +
+  `window.ipc.postMessage(${JSON.stringify(RENDER_READY_MESSAGE)});` +
+  `}); });` +
+  `});</script>`;
+
+/**
+ * Render an HTML document to a PNG of `width` x `height` logical pixels,
+ * e.g. for thumbnails and social cards. The document loads through the
+ * internal `loadHtml()` origin in a borderless window placed off-screen,
+ * which never takes focus and is destroyed afterwards. The image is taken
+ * once web fonts have loaded and the page has painted; on high-DPI
+ * displays it is scaled by the display's scale factor.
+ *
+ * @example
+ * ```ts
+ * const png = await renderHtmlToImage(`<h1 style="font:64px system-ui">Release 2.0</h1>`, {
+ *   width: 1200,
+ *   height: 630,
+ * });
+ * await Bun.write("card.png", png);
+ * ```
+ */
+export async function renderHtmlToImage(
+  html: string,
+  options: RenderHtmlToImageOptions,
+): Promise<Buffer> {
+  const { width, height, timeoutMs = 10_000 } = options;
+  if (!(width > 0) || !(height > 0)) {
+    throw new Error("renderHtmlToImage() needs a positive width and height");
+  }
+  // Off-screen rather than hidden: engines skip painting hidden webviews.
+  const win = new NativeWindow({
+    title: "native-window render",
+    width,
+    height,
+    x: -32_000,
+    y: -32_000,
+    decorations: false,
+    resizable: false,
+    skipTaskbar: true,
+    focused: false,
+    trustedOrigins: [loadHtmlOrigin()],
+  });
+  let timer: ReturnType<typeof setTimeout> | undefined;
+  try {
+    await Promise.race([
+      new Promise<void>((resolve) => {
+        win.onMessage((message) => {
+          if (message === RENDER_READY_MESSAGE) resolve();
+        });
+        win.loadHtml(html + RENDER_READY_SCRIPT);
+      }),
+      new Promise<never>((_, reject) => {
+        timer = setTimeout(
+          () => reject(new Error(`renderHtmlToImage() timed out after ${timeoutMs}ms`)),
+          timeoutMs,
+        );
+      }),
+    ]);
+    return await win.capturePage();
+  } finally {
+    clearTimeout(timer);
+    win.destroy();
+  }
+}
+
+// ---------------------------------------------------------------------------
+// Self-test
+// ---------------------------------------------------------------------------
+
+/** One step of a {@link selfTest} run. */
+export interface SelfTestStep {
+  name: "runtime" | "window" | "load" | "ipc" | "teardown";
+  ok: boolean;
+  durationMs: number;
+  /** Why the step failed. */
+  error?: string;
+}
+
+/** Result of {@link selfTest}. */
+export interface SelfTestReport {
+  /** Whether every step passed. */
+  ok: boolean;
+  runtime: RuntimeInfo;
+  /** Steps in the order they ran. A failed step ends the run. */
+  steps: SelfTestStep[];
+  durationMs: number;
+}
+
+/** Options for {@link selfTest}. */
+export interface SelfTestOptions {
+  /** Time allowed for each step. Default: 10000 */
+  stepTimeoutMs?: number;
+}
+
+const SELF_TEST_HTML =
+  `<!doctype html><html><body><script>` +
+  `window.__native_message__ = function (m) { window.ipc.postMessage("echo:" + m); };` +
+  `window.ipc.postMessage("ready");` +
+  `</script></body></html>`;
+
+/**
+ * Verify that the whole stack works on this machine: the webview runtime is
+ * present, a hidden window can be created, a document loads through the
+ * internal `loadHtml()` origin, and an IPC message makes a round trip.
+ * The window is closed afterwards. Never rejects — failures are reported
+ * in the returned steps.
+ *
+ * @example
+ * ```ts
+ * const report = await selfTest();
+ * if (!report.ok) console.error(report.steps.find((s) => !s.ok));
+ * ```
+ */
+export async function selfTest(options: SelfTestOptions = {}): Promise<SelfTestReport> {
+  const { stepTimeoutMs = 10_000 } = options;
+  const started = Date.now();
+  const steps: SelfTestStep[] = [];
+  const runtime = checkRuntime();
+
+  const step = async (name: SelfTestStep["name"], run: () => Promise<void> | void) => {
+    const stepStarted = Date.now();
+    let timer: ReturnType<typeof setTimeout> | undefined;
+    try {
+      await Promise.race([
+        run(),
+        new Promise<never>((_, reject) => {
+          timer = setTimeout(
+            () => reject(new Error(`timed out after ${stepTimeoutMs}ms`)),
+            stepTimeoutMs,
+          );
+        }),
+      ]);
+      steps.push({ name, ok: true, durationMs: Date.now() - stepStarted });
+      return true;
+    } catch (e) {
+      const error = e instanceof Error ? e.message : String(e);
+      steps.push({ name, ok: false, durationMs: Date.now() - stepStarted, error });
+      return false;
+    } finally {
+      clearTimeout(timer);
+    }
+  };
+
+  let win: NativeWindow | undefined;
+  // Messages from the page, registered before anything is loaded.
+  const messages: string[] = [];
+  let onPageMessage: (() => void) | undefined;
+  const nextMessage = () =>
+    new Promise<string>((resolve) => {
+      const take = () => {
+        const message = messages.shift();
+        if (message === undefined) return false;
+        onPageMessage = undefined;
+        resolve(message);
+        return true;
+      };
+      if (!take()) onPageMessage = take;
+    });
+
+  const passed =
+    (await step("runtime", () => {
+      if (!runtime.available) {
+        throw new Error(`webview runtime not available on ${runtime.platform}`);
+      }
+    })) &&
+    (await step("window", async () => {
+      win = new NativeWindow({
+        title: "native-window self-test",
+        width: 320,
+        height: 240,
+        visible: false,
+        trustedOrigins: [loadHtmlOrigin()],
+      });
+      win.onMessage((message) => {
+        messages.push(message);
+        onPageMessage?.();
+      });
+      // Resolves once the native window exists (the first pump).
+      await win.getWindowState();
+    })) &&
+    (await step("load", async () => {
+      win!.loadHtml(SELF_TEST_HTML);
+      const message = await nextMessage();
+      if (message !== "ready") throw new Error(`unexpected message: ${message}`);
+    })) &&
+    (await step("ipc", async () => {
+      const nonce = Math.random().toString(36).slice(2);
+      win!.postMessage(nonce);
+      const message = await nextMessage();
+      if (message !== `echo:${nonce}`) throw new Error(`unexpected message: ${message}`);
+    }));
+
+  if (win) {
+    const opened = win;
+    await step("teardown", () => {
+      const closed = new Promise<void>((resolve) => opened.onClosed(resolve));
+      opened.destroy();
+      return closed;
+    });
+  }
+
+  return {
+    ok: passed && steps.every((s) => s.ok),
+    runtime,
+    steps,
+    durationMs: Date.now() - started,
+  };
 }
 
 // ---------------------------------------------------------------------------
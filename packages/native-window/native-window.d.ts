@@ -10,10 +10,33 @@ export interface WindowOptions {
   width?: number;
   /** Inner height in logical pixels. Default: 600 */
   height?: number;
-  /** X position in screen coordinates */
+  /** X position in screen coordinates, or relative to the work area of `display` when set */
   x?: number;
-  /** Y position in screen coordinates */
+  /** Y position in screen coordinates, or relative to the work area of `display` when set */
   y?: number;
+  /**
+   * Monitor to open the window on: a monitor index, `"primary"`, or
+   * `"cursor"` (the monitor containing the mouse pointer). When set, `x`/`y`
+   * are offsets from that monitor's work area; if omitted the window is
+   * centered in it.
+   */
+  display?: number | "primary" | "cursor";
+  /**
+   * Center the window in the work area of `display`, or of the primary
+   * monitor. Takes precedence over `x`/`y`. Default: false
+   */
+  center?: boolean;
+  /**
+   * Anchor the window as a popup next to a rectangle inside another window.
+   * Popups are borderless, always on top, not focused on open, and close
+   * automatically on an outside click. Usually set via `openPopup()`.
+   */
+  anchor?: PopupAnchor;
+  /**
+   * ID of the window that owns this one. An owned window stays above its
+   * owner, minimizes with it, and closes when it closes.
+   */
+  parentId?: number;
   /** Minimum inner width */
   minWidth?: number;
   /** Minimum inner height */
@@ -24,14 +47,76 @@ export interface WindowOptions {
   maxHeight?: number;
   /** Allow resizing. Default: true */
   resizable?: boolean;
+  /** Enable the close button. Default: true */
+  closable?: boolean;
+  /** Enable the minimize button (not on Linux). Default: true */
+  minimizable?: boolean;
+  /** Enable the maximize button, which is also the fullscreen button on macOS (not on Linux). Default: true */
+  maximizable?: boolean;
   /** Show window decorations (title bar, borders). Default: true */
   decorations?: boolean;
   /** Transparent window background. Default: false */
   transparent?: boolean;
+  /**
+   * Blurred native material behind the page, e.g. `"sidebar"` or
+   * `"under-window"`. Shows through wherever the page background is
+   * transparent. macOS only.
+   */
+  vibrancy?: VibrancyMaterial;
+  /**
+   * Light or dark native chrome, or `"system"` to follow the OS.
+   * App-wide on macOS and Linux. Default: "system"
+   */
+  theme?: WindowTheme;
   /** Always on top of other windows. Default: false */
   alwaysOnTop?: boolean;
+  /**
+   * Keep the window below all normal windows, e.g. for desktop widgets.
+   * Ignored when `alwaysOnTop` is set. Default: false
+   */
+  alwaysOnBottom?: boolean;
+  /**
+   * Show the window on every macOS Space / Linux workspace / Windows
+   * virtual desktop, so it follows the user when they switch. Default: false
+   */
+  visibleOnAllWorkspaces?: boolean;
+  /**
+   * Keep the window out of the taskbar (Windows/Linux) and out of window
+   * cycling with Cmd+` (macOS). Default: false
+   */
+  skipTaskbar?: boolean;
+  /** Start in fullscreen. Default: false */
+  fullscreen?: boolean;
+  /**
+   * Fullscreen style. `"native"` uses macOS Spaces fullscreen; `"borderless"`
+   * covers the screen without creating a Space. Only differs on macOS.
+   * Default: "native"
+   */
+  fullscreenMode?: FullscreenMode;
+  /**
+   * Replace the native title bar for a custom HTML one, keeping the resize
+   * borders. `"hidden"` keeps the macOS traffic lights in place,
+   * `"hiddenInset"` insets them from the corner, and `"overlay"` extends the
+   * content under a transparent macOS title bar that keeps its title.
+   * Windows and Linux treat all three as `"hidden"`. Default: "default"
+   */
+  titleBarStyle?: TitleBarStyle;
+  /**
+   * Position of the traffic lights from the top-left corner, in logical
+   * pixels, to line them up with a custom header. Only applies with a
+   * hidden `titleBarStyle`. macOS only.
+   */
+  trafficLightPosition?: Point;
+  /**
+   * Windows 11 corner rounding: `"square"` opts out, e.g. for a frameless
+   * window with its own border. Ignored on Windows 10. Windows only.
+   * Default: "default"
+   */
+  cornerStyle?: CornerStyle;
   /** Initially visible. Default: true */
   visible?: boolean;
+  /** Take keyboard focus when first shown. Default: true */
+  focused?: boolean;
   /** Enable devtools. Default: false */
   devtools?: boolean;
   /**
@@ -78,6 +163,24 @@ export interface WindowOptions {
    * @example `["myapp.com", "*.cdn.myapp.com"]`
    */
   allowedHosts?: string[];
+  /**
+   * URL schemes (e.g. `"file"`, `"zoommtg"`) exempt from the built-in
+   * scheme blocking. `loadUrl()` accepts them too. URLs with a host are
+   * still checked against `allowedHosts`. `javascript`, `http`, `https`,
+   * `nativewindow` and `about` cannot be listed.
+   */
+  allowedSchemes?: string[];
+  /**
+   * Which hosts may be embedded as iframes, and the `sandbox` applied to
+   * every iframe. Blocked iframes fire `onNavigationBlocked`.
+   */
+  iframePolicy?: IframePolicy;
+  /**
+   * Block top-level navigations beyond this many per rolling minute, so a
+   * page stuck in a redirect loop cannot peg the CPU. Blocked navigations
+   * fire `onNavigationThrottled`. Default: unlimited.
+   */
+  maxNavigationsPerMinute?: number;
   /**
    * Allow the webview to access the camera when requested.
    * Default: false (all camera requests are denied).
@@ -97,6 +200,22 @@ export interface WindowOptions {
    * @note Not yet enforced in the wry backend. The OS default applies.
    */
   allowFileSystem?: boolean;
+  /**
+   * Hide the window instead of minimizing or closing it (close button,
+   * Alt+F4, Cmd+W), until `restoreFromTray()` brings it back.
+   * Programmatic `close()` still closes. Default: false
+   */
+  minimizeToTray?: boolean;
+  /**
+   * Hide the menu bar set by `setWindowMenu()` until Alt is pressed
+   * (Windows/Linux). Default: false
+   */
+  autoHideMenuBar?: boolean;
+  /**
+   * Keep the window open when its `NativeWindow` object is garbage
+   * collected, instead of closing it. Default: false
+   */
+  persistAfterGc?: boolean;
 
   /**
    * Path to a PNG or ICO file for the window icon (title bar).
@@ -106,6 +225,87 @@ export interface WindowOptions {
   icon?: string;
 }
 
+/** An entry in a window menu bar, for `setWindowMenu()`. */
+export interface WindowMenuItem {
+  /**
+   * Displayed text. Prefix a character with `&` to make it the Alt
+   * mnemonic, e.g. `"&File"`; `"&&"` is a literal ampersand.
+   * Required unless `type` is `"separator"`.
+   */
+  label?: string;
+  /** Reported to `onMenuItemClick` when the item is chosen. */
+  id?: string;
+  /** Default: "normal" */
+  type?: "normal" | "separator";
+  /** Whether the item can be chosen. Default: true */
+  enabled?: boolean;
+  /** Child items; makes this entry a submenu. */
+  submenu?: WindowMenuItem[];
+}
+
+/** Engine font settings, for `setFontOverrides()`. */
+export interface FontOverrides {
+  /**
+   * Font for text whose CSS names no font, e.g. `"Atkinson Hyperlegible"`.
+   * Default: the engine's own.
+   */
+  standardFamily?: string;
+  /** Smallest font size text is drawn at, in CSS pixels. Default: none */
+  minimumFontSize?: number;
+}
+
+/** Emulated network conditions, for `setNetworkConditions()`. */
+export interface NetworkConditions {
+  /** Fail all requests as if the network were down. Default: false */
+  offline?: boolean;
+  /** Extra round-trip latency in milliseconds. Default: 0 */
+  latencyMs?: number;
+  /** Maximum download speed in bytes per second. Default: unlimited */
+  downloadThroughput?: number;
+  /** Maximum upload speed in bytes per second. Default: unlimited */
+  uploadThroughput?: number;
+}
+
+/** Options for `loadHtml()`. */
+export interface LoadHtmlOptions {
+  /**
+   * Path the document is served at under the `loadHtml()` origin, e.g.
+   * `"/settings"`. Documents at other paths stay available, so in-page links
+   * between them work. Unknown paths get the `"/"` document. Default: `"/"`
+   */
+  path?: string;
+}
+
+/**
+ * A synthetic input event for `sendInputEvent()`.
+ * Coordinates are CSS pixels relative to the webview's top-left corner.
+ */
+export interface InputEvent {
+  /** Event category. */
+  type: "mouse" | "key" | "wheel";
+  /**
+   * Mouse: `"down"`, `"up"`, `"move"`, or `"click"` (default).
+   * Key: `"down"`, `"up"`, or `"press"` (default).
+   */
+  action?: "down" | "up" | "move" | "click" | "press";
+  /** X position in CSS pixels. Default: 0 */
+  x?: number;
+  /** Y position in CSS pixels. Default: 0 */
+  y?: number;
+  /** Mouse button. Default: "left" */
+  button?: "left" | "right" | "middle";
+  /** Click count for mouse down/up/click events. Default: 1 */
+  clickCount?: number;
+  /** DOM `KeyboardEvent.key` value, e.g. `"a"`, `"Enter"`. Required for key events. */
+  key?: string;
+  /** Modifier keys held during the event. */
+  modifiers?: Array<"shift" | "control" | "alt" | "meta">;
+  /** Horizontal scroll delta in CSS pixels (wheel events). Default: 0 */
+  deltaX?: number;
+  /** Vertical scroll delta in CSS pixels (wheel events). Default: 0 */
+  deltaY?: number;
+}
+
 export class NativeWindow {
   constructor(options?: WindowOptions);
 
@@ -114,57 +314,620 @@ export class NativeWindow {
 
   // Content loading
   loadUrl(url: string): void;
-  loadHtml(html: string): void;
+  loadHtml(html: string, options?: LoadHtmlOptions): void;
+  setServiceWorker(script: string | null): void;
+  unregisterServiceWorkers(): void;
+  setFallbackHtml(html: string | null): void;
+  preconnect(origins: string[]): void;
+  prefetch(urls: string[]): void;
   evaluateJs(script: string): void;
+  evaluateJsWithUserGesture(script: string): void;
   postMessage(message: string): void;
+  sendInputEvent(event: InputEvent): void;
 
   // Window control
   setTitle(title: string): void;
+  setTitleTemplate(template: string | null): void;
   setSize(width: number, height: number): void;
   setMinSize(width: number, height: number): void;
   setMaxSize(width: number, height: number): void;
+  /** Keep the content at `width`:`height` during user resizes. `0, 0` removes it. */
+  setAspectRatio(width: number, height: number): void;
   setPosition(x: number, y: number): void;
+  /** Tile the window to a half/quarter of a display's work area. `display` is a monitor index. */
+  snapTo(position: SnapPosition, display?: number): void;
+  /** Center the window in a display's work area. `display` is a monitor index. */
+  center(display?: number): void;
+  /** Move the window to another display, keeping its place in the work area. `display` is a monitor index. */
+  moveToDisplay(display: number): void;
   setResizable(resizable: boolean): void;
+  setClosable(closable: boolean): void;
+  setMinimizable(minimizable: boolean): void;
+  setMaximizable(maximizable: boolean): void;
   setDecorations(decorations: boolean): void;
   setAlwaysOnTop(alwaysOnTop: boolean): void;
+  setAlwaysOnBottom(alwaysOnBottom: boolean): void;
+  moveAbove(otherWindowId: number): void;
+  moveBelow(otherWindowId: number): void;
+  setVisibleOnAllWorkspaces(visible: boolean): void;
+  setTheme(theme: WindowTheme): void;
+  /** Move the window to a virtual desktop from `getVirtualDesktops()` (Windows only). */
+  moveToVirtualDesktop(desktopId: string): void;
+  /** Keep the window out of the taskbar (Windows/Linux) / window cycling (macOS). */
+  setSkipTaskbar(skip: boolean): void;
+  /** Let mouse input pass through the window to whatever is underneath. */
+  startDragging(): void;
+  setIgnoreCursorEvents(ignore: boolean): void;
+  /** Enter or leave fullscreen. `mode` overrides and replaces `fullscreenMode`. */
+  setFullscreen(fullscreen: boolean, mode?: FullscreenMode): void;
   /** Set the window icon from a PNG or ICO file path. Ignored on macOS. */
   setIcon(path: string): void;
+  /** Set the window icon from PNG/ICO bytes, or raw RGBA pixels with `width` and `height`. Ignored on macOS. */
+  setIconFromBuffer(data: Buffer, width?: number, height?: number): void;
+  /** Show a count on the taskbar button / Dock icon. `null` or `0` clears it. */
+  setTaskbarBadge(count: number | null): void;
+  setOverlayIcon(path: string | null, description?: string): void;
+  setBadgeLabel(text: string | null): void;
+  setProgressBar(value: number | null, state?: ProgressBarState): void;
+  /** Attach a menu bar to this window (Windows/Linux). `null` removes it. */
+  setWindowMenu(menu: WindowMenuItem[] | null): void;
+  setAutoHideMenuBar(hide: boolean): void;
+  setPriority(priority: "normal" | "background"): void;
+
+  // Coordinates (throw until the window has been created by the first pump)
+  screenToClient(x: number, y: number): Point;
+  clientToScreen(x: number, y: number): Point;
+  getScaleFactor(): number;
+  onScaleFactorChanged(callback: (scale: number) => void): void;
+  dipToPhysical(x: number, y: number): Point;
+  physicalToDip(x: number, y: number): Point;
+
+  // Geometry
+  getBounds(): void;
+  onBounds(callback: (bounds: Rect) => void): void;
+  getWindowState(): void;
+  onWindowState(callback: (state: WindowState) => void): void;
+
+  // Diagnostics
+  /** The last `limit` commands processed for this window, oldest first. */
+  getCommandHistory(limit?: number): CommandRecord[];
 
   // Window state
   show(): void;
   hide(): void;
   close(): void;
   focus(): void;
+  requestAttention(critical?: boolean): void;
+  /** Move keyboard focus into the webview. */
+  focusWebview(): void;
+  /** Open the system emoji picker for the page's focused input. Not on Linux. */
+  showEmojiPicker(): void;
+  /** Move keyboard focus from the webview back to the window. */
+  blurWebview(): void;
+  /** Focus the first or last element of the page's tab order. */
+  moveFocusToWebview(edge: "first" | "last"): void;
+  /** Stop garbage collection of this object from closing the window. */
+  detach(): void;
+  /** Close the window and release this object's hold on it. */
+  dispose(): void;
+  /** Show, un-minimize and focus a window hidden by `minimizeToTray`. */
+  restoreFromTray(): void;
   maximize(): void;
   minimize(): void;
   unmaximize(): void;
   reload(): void;
 
   // Event handlers
+  /** Deliver only the listed events for this window. `null` delivers every event. */
+  setEventMask(events: MaskableEvent[] | null): void;
   onMessage(callback: (message: string, sourceUrl: string) => void): void;
+  /** Fires after the native window and webview are destroyed. */
   onClose(callback: () => void): void;
+  /**
+   * Fires on OS close requests (close button, Alt+F4, Cmd+W) instead of
+   * closing. Call `close()` to proceed.
+   */
+  onCloseRequested(callback: () => void): void;
+  /** Fires after `restoreFromTray()` brings the window back. */
+  onTrayRestore(callback: () => void): void;
   onResize(callback: (width: number, height: number) => void): void;
   onMove(callback: (x: number, y: number) => void): void;
+  onResizeEnd(callback: (width: number, height: number) => void): void;
+  onMoveEnd(callback: (x: number, y: number) => void): void;
+  /** Fires when the window was found entirely off-screen and moved back. */
+  onWindowRescued(callback: (x: number, y: number) => void): void;
+  /** Fires when the cursor moves anywhere on screen, at most every `throttleMs`. */
+  onGlobalMouseMove(throttleMs: number, callback: (x: number, y: number) => void): void;
   onFocus(callback: () => void): void;
   onBlur(callback: () => void): void;
+  /** Fires when the webview gains or loses keyboard focus. */
+  onWebviewFocusChanged(callback: (focused: boolean) => void): void;
+  onWorkspaceChanged(callback: (onActiveWorkspace: boolean) => void): void;
+  /** Fires when the window's theme switches between light and dark. */
+  onThemeChanged(callback: (theme: "light" | "dark") => void): void;
+  /** Fires when Tab / Shift+Tab moves focus out of the page (WebView2 only). */
+  onFocusExitRequested(callback: (direction: "next" | "previous") => void): void;
   onPageLoad(
     callback: (event: "started" | "finished", url: string) => void,
   ): void;
+  /** Fires once, when the first page load finishes. */
+  onReadyToShow(callback: () => void): void;
   onTitleChanged(callback: (title: string) => void): void;
+  onMenuItemClick(callback: (id: string) => void): void;
   onReload(callback: () => void): void;
   onNavigationBlocked(callback: (url: string) => void): void;
+  onFrameCreated(callback: (frameId: number, url: string) => void): void;
+  onFrameDestroyed(callback: (frameId: number) => void): void;
+  onNavigationThrottled(callback: (url: string) => void): void;
+  onFallbackShown(callback: (originalUrl: string, errorCode: string) => void): void;
 
   // Cookie access
   getCookies(url?: string): void;
   onCookies(callback: (cookies: string) => void): void;
+
+  // Text search
+  countMatches(text: string): void;
+  onMatchCount(callback: (count: number | null) => void): void;
+
+  // Accessibility
+  setAccessibilityEnabled(enabled: boolean): void;
+  getAccessibilitySnapshot(): void;
+  onAccessibilitySnapshot(callback: (snapshot: string) => void): void;
+
+  // Performance
+  getPagePerformanceMetrics(): void;
+  onPerformanceMetrics(callback: (json: string) => void): void;
+
+  // Reader mode
+  extractReadableContent(): void;
+  onReadableContent(callback: (json: string) => void): void;
+
+  // Emulation
+  /** Emulate an IANA time zone, e.g. `"America/New_York"`. `null` restores the host zone. */
+  setEmulatedTimezone(timezone: string | null): void;
+  setFontOverrides(overrides: FontOverrides | null): void;
+  setTextZoom(percent: number): void;
+  /** Emulate a BCP 47 locale, e.g. `"de-DE"`. `null` restores the host locale. */
+  setEmulatedLocale(locale: string | null): void;
+  /** Throttle or cut off the network. `null` restores it. */
+  setNetworkConditions(conditions: NetworkConditions | null): void;
+
+  // Network capture
+  startNetworkCapture(): void;
+  stopNetworkCapture(): void;
+  onNetworkCapture(callback: (json: string | null) => void): void;
+
+  // Tracing
+  /** Throws on platforms without the DevTools protocol (macOS/Linux). */
+  startTracing(categories: string[]): void;
+  stopTracing(): void;
+  onTrace(callback: (json: string | null) => void): void;
+
+  // Heap snapshots
+  /** Throws on platforms without the DevTools protocol (macOS/Linux). */
+  takeHeapSnapshot(): void;
+  onHeapSnapshot(callback: (json: string | null) => void): void;
+
+  // Screenshots
+  captureWindow(): void;
+  onWindowCapture(callback: (png: Buffer | null) => void): void;
+  capturePage(): void;
+  onPageCapture(callback: (png: Buffer | null) => void): void;
+  getThumbnail(maxWidth: number, maxHeight: number): void;
+  onThumbnail(callback: (png: Buffer | null) => void): void;
+  /** Show the screen color picker; the result goes to `onColorPicked`. Not on Linux. */
+  pickColorFromScreen(): void;
+  onColorPicked(callback: (color: PickedColor | null) => void): void;
+  /** Show the native share sheet. Not on Linux. */
+  share(options: ShareOptions): void;
+
+  // Frame capture
+  /** Throws on platforms without the DevTools protocol (macOS/Linux). */
+  startFrameCapture(fps: number, format?: "jpeg" | "png"): void;
+  stopFrameCapture(): void;
+  /** Receives the JSON `Page.screencastFrame` params. */
+  onFrame(callback: (json: string) => void): void;
+
+  // Audio capture
+  startAudioCapture(): void;
+  stopAudioCapture(): void;
+  /** 16-bit stereo PCM at 48 kHz; `pcm` is `null` once the capture ends. */
+  onAudioCapture(callback: (pcm: Buffer | null, error: string | null) => void): void;
 }
 
+/** A command processed for a window, as returned by `getCommandHistory()`. */
+export interface CommandRecord {
+  /** Command name, e.g. `"loadUrl"`. */
+  command: string;
+  /** When the command was processed (or dropped), in ms since the Unix epoch. */
+  timestamp: number;
+  /**
+   * `"windowNotFound"`: the native window did not exist, so nothing happened.
+   * `"dropped"`: the command queue was full.
+   */
+  outcome: "ok" | "error" | "windowNotFound" | "dropped";
+  /** Error message when `outcome` is `"error"`. */
+  error?: string;
+}
+
+/** A rectangle in CSS pixels relative to a webview's top-left corner. */
+export interface Rect {
+  x: number;
+  y: number;
+  width: number;
+  height: number;
+}
+
+/** A color picked with `pickColorFromScreen()`, in sRGB. */
+export interface PickedColor {
+  /** Red, 0–255. */
+  r: number;
+  /** Green, 0–255. */
+  g: number;
+  /** Blue, 0–255. */
+  b: number;
+  /** Alpha, 0–1. Screen pixels are opaque, so this is normally 1. */
+  a: number;
+}
+
+/** Restrictions on embedded frames, for the `iframePolicy` window option. */
+export interface IframePolicy {
+  /**
+   * Hosts iframes may load, with the same patterns as `allowedHosts`.
+   * When unset, iframes follow the window's `allowedHosts`. Best-effort on
+   * Linux, where only the page script enforces it.
+   */
+  allowedHosts?: string[];
+  /**
+   * `sandbox` attribute set on every iframe, e.g. `"allow-scripts"`.
+   * `""` applies every restriction. When unset, iframes keep their own.
+   */
+  sandbox?: string;
+}
+
+/** Content for `share()`. At least one of `text`, `url` or `files` must be set. */
+export interface ShareOptions {
+  /** Title of the shared data. Shown by the Windows Share UI; ignored on macOS. */
+  title?: string;
+  text?: string;
+  url?: string;
+  /** Absolute paths of files to share. */
+  files?: string[];
+}
+
+/** A window's state as reported by the OS. */
+export interface WindowState {
+  maximized: boolean;
+  minimized: boolean;
+  visible: boolean;
+  focused: boolean;
+  /** On the macOS Space / Windows virtual desktop the user is looking at. Always `true` on Linux. */
+  onActiveWorkspace: boolean;
+  /** Shown on every Space / workspace / virtual desktop. */
+  visibleOnAllWorkspaces: boolean;
+}
+
+/** One window's placement in `applyLayout()`. Omitted fields are left unchanged. */
+export interface WindowLayout {
+  windowId: number;
+  /** Outer position and content size in logical pixels, as set by `setPosition()` and `setSize()`. */
+  bounds?: Rect;
+  visible?: boolean;
+  /** Stacking order among the windows in the layout; 0 is frontmost. */
+  zOrder?: number;
+}
+
+/** Where a popup window is attached. */
+export interface PopupAnchor {
+  /**
+   * ID of the window containing the anchor rectangle. When omitted, `rect`
+   * is in logical screen coordinates.
+   */
+  windowId?: number;
+  /** Anchor rectangle, e.g. from `element.getBoundingClientRect()`. */
+  rect: Rect;
+  /**
+   * Preferred side of the rectangle. Flips to the opposite side if the popup
+   * would leave the display's work area. Default: "below"
+   */
+  placement?: "below" | "above" | "right" | "left";
+}
+
+/** A 2D point. */
+export interface Point {
+  x: number;
+  y: number;
+}
+
+/** Work-area tile targeted by {@link NativeWindow.snapTo}. */
+export type SnapPosition =
+  | "left"
+  | "right"
+  | "top"
+  | "bottom"
+  | "top-left"
+  | "top-right"
+  | "bottom-left"
+  | "bottom-right"
+  | "fill";
+
+/** Taskbar progress style; see {@link NativeWindow.setProgressBar}. */
+export type ProgressBarState = "normal" | "indeterminate" | "paused" | "error";
+
+/** Fullscreen style; see {@link WindowOptions.fullscreenMode}. */
+export type FullscreenMode = "native" | "borderless";
+
+/** Title bar style; see {@link WindowOptions.titleBarStyle}. */
+export type TitleBarStyle = "default" | "hidden" | "hiddenInset" | "overlay";
+
+/** macOS vibrancy material; see {@link WindowOptions.vibrancy}. */
+export type VibrancyMaterial =
+  | "titlebar"
+  | "selection"
+  | "menu"
+  | "popover"
+  | "sidebar"
+  | "header"
+  | "sheet"
+  | "window"
+  | "hud"
+  | "fullscreen-ui"
+  | "tooltip"
+  | "content"
+  | "under-window"
+  | "under-page";
+
+/** Native chrome theme; see {@link WindowOptions.theme}. */
+export type WindowTheme = "light" | "dark" | "system";
+
+/** Window corner rounding; see {@link WindowOptions.cornerStyle}. */
+export type CornerStyle = "default" | "round" | "square";
+
+/** Events that `setEventMask()` can filter. */
+export type MaskableEvent = "resize" | "move" | "focus" | "blur" | "message" | "titleChanged";
+
 /** Initialize the native window system. Must be called once before creating any windows. */
 export function init(): void;
+/** Options for {@link init}. */
+export interface InitOptions {
+  /**
+   * Reject window creation when a `WindowOptions` field is not supported
+   * by the current platform, instead of silently ignoring it. Default: false
+   */
+  strictOptions?: boolean;
+  /**
+   * Windowing backend. `"unified"` (tao + wry) is the only backend in this
+   * build; `"auto"` selects it. Default: "auto"
+   */
+  backend?: "unified" | "auto";
+  /**
+   * Language of the engine's built-in UI (context menus, auth prompts, PDF
+   * viewer) as a BCP 47 tag, e.g. `"de"` or `"pt-BR"`. Must be set before
+   * the first window is created. Not supported on Linux. Default: OS language
+   */
+  uiLanguage?: string;
+}
+
+/**
+ * Initialize the native window system. Must be called once before creating
+ * any windows. Calling it again only updates the options.
+ */
+export function init(options?: InitOptions): void;
+
+/** Features supported by the current platform/backend. */
+export interface Capabilities {
+  /** Windowing backend, as chosen with `init({ backend })`; always `"unified"` in this build. */
+  backend: "unified";
+  /** Webview engine the addon was built against. */
+  engine: "webview2" | "wkwebview" | "webkitgtk";
+  /** Per-window title bar icons (`icon` option). Not on macOS. */
+  supportsWindowIcons: boolean;
+  /** Transparent windows and webviews (`transparent` option). */
+  supportsTransparency: boolean;
+  /** Enforcing `allowCamera` / `allowMicrophone` / `allowFileSystem`. */
+  supportsPermissionDelegates: boolean;
+  /** Per-window menu bars (`setWindowMenu()`, `autoHideMenuBar`). Not on macOS. */
+  supportsWindowMenus: boolean;
+  /** Blurred, translucent window backgrounds (`vibrancy` option). macOS only. */
+  supportsVibrancy: boolean;
+  /** DevTools Protocol features (`startTracing()`, `takeHeapSnapshot()`, `startFrameCapture()`, full network capture). WebView2 only. */
+  supportsDevToolsProtocol: boolean;
+  /** Service workers registered via `setServiceWorker()`. WebView2 only. */
+  supportsServiceWorkers: boolean;
+  /** Lowering the content process priority with `setPriority()`. Not on Linux. */
+  supportsProcessPriority: boolean;
+  /** Disabling the minimize and maximize buttons (`minimizable`, `maximizable`). Not on Linux. */
+  supportsWindowButtonControl: boolean;
+  /** Capturing webview audio with `startAudioCapture()`. WebView2 only. */
+  supportsAudioCapture: boolean;
+  /** Picking a color from the screen with `pickColorFromScreen()`. Not on Linux. */
+  supportsColorPicker: boolean;
+  /** The native share sheet with `share()`. Not on Linux. */
+  supportsShare: boolean;
+}
+
+/** Query which optional features the current platform supports. */
+export function getCapabilities(): Capabilities;
+
+/** A connected display, as reported by `getDiagnostics()`. */
+export interface DiagnosticsDisplay {
+  name?: string;
+  /** Position and size in physical pixels. */
+  x: number;
+  y: number;
+  width: number;
+  height: number;
+  scaleFactor: number;
+  primary: boolean;
+}
+
+/** Machine details for bug reports. */
+export interface Diagnostics {
+  os: "macos" | "windows" | "linux";
+  osVersion?: string;
+  /** CPU architecture, e.g. `"x86_64"` or `"aarch64"`. */
+  arch: string;
+  engine: "webview2" | "wkwebview" | "webkitgtk";
+  engineVersion?: string;
+  /** Graphics adapter names, `"; "`-separated when there are several. */
+  gpuInfo?: string;
+  /** Scale factor of each display, in the order of `displays`. */
+  scaleFactors: number[];
+  /** Connected displays. Empty before `init()`. */
+  displays: DiagnosticsDisplay[];
+  /** Windowing backend; always `"unified"`. */
+  backend: string;
+}
+
+/** Collect OS, engine, GPU and display details for bug reports. */
+export function getDiagnostics(): Diagnostics;
+
+/** List the fields set in `options` that the current platform would ignore. */
+export function unsupportedWindowOptions(options: WindowOptions): string[];
 
 /** Process pending native UI events. Call periodically (~16ms) to keep windows responsive. */
 export function pumpEvents(): void;
 
+/** Move, resize, show/hide and restack several windows in one update. */
+export function applyLayout(layout: WindowLayout[]): void;
+
+/**
+ * Execute queued commands now instead of on the next `pumpEvents()`.
+ * With `windowId`, only that window's commands run.
+ */
+export function flush(windowId?: number): void;
+
+/** OS-level accessibility display preferences. */
+export interface AccessibilityPreferences {
+  /** High contrast mode / "Increase contrast" is enabled. */
+  highContrast: boolean;
+  /** The user asked for reduced motion (animations disabled). */
+  reducedMotion: boolean;
+  /** The user asked for reduced transparency (always `false` on Linux). */
+  reducedTransparency: boolean;
+}
+
+/** Read the current OS accessibility display preferences. */
+export function getAccessibilityPreferences(): AccessibilityPreferences;
+
+/**
+ * Register a handler fired when the OS accessibility display preferences
+ * change. Changes are detected while the event pump is running.
+ */
+export function onAccessibilityPreferencesChanged(
+  callback: (preferences: AccessibilityPreferences) => void,
+): void;
+
+/** Contents of the About panel, for `showAboutPanel()`. */
+export interface AboutPanelOptions {
+  /** Application name. Default (macOS): the bundle name */
+  appName?: string;
+  /** Version string, e.g. `"1.4.2"`. */
+  version?: string;
+  /** Copyright line, e.g. `"© 2026 Example Inc."`. */
+  copyright?: string;
+  /** Path to a PNG or ICO file shown as the application icon. */
+  iconPath?: string;
+}
+
+/**
+ * Show the platform's standard About panel. Returns `false` when the
+ * platform has none (Windows, Linux).
+ */
+export function showNativeAboutPanel(options: AboutPanelOptions): boolean;
+
+/** The OS accent color as a `#rrggbb` hex string. */
+export function getAccentColor(): string;
+
+/**
+ * Register a handler fired with the new color when the OS accent color
+ * changes. Changes are detected while the event pump is running.
+ */
+export function onAccentColorChanged(callback: (color: string) => void): void;
+
+/** Seconds since the last keyboard or mouse input anywhere on the system. */
+export function getSystemIdleTime(): number;
+
+/**
+ * Register a handler fired when the user goes idle (no input for
+ * `threshold` seconds) or becomes active again. Changes are detected while
+ * the event pump is running.
+ */
+export function onIdleStateChanged(
+  threshold: number,
+  callback: (state: "idle" | "active") => void,
+): void;
+
+/** An audio endpoint as reported by the OS. */
+export interface AudioDevice {
+  /** Stable OS identifier: the endpoint ID on Windows, the device UID on macOS. */
+  id: string;
+  /** Human-readable name, e.g. `"Headphones (USB Audio)"`. */
+  name: string;
+}
+
+/** A Windows virtual desktop. */
+export interface VirtualDesktop {
+  /** Desktop GUID, for `moveToVirtualDesktop()`. */
+  id: string;
+  /** Name shown in Task View, or `"Desktop N"` when unnamed. */
+  name: string;
+  /** Whether the user is looking at this desktop. */
+  current: boolean;
+}
+
+/**
+ * The user's virtual desktops in Task View order. Always empty on macOS
+ * and Linux, and on Windows until a second desktop has been created.
+ */
+export function getVirtualDesktops(): VirtualDesktop[];
+
+/**
+ * The default audio output device, or `null` when there is none.
+ * Always `null` on Linux.
+ */
+export function getDefaultAudioDevice(): AudioDevice | null;
+
+/**
+ * Register a handler fired when the default audio output device changes,
+ * e.g. when headphones are plugged in. Changes are detected while the
+ * event pump is running.
+ */
+export function onAudioDevicesChanged(
+  callback: (device: AudioDevice | null) => void,
+): void;
+
+/**
+ * Register the app as the default handler of `scheme` links (e.g. `myapp`
+ * for `myapp://...`): LaunchServices on macOS (the bundle must declare the
+ * scheme in `CFBundleURLTypes`; `path` and `args` are ignored), the
+ * per-user registry on Windows, and an `xdg-mime` default on Linux.
+ * `path` defaults to the current executable; the URL is appended after
+ * `args` when the OS starts the app.
+ */
+export function setAsDefaultProtocolClient(
+  scheme: string,
+  path?: string,
+  args?: string[],
+): void;
+
+/** Undo `setAsDefaultProtocolClient()`. Does nothing when the app is not the handler. */
+export function removeAsDefaultProtocolClient(
+  scheme: string,
+  path?: string,
+  args?: string[],
+): void;
+
+/**
+ * Whether the app is the default handler of `scheme` links. On Windows
+ * the registered command must also match `path` and `args`.
+ */
+export function isDefaultProtocolClient(
+  scheme: string,
+  path?: string,
+  args?: string[],
+): boolean;
+
 /** Information about the native webview runtime. */
 export interface RuntimeInfo {
   /** Whether the webview runtime is available. */
@@ -205,6 +968,89 @@ export function checkRuntime(): RuntimeInfo;
  */
 export function ensureRuntime(): RuntimeInfo;
 
+/** Reported by {@link onProfileFallback}. */
+export interface ProfileFallback {
+  /** Why the default profile failed, e.g. "Access is denied." */
+  reason: string;
+  /** The temporary user data folder now in use. */
+  userDataFolder: string;
+}
+
+/**
+ * Delete the WebView2 user data folder (`<executable>.WebView2` next to the
+ * executable) so the next window starts with a fresh profile. Cookies,
+ * storage and caches are lost. Returns `true` if a folder was removed.
+ *
+ * Must be called before the first window is created; throws otherwise.
+ * Returns `false` on macOS and Linux.
+ */
+export function repairRuntimeProfile(): boolean;
+
+/**
+ * Register a handler fired when WebView2 could not open its user data
+ * folder (locked or corrupted) and windows fell back to a temporary
+ * profile. Windows only.
+ */
+export function onProfileFallback(
+  callback: (fallback: ProfileFallback) => void,
+): void;
+
+/** What to download with `downloadUpdate()` and how to verify it. */
+export interface UpdateDownloadOptions {
+  /** `https://` URL of the installer or archive. */
+  url: string;
+  /** Expected SHA-256 of the file, as hex. */
+  sha256?: string;
+  /**
+   * Organization the file must be signed by: the `O=` name of the
+   * Authenticode certificate on Windows, or the Developer ID name or team
+   * ID on macOS (`.pkg`, `.dmg` and `.app` only). Not supported on Linux.
+   */
+  signer?: string;
+  /** Where to save the file. Default: a new private directory under the temp directory. */
+  path?: string;
+}
+
+/** Progress of a `downloadUpdate()` call, in bytes. */
+export interface UpdateProgress {
+  received: number;
+  /** Size reported by the server, if any. */
+  total?: number;
+}
+
+/** Outcome of a native `downloadUpdate()` call. */
+export interface UpdateDownloadResult {
+  path?: string;
+  error?: string;
+}
+
+/**
+ * Download an update on a background thread. At least one of `sha256` and
+ * `signer` is required. Prefer the promise-based wrapper in `index.ts`.
+ */
+export function downloadUpdate(
+  options: UpdateDownloadOptions,
+  onProgress: (progress: UpdateProgress) => void,
+  onDone: (result: UpdateDownloadResult) => void,
+): void;
+
+/** What `relaunch()` starts. */
+export interface RelaunchOptions {
+  /** Executable to start. Default: the current executable. */
+  execPath?: string;
+  /**
+   * Arguments for the new process. Default: the current arguments when
+   * `execPath` is omitted, none otherwise.
+   */
+  args?: string[];
+}
+
+/**
+ * Start a new instance of the app, or a downloaded installer, detached
+ * from this process. Does not exit the current process.
+ */
+export function relaunch(options?: RelaunchOptions): void;
+
 /**
  * Escape a string for safe embedding inside a JavaScript string literal.
  * Handles backslashes, quotes, newlines, null bytes, closing `</script>` tags,
@@ -0,0 +1,89 @@
+/// The native "About" panel.
+///
+/// Only macOS has a standard About panel. On other platforms
+/// `showNativeAboutPanel()` returns `false` and `showAboutPanel()` in
+/// index.ts builds a small window instead.
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+
+/// Contents of the About panel, for `showAboutPanel()`.
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct AboutPanelOptions {
+    /// Application name. Default (macOS): the bundle name
+    pub app_name: Option<String>,
+    /// Version string, e.g. `"1.4.2"`.
+    pub version: Option<String>,
+    /// Copyright line, e.g. `"© 2026 Example Inc."`.
+    pub copyright: Option<String>,
+    /// Path to a PNG or ICO file shown as the application icon.
+    pub icon_path: Option<String>,
+}
+
+/// Show the platform's standard About panel. Returns `false` when the
+/// platform has none (Windows, Linux).
+#[napi]
+pub fn show_native_about_panel(options: AboutPanelOptions) -> Result<bool> {
+    #[cfg(target_os = "macos")]
+    {
+        use objc2::rc::Retained;
+        use objc2::runtime::AnyObject;
+        use objc2::AnyThread;
+        use objc2_app_kit::{
+            NSAboutPanelOptionApplicationIcon, NSAboutPanelOptionApplicationName,
+            NSAboutPanelOptionApplicationVersion, NSApplication, NSImage,
+        };
+        use objc2_foundation::{MainThreadMarker, NSDictionary, NSString};
+
+        let mtm = MainThreadMarker::new().ok_or_else(|| {
+            napi::Error::from_reason("showAboutPanel() must be called from the main thread")
+        })?;
+
+        let string = |s: &str| Retained::into_super(Retained::into_super(NSString::from_str(s)));
+        // No AppKit constant exists for the copyright key.
+        let copyright_key = NSString::from_str("Copyright");
+
+        let mut keys: Vec<&NSString> = Vec::new();
+        let mut values: Vec<Retained<AnyObject>> = Vec::new();
+        unsafe {
+            if let Some(ref name) = options.app_name {
+                keys.push(NSAboutPanelOptionApplicationName);
+                values.push(string(name));
+            }
+            if let Some(ref version) = options.version {
+                keys.push(NSAboutPanelOptionApplicationVersion);
+                values.push(string(version));
+            }
+            if let Some(ref path) = options.icon_path {
+                let image =
+                    NSImage::initWithContentsOfFile(NSImage::alloc(), &NSString::from_str(path))
+                        .ok_or_else(|| {
+                            napi::Error::from_reason(format!(
+                                "Failed to load about panel icon: {}",
+                                path
+                            ))
+                        })?;
+                keys.push(NSAboutPanelOptionApplicationIcon);
+                values.push(Retained::into_super(Retained::into_super(image)));
+            }
+        }
+        if let Some(ref copyright) = options.copyright {
+            keys.push(&copyright_key);
+            values.push(string(copyright));
+        }
+
+        let dictionary = NSDictionary::from_retained_objects(&keys, &values);
+        let app = NSApplication::sharedApplication(mtm);
+        // `activate()` needs macOS 14; this still works everywhere.
+        #[allow(deprecated)]
+        app.activateIgnoringOtherApps(true);
+        unsafe { app.orderFrontStandardAboutPanelWithOptions(&dictionary) };
+        Ok(true)
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    {
+        let _ = options;
+        Ok(false)
+    }
+}
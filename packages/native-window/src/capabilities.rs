@@ -0,0 +1,160 @@
+/// Runtime feature discovery.
+///
+/// Some `WindowOptions` are only honored on some platforms. `getCapabilities()`
+/// reports what the current backend supports, and strict mode
+/// (`init({ strictOptions: true })`) uses the same table to reject options
+/// that would otherwise be silently ignored.
+use napi_derive::napi;
+
+use crate::options::WindowOptions;
+
+/// Features supported by the current platform/backend.
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct Capabilities {
+    /// Windowing backend, as chosen with `init({ backend })`; always
+    /// `"unified"` (tao + wry) in this build.
+    #[napi(ts_type = "\"unified\"")]
+    pub backend: String,
+    /// Webview engine: `"webview2"`, `"wkwebview"` or `"webkitgtk"`.
+    #[napi(ts_type = "\"webview2\" | \"wkwebview\" | \"webkitgtk\"")]
+    pub engine: String,
+    /// Per-window title bar icons (`icon` option). Not on macOS.
+    pub supports_window_icons: bool,
+    /// Transparent windows and webviews (`transparent` option).
+    pub supports_transparency: bool,
+    /// Enforcing `allowCamera` / `allowMicrophone` / `allowFileSystem`.
+    pub supports_permission_delegates: bool,
+    /// Per-window menu bars (`setWindowMenu()`, `autoHideMenuBar`). Not on macOS.
+    pub supports_window_menus: bool,
+    /// Blurred, translucent window backgrounds (`vibrancy` option). macOS
+    /// only; Windows Mica/Acrylic is not available yet.
+    pub supports_vibrancy: bool,
+    /// Chrome DevTools Protocol features: `startTracing()`,
+    /// `takeHeapSnapshot()`, `startFrameCapture()` and full network capture.
+    /// WebView2 only.
+    pub supports_dev_tools_protocol: bool,
+    /// Service workers registered via `setServiceWorker()`. WebKit does not
+    /// run them on custom schemes.
+    pub supports_service_workers: bool,
+    /// Lowering the content process priority with `setPriority()`. Not on Linux.
+    pub supports_process_priority: bool,
+    /// Disabling the minimize and maximize buttons (`minimizable`,
+    /// `maximizable`). Not on Linux, where GTK only controls the close button.
+    pub supports_window_button_control: bool,
+    /// Capturing the audio played by webviews with `startAudioCapture()`.
+    /// WebView2 only.
+    pub supports_audio_capture: bool,
+    /// Picking a color from the screen with `pickColorFromScreen()`.
+    /// Not on Linux.
+    pub supports_color_picker: bool,
+    /// The native share sheet with `share()`. Not on Linux.
+    pub supports_share: bool,
+}
+
+/// Capabilities of the platform this addon was built for.
+pub fn capabilities() -> Capabilities {
+    Capabilities {
+        backend: "unified".to_string(),
+        engine: engine().to_string(),
+        supports_window_icons: cfg!(not(target_os = "macos")),
+        // A transparent WKWebView needs wry's `transparent` feature
+        // (private API), which is not enabled.
+        supports_transparency: cfg!(not(target_os = "macos")),
+        // wry exposes no permission delegates on any backend.
+        supports_permission_delegates: false,
+        supports_window_menus: cfg!(not(target_os = "macos")),
+        supports_vibrancy: cfg!(target_os = "macos"),
+        supports_dev_tools_protocol: cfg!(target_os = "windows"),
+        supports_service_workers: cfg!(target_os = "windows"),
+        // WebKitGTK does not expose its web process.
+        supports_process_priority: cfg!(any(target_os = "windows", target_os = "macos")),
+        supports_window_button_control: cfg!(any(target_os = "windows", target_os = "macos")),
+        supports_audio_capture: cfg!(target_os = "windows"),
+        supports_color_picker: cfg!(any(target_os = "macos", target_os = "windows")),
+        supports_share: cfg!(any(target_os = "macos", target_os = "windows")),
+    }
+}
+
+/// Name of the webview engine this addon was built against.
+fn engine() -> &'static str {
+    #[cfg(target_os = "windows")]
+    {
+        "webview2"
+    }
+    #[cfg(target_os = "macos")]
+    {
+        "wkwebview"
+    }
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    {
+        "webkitgtk"
+    }
+}
+
+/// Query which optional features the current platform supports.
+#[napi]
+pub fn get_capabilities() -> Capabilities {
+    capabilities()
+}
+
+/// JS names of the fields set in `options` that the current platform
+/// would ignore.
+pub fn unsupported_options(options: &WindowOptions) -> Vec<&'static str> {
+    let caps = capabilities();
+    let mut unsupported = Vec::new();
+    if options.icon.is_some() && !caps.supports_window_icons {
+        unsupported.push("icon");
+    }
+    if options.transparent == Some(true) && !caps.supports_transparency {
+        unsupported.push("transparent");
+    }
+    if !caps.supports_permission_delegates {
+        if options.allow_camera == Some(true) {
+            unsupported.push("allowCamera");
+        }
+        if options.allow_microphone == Some(true) {
+            unsupported.push("allowMicrophone");
+        }
+        if options.allow_file_system == Some(true) {
+            unsupported.push("allowFileSystem");
+        }
+    }
+    if options.auto_hide_menu_bar == Some(true) && !caps.supports_window_menus {
+        unsupported.push("autoHideMenuBar");
+    }
+    if options.vibrancy.is_some() && !caps.supports_vibrancy {
+        unsupported.push("vibrancy");
+    }
+    if !caps.supports_window_button_control {
+        if options.minimizable == Some(false) {
+            unsupported.push("minimizable");
+        }
+        if options.maximizable == Some(false) {
+            unsupported.push("maximizable");
+        }
+    }
+    #[cfg(not(target_os = "macos"))]
+    {
+        if options.traffic_light_position.is_some() {
+            unsupported.push("trafficLightPosition");
+        }
+    }
+    #[cfg(not(target_os = "windows"))]
+    {
+        if options.corner_style.is_some() {
+            unsupported.push("cornerStyle");
+        }
+    }
+    unsupported
+}
+
+/// List the fields set in `options` that the current platform would
+/// ignore, e.g. `["icon"]` on macOS.
+#[napi]
+pub fn unsupported_window_options(options: WindowOptions) -> Vec<String> {
+    unsupported_options(&options)
+        .into_iter()
+        .map(String::from)
+        .collect()
+}
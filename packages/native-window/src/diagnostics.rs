@@ -0,0 +1,272 @@
+/// Machine details for bug reports.
+///
+/// `getDiagnostics()` gathers the OS, webview engine, GPU and display
+/// configuration in one call, so apps embedding this addon report them the
+/// same way instead of each assembling its own subset from JS.
+use napi_derive::napi;
+
+use crate::window_manager::EVENT_LOOP;
+
+/// A connected display, for [`Diagnostics`].
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct DiagnosticsDisplay {
+    /// OS name of the display, if it has one.
+    pub name: Option<String>,
+    /// Position in physical pixels.
+    pub x: i32,
+    pub y: i32,
+    /// Size in physical pixels.
+    pub width: u32,
+    pub height: u32,
+    pub scale_factor: f64,
+    pub primary: bool,
+}
+
+/// Environment fingerprint returned by `getDiagnostics()`.
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct Diagnostics {
+    /// `"macos"`, `"windows"` or `"linux"`.
+    pub os: String,
+    /// e.g. `"14.4.1"`, `"10.0.22631.3447"`, `"Ubuntu 24.04 LTS (kernel 6.8.0-31-generic)"`.
+    pub os_version: Option<String>,
+    /// CPU architecture, e.g. `"x86_64"` or `"aarch64"`.
+    pub arch: String,
+    /// Webview engine: `"webview2"`, `"wkwebview"` or `"webkitgtk"`.
+    #[napi(ts_type = "\"webview2\" | \"wkwebview\" | \"webkitgtk\"")]
+    pub engine: String,
+    pub engine_version: Option<String>,
+    /// Graphics adapter names, `"; "`-separated when there are several.
+    pub gpu_info: Option<String>,
+    /// Scale factor of each display, in the order of `displays`.
+    pub scale_factors: Vec<f64>,
+    /// Connected displays. Empty before `init()`.
+    pub displays: Vec<DiagnosticsDisplay>,
+    /// Windowing backend; always `"unified"` (tao + wry).
+    pub backend: String,
+}
+
+/// Collect OS, engine, GPU and display details for bug reports.
+#[napi]
+pub fn get_diagnostics() -> Diagnostics {
+    let displays = displays();
+    Diagnostics {
+        os: std::env::consts::OS.to_string(),
+        os_version: os_version(),
+        arch: std::env::consts::ARCH.to_string(),
+        engine: crate::capabilities::capabilities().engine,
+        engine_version: wry::webview_version().ok(),
+        gpu_info: gpu_info(),
+        scale_factors: displays.iter().map(|d| d.scale_factor).collect(),
+        displays,
+        backend: "unified".to_string(),
+    }
+}
+
+/// Displays known to the event loop, which only exists after `init()`.
+fn displays() -> Vec<DiagnosticsDisplay> {
+    EVENT_LOOP.with(|el| {
+        let el = el.borrow();
+        let Some(event_loop) = el.as_ref() else {
+            return Vec::new();
+        };
+        let primary = event_loop.primary_monitor();
+        event_loop
+            .available_monitors()
+            .map(|monitor| {
+                let position = monitor.position();
+                let size = monitor.size();
+                DiagnosticsDisplay {
+                    name: monitor.name(),
+                    x: position.x,
+                    y: position.y,
+                    width: size.width,
+                    height: size.height,
+                    scale_factor: monitor.scale_factor(),
+                    primary: primary.as_ref() == Some(&monitor),
+                }
+            })
+            .collect()
+    })
+}
+
+// ── OS version ─────────────────────────────────────────────────
+
+#[cfg(target_os = "macos")]
+fn os_version() -> Option<String> {
+    use objc2_foundation::NSProcessInfo;
+
+    let v = NSProcessInfo::processInfo().operatingSystemVersion();
+    Some(format!(
+        "{}.{}.{}",
+        v.majorVersion, v.minorVersion, v.patchVersion
+    ))
+}
+
+/// Read from the registry: `GetVersionEx` reports the version the
+/// executable's manifest declares support for, not the real one.
+#[cfg(target_os = "windows")]
+fn os_version() -> Option<String> {
+    use windows::core::{HSTRING, PCWSTR};
+    use windows::Win32::Foundation::ERROR_SUCCESS;
+    use windows::Win32::System::Registry::{
+        RegGetValueW, HKEY_LOCAL_MACHINE, RRF_RT_REG_DWORD, RRF_RT_REG_SZ,
+    };
+
+    let key = HSTRING::from("SOFTWARE\\Microsoft\\Windows NT\\CurrentVersion");
+    let dword = |name: &str| {
+        let name = HSTRING::from(name);
+        let mut value = 0u32;
+        let mut size = std::mem::size_of::<u32>() as u32;
+        let status = unsafe {
+            RegGetValueW(
+                HKEY_LOCAL_MACHINE,
+                PCWSTR(key.as_ptr()),
+                PCWSTR(name.as_ptr()),
+                RRF_RT_REG_DWORD,
+                None,
+                Some((&mut value as *mut u32).cast()),
+                Some(&mut size),
+            )
+        };
+        (status == ERROR_SUCCESS).then_some(value)
+    };
+    let build = {
+        let name = HSTRING::from("CurrentBuildNumber");
+        let mut data = [0u16; 32];
+        let mut size = std::mem::size_of_val(&data) as u32;
+        let status = unsafe {
+            RegGetValueW(
+                HKEY_LOCAL_MACHINE,
+                PCWSTR(key.as_ptr()),
+                PCWSTR(name.as_ptr()),
+                RRF_RT_REG_SZ,
+                None,
+                Some(data.as_mut_ptr().cast()),
+                Some(&mut size),
+            )
+        };
+        if status != ERROR_SUCCESS {
+            return None;
+        }
+        let len = data.iter().position(|&c| c == 0).unwrap_or(data.len());
+        String::from_utf16_lossy(&data[..len])
+    };
+
+    let major = dword("CurrentMajorVersionNumber")?;
+    let minor = dword("CurrentMinorVersionNumber").unwrap_or(0);
+    let revision = dword("UBR").unwrap_or(0);
+    Some(format!("{}.{}.{}.{}", major, minor, build, revision))
+}
+
+/// The distribution name from `/etc/os-release` and the kernel release.
+#[cfg(target_os = "linux")]
+fn os_version() -> Option<String> {
+    let kernel = std::fs::read_to_string("/proc/sys/kernel/osrelease")
+        .ok()
+        .map(|k| k.trim().to_string());
+    let distro = std::fs::read_to_string("/etc/os-release")
+        .ok()
+        .and_then(|release| {
+            release.lines().find_map(|line| {
+                line.strip_prefix("PRETTY_NAME=")
+                    .map(|name| name.trim_matches('"').to_string())
+            })
+        });
+    match (distro, kernel) {
+        (Some(distro), Some(kernel)) => Some(format!("{} (kernel {})", distro, kernel)),
+        (distro, kernel) => distro.or(kernel),
+    }
+}
+
+// ── GPU ────────────────────────────────────────────────────────
+
+/// The name of Metal's default device.
+#[cfg(target_os = "macos")]
+fn gpu_info() -> Option<String> {
+    use objc2::msg_send;
+    use objc2::rc::Retained;
+    use objc2::runtime::AnyObject;
+    use objc2_foundation::NSString;
+
+    #[link(name = "Metal", kind = "framework")]
+    extern "C" {
+        fn MTLCreateSystemDefaultDevice() -> *mut AnyObject;
+    }
+
+    // The create function returns a +1 reference.
+    let device = unsafe { Retained::from_raw(MTLCreateSystemDefaultDevice()) }?;
+    let name: Retained<NSString> = unsafe { msg_send![&*device, name] };
+    Some(name.to_string())
+}
+
+/// The adapters driving the desktop, from `EnumDisplayDevices`.
+#[cfg(target_os = "windows")]
+fn gpu_info() -> Option<String> {
+    use windows::core::PCWSTR;
+    use windows::Win32::Graphics::Gdi::{
+        EnumDisplayDevicesW, DISPLAY_DEVICEW, DISPLAY_DEVICE_ATTACHED_TO_DESKTOP,
+    };
+
+    let mut adapters: Vec<String> = Vec::new();
+    for index in 0.. {
+        let mut device = DISPLAY_DEVICEW {
+            cb: std::mem::size_of::<DISPLAY_DEVICEW>() as u32,
+            ..Default::default()
+        };
+        if !unsafe { EnumDisplayDevicesW(PCWSTR::null(), index, &mut device, 0) }.as_bool() {
+            break;
+        }
+        if !device
+            .StateFlags
+            .contains(DISPLAY_DEVICE_ATTACHED_TO_DESKTOP)
+        {
+            continue;
+        }
+        let len = device
+            .DeviceString
+            .iter()
+            .position(|&c| c == 0)
+            .unwrap_or(device.DeviceString.len());
+        let name = String::from_utf16_lossy(&device.DeviceString[..len]);
+        if !adapters.contains(&name) {
+            adapters.push(name);
+        }
+    }
+    (!adapters.is_empty()).then(|| adapters.join("; "))
+}
+
+/// Driver and PCI ID of each DRM card, e.g. `"amdgpu (1002:73BF)"`.
+#[cfg(target_os = "linux")]
+fn gpu_info() -> Option<String> {
+    let mut cards: Vec<_> = std::fs::read_dir("/sys/class/drm")
+        .ok()?
+        .flatten()
+        .map(|entry| entry.file_name().to_string_lossy().into_owned())
+        // Skip connectors such as `card0-HDMI-A-1`.
+        .filter(|name| {
+            name.strip_prefix("card")
+                .is_some_and(|n| n.chars().all(|c| c.is_ascii_digit()))
+        })
+        .collect();
+    cards.sort();
+
+    let adapters: Vec<String> = cards
+        .iter()
+        .filter_map(|card| {
+            let uevent =
+                std::fs::read_to_string(format!("/sys/class/drm/{}/device/uevent", card)).ok()?;
+            let field = |key: &str| {
+                uevent
+                    .lines()
+                    .find_map(|line| line.strip_prefix(key).map(str::to_string))
+            };
+            match (field("DRIVER="), field("PCI_ID=")) {
+                (Some(driver), Some(id)) => Some(format!("{} ({})", driver, id)),
+                (driver, id) => driver.or(id),
+            }
+        })
+        .collect();
+    (!adapters.is_empty()).then(|| adapters.join("; "))
+}
@@ -1,5 +1,9 @@
 use napi::threadsafe_function::{ErrorStrategy, ThreadsafeFunction};
 
+use crate::options::{PickedColor, Rect, WindowState};
+use crate::runtime::ProfileFallback;
+use crate::system::{AccessibilityPreferences, AudioDevice};
+
 // NOTE (V-25): All callbacks use ErrorStrategy::Fatal — if a JavaScript callback
 // throws an uncaught exception, the entire process will abort. This is a
 // deliberate fail-fast design choice. Users should wrap their callback bodies in
@@ -11,22 +15,56 @@ pub type MessageCallback = ThreadsafeFunction<(String, String), ErrorStrategy::F
 /// Callback for window close events.
 pub type CloseCallback = ThreadsafeFunction<(), ErrorStrategy::Fatal>;
 
+/// Callback for OS close requests on windows that intercept them (no payload).
+pub type CloseRequestedCallback = ThreadsafeFunction<(), ErrorStrategy::Fatal>;
+
+/// Callback for windows restored by `restoreFromTray()` (no payload).
+pub type TrayRestoreCallback = ThreadsafeFunction<(), ErrorStrategy::Fatal>;
+
 /// Callback for resize events: (width, height).
 pub type ResizeCallback = ThreadsafeFunction<(f64, f64), ErrorStrategy::Fatal>;
 
 /// Callback for move events: (x, y).
 pub type MoveCallback = ThreadsafeFunction<(f64, f64), ErrorStrategy::Fatal>;
 
+/// Callback for scale factor changes: (scale).
+pub type ScaleFactorChangedCallback = ThreadsafeFunction<f64, ErrorStrategy::Fatal>;
+
+/// Callback for off-screen rescue events: (x, y) new position.
+pub type WindowRescuedCallback = ThreadsafeFunction<(f64, f64), ErrorStrategy::Fatal>;
+
+/// Callback for global cursor moves: (x, y) in screen coordinates.
+pub type GlobalMouseMoveCallback = ThreadsafeFunction<(f64, f64), ErrorStrategy::Fatal>;
+
 /// Callback for focus/blur events (no payload).
 pub type FocusCallback = ThreadsafeFunction<(), ErrorStrategy::Fatal>;
 
+/// Callback for webview keyboard focus changes: (focused).
+pub type WebviewFocusCallback = ThreadsafeFunction<bool, ErrorStrategy::Fatal>;
+
+/// Callback for a window entering (`true`) or leaving (`false`) the
+/// active workspace.
+pub type WorkspaceChangedCallback = ThreadsafeFunction<bool, ErrorStrategy::Fatal>;
+
+/// Callback for window theme changes: ("light" | "dark").
+pub type ThemeChangedCallback = ThreadsafeFunction<String, ErrorStrategy::Fatal>;
+
+/// Callback for Tab leaving the webview: ("next" | "previous").
+pub type FocusExitCallback = ThreadsafeFunction<String, ErrorStrategy::Fatal>;
+
 /// Callback for page load events: (event_type, url)
 /// event_type is "started" or "finished"
 pub type PageLoadCallback = ThreadsafeFunction<(String, String), ErrorStrategy::Fatal>;
 
+/// Callback for the one-time ready-to-show event (no payload).
+pub type ReadyToShowCallback = ThreadsafeFunction<(), ErrorStrategy::Fatal>;
+
 /// Callback for document title change events.
 pub type TitleChangedCallback = ThreadsafeFunction<String, ErrorStrategy::Fatal>;
 
+/// Callback for window menu bar clicks: (item_id).
+pub type MenuItemClickCallback = ThreadsafeFunction<String, ErrorStrategy::Fatal>;
+
 /// Callback for reload events (no payload).
 pub type ReloadCallback = ThreadsafeFunction<(), ErrorStrategy::Fatal>;
 
@@ -34,22 +72,128 @@ pub type ReloadCallback = ThreadsafeFunction<(), ErrorStrategy::Fatal>;
 /// The payload is a JSON array of cookie objects.
 pub type CookiesCallback = ThreadsafeFunction<String, ErrorStrategy::Fatal>;
 
+/// Callback for text match count results: (count), or `None` on failure.
+pub type MatchCountCallback = ThreadsafeFunction<Option<f64>, ErrorStrategy::Fatal>;
+
+/// Callback for accessibility snapshot results (JSON payload string).
+/// The payload is `{ "nodes": [...] }` or `null` on failure.
+pub type AccessibilitySnapshotCallback = ThreadsafeFunction<String, ErrorStrategy::Fatal>;
+
+/// Callback for page performance metrics (JSON payload string).
+/// The payload is `{ "timing": {...}, "metrics": ... }` or `null` on failure.
+pub type PerformanceMetricsCallback = ThreadsafeFunction<String, ErrorStrategy::Fatal>;
+
+/// Callback for reader-mode extraction results (JSON payload string).
+/// The payload is `{ "title", "html", "text" }` or `null` on failure.
+pub type ReadableContentCallback = ThreadsafeFunction<String, ErrorStrategy::Fatal>;
+
+/// Callback for screencast frames (JSON `Page.screencastFrame` params).
+pub type FrameCallback = ThreadsafeFunction<String, ErrorStrategy::Fatal>;
+
+/// Callback for window screenshot results (PNG bytes, `None` on failure).
+pub type WindowCaptureCallback = ThreadsafeFunction<Option<Vec<u8>>, ErrorStrategy::Fatal>;
+
+/// Callback for captured audio: (pcm, error). `pcm` is `None` when the
+/// capture ended.
+pub type AudioCaptureCallback =
+    ThreadsafeFunction<(Option<Vec<u8>>, Option<String>), ErrorStrategy::Fatal>;
+
+/// Callback for window geometry query results.
+pub type BoundsCallback = ThreadsafeFunction<Rect, ErrorStrategy::Fatal>;
+
+/// Callback for window state query results.
+pub type WindowStateCallback = ThreadsafeFunction<WindowState, ErrorStrategy::Fatal>;
+
+/// Callback for `pickColorFromScreen()` results (`None` when cancelled).
+pub type ColorPickedCallback = ThreadsafeFunction<Option<PickedColor>, ErrorStrategy::Fatal>;
+
+/// Callback for network capture results (JSON payload, `None` when no
+/// capture was running).
+pub type NetworkCaptureCallback = ThreadsafeFunction<Option<String>, ErrorStrategy::Fatal>;
+
+/// Callback for completed traces (JSON payload, `None` when no trace was
+/// running or it could not be stopped).
+pub type TraceCallback = ThreadsafeFunction<Option<String>, ErrorStrategy::Fatal>;
+
+/// Callback for heap snapshots (JSON payload, `None` on failure).
+pub type HeapSnapshotCallback = ThreadsafeFunction<Option<String>, ErrorStrategy::Fatal>;
+
 /// Callback for blocked navigation events: (url).
 pub type NavigationBlockedCallback = ThreadsafeFunction<String, ErrorStrategy::Fatal>;
 
+/// Callback for iframes loading their first document: (frame_id, url).
+pub type FrameCreatedCallback = ThreadsafeFunction<(u32, String), ErrorStrategy::Fatal>;
+
+/// Callback for destroyed iframes: (frame_id).
+pub type FrameDestroyedCallback = ThreadsafeFunction<u32, ErrorStrategy::Fatal>;
+
+/// Callback for navigations blocked by `maxNavigationsPerMinute`: (url).
+pub type NavigationThrottledCallback = ThreadsafeFunction<String, ErrorStrategy::Fatal>;
+
+/// Callback for fallback pages shown after failed navigations: (original_url, error_code).
+pub type FallbackShownCallback = ThreadsafeFunction<(String, String), ErrorStrategy::Fatal>;
+
+/// Callback for OS accessibility preference changes.
+pub type AccessibilityPreferencesCallback =
+    ThreadsafeFunction<AccessibilityPreferences, ErrorStrategy::Fatal>;
+
+/// Callback for OS accent color changes ("#rrggbb").
+pub type AccentColorCallback = ThreadsafeFunction<String, ErrorStrategy::Fatal>;
+
+/// Callback for idle state changes: ("idle" | "active").
+pub type IdleStateCallback = ThreadsafeFunction<String, ErrorStrategy::Fatal>;
+
+/// Callback for default audio output device changes: (device | null).
+pub type AudioDevicesCallback = ThreadsafeFunction<Option<AudioDevice>, ErrorStrategy::Fatal>;
+
+/// Callback for WebView2 temporary profile fallbacks.
+pub type ProfileFallbackCallback = ThreadsafeFunction<ProfileFallback, ErrorStrategy::Fatal>;
+
 /// Stored event handlers for a window.
 pub struct WindowEventHandlers {
     pub on_message: Option<MessageCallback>,
     pub on_close: Option<CloseCallback>,
+    pub on_close_requested: Option<CloseRequestedCallback>,
+    pub on_tray_restore: Option<TrayRestoreCallback>,
     pub on_resize: Option<ResizeCallback>,
     pub on_move: Option<MoveCallback>,
+    pub on_resize_end: Option<ResizeCallback>,
+    pub on_move_end: Option<MoveCallback>,
+    pub on_scale_factor_changed: Option<ScaleFactorChangedCallback>,
+    pub on_window_rescued: Option<WindowRescuedCallback>,
+    pub on_global_mouse_move: Option<GlobalMouseMoveCallback>,
     pub on_focus: Option<FocusCallback>,
     pub on_blur: Option<FocusCallback>,
+    pub on_webview_focus_changed: Option<WebviewFocusCallback>,
+    pub on_workspace_changed: Option<WorkspaceChangedCallback>,
+    pub on_theme_changed: Option<ThemeChangedCallback>,
+    pub on_focus_exit_requested: Option<FocusExitCallback>,
     pub on_page_load: Option<PageLoadCallback>,
+    pub on_ready_to_show: Option<ReadyToShowCallback>,
     pub on_title_changed: Option<TitleChangedCallback>,
+    pub on_menu_item_click: Option<MenuItemClickCallback>,
     pub on_reload: Option<ReloadCallback>,
     pub on_cookies: Option<CookiesCallback>,
     pub on_navigation_blocked: Option<NavigationBlockedCallback>,
+    pub on_navigation_throttled: Option<NavigationThrottledCallback>,
+    pub on_frame_created: Option<FrameCreatedCallback>,
+    pub on_frame_destroyed: Option<FrameDestroyedCallback>,
+    pub on_fallback_shown: Option<FallbackShownCallback>,
+    pub on_match_count: Option<MatchCountCallback>,
+    pub on_accessibility_snapshot: Option<AccessibilitySnapshotCallback>,
+    pub on_performance_metrics: Option<PerformanceMetricsCallback>,
+    pub on_readable_content: Option<ReadableContentCallback>,
+    pub on_window_capture: Option<WindowCaptureCallback>,
+    pub on_page_capture: Option<WindowCaptureCallback>,
+    pub on_thumbnail: Option<WindowCaptureCallback>,
+    pub on_audio_capture: Option<AudioCaptureCallback>,
+    pub on_frame: Option<FrameCallback>,
+    pub on_bounds: Option<BoundsCallback>,
+    pub on_window_state: Option<WindowStateCallback>,
+    pub on_color_picked: Option<ColorPickedCallback>,
+    pub on_network_capture: Option<NetworkCaptureCallback>,
+    pub on_trace: Option<TraceCallback>,
+    pub on_heap_snapshot: Option<HeapSnapshotCallback>,
 }
 
 impl WindowEventHandlers {
@@ -57,15 +201,69 @@ impl WindowEventHandlers {
         Self {
             on_message: None,
             on_close: None,
+            on_close_requested: None,
+            on_tray_restore: None,
             on_resize: None,
             on_move: None,
+            on_resize_end: None,
+            on_move_end: None,
+            on_scale_factor_changed: None,
+            on_window_rescued: None,
+            on_global_mouse_move: None,
             on_focus: None,
             on_blur: None,
+            on_webview_focus_changed: None,
+            on_workspace_changed: None,
+            on_theme_changed: None,
+            on_focus_exit_requested: None,
             on_page_load: None,
+            on_ready_to_show: None,
             on_title_changed: None,
+            on_menu_item_click: None,
             on_reload: None,
             on_cookies: None,
             on_navigation_blocked: None,
+            on_navigation_throttled: None,
+            on_frame_created: None,
+            on_frame_destroyed: None,
+            on_fallback_shown: None,
+            on_match_count: None,
+            on_accessibility_snapshot: None,
+            on_performance_metrics: None,
+            on_readable_content: None,
+            on_window_capture: None,
+            on_page_capture: None,
+            on_thumbnail: None,
+            on_audio_capture: None,
+            on_frame: None,
+            on_bounds: None,
+            on_window_state: None,
+            on_color_picked: None,
+            on_network_capture: None,
+            on_trace: None,
+            on_heap_snapshot: None,
+        }
+    }
+}
+
+/// Stored app-level event handlers (not tied to a window).
+pub struct AppEventHandlers {
+    pub on_accessibility_preferences_changed: Option<AccessibilityPreferencesCallback>,
+    pub on_accent_color_changed: Option<AccentColorCallback>,
+    /// Idle threshold in seconds, with its handler.
+    pub on_idle_state_changed: Option<(f64, IdleStateCallback)>,
+    pub on_audio_devices_changed: Option<AudioDevicesCallback>,
+    pub on_profile_fallback: Option<ProfileFallbackCallback>,
+}
+
+impl AppEventHandlers {
+    pub fn new() -> Self {
+        Self {
+            on_accessibility_preferences_changed: None,
+            on_accent_color_changed: None,
+            on_idle_state_changed: None,
+            on_audio_devices_changed: None,
+            on_profile_fallback: None,
         }
     }
 }
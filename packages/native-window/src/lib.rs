@@ -1,21 +1,40 @@
 #[macro_use]
 extern crate napi_derive;
 
+mod about;
+mod capabilities;
+mod diagnostics;
 mod events;
 mod options;
 mod platform;
 mod runtime;
+mod system;
+mod updater;
 mod window;
 mod window_manager;
 
 // Re-export runtime functions so napi picks them up
+pub use about::*;
+pub use capabilities::*;
+pub use diagnostics::*;
 pub use runtime::*;
+pub use system::*;
+pub use updater::*;
 
 use napi::threadsafe_function::ThreadsafeFunctionCallMode;
 use window_manager::{
-    is_origin_trusted, with_manager, PENDING_BLURS, PENDING_CLOSES, PENDING_COOKIES,
-    PENDING_FOCUSES, PENDING_MESSAGES, PENDING_MOVES, PENDING_NAVIGATION_BLOCKED,
-    PENDING_PAGE_LOADS, PENDING_RELOADS, PENDING_RESIZE_CALLBACKS, PENDING_TITLE_CHANGES,
+    is_origin_trusted, with_manager, COMMAND_HISTORY, PENDING_ACCESSIBILITY_SNAPSHOTS,
+    PENDING_AUDIO_CAPTURES, PENDING_BLURS, PENDING_BOUNDS, PENDING_CLOSES, PENDING_CLOSE_REQUESTS,
+    PENDING_COLOR_PICKS, PENDING_COOKIES, PENDING_FALLBACKS_SHOWN, PENDING_FOCUSES,
+    PENDING_FOCUS_EXITS, PENDING_FRAMES, PENDING_FRAME_EVENTS, PENDING_GLOBAL_MOUSE_MOVES,
+    PENDING_HEAP_SNAPSHOTS, PENDING_MATCH_COUNTS, PENDING_MENU_CLICKS, PENDING_MESSAGES,
+    PENDING_MOVES, PENDING_MOVE_ENDS, PENDING_NAVIGATION_BLOCKED, PENDING_NAVIGATION_THROTTLED,
+    PENDING_NETWORK_CAPTURES, PENDING_PAGE_CAPTURES, PENDING_PAGE_LOADS,
+    PENDING_PERFORMANCE_METRICS, PENDING_PROFILE_FALLBACKS, PENDING_READABLE_CONTENTS,
+    PENDING_READY_TO_SHOW, PENDING_RELOADS, PENDING_RESCUES, PENDING_RESIZE_CALLBACKS,
+    PENDING_RESIZE_ENDS, PENDING_SCALE_FACTOR_CHANGES, PENDING_THEME_CHANGES, PENDING_THUMBNAILS,
+    PENDING_TITLE_CHANGES, PENDING_TRACES, PENDING_TRAY_RESTORES, PENDING_WEBVIEW_FOCUS,
+    PENDING_WINDOW_CAPTURES, PENDING_WINDOW_STATES, PENDING_WORKSPACE_CHANGES,
 };
 
 /// Returns the origin of pages loaded via `loadHtml()`.
@@ -38,18 +57,60 @@ pub fn load_html_origin() -> String {
 }
 
 /// Initialize the native window system.
-/// Must be called once before creating any windows.
+/// Must be called once before creating any windows. Calling it again only
+/// updates the options.
+///
+/// The first call registers an env cleanup hook that tears everything down
+/// when the Node process exits or the addon is unloaded.
 #[napi]
-pub fn init() -> napi::Result<()> {
-    with_manager(|mgr| {
+pub fn init(mut env: napi::Env, options: Option<options::InitOptions>) -> napi::Result<()> {
+    let first_init = with_manager(|mgr| {
+        if let Some(options) = options {
+            match options.backend.as_deref() {
+                None | Some("auto") | Some("unified") => {}
+                Some(other) => {
+                    return Err(napi::Error::from_reason(format!(
+                        "Unknown backend '{}'; expected 'unified' or 'auto'",
+                        other
+                    )));
+                }
+            }
+            mgr.strict_options = options.strict_options.unwrap_or(false);
+            if let Some(ref language) = options.ui_language {
+                platform::set_ui_language(language).map_err(napi::Error::from_reason)?;
+            }
+        }
         if mgr.initialized {
-            return Ok(());
+            return Ok(false);
         }
 
-        mgr.platform = Some(platform::Platform::new()?);
+        mgr.platform = Some(Box::new(platform::Platform::new()?));
         mgr.initialized = true;
-        Ok(())
-    })
+        Ok(true)
+    })?;
+    if first_init {
+        env.add_env_cleanup_hook((), |()| shutdown())?;
+    }
+    Ok(())
+}
+
+/// Env cleanup hook: destroy all windows and webviews, stop helper
+/// threads and release COM while the environment is still valid. Without
+/// it, native objects outlive the JS thread and fail their own teardown
+/// (WebView2 "Failed to unregister class", orphaned browser processes).
+fn shutdown() {
+    let platform = with_manager(|mgr| {
+        mgr.initialized = false;
+        mgr.command_queue.clear();
+        mgr.platform.take()
+    });
+    if let Some(mut platform) = platform {
+        platform.shutdown();
+    }
+    window_manager::EVENT_LOOP.with(|el| el.borrow_mut().take());
+    updater::shutdown();
+    #[cfg(target_os = "windows")]
+    system::uninitialize_com();
 }
 
 /// Process pending native UI events and execute queued commands.
@@ -77,25 +138,15 @@ pub fn pump_events() -> napi::Result<()> {
 
     // Phase 2: process commands + pump OS events (MANAGER not borrowed)
     let result = if let Some(ref mut plat) = platform {
-        let mut first_err: Option<napi::Error> = None;
-        for cmd in commands {
-            if let Err(e) = plat.process_command(cmd, &mut event_handlers) {
-                eprintln!("[native-window] Command failed: {}", e);
-                if first_err.is_none() {
-                    first_err = Some(e);
-                }
-                // Continue processing remaining commands
-            }
-        }
+        let mut first_err = process_commands(plat.as_mut(), commands, &mut event_handlers).err();
 
         plat.pump_events();
 
-        // Destroy native resources for windows that received OS-initiated
-        // CloseRequested.  This ensures tao::Window and wry::WebView are
-        // properly dropped before the JS on_close callback fires — an
-        // abrupt process.exit() in the callback would otherwise leave live
-        // native objects whose teardown fails on Windows.
-        plat.destroy_pending_closes();
+        if let Err(e) = plat.finish_pump(&mut event_handlers) {
+            if first_err.is_none() {
+                first_err = Some(e);
+            }
+        }
 
         match first_err {
             Some(e) => Err(e),
@@ -115,17 +166,135 @@ pub fn pump_events() -> napi::Result<()> {
         let closed_ids: Vec<u32> = PENDING_CLOSES.with(|p| p.borrow().clone());
 
         flush_pending_callbacks(&mgr.event_handlers);
+        flush_profile_fallbacks(&mgr.app_handlers);
+        system::poll(&mgr.app_handlers);
 
         // Clean up event handlers for all closed windows now that
         // callbacks have been dispatched.
         for id in closed_ids {
             mgr.event_handlers.remove(&id);
+            COMMAND_HISTORY.with(|h| h.borrow_mut().remove(&id));
+        }
+    });
+
+    result
+}
+
+/// Execute queued commands now instead of on the next `pumpEvents()`,
+/// e.g. to resize a window right before capturing it. With `windowId`,
+/// only that window's commands run; the rest stay queued. OS events and
+/// callbacks are still delivered by `pumpEvents()`.
+#[napi]
+pub fn flush(window_id: Option<u32>) -> napi::Result<()> {
+    let (commands, mut platform, mut event_handlers) = with_manager(|mgr| {
+        if !mgr.initialized {
+            return Err(napi::Error::from_reason(
+                "Native window system not initialized. Call init() first.",
+            ));
         }
+        let commands = match window_id {
+            Some(id) => mgr.drain_window_commands(id),
+            None => mgr.drain_commands(),
+        };
+        Ok((
+            commands,
+            mgr.platform.take(),
+            std::mem::take(&mut mgr.event_handlers),
+        ))
+    })?;
+
+    let result = match platform {
+        Some(ref mut plat) => process_commands(plat.as_mut(), commands, &mut event_handlers),
+        None => Ok(()),
+    };
+
+    with_manager(|mgr| {
+        mgr.platform = platform;
+        mgr.event_handlers = event_handlers;
     });
 
     result
 }
 
+/// Move, resize, show/hide and restack several windows in one update, so
+/// docking and tiling UIs can rearrange them without flicker. Applied on
+/// the next `pumpEvents()`.
+#[napi]
+pub fn apply_layout(layout: Vec<options::WindowLayout>) -> napi::Result<()> {
+    let mut seen = std::collections::HashSet::new();
+    for item in &layout {
+        if !seen.insert(item.window_id) {
+            return Err(napi::Error::from_reason(format!(
+                "Window {} appears more than once in the layout",
+                item.window_id
+            )));
+        }
+        if let Some(bounds) = item.bounds {
+            let finite = [bounds.x, bounds.y, bounds.width, bounds.height]
+                .iter()
+                .all(|v| v.is_finite());
+            if !finite || bounds.width <= 0.0 || bounds.height <= 0.0 {
+                return Err(napi::Error::from_reason(format!(
+                    "Invalid bounds for window {}: values must be finite with a positive size",
+                    item.window_id
+                )));
+            }
+        }
+    }
+    with_manager(|mgr| mgr.push_command(window_manager::Command::ApplyLayout { layout }));
+    Ok(())
+}
+
+/// Execute `commands` in order, continuing past failures. Returns the
+/// first error.
+fn process_commands(
+    platform: &mut dyn platform::PlatformBackend,
+    commands: Vec<window_manager::Command>,
+    event_handlers: &mut std::collections::HashMap<u32, crate::events::WindowEventHandlers>,
+) -> napi::Result<()> {
+    let mut first_err: Option<napi::Error> = None;
+    for cmd in commands {
+        let (window_id, name) = (cmd.window_id(), cmd.name());
+        // Commands for a missing window are silently skipped by the backend;
+        // the history records why nothing happened.
+        let found = matches!(cmd, window_manager::Command::CreateWindow { .. })
+            || window_id == 0
+            || platform.has_window(window_id);
+        match platform.process_command(cmd, event_handlers) {
+            Ok(()) => {
+                let outcome = if found { "ok" } else { "windowNotFound" };
+                // Skip windows already closed and cleaned up, so late
+                // commands do not recreate their history.
+                if event_handlers.contains_key(&window_id) {
+                    window_manager::record_command(window_id, name, outcome, None);
+                }
+            }
+            Err(e) => {
+                eprintln!("[native-window] Command failed: {}", e);
+                window_manager::record_command(window_id, name, "error", Some(e.reason.clone()));
+                if first_err.is_none() {
+                    first_err = Some(e);
+                }
+                // Continue processing remaining commands
+            }
+        }
+    }
+    match first_err {
+        Some(e) => Err(e),
+        None => Ok(()),
+    }
+}
+
+/// Report WebView2 profile fallbacks queued during pump_events.
+fn flush_profile_fallbacks(app_handlers: &crate::events::AppEventHandlers) {
+    let fallbacks = PENDING_PROFILE_FALLBACKS.with(|p| std::mem::take(&mut *p.borrow_mut()));
+    if let Some(ref cb) = app_handlers.on_profile_fallback {
+        for fallback in fallbacks {
+            cb.call(fallback, ThreadsafeFunctionCallMode::NonBlocking);
+        }
+    }
+}
+
 /// Flush all pending callback buffers that were deferred during pump_events.
 fn flush_pending_callbacks(
     event_handlers: &std::collections::HashMap<u32, crate::events::WindowEventHandlers>,
@@ -149,6 +318,29 @@ fn flush_pending_callbacks(
         }
     }
 
+    // Flush any intercepted close requests before close events, so a
+    // request always precedes the close it may lead to.
+    let pending_requests: Vec<u32> =
+        PENDING_CLOSE_REQUESTS.with(|p| std::mem::take(&mut *p.borrow_mut()));
+    for window_id in pending_requests {
+        if let Some(handlers) = event_handlers.get(&window_id) {
+            if let Some(ref cb) = handlers.on_close_requested {
+                cb.call((), ThreadsafeFunctionCallMode::NonBlocking);
+            }
+        }
+    }
+
+    // Flush any restoreFromTray() restores that were deferred during pump_events
+    let pending_restores: Vec<u32> =
+        PENDING_TRAY_RESTORES.with(|p| std::mem::take(&mut *p.borrow_mut()));
+    for window_id in pending_restores {
+        if let Some(handlers) = event_handlers.get(&window_id) {
+            if let Some(ref cb) = handlers.on_tray_restore {
+                cb.call((), ThreadsafeFunctionCallMode::NonBlocking);
+            }
+        }
+    }
+
     // Flush any close events that were deferred during pump_events
     let pending_closes: Vec<u32> = PENDING_CLOSES.with(|p| std::mem::take(&mut *p.borrow_mut()));
     for window_id in pending_closes {
@@ -191,6 +383,49 @@ fn flush_pending_callbacks(
         }
     }
 
+    // Flush finished resizes and moves, after the intermediate events above
+    let pending_resize_ends: Vec<(u32, f64, f64)> =
+        PENDING_RESIZE_ENDS.with(|p| std::mem::take(&mut *p.borrow_mut()));
+    for (window_id, width, height) in pending_resize_ends {
+        if let Some(handlers) = event_handlers.get(&window_id) {
+            if let Some(ref cb) = handlers.on_resize_end {
+                cb.call((width, height), ThreadsafeFunctionCallMode::NonBlocking);
+            }
+        }
+    }
+
+    let pending_move_ends: Vec<(u32, f64, f64)> =
+        PENDING_MOVE_ENDS.with(|p| std::mem::take(&mut *p.borrow_mut()));
+    for (window_id, x, y) in pending_move_ends {
+        if let Some(handlers) = event_handlers.get(&window_id) {
+            if let Some(ref cb) = handlers.on_move_end {
+                cb.call((x, y), ThreadsafeFunctionCallMode::NonBlocking);
+            }
+        }
+    }
+
+    // Flush any scale factor changes that were deferred during pump_events
+    let pending_scale_factors: Vec<(u32, f64)> =
+        PENDING_SCALE_FACTOR_CHANGES.with(|p| std::mem::take(&mut *p.borrow_mut()));
+    for (window_id, scale) in pending_scale_factors {
+        if let Some(handlers) = event_handlers.get(&window_id) {
+            if let Some(ref cb) = handlers.on_scale_factor_changed {
+                cb.call(scale, ThreadsafeFunctionCallMode::NonBlocking);
+            }
+        }
+    }
+
+    // Flush any off-screen rescues that were deferred during pump_events
+    let pending_rescues: Vec<(u32, f64, f64)> =
+        PENDING_RESCUES.with(|p| std::mem::take(&mut *p.borrow_mut()));
+    for (window_id, x, y) in pending_rescues {
+        if let Some(handlers) = event_handlers.get(&window_id) {
+            if let Some(ref cb) = handlers.on_window_rescued {
+                cb.call((x, y), ThreadsafeFunctionCallMode::NonBlocking);
+            }
+        }
+    }
+
     // Flush any focus events that were deferred during pump_events
     let pending_focuses: Vec<u32> = PENDING_FOCUSES.with(|p| std::mem::take(&mut *p.borrow_mut()));
     for window_id in pending_focuses {
@@ -201,6 +436,64 @@ fn flush_pending_callbacks(
         }
     }
 
+    // Flush webview keyboard focus changes
+    let pending_webview_focus: Vec<(u32, bool)> =
+        PENDING_WEBVIEW_FOCUS.with(|p| std::mem::take(&mut *p.borrow_mut()));
+    for (window_id, focused) in pending_webview_focus {
+        if let Some(handlers) = event_handlers.get(&window_id) {
+            if let Some(ref cb) = handlers.on_webview_focus_changed {
+                cb.call(focused, ThreadsafeFunctionCallMode::NonBlocking);
+            }
+        }
+    }
+
+    // Flush windows entering or leaving the active workspace
+    let pending_workspace_changes: Vec<(u32, bool)> =
+        PENDING_WORKSPACE_CHANGES.with(|p| std::mem::take(&mut *p.borrow_mut()));
+    for (window_id, active) in pending_workspace_changes {
+        if let Some(handlers) = event_handlers.get(&window_id) {
+            if let Some(ref cb) = handlers.on_workspace_changed {
+                cb.call(active, ThreadsafeFunctionCallMode::NonBlocking);
+            }
+        }
+    }
+
+    // Flush window theme changes
+    let pending_theme_changes: Vec<(u32, &'static str)> =
+        PENDING_THEME_CHANGES.with(|p| std::mem::take(&mut *p.borrow_mut()));
+    for (window_id, theme) in pending_theme_changes {
+        if let Some(handlers) = event_handlers.get(&window_id) {
+            if let Some(ref cb) = handlers.on_theme_changed {
+                cb.call(theme.to_string(), ThreadsafeFunctionCallMode::NonBlocking);
+            }
+        }
+    }
+
+    // Flush global cursor moves
+    let pending_global_mouse_moves: Vec<(u32, f64, f64)> =
+        PENDING_GLOBAL_MOUSE_MOVES.with(|p| std::mem::take(&mut *p.borrow_mut()));
+    for (window_id, x, y) in pending_global_mouse_moves {
+        if let Some(handlers) = event_handlers.get(&window_id) {
+            if let Some(ref cb) = handlers.on_global_mouse_move {
+                cb.call((x, y), ThreadsafeFunctionCallMode::NonBlocking);
+            }
+        }
+    }
+
+    // Flush Tab moving focus out of the webview
+    let pending_focus_exits: Vec<(u32, &'static str)> =
+        PENDING_FOCUS_EXITS.with(|p| std::mem::take(&mut *p.borrow_mut()));
+    for (window_id, direction) in pending_focus_exits {
+        if let Some(handlers) = event_handlers.get(&window_id) {
+            if let Some(ref cb) = handlers.on_focus_exit_requested {
+                cb.call(
+                    direction.to_string(),
+                    ThreadsafeFunctionCallMode::NonBlocking,
+                );
+            }
+        }
+    }
+
     // Flush any blur events that were deferred during pump_events
     let pending_blurs: Vec<u32> = PENDING_BLURS.with(|p| std::mem::take(&mut *p.borrow_mut()));
     for window_id in pending_blurs {
@@ -222,6 +515,17 @@ fn flush_pending_callbacks(
         }
     }
 
+    // Flush any ready-to-show events that were deferred during pump_events
+    let pending_ready: Vec<u32> =
+        PENDING_READY_TO_SHOW.with(|p| std::mem::take(&mut *p.borrow_mut()));
+    for window_id in pending_ready {
+        if let Some(handlers) = event_handlers.get(&window_id) {
+            if let Some(ref cb) = handlers.on_ready_to_show {
+                cb.call((), ThreadsafeFunctionCallMode::NonBlocking);
+            }
+        }
+    }
+
     // Flush any navigation-blocked events that were deferred during pump_events
     let pending_nav_blocked: Vec<(u32, String)> =
         PENDING_NAVIGATION_BLOCKED.with(|p| std::mem::take(&mut *p.borrow_mut()));
@@ -233,6 +537,45 @@ fn flush_pending_callbacks(
         }
     }
 
+    let pending_frame_events: Vec<(u32, u32, Option<String>)> =
+        PENDING_FRAME_EVENTS.with(|p| std::mem::take(&mut *p.borrow_mut()));
+    for (window_id, frame_id, url) in pending_frame_events {
+        if let Some(handlers) = event_handlers.get(&window_id) {
+            match url {
+                Some(url) => {
+                    if let Some(ref cb) = handlers.on_frame_created {
+                        cb.call((frame_id, url), ThreadsafeFunctionCallMode::NonBlocking);
+                    }
+                }
+                None => {
+                    if let Some(ref cb) = handlers.on_frame_destroyed {
+                        cb.call(frame_id, ThreadsafeFunctionCallMode::NonBlocking);
+                    }
+                }
+            }
+        }
+    }
+
+    let pending_nav_throttled: Vec<(u32, String)> =
+        PENDING_NAVIGATION_THROTTLED.with(|p| std::mem::take(&mut *p.borrow_mut()));
+    for (window_id, url) in pending_nav_throttled {
+        if let Some(handlers) = event_handlers.get(&window_id) {
+            if let Some(ref cb) = handlers.on_navigation_throttled {
+                cb.call(url, ThreadsafeFunctionCallMode::NonBlocking);
+            }
+        }
+    }
+
+    let pending_fallbacks: Vec<(u32, String, String)> =
+        PENDING_FALLBACKS_SHOWN.with(|p| std::mem::take(&mut *p.borrow_mut()));
+    for (window_id, url, code) in pending_fallbacks {
+        if let Some(handlers) = event_handlers.get(&window_id) {
+            if let Some(ref cb) = handlers.on_fallback_shown {
+                cb.call((url, code), ThreadsafeFunctionCallMode::NonBlocking);
+            }
+        }
+    }
+
     // Flush any title change events that were deferred during pump_events
     let pending_titles: Vec<(u32, String)> =
         PENDING_TITLE_CHANGES.with(|p| std::mem::take(&mut *p.borrow_mut()));
@@ -244,6 +587,17 @@ fn flush_pending_callbacks(
         }
     }
 
+    // Flush any menu bar clicks that were deferred during pump_events
+    let pending_menu_clicks: Vec<(u32, String)> =
+        PENDING_MENU_CLICKS.with(|p| std::mem::take(&mut *p.borrow_mut()));
+    for (window_id, item_id) in pending_menu_clicks {
+        if let Some(handlers) = event_handlers.get(&window_id) {
+            if let Some(ref cb) = handlers.on_menu_item_click {
+                cb.call(item_id, ThreadsafeFunctionCallMode::NonBlocking);
+            }
+        }
+    }
+
     // Flush any cookie query results that were deferred during pump_events
     let pending_cookies: Vec<(u32, String)> =
         PENDING_COOKIES.with(|p| std::mem::take(&mut *p.borrow_mut()));
@@ -254,4 +608,156 @@ fn flush_pending_callbacks(
             }
         }
     }
+
+    // Flush any text match count results that were deferred during pump_events
+    let pending_match_counts: Vec<(u32, Option<f64>)> =
+        PENDING_MATCH_COUNTS.with(|p| std::mem::take(&mut *p.borrow_mut()));
+    for (window_id, count) in pending_match_counts {
+        if let Some(handlers) = event_handlers.get(&window_id) {
+            if let Some(ref cb) = handlers.on_match_count {
+                cb.call(count, ThreadsafeFunctionCallMode::NonBlocking);
+            }
+        }
+    }
+
+    // Flush any accessibility snapshot results that were deferred during pump_events
+    let pending_snapshots: Vec<(u32, String)> =
+        PENDING_ACCESSIBILITY_SNAPSHOTS.with(|p| std::mem::take(&mut *p.borrow_mut()));
+    for (window_id, json) in pending_snapshots {
+        if let Some(handlers) = event_handlers.get(&window_id) {
+            if let Some(ref cb) = handlers.on_accessibility_snapshot {
+                cb.call(json, ThreadsafeFunctionCallMode::NonBlocking);
+            }
+        }
+    }
+
+    // Flush any page performance metrics that were deferred during pump_events
+    let pending_metrics: Vec<(u32, String)> =
+        PENDING_PERFORMANCE_METRICS.with(|p| std::mem::take(&mut *p.borrow_mut()));
+    for (window_id, json) in pending_metrics {
+        if let Some(handlers) = event_handlers.get(&window_id) {
+            if let Some(ref cb) = handlers.on_performance_metrics {
+                cb.call(json, ThreadsafeFunctionCallMode::NonBlocking);
+            }
+        }
+    }
+
+    // Flush any reader-mode extraction results that were deferred during pump_events
+    let pending_readable: Vec<(u32, String)> =
+        PENDING_READABLE_CONTENTS.with(|p| std::mem::take(&mut *p.borrow_mut()));
+    for (window_id, json) in pending_readable {
+        if let Some(handlers) = event_handlers.get(&window_id) {
+            if let Some(ref cb) = handlers.on_readable_content {
+                cb.call(json, ThreadsafeFunctionCallMode::NonBlocking);
+            }
+        }
+    }
+
+    // Flush any window screenshots that were deferred during pump_events
+    let pending_captures: Vec<(u32, Option<Vec<u8>>)> =
+        PENDING_WINDOW_CAPTURES.with(|p| std::mem::take(&mut *p.borrow_mut()));
+    for (window_id, png) in pending_captures {
+        if let Some(handlers) = event_handlers.get(&window_id) {
+            if let Some(ref cb) = handlers.on_window_capture {
+                cb.call(png, ThreadsafeFunctionCallMode::NonBlocking);
+            }
+        }
+    }
+
+    let pending_page_captures: Vec<(u32, Option<Vec<u8>>)> =
+        PENDING_PAGE_CAPTURES.with(|p| std::mem::take(&mut *p.borrow_mut()));
+    for (window_id, png) in pending_page_captures {
+        if let Some(handlers) = event_handlers.get(&window_id) {
+            if let Some(ref cb) = handlers.on_page_capture {
+                cb.call(png, ThreadsafeFunctionCallMode::NonBlocking);
+            }
+        }
+    }
+
+    let pending_thumbnails: Vec<(u32, Option<Vec<u8>>)> =
+        PENDING_THUMBNAILS.with(|p| std::mem::take(&mut *p.borrow_mut()));
+    for (window_id, png) in pending_thumbnails {
+        if let Some(handlers) = event_handlers.get(&window_id) {
+            if let Some(ref cb) = handlers.on_thumbnail {
+                cb.call(png, ThreadsafeFunctionCallMode::NonBlocking);
+            }
+        }
+    }
+
+    let pending_color_picks: Vec<(u32, Option<options::PickedColor>)> =
+        PENDING_COLOR_PICKS.with(|p| std::mem::take(&mut *p.borrow_mut()));
+    for (window_id, color) in pending_color_picks {
+        if let Some(handlers) = event_handlers.get(&window_id) {
+            if let Some(ref cb) = handlers.on_color_picked {
+                cb.call(color, ThreadsafeFunctionCallMode::NonBlocking);
+            }
+        }
+    }
+
+    let pending_audio: Vec<(u32, Option<Vec<u8>>, Option<String>)> =
+        PENDING_AUDIO_CAPTURES.with(|p| std::mem::take(&mut *p.borrow_mut()));
+    for (window_id, pcm, error) in pending_audio {
+        if let Some(handlers) = event_handlers.get(&window_id) {
+            if let Some(ref cb) = handlers.on_audio_capture {
+                cb.call((pcm, error), ThreadsafeFunctionCallMode::NonBlocking);
+            }
+        }
+    }
+
+    let pending_frames = PENDING_FRAMES.with(|p| std::mem::take(&mut *p.borrow_mut()));
+    for (window_id, json) in pending_frames {
+        if let Some(handlers) = event_handlers.get(&window_id) {
+            if let Some(ref cb) = handlers.on_frame {
+                cb.call(json, ThreadsafeFunctionCallMode::NonBlocking);
+            }
+        }
+    }
+
+    // Flush any window geometry query results that were deferred during pump_events
+    let pending_bounds = PENDING_BOUNDS.with(|p| std::mem::take(&mut *p.borrow_mut()));
+    for (window_id, bounds) in pending_bounds {
+        if let Some(handlers) = event_handlers.get(&window_id) {
+            if let Some(ref cb) = handlers.on_bounds {
+                cb.call(bounds, ThreadsafeFunctionCallMode::NonBlocking);
+            }
+        }
+    }
+
+    let pending_states = PENDING_WINDOW_STATES.with(|p| std::mem::take(&mut *p.borrow_mut()));
+    for (window_id, state) in pending_states {
+        if let Some(handlers) = event_handlers.get(&window_id) {
+            if let Some(ref cb) = handlers.on_window_state {
+                cb.call(state, ThreadsafeFunctionCallMode::NonBlocking);
+            }
+        }
+    }
+
+    let pending_network_captures =
+        PENDING_NETWORK_CAPTURES.with(|p| std::mem::take(&mut *p.borrow_mut()));
+    for (window_id, json) in pending_network_captures {
+        if let Some(handlers) = event_handlers.get(&window_id) {
+            if let Some(ref cb) = handlers.on_network_capture {
+                cb.call(json, ThreadsafeFunctionCallMode::NonBlocking);
+            }
+        }
+    }
+
+    let pending_traces = PENDING_TRACES.with(|p| std::mem::take(&mut *p.borrow_mut()));
+    for (window_id, json) in pending_traces {
+        if let Some(handlers) = event_handlers.get(&window_id) {
+            if let Some(ref cb) = handlers.on_trace {
+                cb.call(json, ThreadsafeFunctionCallMode::NonBlocking);
+            }
+        }
+    }
+
+    let pending_heap_snapshots =
+        PENDING_HEAP_SNAPSHOTS.with(|p| std::mem::take(&mut *p.borrow_mut()));
+    for (window_id, json) in pending_heap_snapshots {
+        if let Some(handlers) = event_handlers.get(&window_id) {
+            if let Some(ref cb) = handlers.on_heap_snapshot {
+                cb.call(json, ThreadsafeFunctionCallMode::NonBlocking);
+            }
+        }
+    }
 }
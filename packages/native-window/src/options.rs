@@ -1,3 +1,4 @@
+use napi::bindgen_prelude::Either;
 use napi_derive::napi;
 
 /// Options for creating a new native window.
@@ -14,10 +15,27 @@ pub struct WindowOptions {
     pub width: Option<f64>,
     /// Inner height in logical pixels. Default: 600
     pub height: Option<f64>,
-    /// X position in screen coordinates
+    /// X position in screen coordinates, or relative to the work area of
+    /// `display` when set
     pub x: Option<f64>,
-    /// Y position in screen coordinates
+    /// Y position in screen coordinates, or relative to the work area of
+    /// `display` when set
     pub y: Option<f64>,
+    /// Monitor to open the window on: a monitor index, `"primary"`, or
+    /// `"cursor"` (the monitor containing the mouse pointer). When set, `x`/`y`
+    /// are logical offsets from that monitor's work area; if omitted the window
+    /// is centered in the work area.
+    pub display: Option<Either<u32, String>>,
+    /// Center the window in the work area of `display`, or of the primary
+    /// monitor. Takes precedence over `x`/`y`. Default: false
+    pub center: Option<bool>,
+    /// Anchor the window as a popup next to a rectangle inside another
+    /// window. Popups are borderless, always on top, not focused on open, and
+    /// close automatically on an outside click. Set by `openPopup()`.
+    pub anchor: Option<PopupAnchor>,
+    /// ID of the window that owns this one. An owned window stays above its
+    /// owner, minimizes with it, and closes when it closes.
+    pub parent_id: Option<u32>,
     /// Minimum inner width
     pub min_width: Option<f64>,
     /// Minimum inner height
@@ -28,14 +46,64 @@ pub struct WindowOptions {
     pub max_height: Option<f64>,
     /// Allow resizing. Default: true
     pub resizable: Option<bool>,
+    /// Enable the close button. Default: true
+    pub closable: Option<bool>,
+    /// Enable the minimize button (not on Linux). Default: true
+    pub minimizable: Option<bool>,
+    /// Enable the maximize button, which is also the fullscreen button on
+    /// macOS (not on Linux). Default: true
+    pub maximizable: Option<bool>,
     /// Show window decorations (title bar, borders). Default: true
     pub decorations: Option<bool>,
+    /// Replace the native title bar for a custom HTML one, keeping the resize
+    /// borders: `"hidden"` (macOS keeps the traffic lights), `"hiddenInset"`
+    /// (traffic lights inset from the corner) or `"overlay"` (macOS: content
+    /// extends under a transparent title bar that keeps its title). Windows
+    /// and Linux treat all three as `"hidden"`. Default: "default"
+    #[napi(ts_type = "'default' | 'hidden' | 'hiddenInset' | 'overlay'")]
+    pub title_bar_style: Option<String>,
+    /// Position of the macOS traffic lights from the top-left corner, in
+    /// logical pixels, to line them up with a custom header. Requires a
+    /// hidden `title_bar_style` (macOS only).
+    pub traffic_light_position: Option<Point>,
+    /// Windows 11 corner rounding: `"round"`, `"square"` (e.g. for a
+    /// frameless window with its own border) or `"default"` (the system
+    /// choice). Ignored on Windows 10 (Windows only). Default: "default"
+    #[napi(ts_type = "'default' | 'round' | 'square'")]
+    pub corner_style: Option<String>,
     /// Transparent window background. Default: false
     pub transparent: Option<bool>,
+    /// Blurred native material behind the page, e.g. `"sidebar"` or
+    /// `"under-window"`. Shows through wherever the page background is
+    /// transparent (macOS only).
+    #[napi(ts_type = "VibrancyMaterial")]
+    pub vibrancy: Option<String>,
+    /// Light or dark native chrome (title bar, context menus, scroll bars):
+    /// `"light"`, `"dark"` or `"system"` to follow the OS. App-wide on
+    /// macOS and Linux. Default: "system"
+    #[napi(ts_type = "WindowTheme")]
+    pub theme: Option<String>,
     /// Always on top of other windows. Default: false
     pub always_on_top: Option<bool>,
+    /// Keep the window below all normal windows, e.g. for desktop widgets.
+    /// Ignored when `always_on_top` is set. Default: false
+    pub always_on_bottom: Option<bool>,
+    /// Show the window on every macOS Space / Linux workspace / Windows
+    /// virtual desktop, so it follows the user when they switch. Default: false
+    pub visible_on_all_workspaces: Option<bool>,
+    /// Keep the window out of the taskbar (Windows/Linux) and out of window
+    /// cycling with Cmd+` (macOS). Default: false
+    pub skip_taskbar: Option<bool>,
+    /// Start in fullscreen. Default: false
+    pub fullscreen: Option<bool>,
+    /// Fullscreen style: `"native"` (macOS Spaces fullscreen) or
+    /// `"borderless"` (covers the screen without creating a Space).
+    /// Only differs on macOS. Default: "native"
+    pub fullscreen_mode: Option<String>,
     /// Initially visible. Default: true
     pub visible: Option<bool>,
+    /// Take keyboard focus when first shown. Default: true
+    pub focused: Option<bool>,
     /// Enable devtools. Default: false
     pub devtools: Option<bool>,
     /// Content Security Policy to inject via a `<meta>` tag at document start.
@@ -64,6 +132,19 @@ pub struct WindowOptions {
     /// Internal navigations (`about:blank`, `nativewindow://localhost`, `nativewindow.localhost`) are
     /// always permitted regardless of this setting.
     pub allowed_hosts: Option<Vec<String>>,
+    /// URL schemes (e.g. `"file"`, `"zoommtg"`) exempt from the built-in
+    /// scheme blocking, for apps that need to open local files or hand off
+    /// to other apps. `loadUrl()` accepts them too. URLs with a host are
+    /// still checked against `allowedHosts`. `javascript`, `http`, `https`,
+    /// `nativewindow` and `about` cannot be listed. Default: none.
+    pub allowed_schemes: Option<Vec<String>>,
+    /// Which hosts may be embedded as iframes, and the sandbox applied to
+    /// every iframe.
+    pub iframe_policy: Option<IframePolicy>,
+    /// Block top-level navigations beyond this many per rolling minute, so a
+    /// page stuck in a redirect loop cannot peg the CPU. Blocked navigations
+    /// fire `onNavigationThrottled`. Default: unlimited.
+    pub max_navigations_per_minute: Option<u32>,
     /// Allow the webview to access the camera when requested.
     /// Default: false (all camera permission requests are denied).
     pub allow_camera: Option<bool>,
@@ -75,6 +156,16 @@ pub struct WindowOptions {
     /// Default: false (all file system access requests are denied).
     pub allow_file_system: Option<bool>,
 
+    /// Hide the window instead of minimizing or closing it, until
+    /// `restoreFromTray()` brings it back. Default: false
+    pub minimize_to_tray: Option<bool>,
+    /// Hide the menu bar set by `setWindowMenu()` until Alt is pressed
+    /// (Windows/Linux). Default: false
+    pub auto_hide_menu_bar: Option<bool>,
+    /// Keep the window open when its `NativeWindow` object is garbage
+    /// collected, instead of closing it. Default: false
+    pub persist_after_gc: Option<bool>,
+
     /// Path to a PNG or ICO file for the window icon (title bar).
     /// On macOS this option is silently ignored (macOS doesn't support
     /// per-window icons). Relative paths resolve from the working directory.
@@ -89,24 +180,251 @@ impl Default for WindowOptions {
             height: None,
             x: None,
             y: None,
+            display: None,
+            center: None,
+            anchor: None,
+            parent_id: None,
             min_width: None,
             min_height: None,
             max_width: None,
             max_height: None,
             resizable: None,
+            closable: None,
+            minimizable: None,
+            maximizable: None,
             decorations: None,
+            title_bar_style: None,
+            traffic_light_position: None,
+            corner_style: None,
             transparent: None,
+            vibrancy: None,
+            theme: None,
             always_on_top: None,
+            always_on_bottom: None,
+            visible_on_all_workspaces: None,
+            skip_taskbar: None,
+            fullscreen: None,
+            fullscreen_mode: None,
             visible: None,
+            focused: None,
             devtools: None,
             csp: None,
             trusted_origins: None,
             allowed_hosts: None,
+            allowed_schemes: None,
+            iframe_policy: None,
+            max_navigations_per_minute: None,
             allow_camera: None,
             allow_microphone: None,
             allow_file_system: None,
+            minimize_to_tray: None,
+            auto_hide_menu_bar: None,
+            persist_after_gc: None,
 
             icon: None,
         }
     }
 }
+
+/// Options for `init()`.
+#[napi(object)]
+#[derive(Debug, Clone, Default)]
+pub struct InitOptions {
+    /// Reject window creation when a `WindowOptions` field is not supported
+    /// by the current platform, instead of silently ignoring it. Default: false
+    pub strict_options: Option<bool>,
+    /// Windowing backend. `"unified"` (tao + wry) is the only backend in
+    /// this build; `"auto"` selects it. Default: "auto"
+    #[napi(ts_type = "'unified' | 'auto'")]
+    pub backend: Option<String>,
+    /// Language of the engine's built-in UI (context menus, auth prompts,
+    /// PDF viewer) as a BCP 47 tag, e.g. "de" or "pt-BR". Must be set before
+    /// the first window is created. Not supported on Linux. Default: OS language
+    pub ui_language: Option<String>,
+}
+
+/// A 2D point, used by the coordinate conversion helpers.
+#[napi(object)]
+#[derive(Debug, Clone, Copy)]
+pub struct Point {
+    pub x: f64,
+    pub y: f64,
+}
+
+/// A rectangle in CSS pixels relative to a webview's top-left corner.
+#[napi(object)]
+#[derive(Debug, Clone, Copy)]
+pub struct Rect {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+/// A window's state as reported by the OS, for `isMaximized()` and friends.
+#[napi(object)]
+#[derive(Debug, Clone, Copy)]
+pub struct WindowState {
+    pub maximized: bool,
+    pub minimized: bool,
+    pub visible: bool,
+    pub focused: bool,
+    /// On the macOS Space / Windows virtual desktop the user is looking at.
+    /// Always `true` on Linux.
+    pub on_active_workspace: bool,
+    /// Shown on every Space / workspace / virtual desktop.
+    pub visible_on_all_workspaces: bool,
+}
+
+/// A color picked with `pickColorFromScreen()`, in sRGB.
+#[napi(object)]
+#[derive(Debug, Clone, Copy)]
+pub struct PickedColor {
+    /// Red, 0–255.
+    pub r: u32,
+    /// Green, 0–255.
+    pub g: u32,
+    /// Blue, 0–255.
+    pub b: u32,
+    /// Alpha, 0–1. Screen pixels are opaque, so this is normally 1.
+    pub a: f64,
+}
+
+/// Restrictions on embedded frames, for the `iframePolicy` window option.
+#[napi(object)]
+#[derive(Debug, Clone, Default)]
+pub struct IframePolicy {
+    /// Hosts iframes may load, with the same patterns as `allowedHosts`.
+    /// When unset, iframes follow the window's `allowedHosts`. Best-effort on
+    /// Linux, where only the page script enforces it.
+    pub allowed_hosts: Option<Vec<String>>,
+    /// `sandbox` attribute set on every iframe, e.g. `"allow-scripts"`.
+    /// `""` applies every restriction. When unset, iframes keep their own.
+    pub sandbox: Option<String>,
+}
+
+/// Content for `share()`. At least one field must be set.
+#[napi(object)]
+#[derive(Debug, Clone, Default)]
+pub struct ShareOptions {
+    /// Title of the shared data. Shown by the Windows Share UI; macOS
+    /// has no title for shared items and ignores it.
+    pub title: Option<String>,
+    pub text: Option<String>,
+    pub url: Option<String>,
+    /// Absolute paths of files to share.
+    pub files: Option<Vec<String>>,
+}
+
+/// One window's placement in an `applyLayout()` call. Omitted fields are
+/// left unchanged.
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct WindowLayout {
+    pub window_id: u32,
+    /// Outer position and content size in logical pixels, as set by
+    /// `setPosition()` and `setSize()`.
+    pub bounds: Option<Rect>,
+    pub visible: Option<bool>,
+    /// Stacking order among the windows in the layout; 0 is frontmost.
+    pub z_order: Option<u32>,
+}
+
+/// Where a popup window is attached, for `WindowOptions.anchor`.
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct PopupAnchor {
+    /// ID of the window containing the anchor rectangle. When omitted,
+    /// `rect` is in logical screen coordinates.
+    pub window_id: Option<u32>,
+    /// Anchor rectangle, e.g. from `element.getBoundingClientRect()`.
+    pub rect: Rect,
+    /// Preferred side: "below" (default), "above", "right", or "left".
+    /// Flips to the opposite side if the popup would leave the work area.
+    pub placement: Option<String>,
+}
+
+/// An entry in a window menu bar, for `setWindowMenu()`.
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct WindowMenuItem {
+    /// Displayed text. Prefix a character with `&` to make it the Alt
+    /// mnemonic, e.g. `"&File"`; `"&&"` is a literal ampersand.
+    pub label: Option<String>,
+    /// Reported to `onMenuItemClick` when the item is chosen.
+    pub id: Option<String>,
+    /// "normal" (default) or "separator".
+    #[napi(js_name = "type")]
+    pub kind: Option<String>,
+    /// Whether the item can be chosen. Default: true
+    pub enabled: Option<bool>,
+    /// Child items; makes this entry a submenu.
+    pub submenu: Option<Vec<WindowMenuItem>>,
+}
+
+/// Emulated network conditions, for `setNetworkConditions()`.
+#[napi(object)]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NetworkConditions {
+    /// Fail all requests as if the network were down. Default: false
+    pub offline: Option<bool>,
+    /// Extra round-trip latency in milliseconds. Default: 0
+    pub latency_ms: Option<f64>,
+    /// Maximum download speed in bytes per second. Default: unlimited
+    pub download_throughput: Option<f64>,
+    /// Maximum upload speed in bytes per second. Default: unlimited
+    pub upload_throughput: Option<f64>,
+}
+
+/// Engine font settings, for `setFontOverrides()`.
+#[napi(object)]
+#[derive(Debug, Clone, Default)]
+pub struct FontOverrides {
+    /// Font for text whose CSS names no font, e.g. `"Atkinson Hyperlegible"`.
+    /// Default: the engine's own.
+    pub standard_family: Option<String>,
+    /// Smallest font size text is drawn at, in CSS pixels. Default: none
+    pub minimum_font_size: Option<u32>,
+}
+
+/// A synthetic input event for `sendInputEvent()`.
+///
+/// Coordinates are CSS pixels relative to the webview's top-left corner.
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct InputEvent {
+    /// Event category: "mouse", "key", or "wheel".
+    #[napi(js_name = "type")]
+    pub kind: String,
+    /// Mouse: "down", "up", "move", or "click" (default).
+    /// Key: "down", "up", or "press" (default).
+    pub action: Option<String>,
+    /// X position in CSS pixels. Default: 0
+    pub x: Option<f64>,
+    /// Y position in CSS pixels. Default: 0
+    pub y: Option<f64>,
+    /// Mouse button: "left", "right", or "middle". Default: "left"
+    pub button: Option<String>,
+    /// Click count for mouse down/up/click events. Default: 1
+    pub click_count: Option<u32>,
+    /// DOM `KeyboardEvent.key` value, e.g. `"a"`, `"Enter"`, `"ArrowLeft"`.
+    /// Required for key events.
+    pub key: Option<String>,
+    /// Modifier keys held during the event: "shift", "control", "alt", "meta".
+    pub modifiers: Option<Vec<String>>,
+    /// Horizontal scroll delta in CSS pixels (wheel events). Default: 0
+    pub delta_x: Option<f64>,
+    /// Vertical scroll delta in CSS pixels (wheel events). Default: 0
+    pub delta_y: Option<f64>,
+}
+
+/// Options for `loadHtml()`.
+#[napi(object)]
+#[derive(Debug, Clone, Default)]
+pub struct LoadHtmlOptions {
+    /// Path the document is served at under the `loadHtml()` origin, e.g.
+    /// `"/settings"`. Documents at other paths stay available, so in-page
+    /// links between them work. Unknown paths get the `"/"` document.
+    /// Default: `"/"`
+    pub path: Option<String>,
+}
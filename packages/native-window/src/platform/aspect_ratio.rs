@@ -0,0 +1,168 @@
+/// Fixed content aspect ratio during user resizes, for `setAspectRatio()`.
+///
+/// Video players and similar windows should keep their proportions when
+/// the user drags an edge. macOS supports this natively with
+/// `contentAspectRatio`; Windows adjusts the drag rectangle in `WM_SIZING`;
+/// Linux sets a GTK geometry hint, which window managers may ignore.
+/// Programmatic resizes are not constrained.
+use tao::window::Window;
+
+/// Keep `window`'s content at `ratio` (width / height), or stop with `None`.
+pub fn set_aspect_ratio(window: &Window, ratio: Option<f64>) {
+    #[cfg(target_os = "windows")]
+    {
+        use tao::platform::windows::WindowExtWindows;
+        set_aspect_ratio_windows(window.hwnd(), ratio);
+    }
+    #[cfg(target_os = "macos")]
+    {
+        set_aspect_ratio_macos(window, ratio);
+    }
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    {
+        set_aspect_ratio_linux(window, ratio);
+    }
+}
+
+// ── Windows ────────────────────────────────────────────────────
+
+#[cfg(target_os = "windows")]
+use windows_sizing::set_aspect_ratio_windows;
+
+#[cfg(target_os = "windows")]
+mod windows_sizing {
+    use std::cell::RefCell;
+    use std::collections::HashMap;
+
+    use windows::Win32::Foundation::{HWND, LPARAM, LRESULT, RECT, WPARAM};
+    use windows::Win32::UI::Shell::{DefSubclassProc, RemoveWindowSubclass, SetWindowSubclass};
+    use windows::Win32::UI::WindowsAndMessaging::{
+        GetClientRect, GetWindowRect, WMSZ_BOTTOM, WMSZ_TOP, WMSZ_TOPLEFT, WMSZ_TOPRIGHT,
+        WM_NCDESTROY, WM_SIZING,
+    };
+
+    /// Subclass ID for our window procedure hook.
+    const SUBCLASS_ID: usize = 0x6e77_6172; // "nwar"
+
+    thread_local! {
+        /// Content aspect ratio of each constrained window, by HWND.
+        static RATIOS: RefCell<HashMap<isize, f64>> = RefCell::new(HashMap::new());
+    }
+
+    pub fn set_aspect_ratio_windows(hwnd: isize, ratio: Option<f64>) {
+        let previous = RATIOS.with(|r| {
+            let mut ratios = r.borrow_mut();
+            match ratio {
+                Some(ratio) => ratios.insert(hwnd, ratio),
+                None => ratios.remove(&hwnd),
+            }
+        });
+        unsafe {
+            match (previous, ratio) {
+                (None, Some(_)) => {
+                    let _ = SetWindowSubclass(HWND(hwnd as _), Some(subclass_proc), SUBCLASS_ID, 0);
+                }
+                (Some(_), None) => {
+                    let _ = RemoveWindowSubclass(HWND(hwnd as _), Some(subclass_proc), SUBCLASS_ID);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Window procedure hook: reshapes the drag rectangle so the client
+    /// area keeps its ratio. Chains to tao's procedure.
+    unsafe extern "system" fn subclass_proc(
+        hwnd: HWND,
+        msg: u32,
+        wparam: WPARAM,
+        lparam: LPARAM,
+        _subclass_id: usize,
+        _ref_data: usize,
+    ) -> LRESULT {
+        match msg {
+            WM_SIZING => {
+                let ratio = RATIOS.with(|r| r.borrow().get(&(hwnd.0 as isize)).copied());
+                let rect = lparam.0 as *mut RECT;
+                if let (Some(ratio), Some(rect)) = (ratio, unsafe { rect.as_mut() }) {
+                    constrain(hwnd, wparam.0 as u32, rect, ratio);
+                }
+            }
+            WM_NCDESTROY => {
+                RATIOS.with(|r| r.borrow_mut().remove(&(hwnd.0 as isize)));
+                let _ = unsafe { RemoveWindowSubclass(hwnd, Some(subclass_proc), SUBCLASS_ID) };
+            }
+            _ => {}
+        }
+        unsafe { DefSubclassProc(hwnd, msg, wparam, lparam) }
+    }
+
+    /// Adjust `rect` (the proposed window rectangle) for a drag on `edge`.
+    /// Side drags follow the dragged dimension; corner drags follow the
+    /// width. The opposite edge stays in place.
+    fn constrain(hwnd: HWND, edge: u32, rect: &mut RECT, ratio: f64) {
+        let mut window = RECT::default();
+        let mut client = RECT::default();
+        if unsafe { GetWindowRect(hwnd, &mut window) }.is_err()
+            || unsafe { GetClientRect(hwnd, &mut client) }.is_err()
+        {
+            return;
+        }
+        // Frame size around the client area.
+        let frame_w = (window.right - window.left) - (client.right - client.left);
+        let frame_h = (window.bottom - window.top) - (client.bottom - client.top);
+
+        let content_w = (rect.right - rect.left - frame_w).max(0);
+        let content_h = (rect.bottom - rect.top - frame_h).max(0);
+
+        if edge == WMSZ_TOP || edge == WMSZ_BOTTOM {
+            let width = (content_h as f64 * ratio).round() as i32 + frame_w;
+            rect.right = rect.left + width;
+        } else {
+            let height = (content_w as f64 / ratio).round() as i32 + frame_h;
+            if edge == WMSZ_TOPLEFT || edge == WMSZ_TOPRIGHT {
+                rect.top = rect.bottom - height;
+            } else {
+                rect.bottom = rect.top + height;
+            }
+        }
+    }
+}
+
+// ── macOS ──────────────────────────────────────────────────────
+
+#[cfg(target_os = "macos")]
+fn set_aspect_ratio_macos(window: &Window, ratio: Option<f64>) {
+    use objc2_app_kit::NSWindow;
+    use objc2_foundation::NSSize;
+    use tao::platform::macos::WindowExtMacOS;
+
+    let Some(ns_window) = (unsafe { (window.ns_window() as *const NSWindow).as_ref() }) else {
+        return;
+    };
+    match ratio {
+        Some(ratio) => ns_window.setContentAspectRatio(NSSize::new(ratio, 1.0)),
+        // Setting resize increments cancels the aspect ratio.
+        None => ns_window.setContentResizeIncrements(NSSize::new(1.0, 1.0)),
+    }
+}
+
+// ── Linux ──────────────────────────────────────────────────────
+
+/// GTK keeps one set of geometry hints per window, so this replaces the
+/// min/max size hints and `setMinSize()`/`setMaxSize()` replace it.
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+fn set_aspect_ratio_linux(window: &Window, ratio: Option<f64>) {
+    use gtk::gdk::{Geometry, Gravity, WindowHints};
+    use gtk::prelude::*;
+    use tao::platform::unix::WindowExtUnix;
+
+    let (ratio, hints) = match ratio {
+        Some(ratio) => (ratio, WindowHints::ASPECT),
+        None => (0.0, WindowHints::empty()),
+    };
+    let geometry = Geometry::new(0, 0, 0, 0, 0, 0, 0, 0, ratio, ratio, Gravity::Center);
+    window
+        .gtk_window()
+        .set_geometry_hints(None::<&gtk::Window>, Some(&geometry), hints);
+}
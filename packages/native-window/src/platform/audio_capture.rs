@@ -0,0 +1,312 @@
+/// Audio produced by webview pages, for `startAudioCapture()`.
+///
+/// A capture thread records the audio and queues it here; the platform
+/// moves it to the event buffers after each pump. Chunks are 16-bit
+/// little-endian interleaved stereo PCM at 48 kHz.
+///
+/// Windows uses WASAPI process loopback on the WebView2 browser process
+/// tree (Windows 10 2004+). Chromium plays all audio from a shared audio
+/// service, so every webview of the app is captured, not just one window.
+/// Other platforms do not support capture yet.
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+
+use wry::WebView;
+
+/// Sample rate of captured audio, in Hz.
+pub const SAMPLE_RATE: u32 = 48_000;
+/// Interleaved channels of captured audio.
+pub const CHANNELS: u16 = 2;
+/// Bytes per frame (one 16-bit sample per channel).
+const BLOCK_ALIGN: u16 = CHANNELS * 2;
+/// Audio queued beyond this many bytes (~10 s) is dropped until the JS
+/// side catches up.
+const MAX_QUEUED_BYTES: usize = SAMPLE_RATE as usize * BLOCK_ALIGN as usize * 10;
+
+/// Output of a capture.
+pub enum AudioEvent {
+    /// PCM samples.
+    #[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+    Data(Vec<u8>),
+    /// The capture ended, with an error message if it failed.
+    End(Option<String>),
+}
+
+/// Output waiting for the next pump: (window_id, event). Written by
+/// capture threads, so not thread-local.
+static EVENTS: Mutex<Vec<(u32, AudioEvent)>> = Mutex::new(Vec::new());
+
+thread_local! {
+    /// Stop flags of running captures, by window ID. Whoever sets a flag
+    /// first — `stop()` or the capture thread on exit — queues the end of
+    /// the capture, so it is reported exactly once.
+    static RUNNING: RefCell<HashMap<u32, Arc<AtomicBool>>> = RefCell::new(HashMap::new());
+    /// Capture threads, joined on shutdown. Finished ones are dropped
+    /// when the next capture starts.
+    static THREADS: RefCell<Vec<JoinHandle<()>>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Queue capture output. Consecutive chunks of a window are merged, so a
+/// pump delivers one buffer per window.
+pub fn push(window_id: u32, event: AudioEvent) {
+    let Ok(mut events) = EVENTS.lock() else {
+        return;
+    };
+    if let AudioEvent::Data(ref data) = event {
+        let queued: usize = events
+            .iter()
+            .map(|(_, e)| match e {
+                AudioEvent::Data(d) => d.len(),
+                AudioEvent::End(_) => 0,
+            })
+            .sum();
+        if queued + data.len() > MAX_QUEUED_BYTES {
+            return;
+        }
+        if let Some((last_id, AudioEvent::Data(last))) = events.last_mut() {
+            if *last_id == window_id {
+                last.extend_from_slice(data);
+                return;
+            }
+        }
+    }
+    events.push((window_id, event));
+}
+
+/// Drain the output queued since the last call.
+pub fn take_events() -> Vec<(u32, AudioEvent)> {
+    let events = EVENTS
+        .lock()
+        .map(|mut e| std::mem::take(&mut *e))
+        .unwrap_or_default();
+    RUNNING.with(|r| {
+        let mut running = r.borrow_mut();
+        for (id, event) in &events {
+            // Keep a newer capture started after this one ended.
+            if let AudioEvent::End(_) = event {
+                if running.get(id).is_some_and(|f| f.load(Ordering::Relaxed)) {
+                    running.remove(id);
+                }
+            }
+        }
+    });
+    events
+}
+
+/// Start capturing the audio of `webview`'s window.
+pub fn start(webview: &WebView, window_id: u32) -> Result<(), String> {
+    if RUNNING.with(|r| r.borrow().contains_key(&window_id)) {
+        return Err("Audio capture is already running".to_string());
+    }
+    #[cfg(target_os = "windows")]
+    {
+        let stop = Arc::new(AtomicBool::new(false));
+        let thread = start_windows(webview, window_id, stop.clone())?;
+        RUNNING.with(|r| r.borrow_mut().insert(window_id, stop));
+        THREADS.with(|t| {
+            let mut threads = t.borrow_mut();
+            threads.retain(|t| !t.is_finished());
+            threads.push(thread);
+        });
+        Ok(())
+    }
+    #[cfg(not(target_os = "windows"))]
+    {
+        let _ = webview;
+        Err("Audio capture is not supported on this platform".to_string())
+    }
+}
+
+/// Stop a window's capture and queue its end. Returns `false` if none was
+/// running.
+pub fn stop(window_id: u32) -> bool {
+    match RUNNING.with(|r| r.borrow_mut().remove(&window_id)) {
+        Some(flag) => {
+            if !flag.swap(true, Ordering::Relaxed) {
+                push(window_id, AudioEvent::End(None));
+            }
+            true
+        }
+        None => false,
+    }
+}
+
+/// Stop every capture and wait for the capture threads to exit.
+pub fn shutdown() {
+    let running: Vec<u32> = RUNNING.with(|r| r.borrow().keys().copied().collect());
+    for window_id in running {
+        stop(window_id);
+    }
+    for thread in THREADS.with(|t| std::mem::take(&mut *t.borrow_mut())) {
+        let _ = thread.join();
+    }
+}
+
+// ── Windows ────────────────────────────────────────────────────
+
+#[cfg(target_os = "windows")]
+fn start_windows(
+    webview: &WebView,
+    window_id: u32,
+    stop: Arc<AtomicBool>,
+) -> Result<JoinHandle<()>, String> {
+    use wry::WebViewExtWindows;
+
+    let mut process_id = 0u32;
+    unsafe { webview.webview().BrowserProcessId(&mut process_id) }.map_err(|e| e.to_string())?;
+    std::thread::Builder::new()
+        .name("native-window-audio".to_string())
+        .spawn(move || {
+            let result = capture_windows(window_id, process_id, &stop);
+            if !stop.swap(true, Ordering::Relaxed) {
+                push(
+                    window_id,
+                    AudioEvent::End(result.err().map(|e| e.to_string())),
+                );
+            }
+        })
+        .map_err(|e| e.to_string())
+}
+
+/// Completion handler for `ActivateAudioInterfaceAsync`, which must be agile.
+#[cfg(target_os = "windows")]
+#[windows_core::implement(
+    windows::Win32::Media::Audio::IActivateAudioInterfaceCompletionHandler,
+    windows::Win32::System::Com::IAgileObject
+)]
+struct ActivationHandler(std::sync::mpsc::Sender<()>);
+
+#[cfg(target_os = "windows")]
+impl windows::Win32::Media::Audio::IActivateAudioInterfaceCompletionHandler_Impl
+    for ActivationHandler_Impl
+{
+    fn ActivateCompleted(
+        &self,
+        _operation: windows_core::Ref<
+            '_,
+            windows::Win32::Media::Audio::IActivateAudioInterfaceAsyncOperation,
+        >,
+    ) -> windows_core::Result<()> {
+        let _ = self.0.send(());
+        Ok(())
+    }
+}
+
+#[cfg(target_os = "windows")]
+impl windows::Win32::System::Com::IAgileObject_Impl for ActivationHandler_Impl {}
+
+/// Record the process tree of `process_id` until `stop` is set.
+#[cfg(target_os = "windows")]
+fn capture_windows(
+    window_id: u32,
+    process_id: u32,
+    stop: &AtomicBool,
+) -> windows::core::Result<()> {
+    use std::time::Duration;
+    use windows::core::{Interface, HRESULT, PCWSTR};
+    use windows::Win32::Foundation::{CloseHandle, E_FAIL};
+    use windows::Win32::Media::Audio::*;
+    use windows::Win32::System::Com::StructuredStorage::PROPVARIANT;
+    use windows::Win32::System::Com::{CoInitializeEx, CoUninitialize, BLOB, COINIT_MULTITHREADED};
+    use windows::Win32::System::Threading::{CreateEventW, WaitForSingleObject};
+    use windows::Win32::System::Variant::VT_BLOB;
+
+    unsafe {
+        CoInitializeEx(None, COINIT_MULTITHREADED).ok()?;
+    }
+    let result = (|| unsafe {
+        let mut params = AUDIOCLIENT_ACTIVATION_PARAMS {
+            ActivationType: AUDIOCLIENT_ACTIVATION_TYPE_PROCESS_LOOPBACK,
+            Anonymous: AUDIOCLIENT_ACTIVATION_PARAMS_0 {
+                ProcessLoopbackParams: AUDIOCLIENT_PROCESS_LOOPBACK_PARAMS {
+                    TargetProcessId: process_id,
+                    ProcessLoopbackMode: PROCESS_LOOPBACK_MODE_INCLUDE_TARGET_PROCESS_TREE,
+                },
+            },
+        };
+        let mut activation = PROPVARIANT::default();
+        (*activation.Anonymous.Anonymous).vt = VT_BLOB;
+        (*activation.Anonymous.Anonymous).Anonymous.blob = BLOB {
+            cbSize: std::mem::size_of::<AUDIOCLIENT_ACTIVATION_PARAMS>() as u32,
+            pBlobData: &mut params as *mut _ as *mut u8,
+        };
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        let handler: IActivateAudioInterfaceCompletionHandler = ActivationHandler(tx).into();
+        let operation = ActivateAudioInterfaceAsync(
+            VIRTUAL_AUDIO_DEVICE_PROCESS_LOOPBACK,
+            &IAudioClient::IID,
+            Some(&activation as *const PROPVARIANT),
+            &handler,
+        )?;
+        rx.recv_timeout(Duration::from_secs(10))
+            .map_err(|_| windows::core::Error::new(E_FAIL, "audio activation timed out"))?;
+        let mut activate_result = HRESULT::default();
+        let mut activated = None;
+        operation.GetActivateResult(&mut activate_result, &mut activated)?;
+        activate_result.ok()?;
+        let client: IAudioClient = activated
+            .ok_or_else(|| windows::core::Error::new(E_FAIL, "no audio client"))?
+            .cast()?;
+
+        // Process loopback has no mix format; the engine converts to ours.
+        let format = WAVEFORMATEX {
+            wFormatTag: WAVE_FORMAT_PCM as u16,
+            nChannels: CHANNELS,
+            nSamplesPerSec: SAMPLE_RATE,
+            nAvgBytesPerSec: SAMPLE_RATE * BLOCK_ALIGN as u32,
+            nBlockAlign: BLOCK_ALIGN,
+            wBitsPerSample: 16,
+            cbSize: 0,
+        };
+        client.Initialize(
+            AUDCLNT_SHAREMODE_SHARED,
+            AUDCLNT_STREAMFLAGS_LOOPBACK
+                | AUDCLNT_STREAMFLAGS_EVENTCALLBACK
+                | AUDCLNT_STREAMFLAGS_AUTOCONVERTPCM,
+            200_000, // 20 ms, in 100 ns units
+            0,
+            &format,
+            None,
+        )?;
+        let event = CreateEventW(None, false, false, PCWSTR::null())?;
+        let read = (|| {
+            client.SetEventHandle(event)?;
+            let capture: IAudioCaptureClient = client.GetService()?;
+            client.Start()?;
+            while !stop.load(Ordering::Relaxed) {
+                // Time out to notice `stop` while the page is silent.
+                WaitForSingleObject(event, 100);
+                loop {
+                    if capture.GetNextPacketSize()? == 0 {
+                        break;
+                    }
+                    let mut data = std::ptr::null_mut();
+                    let mut frames = 0u32;
+                    let mut flags = 0u32;
+                    capture.GetBuffer(&mut data, &mut frames, &mut flags, None, None)?;
+                    let len = frames as usize * BLOCK_ALIGN as usize;
+                    let chunk =
+                        if data.is_null() || flags & AUDCLNT_BUFFERFLAGS_SILENT.0 as u32 != 0 {
+                            vec![0; len]
+                        } else {
+                            std::slice::from_raw_parts(data, len).to_vec()
+                        };
+                    capture.ReleaseBuffer(frames)?;
+                    if stop.load(Ordering::Relaxed) {
+                        break;
+                    }
+                    push(window_id, AudioEvent::Data(chunk));
+                }
+            }
+            client.Stop()
+        })();
+        let _ = CloseHandle(event);
+        read
+    })();
+    unsafe { CoUninitialize() };
+    result
+}
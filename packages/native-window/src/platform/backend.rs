@@ -0,0 +1,51 @@
+/// The interface between the window manager and a windowing backend.
+///
+/// `pumpEvents()` drives whichever backend `init()` created through this
+/// trait only, so adding a backend (offscreen, mock, mobile) means
+/// implementing it rather than touching every call site.
+use std::collections::HashMap;
+
+use crate::events::WindowEventHandlers;
+use crate::options::{Point, Rect, WindowState};
+use crate::window_manager::Command;
+
+pub trait PlatformBackend {
+    /// Execute one queued command.
+    fn process_command(
+        &mut self,
+        cmd: Command,
+        event_handlers: &mut HashMap<u32, WindowEventHandlers>,
+    ) -> napi::Result<()>;
+
+    /// Dispatch pending OS events, filling the `PENDING_*` buffers.
+    fn pump_events(&mut self);
+
+    /// Work that must follow [`pump_events`](Self::pump_events) in the same
+    /// pump: creating deferred windows and releasing the native resources
+    /// of windows that closed, before their `onClose` callbacks run.
+    fn finish_pump(
+        &mut self,
+        event_handlers: &mut HashMap<u32, WindowEventHandlers>,
+    ) -> napi::Result<()>;
+
+    /// Whether window `id` has been created and not destroyed yet.
+    fn has_window(&self, id: u32) -> bool;
+
+    /// Outer position and content size of a live window in logical pixels,
+    /// the spaces of `setPosition()` and `setSize()`.
+    fn bounds(&self, id: u32) -> Option<Rect>;
+
+    /// OS state of a live window.
+    fn window_state(&self, id: u32) -> Option<WindowState>;
+
+    /// Physical pixels per logical pixel on the display showing a window.
+    fn scale_factor(&self, id: u32) -> Option<f64>;
+
+    /// Screen position of a window's content area in logical pixels, for
+    /// converting between screen and client coordinates.
+    fn client_origin(&self, id: u32) -> Option<Point>;
+
+    /// Release every native resource (windows, webviews, helper threads)
+    /// before the Node environment exits or the addon is unloaded.
+    fn shutdown(&mut self);
+}
@@ -0,0 +1,135 @@
+/// Taskbar badge overlay icons for Windows.
+///
+/// Windows has no per-window badge API, only a 16×16 overlay icon on the
+/// taskbar button. We draw the count ourselves — a red disc with white
+/// digits — using a tiny built-in bitmap font, so no font rendering
+/// dependency is needed.
+
+/// Overlay edge length in pixels (rendered at 2× the 16px overlay size so
+/// it stays crisp on high-DPI taskbars).
+pub const SIZE: u32 = 32;
+
+/// Badge text for a count: `"1"`…`"99"`, then `"99+"`.
+pub fn label(count: u32) -> String {
+    if count > 99 {
+        "99+".to_string()
+    } else {
+        count.to_string()
+    }
+}
+
+/// 3×5 glyphs, one row per byte (low 3 bits, MSB = leftmost column).
+fn glyph(c: char) -> Option<[u8; 5]> {
+    Some(match c {
+        '0' => [0b111, 0b101, 0b101, 0b101, 0b111],
+        '1' => [0b010, 0b110, 0b010, 0b010, 0b111],
+        '2' => [0b111, 0b001, 0b111, 0b100, 0b111],
+        '3' => [0b111, 0b001, 0b111, 0b001, 0b111],
+        '4' => [0b101, 0b101, 0b111, 0b001, 0b001],
+        '5' => [0b111, 0b100, 0b111, 0b001, 0b111],
+        '6' => [0b111, 0b100, 0b111, 0b101, 0b111],
+        '7' => [0b111, 0b001, 0b010, 0b010, 0b010],
+        '8' => [0b111, 0b101, 0b111, 0b101, 0b111],
+        '9' => [0b111, 0b101, 0b111, 0b001, 0b111],
+        '+' => [0b000, 0b010, 0b111, 0b010, 0b000],
+        _ => return None,
+    })
+}
+
+/// Render `text` as a [`SIZE`]×[`SIZE`] RGBA badge.
+pub fn render(text: &str) -> Vec<u8> {
+    let size = SIZE as i32;
+    let mut rgba = vec![0u8; (SIZE * SIZE * 4) as usize];
+
+    // Anti-aliased red disc.
+    let radius = size as f64 / 2.0;
+    for y in 0..size {
+        for x in 0..size {
+            let dx = x as f64 + 0.5 - radius;
+            let dy = y as f64 + 0.5 - radius;
+            let coverage = (radius - (dx * dx + dy * dy).sqrt()).clamp(0.0, 1.0);
+            let i = ((y * size + x) * 4) as usize;
+            rgba[i..i + 4].copy_from_slice(&[0xe5, 0x39, 0x35, (coverage * 255.0) as u8]);
+        }
+    }
+
+    // White digits, scaled up and centered. Three glyphs need a smaller
+    // scale to fit inside the disc.
+    let glyphs: Vec<[u8; 5]> = text.chars().filter_map(glyph).collect();
+    if glyphs.is_empty() {
+        return rgba;
+    }
+    let scale = if glyphs.len() > 2 { 2 } else { 3 };
+    let advance = 4 * scale; // 3 columns + 1 spacing
+    let text_width = glyphs.len() as i32 * advance - scale;
+    let origin_x = (size - text_width) / 2;
+    let origin_y = (size - 5 * scale) / 2;
+    for (n, rows) in glyphs.iter().enumerate() {
+        for (row, bits) in rows.iter().enumerate() {
+            for col in 0..3 {
+                if bits & (0b100 >> col) == 0 {
+                    continue;
+                }
+                for sy in 0..scale {
+                    for sx in 0..scale {
+                        let x = origin_x + n as i32 * advance + col * scale + sx;
+                        let y = origin_y + row as i32 * scale + sy;
+                        let i = ((y * size + x) * 4) as usize;
+                        rgba[i..i + 4].copy_from_slice(&[0xff, 0xff, 0xff, 0xff]);
+                    }
+                }
+            }
+        }
+    }
+    rgba
+}
+
+/// Set or clear (`None`) the overlay icon on `hwnd`'s taskbar button, with
+/// `description` as its accessible text. Unlike tao's `set_overlay_icon`,
+/// this takes the description screen readers announce.
+pub fn set_overlay_icon(
+    hwnd: isize,
+    icon: Option<(&[u8], u32, u32)>,
+    description: &str,
+) -> windows::core::Result<()> {
+    use windows::core::HSTRING;
+    use windows::Win32::Foundation::HWND;
+    use windows::Win32::System::Com::{CoCreateInstance, CLSCTX_ALL};
+    use windows::Win32::UI::Shell::{ITaskbarList3, TaskbarList};
+    use windows::Win32::UI::WindowsAndMessaging::{CreateIcon, DestroyIcon, HICON};
+
+    let hicon = match icon {
+        Some((rgba, width, height)) => {
+            // CreateIcon wants BGRA, plus an AND mask with one byte per pixel.
+            let mut bgra = rgba.to_vec();
+            let mut and_mask = Vec::with_capacity(bgra.len() / 4);
+            for pixel in bgra.chunks_exact_mut(4) {
+                pixel.swap(0, 2);
+                and_mask.push(pixel[3].wrapping_sub(u8::MAX));
+            }
+            unsafe {
+                CreateIcon(
+                    None,
+                    width as i32,
+                    height as i32,
+                    1,
+                    32,
+                    and_mask.as_ptr(),
+                    bgra.as_ptr(),
+                )?
+            }
+        }
+        None => HICON::default(),
+    };
+    let result = unsafe {
+        CoCreateInstance::<_, ITaskbarList3>(&TaskbarList, None, CLSCTX_ALL).and_then(|taskbar| {
+            taskbar.HrInit()?;
+            taskbar.SetOverlayIcon(HWND(hwnd as _), hicon, &HSTRING::from(description))
+        })
+    };
+    // The taskbar keeps its own copy.
+    if !hicon.is_invalid() {
+        let _ = unsafe { DestroyIcon(hicon) };
+    }
+    result
+}
@@ -0,0 +1,388 @@
+/// Whole-window screenshots, including the native frame, and snapshots of
+/// the page alone.
+///
+/// Window captures go through the window server so the title bar and
+/// borders are part of the image — what a user would see in a bug report.
+/// Page snapshots come from the engine instead (`CapturePreview`,
+/// `takeSnapshot`, `webkit_web_view_get_snapshot`), so they work for
+/// windows that are off-screen. Every backend returns PNG bytes.
+use tao::window::Window;
+use wry::WebView;
+
+/// Capture `window` (frame + content) as a PNG.
+pub fn capture_window(window: &Window) -> Result<Vec<u8>, String> {
+    #[cfg(target_os = "macos")]
+    {
+        capture_macos(window)
+    }
+    #[cfg(target_os = "windows")]
+    {
+        capture_windows(window)
+    }
+    #[cfg(target_os = "linux")]
+    {
+        capture_linux(window)
+    }
+    #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+    {
+        let _ = window;
+        Err("captureWindow is not supported on this platform".to_string())
+    }
+}
+
+/// Snapshot the visible area of `webview`'s page as a PNG. `on_result`
+/// runs during a later pump.
+pub fn capture_page<F>(webview: &WebView, on_result: F)
+where
+    F: FnOnce(Result<Vec<u8>, String>) + 'static,
+{
+    #[cfg(target_os = "macos")]
+    {
+        capture_page_macos(webview, on_result);
+    }
+    #[cfg(target_os = "windows")]
+    {
+        capture_page_windows(webview, on_result);
+    }
+    #[cfg(target_os = "linux")]
+    {
+        capture_page_linux(webview, on_result);
+    }
+    #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+    {
+        let _ = webview;
+        on_result(Err(
+            "capturePage is not supported on this platform".to_string()
+        ));
+    }
+}
+
+/// Capture `window` and scale it down to fit `max_width` x `max_height`,
+/// keeping its aspect ratio. Smaller captures are returned as-is.
+pub fn capture_thumbnail(
+    window: &Window,
+    max_width: u32,
+    max_height: u32,
+) -> Result<Vec<u8>, String> {
+    let png = capture_window(window)?;
+    let image = image::load_from_memory_with_format(&png, image::ImageFormat::Png)
+        .map_err(|e| e.to_string())?;
+    if image.width() <= max_width && image.height() <= max_height {
+        return Ok(png);
+    }
+    let mut thumbnail = std::io::Cursor::new(Vec::new());
+    image
+        .thumbnail(max_width, max_height)
+        .write_to(&mut thumbnail, image::ImageFormat::Png)
+        .map_err(|e| e.to_string())?;
+    Ok(thumbnail.into_inner())
+}
+
+// ── macOS ──────────────────────────────────────────────────────
+
+/// `CGWindowListCreateImage` for the window's CGWindowID, encoded through
+/// `NSBitmapImageRep`. Requires the Screen Recording permission on
+/// macOS 10.15+ for windows of other apps, but not for our own.
+#[cfg(target_os = "macos")]
+fn capture_macos(window: &Window) -> Result<Vec<u8>, String> {
+    use objc2::AnyThread;
+    use objc2_app_kit::{NSBitmapImageFileType, NSBitmapImageRep, NSWindow};
+    use objc2_core_graphics::{
+        CGRectNull, CGWindowImageOption, CGWindowListCreateImage, CGWindowListOption,
+    };
+    use objc2_foundation::NSDictionary;
+    use tao::platform::macos::WindowExtMacOS;
+
+    let ns_window = window.ns_window() as *const NSWindow;
+    let ns_window = unsafe { ns_window.as_ref() }.ok_or("window has no NSWindow")?;
+    let window_id = ns_window.windowNumber() as u32;
+
+    // Deprecated in favor of ScreenCaptureKit, which is async-only and
+    // needs a newer deployment target; still functional for own windows.
+    #[allow(deprecated)]
+    let image = CGWindowListCreateImage(
+        unsafe { CGRectNull },
+        CGWindowListOption::OptionIncludingWindow,
+        window_id,
+        CGWindowImageOption::BoundsIgnoreFraming | CGWindowImageOption::BestResolution,
+    )
+    .ok_or("CGWindowListCreateImage returned no image")?;
+
+    let rep = NSBitmapImageRep::initWithCGImage(NSBitmapImageRep::alloc(), &image);
+    let data = unsafe {
+        rep.representationUsingType_properties(NSBitmapImageFileType::PNG, &NSDictionary::new())
+    }
+    .ok_or("PNG encoding failed")?;
+    Ok(data.to_vec())
+}
+
+/// `takeSnapshotWithConfiguration:completionHandler:`, re-encoded from the
+/// image's TIFF representation.
+#[cfg(target_os = "macos")]
+fn capture_page_macos<F>(webview: &WebView, on_result: F)
+where
+    F: FnOnce(Result<Vec<u8>, String>) + 'static,
+{
+    use std::cell::Cell;
+
+    use block2::RcBlock;
+    use objc2::msg_send;
+    use objc2::runtime::AnyObject;
+    use objc2_app_kit::{NSBitmapImageFileType, NSBitmapImageRep, NSImage};
+    use objc2_foundation::{NSDictionary, NSError};
+    use wry::WebViewExtMacOS;
+
+    let on_result = Cell::new(Some(on_result));
+    let handler = RcBlock::new(move |image: *mut NSImage, _error: *mut NSError| {
+        let png = unsafe { image.as_ref() }
+            .ok_or_else(|| "takeSnapshot returned no image".to_string())
+            .and_then(|image| {
+                let tiff = image
+                    .TIFFRepresentation()
+                    .ok_or("image has no bitmap data")?;
+                let rep = NSBitmapImageRep::imageRepWithData(&tiff).ok_or("invalid bitmap data")?;
+                let data = unsafe {
+                    rep.representationUsingType_properties(
+                        NSBitmapImageFileType::PNG,
+                        &NSDictionary::new(),
+                    )
+                }
+                .ok_or("PNG encoding failed")?;
+                Ok(data.to_vec())
+            });
+        if let Some(on_result) = on_result.take() {
+            on_result(png);
+        }
+    });
+    let webview = webview.webview();
+    unsafe {
+        let _: () = msg_send![
+            &*webview,
+            takeSnapshotWithConfiguration: std::ptr::null::<AnyObject>(),
+            completionHandler: &*handler
+        ];
+    }
+}
+
+// ── Windows ────────────────────────────────────────────────────
+
+/// `PrintWindow` with `PW_RENDERFULLCONTENT`, which also captures the
+/// DirectComposition surface WebView2 renders into.
+#[cfg(target_os = "windows")]
+fn capture_windows(window: &Window) -> Result<Vec<u8>, String> {
+    use tao::platform::windows::WindowExtWindows;
+    use windows::Win32::Foundation::{HWND, RECT};
+    use windows::Win32::Graphics::Gdi::{
+        CreateCompatibleBitmap, CreateCompatibleDC, DeleteDC, DeleteObject, GetDC, GetDIBits,
+        ReleaseDC, SelectObject, BITMAPINFO, BITMAPINFOHEADER, BI_RGB, DIB_RGB_COLORS,
+    };
+    use windows::Win32::Storage::Xps::{PrintWindow, PRINT_WINDOW_FLAGS};
+    use windows::Win32::UI::WindowsAndMessaging::{GetWindowRect, PW_RENDERFULLCONTENT};
+
+    let hwnd = HWND(window.hwnd() as _);
+    let mut rect = RECT::default();
+    unsafe { GetWindowRect(hwnd, &mut rect) }.map_err(|e| e.to_string())?;
+    let width = rect.right - rect.left;
+    let height = rect.bottom - rect.top;
+    if width <= 0 || height <= 0 {
+        return Err("window has no visible area".to_string());
+    }
+
+    let mut pixels = vec![0u8; width as usize * height as usize * 4];
+    let copied = unsafe {
+        let screen_dc = GetDC(None);
+        let mem_dc = CreateCompatibleDC(Some(screen_dc));
+        let bitmap = CreateCompatibleBitmap(screen_dc, width, height);
+        let previous = SelectObject(mem_dc, bitmap.into());
+
+        let printed = PrintWindow(hwnd, mem_dc, PRINT_WINDOW_FLAGS(PW_RENDERFULLCONTENT)).as_bool();
+        let mut info = BITMAPINFO {
+            bmiHeader: BITMAPINFOHEADER {
+                biSize: std::mem::size_of::<BITMAPINFOHEADER>() as u32,
+                biWidth: width,
+                // Negative height: top-down rows.
+                biHeight: -height,
+                biPlanes: 1,
+                biBitCount: 32,
+                biCompression: BI_RGB.0,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let lines = if printed {
+            GetDIBits(
+                mem_dc,
+                bitmap,
+                0,
+                height as u32,
+                Some(pixels.as_mut_ptr().cast()),
+                &mut info,
+                DIB_RGB_COLORS,
+            )
+        } else {
+            0
+        };
+
+        SelectObject(mem_dc, previous);
+        let _ = DeleteObject(bitmap.into());
+        let _ = DeleteDC(mem_dc);
+        ReleaseDC(None, screen_dc);
+        lines == height
+    };
+    if !copied {
+        return Err("PrintWindow failed".to_string());
+    }
+
+    // GDI gives BGRX; PNG wants RGBA.
+    for px in pixels.chunks_exact_mut(4) {
+        px.swap(0, 2);
+        px[3] = 255;
+    }
+    encode_png(width as u32, height as u32, pixels)
+}
+
+/// `ICoreWebView2::CapturePreview` into an in-memory stream.
+#[cfg(target_os = "windows")]
+fn capture_page_windows<F>(webview: &WebView, on_result: F)
+where
+    F: FnOnce(Result<Vec<u8>, String>) + 'static,
+{
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    use webview2_com::CapturePreviewCompletedHandler;
+    use webview2_com::Microsoft::Web::WebView2::Win32::COREWEBVIEW2_CAPTURE_PREVIEW_IMAGE_FORMAT_PNG;
+    use windows::Win32::UI::Shell::SHCreateMemStream;
+    use wry::WebViewExtWindows;
+
+    let Some(stream) = (unsafe { SHCreateMemStream(None) }) else {
+        on_result(Err("could not create a memory stream".to_string()));
+        return;
+    };
+    // Shared so a synchronous failure can still report.
+    let on_result = Rc::new(Cell::new(Some(on_result)));
+    let handler = {
+        let stream = stream.clone();
+        let on_result = on_result.clone();
+        CapturePreviewCompletedHandler::create(Box::new(move |result| {
+            let png = result
+                .and_then(|()| read_stream(&stream))
+                .map_err(|e| e.to_string());
+            if let Some(on_result) = on_result.take() {
+                on_result(png);
+            }
+            Ok(())
+        }))
+    };
+    let started = unsafe {
+        webview.webview().CapturePreview(
+            COREWEBVIEW2_CAPTURE_PREVIEW_IMAGE_FORMAT_PNG,
+            &stream,
+            &handler,
+        )
+    };
+    if let Err(e) = started {
+        if let Some(on_result) = on_result.take() {
+            on_result(Err(e.to_string()));
+        }
+    }
+}
+
+/// Read a whole `IStream` from the start.
+#[cfg(target_os = "windows")]
+fn read_stream(stream: &windows::Win32::System::Com::IStream) -> windows::core::Result<Vec<u8>> {
+    use windows::Win32::System::Com::{STATFLAG_NONAME, STATSTG, STREAM_SEEK_SET};
+
+    let mut stat = STATSTG::default();
+    unsafe {
+        stream.Stat(&mut stat, STATFLAG_NONAME)?;
+        stream.Seek(0, STREAM_SEEK_SET, None)?;
+    }
+    let mut data = vec![0u8; stat.cbSize as usize];
+    let mut read = 0u32;
+    unsafe { stream.Read(data.as_mut_ptr().cast(), data.len() as u32, Some(&mut read)) }.ok()?;
+    data.truncate(read as usize);
+    Ok(data)
+}
+
+// ── Linux ──────────────────────────────────────────────────────
+
+/// Read the window's frame extents back from the root window, so
+/// server-side decorations are included. X11 only — Wayland does not
+/// allow reading other surfaces.
+#[cfg(target_os = "linux")]
+fn capture_linux(window: &Window) -> Result<Vec<u8>, String> {
+    use gtk::gdk::prelude::*;
+    use gtk::prelude::*;
+    use tao::platform::unix::WindowExtUnix;
+
+    let gdk_window = window
+        .gtk_window()
+        .window()
+        .ok_or("window is not realized")?;
+    let frame = gdk_window.frame_extents();
+    let root = gdk_window
+        .screen()
+        .root_window()
+        .ok_or("no root window (captureWindow requires X11)")?;
+    let pixbuf = root
+        .pixbuf(frame.x(), frame.y(), frame.width(), frame.height())
+        .ok_or("could not read window contents (captureWindow requires X11)")?;
+    pixbuf
+        .save_to_bufferv("png", &[])
+        .map_err(|e| e.to_string())
+}
+
+/// `webkit_web_view_get_snapshot` of the visible region, converted to a
+/// pixbuf for PNG encoding.
+#[cfg(target_os = "linux")]
+fn capture_page_linux<F>(webview: &WebView, on_result: F)
+where
+    F: FnOnce(Result<Vec<u8>, String>) + 'static,
+{
+    use gtk::cairo::ImageSurface;
+    use webkit2gtk::{SnapshotOptions, SnapshotRegion, WebViewExt};
+    use wry::WebViewExtUnix;
+
+    webview.webview().snapshot(
+        SnapshotRegion::Visible,
+        SnapshotOptions::NONE,
+        None::<&webkit2gtk::gio::Cancellable>,
+        move |result| {
+            let png = result
+                .map_err(|e| e.to_string())
+                .and_then(|surface| {
+                    let surface = ImageSurface::try_from(surface)
+                        .map_err(|_| "snapshot is not an image surface".to_string())?;
+                    gtk::gdk::pixbuf_get_from_surface(
+                        &surface,
+                        0,
+                        0,
+                        surface.width(),
+                        surface.height(),
+                    )
+                    .ok_or_else(|| "could not read snapshot pixels".to_string())
+                })
+                .and_then(|pixbuf| {
+                    pixbuf
+                        .save_to_bufferv("png", &[])
+                        .map_err(|e| e.to_string())
+                });
+            on_result(png);
+        },
+    );
+}
+
+// ── PNG encoding ───────────────────────────────────────────────
+
+/// Encode tightly packed RGBA rows as PNG.
+#[cfg(target_os = "windows")]
+fn encode_png(width: u32, height: u32, rgba: Vec<u8>) -> Result<Vec<u8>, String> {
+    let image =
+        image::RgbaImage::from_raw(width, height, rgba).ok_or("pixel buffer size mismatch")?;
+    let mut png = std::io::Cursor::new(Vec::new());
+    image
+        .write_to(&mut png, image::ImageFormat::Png)
+        .map_err(|e| e.to_string())?;
+    Ok(png.into_inner())
+}
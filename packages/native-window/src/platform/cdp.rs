@@ -0,0 +1,113 @@
+/// Chrome DevTools Protocol (CDP) access for the WebView2 backend.
+///
+/// WebView2 exposes the full CDP surface through
+/// `ICoreWebView2::CallDevToolsProtocolMethod`, which lets us implement
+/// engine-level features that wry does not surface. Windows only.
+use webview2_com::Microsoft::Web::WebView2::Win32::{
+    ICoreWebView2, ICoreWebView2DevToolsProtocolEventReceiver,
+};
+use webview2_com::{
+    take_pwstr, CallDevToolsProtocolMethodCompletedHandler,
+    DevToolsProtocolEventReceivedEventHandler,
+};
+use windows::core::{HSTRING, PWSTR};
+use wry::{WebView, WebViewExtWindows};
+
+/// Invoke a CDP method on a webview. `params` must be a JSON object string.
+///
+/// The callback receives the JSON result on success or an error message on
+/// failure. WebView2 completes the call on the UI thread, so the callback
+/// runs during a later `pumpEvents()` and may push to PENDING_* buffers.
+pub fn call_method<F>(
+    webview: &WebView,
+    method: &str,
+    params: &str,
+    callback: F,
+) -> napi::Result<()>
+where
+    F: FnOnce(Result<String, String>) + 'static,
+{
+    call_core_method(&webview.webview(), method, params, callback)
+}
+
+/// [`call_method`] addressed to the core webview, for chaining calls from
+/// a completion callback.
+pub fn call_core_method<F>(
+    core: &ICoreWebView2,
+    method: &str,
+    params: &str,
+    callback: F,
+) -> napi::Result<()>
+where
+    F: FnOnce(Result<String, String>) + 'static,
+{
+    let method_name = HSTRING::from(method);
+    let params_json = HSTRING::from(params);
+    let handler = CallDevToolsProtocolMethodCompletedHandler::create(Box::new(
+        move |result: windows::core::Result<()>, json: String| {
+            callback(result.map(|_| json).map_err(|e| e.to_string()));
+            Ok(())
+        },
+    ));
+    unsafe { core.CallDevToolsProtocolMethod(&method_name, &params_json, &handler) }
+        .map_err(|e| napi::Error::from_reason(format!("CDP {} failed: {}", method, e)))
+}
+
+/// Fire-and-forget variant of [`call_method`]. Failures reported by the
+/// DevTools backend are logged rather than surfaced to JS.
+pub fn send(webview: &WebView, method: &str, params: &str) -> napi::Result<()> {
+    send_to(&webview.webview(), method, params)
+}
+
+/// [`send`] addressed to the core webview, for event handlers that must
+/// answer an event without holding the `WebView`.
+pub fn send_to(core: &ICoreWebView2, method: &str, params: &str) -> napi::Result<()> {
+    let name = method.to_string();
+    call_core_method(core, method, params, move |result| {
+        if let Err(e) = result {
+            eprintln!("[native-window] CDP {} failed: {}", name, e);
+        }
+    })
+}
+
+/// A live subscription to a CDP event. Unsubscribes when dropped.
+pub struct Subscription {
+    receiver: ICoreWebView2DevToolsProtocolEventReceiver,
+    token: i64,
+}
+
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        let _ = unsafe {
+            self.receiver
+                .remove_DevToolsProtocolEventReceived(self.token)
+        };
+    }
+}
+
+/// Subscribe to CDP event `event` (e.g. `"Network.responseReceived"`).
+/// The callback receives each event's parameters as a JSON object string
+/// on the UI thread, during `pumpEvents()`. The domain must be enabled
+/// separately (e.g. `Network.enable`).
+pub fn subscribe<F>(webview: &WebView, event: &str, mut callback: F) -> napi::Result<Subscription>
+where
+    F: FnMut(String) + 'static,
+{
+    let core = webview.webview();
+    let handler = DevToolsProtocolEventReceivedEventHandler::create(Box::new(move |_, args| {
+        if let Some(args) = args {
+            let mut json = PWSTR::null();
+            unsafe { args.ParameterObjectAsJson(&mut json) }?;
+            callback(take_pwstr(json));
+        }
+        Ok(())
+    }));
+    let register = || -> windows::core::Result<Subscription> {
+        let mut token = 0i64;
+        let receiver = unsafe { core.GetDevToolsProtocolEventReceiver(&HSTRING::from(event)) }?;
+        unsafe { receiver.add_DevToolsProtocolEventReceived(&handler, &mut token) }?;
+        Ok(Subscription { receiver, token })
+    };
+    register()
+        .map_err(|e| napi::Error::from_reason(format!("CDP subscribe to {} failed: {}", event, e)))
+}
@@ -0,0 +1,351 @@
+/// Screen color picking for `pickColorFromScreen()`.
+///
+/// macOS shows the system eyedropper (`NSColorSampler`). Windows has none,
+/// so the screen is frozen into a full-screen overlay with a magnifier
+/// next to the cursor: a click picks the pixel under it, and Escape or a
+/// right-click cancels. Picks are reported through `PENDING_COLOR_PICKS`,
+/// with `None` when cancelled. Not supported on Linux.
+use crate::options::PickedColor;
+use crate::window_manager::{MAX_PENDING_EVENTS, PENDING_COLOR_PICKS};
+
+/// Queue the result of a pick for the next pump.
+pub fn report(window_id: u32, color: Option<PickedColor>) {
+    PENDING_COLOR_PICKS.with(|p| {
+        let mut buf = p.borrow_mut();
+        if buf.len() < MAX_PENDING_EVENTS {
+            buf.push((window_id, color));
+        }
+    });
+}
+
+/// Start picking a color for `window_id`. The result is reported once the
+/// user picks or cancels.
+#[cfg(target_os = "macos")]
+pub fn start(window_id: u32) -> Result<(), String> {
+    use block2::RcBlock;
+    use objc2_app_kit::{NSColor, NSColorSampler, NSColorSpace};
+
+    let sampler = NSColorSampler::new();
+    // Called on the main thread, with nil when the user cancels.
+    let handler = RcBlock::new(move |color: *mut NSColor| {
+        let srgb = unsafe { color.as_ref() }
+            .and_then(|c| c.colorUsingColorSpace(&NSColorSpace::sRGBColorSpace()));
+        let channel = |v: f64| (v.clamp(0.0, 1.0) * 255.0).round() as u32;
+        report(
+            window_id,
+            srgb.map(|c| PickedColor {
+                r: channel(c.redComponent()),
+                g: channel(c.greenComponent()),
+                b: channel(c.blueComponent()),
+                a: c.alphaComponent(),
+            }),
+        );
+    });
+    // The sampler keeps itself alive until the session ends.
+    unsafe { sampler.showSamplerWithSelectionHandler(&handler) };
+    Ok(())
+}
+
+#[cfg(target_os = "windows")]
+pub use overlay::start;
+
+#[cfg(target_os = "windows")]
+mod overlay {
+    use std::cell::RefCell;
+
+    use windows::core::w;
+    use windows::Win32::Foundation::{HWND, LPARAM, LRESULT, POINT, RECT, WPARAM};
+    use windows::Win32::Graphics::Gdi::{
+        BeginPaint, BitBlt, CreateCompatibleBitmap, CreateCompatibleDC, DeleteDC, DeleteObject,
+        EndPaint, FrameRect, GetDC, GetPixel, GetStockObject, InvalidateRect, ReleaseDC,
+        SelectObject, SetStretchBltMode, StretchBlt, BLACK_BRUSH, CAPTUREBLT, COLORONCOLOR,
+        HBITMAP, HBRUSH, HDC, HGDIOBJ, PAINTSTRUCT, ROP_CODE, SRCCOPY, WHITE_BRUSH,
+    };
+    use windows::Win32::UI::Input::KeyboardAndMouse::VK_ESCAPE;
+    use windows::Win32::UI::WindowsAndMessaging::{
+        CreateWindowExW, DefWindowProcW, DestroyWindow, GetCursorPos, GetSystemMetrics,
+        LoadCursorW, RegisterClassW, SetForegroundWindow, ShowWindow, IDC_CROSS,
+        SM_CXVIRTUALSCREEN, SM_CYVIRTUALSCREEN, SM_XVIRTUALSCREEN, SM_YVIRTUALSCREEN, SW_SHOW,
+        WA_INACTIVE, WM_ACTIVATE, WM_CLOSE, WM_KEYDOWN, WM_LBUTTONDOWN, WM_MOUSEMOVE, WM_PAINT,
+        WM_RBUTTONDOWN, WNDCLASSW, WS_EX_TOOLWINDOW, WS_EX_TOPMOST, WS_POPUP,
+    };
+
+    use super::report;
+    use crate::options::PickedColor;
+
+    /// Screen pixels shown across the magnifier (odd, so one is centered).
+    const SAMPLE: i32 = 11;
+    /// Magnification of the sampled pixels.
+    const ZOOM: i32 = 11;
+    /// Gap between the cursor and the magnifier.
+    const OFFSET: i32 = 16;
+
+    /// A running pick: the overlay window and the frozen screen it shows.
+    struct Overlay {
+        window_id: u32,
+        hwnd: HWND,
+        /// Memory DC holding the screenshot, in virtual-screen pixels.
+        screen: HDC,
+        bitmap: HBITMAP,
+        previous: HGDIOBJ,
+        width: i32,
+        height: i32,
+        /// Magnifier area at the last paint, in overlay coordinates.
+        magnifier: RECT,
+    }
+
+    thread_local! {
+        static ACTIVE: RefCell<Option<Overlay>> = const { RefCell::new(None) };
+    }
+
+    /// Freeze the screen and show the overlay. A pick already in progress
+    /// is cancelled.
+    pub fn start(window_id: u32) -> Result<(), String> {
+        finish(None);
+
+        unsafe {
+            let x = GetSystemMetrics(SM_XVIRTUALSCREEN);
+            let y = GetSystemMetrics(SM_YVIRTUALSCREEN);
+            let width = GetSystemMetrics(SM_CXVIRTUALSCREEN);
+            let height = GetSystemMetrics(SM_CYVIRTUALSCREEN);
+
+            let screen_dc = GetDC(None);
+            let screen = CreateCompatibleDC(Some(screen_dc));
+            let bitmap = CreateCompatibleBitmap(screen_dc, width, height);
+            let previous = SelectObject(screen, bitmap.into());
+            // CAPTUREBLT includes layered windows, e.g. tooltips.
+            let copied = BitBlt(
+                screen,
+                0,
+                0,
+                width,
+                height,
+                Some(screen_dc),
+                x,
+                y,
+                ROP_CODE(SRCCOPY.0 | CAPTUREBLT.0),
+            );
+            ReleaseDC(None, screen_dc);
+            let release = || {
+                SelectObject(screen, previous);
+                let _ = DeleteObject(bitmap.into());
+                let _ = DeleteDC(screen);
+            };
+            if let Err(e) = copied {
+                release();
+                return Err(format!("screen capture failed: {}", e));
+            }
+
+            let class = WNDCLASSW {
+                lpfnWndProc: Some(window_proc),
+                hCursor: LoadCursorW(None, IDC_CROSS).unwrap_or_default(),
+                lpszClassName: w!("NativeWindowColorPicker"),
+                ..Default::default()
+            };
+            // Fails harmlessly once the class is registered.
+            RegisterClassW(&class);
+
+            let hwnd = match CreateWindowExW(
+                WS_EX_TOPMOST | WS_EX_TOOLWINDOW,
+                w!("NativeWindowColorPicker"),
+                w!(""),
+                WS_POPUP,
+                x,
+                y,
+                width,
+                height,
+                None,
+                None,
+                None,
+                None,
+            ) {
+                Ok(hwnd) => hwnd,
+                Err(e) => {
+                    release();
+                    return Err(format!("failed to create overlay: {}", e));
+                }
+            };
+            ACTIVE.with(|a| {
+                *a.borrow_mut() = Some(Overlay {
+                    window_id,
+                    hwnd,
+                    screen,
+                    bitmap,
+                    previous,
+                    width,
+                    height,
+                    magnifier: RECT::default(),
+                })
+            });
+            // Shown only once registered, as showing it paints.
+            let _ = ShowWindow(hwnd, SW_SHOW);
+            let _ = SetForegroundWindow(hwnd);
+        }
+        Ok(())
+    }
+
+    /// End the running pick, if any, and report `color`.
+    fn finish(color: Option<PickedColor>) {
+        // Taken out first: destroying the window re-enters window_proc.
+        let Some(overlay) = ACTIVE.with(|a| a.borrow_mut().take()) else {
+            return;
+        };
+        unsafe {
+            let _ = DestroyWindow(overlay.hwnd);
+            SelectObject(overlay.screen, overlay.previous);
+            let _ = DeleteObject(overlay.bitmap.into());
+            let _ = DeleteDC(overlay.screen);
+        }
+        report(overlay.window_id, color);
+    }
+
+    /// The frozen pixel at overlay coordinates (`x`, `y`).
+    fn sample(overlay: &Overlay, x: i32, y: i32) -> Option<PickedColor> {
+        if x < 0 || y < 0 || x >= overlay.width || y >= overlay.height {
+            return None;
+        }
+        // COLORREF is 0x00BBGGRR.
+        let rgb = unsafe { GetPixel(overlay.screen, x, y) }.0;
+        Some(PickedColor {
+            r: rgb & 0xff,
+            g: (rgb >> 8) & 0xff,
+            b: (rgb >> 16) & 0xff,
+            a: 1.0,
+        })
+    }
+
+    /// Where the magnifier goes for a cursor at (`x`, `y`): below-right of
+    /// it, flipped to stay inside the overlay near its edges.
+    fn magnifier_rect(overlay: &Overlay, x: i32, y: i32) -> RECT {
+        let size = SAMPLE * ZOOM;
+        let left = if x + OFFSET + size > overlay.width {
+            x - OFFSET - size
+        } else {
+            x + OFFSET
+        };
+        let top = if y + OFFSET + size > overlay.height {
+            y - OFFSET - size
+        } else {
+            y + OFFSET
+        };
+        RECT {
+            left,
+            top,
+            right: left + size,
+            bottom: top + size,
+        }
+    }
+
+    /// Repaint the damaged part of the frozen screen, then the magnifier
+    /// around the current cursor position.
+    unsafe fn paint(overlay: &mut Overlay) {
+        let mut ps = PAINTSTRUCT::default();
+        let hdc = BeginPaint(overlay.hwnd, &mut ps);
+        let dirty = ps.rcPaint;
+        let _ = BitBlt(
+            hdc,
+            dirty.left,
+            dirty.top,
+            dirty.right - dirty.left,
+            dirty.bottom - dirty.top,
+            Some(overlay.screen),
+            dirty.left,
+            dirty.top,
+            SRCCOPY,
+        );
+
+        let mut cursor = POINT::default();
+        if GetCursorPos(&mut cursor).is_ok() {
+            let x = cursor.x - GetSystemMetrics(SM_XVIRTUALSCREEN);
+            let y = cursor.y - GetSystemMetrics(SM_YVIRTUALSCREEN);
+            let rect = magnifier_rect(overlay, x, y);
+            SetStretchBltMode(hdc, COLORONCOLOR);
+            let _ = StretchBlt(
+                hdc,
+                rect.left,
+                rect.top,
+                SAMPLE * ZOOM,
+                SAMPLE * ZOOM,
+                Some(overlay.screen),
+                x - SAMPLE / 2,
+                y - SAMPLE / 2,
+                SAMPLE,
+                SAMPLE,
+                SRCCOPY,
+            );
+            FrameRect(hdc, &rect, HBRUSH(GetStockObject(BLACK_BRUSH).0));
+            // Outline the pixel that a click would pick.
+            let center = RECT {
+                left: rect.left + (SAMPLE / 2) * ZOOM - 1,
+                top: rect.top + (SAMPLE / 2) * ZOOM - 1,
+                right: rect.left + (SAMPLE / 2 + 1) * ZOOM + 1,
+                bottom: rect.top + (SAMPLE / 2 + 1) * ZOOM + 1,
+            };
+            FrameRect(hdc, &center, HBRUSH(GetStockObject(WHITE_BRUSH).0));
+            overlay.magnifier = rect;
+        }
+        let _ = EndPaint(overlay.hwnd, &ps);
+    }
+
+    /// Overlay coordinates from a mouse message's `lparam`.
+    fn mouse_position(lparam: LPARAM) -> (i32, i32) {
+        let x = (lparam.0 & 0xffff) as i16 as i32;
+        let y = ((lparam.0 >> 16) & 0xffff) as i16 as i32;
+        (x, y)
+    }
+
+    unsafe extern "system" fn window_proc(
+        hwnd: HWND,
+        msg: u32,
+        wparam: WPARAM,
+        lparam: LPARAM,
+    ) -> LRESULT {
+        match msg {
+            WM_PAINT => {
+                let painted = ACTIVE.with(|a| match a.borrow_mut().as_mut() {
+                    Some(overlay) if overlay.hwnd == hwnd => {
+                        paint(overlay);
+                        true
+                    }
+                    _ => false,
+                });
+                if painted {
+                    return LRESULT(0);
+                }
+            }
+            WM_MOUSEMOVE => {
+                ACTIVE.with(|a| {
+                    if let Some(overlay) = a.borrow().as_ref() {
+                        let (x, y) = mouse_position(lparam);
+                        let next = magnifier_rect(overlay, x, y);
+                        let _ =
+                            InvalidateRect(Some(hwnd), Some(&overlay.magnifier as *const _), false);
+                        let _ = InvalidateRect(Some(hwnd), Some(&next as *const _), false);
+                    }
+                });
+                return LRESULT(0);
+            }
+            WM_LBUTTONDOWN => {
+                let (x, y) = mouse_position(lparam);
+                let color = ACTIVE.with(|a| a.borrow().as_ref().and_then(|o| sample(o, x, y)));
+                finish(color);
+                return LRESULT(0);
+            }
+            WM_KEYDOWN if wparam.0 == VK_ESCAPE.0 as usize => {
+                finish(None);
+                return LRESULT(0);
+            }
+            WM_RBUTTONDOWN | WM_CLOSE => {
+                finish(None);
+                return LRESULT(0);
+            }
+            // Switching away (e.g. Alt+Tab) cancels rather than leaving a
+            // frozen screen on top.
+            WM_ACTIVATE if (wparam.0 & 0xffff) as u32 == WA_INACTIVE => {
+                finish(None);
+            }
+            _ => {}
+        }
+        DefWindowProcW(hwnd, msg, wparam, lparam)
+    }
+}
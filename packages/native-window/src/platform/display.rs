@@ -0,0 +1,261 @@
+/// Monitor geometry helpers shared by window placement features.
+///
+/// All rectangles are in physical pixels, in the same desktop coordinate
+/// space as tao's `MonitorHandle::position()` and `Window::outer_position()`.
+use napi::bindgen_prelude::Either;
+use tao::dpi::{PhysicalPosition, PhysicalSize};
+use tao::event_loop::EventLoopWindowTarget;
+use tao::monitor::MonitorHandle;
+use tao::window::Window;
+
+/// A rectangle in physical desktop pixels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rect {
+    pub x: i32,
+    pub y: i32,
+    pub width: i32,
+    pub height: i32,
+}
+
+impl Rect {
+    /// Shrink the rectangle by the given edge insets (physical pixels).
+    #[cfg_attr(not(any(target_os = "macos", target_os = "linux")), allow(dead_code))]
+    fn inset(self, left: f64, top: f64, right: f64, bottom: f64) -> Self {
+        let left = left.max(0.0).round() as i32;
+        let top = top.max(0.0).round() as i32;
+        let right = right.max(0.0).round() as i32;
+        let bottom = bottom.max(0.0).round() as i32;
+        Self {
+            x: self.x + left,
+            y: self.y + top,
+            width: (self.width - left - right).max(0),
+            height: (self.height - top - bottom).max(0),
+        }
+    }
+}
+
+// ── Monitor lookup ─────────────────────────────────────────────
+
+/// Find a monitor by its index in the OS enumeration order.
+pub fn monitor_by_index(window: &Window, index: u32) -> Option<MonitorHandle> {
+    window.available_monitors().nth(index as usize)
+}
+
+/// Resolve an optional display index to a monitor, defaulting to the
+/// monitor that currently contains the window.
+pub fn resolve_monitor(window: &Window, display: Option<u32>) -> napi::Result<MonitorHandle> {
+    match display {
+        Some(index) => monitor_by_index(window, index)
+            .ok_or_else(|| napi::Error::from_reason(format!("Display {} not found", index))),
+        None => window
+            .current_monitor()
+            .or_else(|| window.primary_monitor())
+            .ok_or_else(|| napi::Error::from_reason("No display available")),
+    }
+}
+
+/// Resolve the `display` window option before the window exists:
+/// a monitor index, `"primary"`, or `"cursor"`.
+pub fn resolve_display(
+    target: &EventLoopWindowTarget<()>,
+    display: &Either<u32, String>,
+) -> napi::Result<MonitorHandle> {
+    let monitor = match display {
+        Either::A(index) => {
+            return target
+                .available_monitors()
+                .nth(*index as usize)
+                .ok_or_else(|| napi::Error::from_reason(format!("Display {} not found", index)));
+        }
+        Either::B(name) if name == "cursor" => target
+            .cursor_position()
+            .ok()
+            .and_then(|p| target.monitor_from_point(p.x, p.y))
+            .or_else(|| target.primary_monitor()),
+        Either::B(_) => target.primary_monitor(),
+    };
+    monitor
+        .or_else(|| target.available_monitors().next())
+        .ok_or_else(|| napi::Error::from_reason("No display available"))
+}
+
+// ── Geometry ───────────────────────────────────────────────────
+
+/// Area shared by two rectangles, in square pixels (0 if disjoint).
+pub fn overlap(a: Rect, b: Rect) -> i64 {
+    let width = (a.x + a.width).min(b.x + b.width) - a.x.max(b.x);
+    let height = (a.y + a.height).min(b.y + b.height) - a.y.max(b.y);
+    if width <= 0 || height <= 0 {
+        return 0;
+    }
+    width as i64 * height as i64
+}
+
+/// Top-left position that centers a `width` x `height` frame in `area`,
+/// clamped so the frame's top-left corner stays inside it.
+pub fn centered(area: Rect, width: i32, height: i32) -> PhysicalPosition<i32> {
+    PhysicalPosition::new(
+        area.x + ((area.width - width) / 2).max(0),
+        area.y + ((area.height - height) / 2).max(0),
+    )
+}
+
+/// Outer frame of a window, or `None` if the position is unavailable.
+pub fn outer_bounds(window: &Window) -> Option<Rect> {
+    let position = window.outer_position().ok()?;
+    let size = window.outer_size();
+    Some(Rect {
+        x: position.x,
+        y: position.y,
+        width: size.width as i32,
+        height: size.height as i32,
+    })
+}
+
+/// Full bounds of a monitor.
+pub fn bounds(monitor: &MonitorHandle) -> Rect {
+    let position = monitor.position();
+    let size = monitor.size();
+    Rect {
+        x: position.x,
+        y: position.y,
+        width: size.width as i32,
+        height: size.height as i32,
+    }
+}
+
+/// Usable area of a monitor, excluding the taskbar, dock, and menu bar.
+/// Falls back to the full monitor bounds if the OS query fails.
+pub fn work_area(monitor: &MonitorHandle) -> Rect {
+    let full = bounds(monitor);
+
+    #[cfg(target_os = "macos")]
+    {
+        use objc2::rc::Retained;
+        use objc2_app_kit::NSScreen;
+        use tao::platform::macos::MonitorHandleExtMacOS;
+
+        // `ns_screen()` hands out a +1 reference; take ownership so it is released.
+        let screen = monitor
+            .ns_screen()
+            .and_then(|ptr| unsafe { Retained::from_raw(ptr as *mut NSScreen) });
+        if let Some(screen) = screen {
+            // AppKit frames are bottom-left origin in points; only the edge
+            // insets are needed, which are origin-independent.
+            let frame = screen.frame();
+            let visible = screen.visibleFrame();
+            let scale = monitor.scale_factor();
+            let left = (visible.origin.x - frame.origin.x) * scale;
+            let bottom = (visible.origin.y - frame.origin.y) * scale;
+            let right = ((frame.origin.x + frame.size.width)
+                - (visible.origin.x + visible.size.width))
+                * scale;
+            let top = ((frame.origin.y + frame.size.height)
+                - (visible.origin.y + visible.size.height))
+                * scale;
+            return full.inset(left, top, right, bottom);
+        }
+        full
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        use tao::platform::windows::MonitorHandleExtWindows;
+        use windows::Win32::Graphics::Gdi::{GetMonitorInfoW, HMONITOR, MONITORINFO};
+
+        let mut info = MONITORINFO {
+            cbSize: std::mem::size_of::<MONITORINFO>() as u32,
+            ..Default::default()
+        };
+        let ok = unsafe { GetMonitorInfoW(HMONITOR(monitor.hmonitor() as _), &mut info) };
+        if ok.as_bool() {
+            let r = info.rcWork;
+            return Rect {
+                x: r.left,
+                y: r.top,
+                width: r.right - r.left,
+                height: r.bottom - r.top,
+            };
+        }
+        full
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        use gtk::prelude::*;
+        use tao::platform::unix::MonitorHandleExtUnix;
+
+        // GDK reports geometry in logical pixels; derive the insets and scale them.
+        let gdk_monitor = monitor.gdk_monitor();
+        let geometry = gdk_monitor.geometry();
+        let workarea = gdk_monitor.workarea();
+        let scale = monitor.scale_factor();
+        let left = (workarea.x() - geometry.x()) as f64 * scale;
+        let top = (workarea.y() - geometry.y()) as f64 * scale;
+        let right =
+            ((geometry.x() + geometry.width()) - (workarea.x() + workarea.width())) as f64 * scale;
+        let bottom = ((geometry.y() + geometry.height()) - (workarea.y() + workarea.height()))
+            as f64
+            * scale;
+        full.inset(left, top, right, bottom)
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+    {
+        full
+    }
+}
+
+/// Move and resize a window so its outer frame covers `rect`.
+/// Restores the window first if it is maximized.
+pub fn set_outer_bounds(window: &Window, rect: Rect) {
+    if window.is_maximized() {
+        window.set_maximized(false);
+    }
+    // tao only sizes the client area — subtract the current frame size.
+    let outer = window.outer_size();
+    let inner = window.inner_size();
+    let frame_width = outer.width.saturating_sub(inner.width);
+    let frame_height = outer.height.saturating_sub(inner.height);
+    window.set_inner_size(PhysicalSize::new(
+        (rect.width.max(1) as u32)
+            .saturating_sub(frame_width)
+            .max(1),
+        (rect.height.max(1) as u32)
+            .saturating_sub(frame_height)
+            .max(1),
+    ));
+    window.set_outer_position(PhysicalPosition::new(rect.x, rect.y));
+}
+
+// ── Snapping ───────────────────────────────────────────────────
+
+/// Compute the half/quarter tile of `work` for a snap position.
+/// Returns `None` for an unknown position.
+pub fn snap_rect(work: Rect, position: &str) -> Option<Rect> {
+    let half_w = work.width / 2;
+    let half_h = work.height / 2;
+    let (x, y, width, height) = match position {
+        "left" => (work.x, work.y, half_w, work.height),
+        "right" => (work.x + half_w, work.y, work.width - half_w, work.height),
+        "top" => (work.x, work.y, work.width, half_h),
+        "bottom" => (work.x, work.y + half_h, work.width, work.height - half_h),
+        "top-left" => (work.x, work.y, half_w, half_h),
+        "top-right" => (work.x + half_w, work.y, work.width - half_w, half_h),
+        "bottom-left" => (work.x, work.y + half_h, half_w, work.height - half_h),
+        "bottom-right" => (
+            work.x + half_w,
+            work.y + half_h,
+            work.width - half_w,
+            work.height - half_h,
+        ),
+        "fill" => (work.x, work.y, work.width, work.height),
+        _ => return None,
+    };
+    Some(Rect {
+        x,
+        y,
+        width,
+        height,
+    })
+}
@@ -0,0 +1,233 @@
+/// Keyboard focus of the webview, as distinct from the window.
+///
+/// A focused window does not guarantee the page receives key input — on
+/// Windows in particular, focus stays on the host window right after
+/// creation. Focus changes are reported through `PENDING_WEBVIEW_FOCUS`,
+/// and Tab leaving the page (WebView2 only) through `PENDING_FOCUS_EXITS`.
+use wry::WebView;
+
+use crate::window_manager::{MAX_PENDING_EVENTS, PENDING_FOCUS_EXITS, PENDING_WEBVIEW_FOCUS};
+
+/// Focuses the first (`false`) or last (`true`) focusable element of the
+/// page, for WebKit, which has no tab-order focus API.
+#[cfg(not(target_os = "windows"))]
+const FOCUS_EDGE_SCRIPT: &str = r#"(function(last){
+  var selector = 'a[href],area[href],button:not([disabled]),input:not([disabled]):not([type=hidden]),' +
+    'select:not([disabled]),textarea:not([disabled]),iframe,[contenteditable]:not([contenteditable=false]),' +
+    '[tabindex]:not([tabindex="-1"])';
+  var elements = Array.prototype.filter.call(document.querySelectorAll(selector), function(el) {
+    return el.tabIndex >= 0 && el.getClientRects().length > 0;
+  });
+  var target = last ? elements[elements.length - 1] : elements[0];
+  if (target) target.focus();
+})"#;
+
+fn push_focus_change(window_id: u32, focused: bool) {
+    PENDING_WEBVIEW_FOCUS.with(|p| {
+        let mut buf = p.borrow_mut();
+        if buf.len() < MAX_PENDING_EVENTS {
+            buf.push((window_id, focused));
+        }
+    });
+}
+
+/// Move keyboard focus into the webview.
+pub fn focus(webview: &WebView) -> Result<(), String> {
+    webview.focus().map_err(|e| e.to_string())
+}
+
+/// Move keyboard focus into the webview at the first (`last == false`) or
+/// last element of its tab order, as when tabbing in from native UI.
+pub fn focus_edge(webview: &WebView, last: bool) -> Result<(), String> {
+    #[cfg(target_os = "windows")]
+    {
+        use webview2_com::Microsoft::Web::WebView2::Win32::{
+            COREWEBVIEW2_MOVE_FOCUS_REASON_NEXT, COREWEBVIEW2_MOVE_FOCUS_REASON_PREVIOUS,
+        };
+        use wry::WebViewExtWindows;
+
+        let reason = if last {
+            COREWEBVIEW2_MOVE_FOCUS_REASON_PREVIOUS
+        } else {
+            COREWEBVIEW2_MOVE_FOCUS_REASON_NEXT
+        };
+        unsafe { webview.controller().MoveFocus(reason) }.map_err(|e| e.to_string())
+    }
+    #[cfg(not(target_os = "windows"))]
+    {
+        focus(webview)?;
+        webview
+            .evaluate_script(&format!("{}({})", FOCUS_EDGE_SCRIPT, last))
+            .map_err(|e| e.to_string())
+    }
+}
+
+/// Move keyboard focus from the webview back to its window.
+pub fn blur(webview: &WebView) -> Result<(), String> {
+    webview.focus_parent().map_err(|e| e.to_string())
+}
+
+/// Open the system emoji picker for the focused field of the page: the
+/// character palette (Cmd+Ctrl+Space) on macOS, the emoji panel (Win+.)
+/// on Windows. Windows has no API for the panel, so the shortcut is
+/// synthesized; the window must be in the foreground to receive it.
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+pub fn show_emoji_picker(webview: &WebView) -> Result<(), String> {
+    focus(webview)?;
+
+    #[cfg(target_os = "macos")]
+    {
+        use objc2_app_kit::NSApplication;
+        use objc2_foundation::MainThreadMarker;
+
+        let mtm = MainThreadMarker::new().ok_or("not called on the main thread")?;
+        NSApplication::sharedApplication(mtm).orderFrontCharacterPalette(None);
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        use windows::Win32::UI::Input::KeyboardAndMouse::{
+            SendInput, INPUT, INPUT_0, INPUT_KEYBOARD, KEYBDINPUT, KEYBD_EVENT_FLAGS,
+            KEYEVENTF_KEYUP, VIRTUAL_KEY, VK_LWIN, VK_OEM_PERIOD,
+        };
+
+        let key = |vk: VIRTUAL_KEY, flags: KEYBD_EVENT_FLAGS| INPUT {
+            r#type: INPUT_KEYBOARD,
+            Anonymous: INPUT_0 {
+                ki: KEYBDINPUT {
+                    wVk: vk,
+                    dwFlags: flags,
+                    ..Default::default()
+                },
+            },
+        };
+        let inputs = [
+            key(VK_LWIN, KEYBD_EVENT_FLAGS(0)),
+            key(VK_OEM_PERIOD, KEYBD_EVENT_FLAGS(0)),
+            key(VK_OEM_PERIOD, KEYEVENTF_KEYUP),
+            key(VK_LWIN, KEYEVENTF_KEYUP),
+        ];
+        let sent = unsafe { SendInput(&inputs, std::mem::size_of::<INPUT>() as i32) };
+        if sent as usize != inputs.len() {
+            return Err("the shortcut was blocked by another application".to_string());
+        }
+    }
+    Ok(())
+}
+
+/// Start reporting focus changes of a newly created webview. macOS has
+/// no notification for this and is polled with [`has_focus`] instead.
+pub fn watch(webview: &WebView, window_id: u32) -> Result<(), String> {
+    #[cfg(target_os = "windows")]
+    {
+        watch_windows(webview, window_id).map_err(|e| e.to_string())
+    }
+    #[cfg(target_os = "linux")]
+    {
+        watch_linux(webview, window_id);
+        Ok(())
+    }
+    #[cfg(not(any(target_os = "windows", target_os = "linux")))]
+    {
+        let _ = (webview, window_id);
+        Ok(())
+    }
+}
+
+// ── Windows ────────────────────────────────────────────────────
+
+/// The controller's `GotFocus`/`LostFocus` events, and `MoveFocusRequested`
+/// for Tab leaving the page. The handlers live as long as the controller.
+#[cfg(target_os = "windows")]
+fn watch_windows(webview: &WebView, window_id: u32) -> windows::core::Result<()> {
+    use webview2_com::Microsoft::Web::WebView2::Win32::{
+        COREWEBVIEW2_MOVE_FOCUS_REASON, COREWEBVIEW2_MOVE_FOCUS_REASON_PREVIOUS,
+    };
+    use webview2_com::{FocusChangedEventHandler, MoveFocusRequestedEventHandler};
+    use wry::WebViewExtWindows;
+
+    let controller = webview.controller();
+    let mut token = 0i64;
+    unsafe {
+        controller.add_GotFocus(
+            &FocusChangedEventHandler::create(Box::new(move |_, _| {
+                push_focus_change(window_id, true);
+                Ok(())
+            })),
+            &mut token,
+        )?;
+        controller.add_LostFocus(
+            &FocusChangedEventHandler::create(Box::new(move |_, _| {
+                push_focus_change(window_id, false);
+                Ok(())
+            })),
+            &mut token,
+        )?;
+        // Not marked handled: WebView2 still hands focus to the host
+        // window, where the app moves it on.
+        controller.add_MoveFocusRequested(
+            &MoveFocusRequestedEventHandler::create(Box::new(move |_, args| {
+                if let Some(args) = args {
+                    let mut reason = COREWEBVIEW2_MOVE_FOCUS_REASON::default();
+                    args.Reason(&mut reason)?;
+                    let direction = if reason == COREWEBVIEW2_MOVE_FOCUS_REASON_PREVIOUS {
+                        "previous"
+                    } else {
+                        "next"
+                    };
+                    PENDING_FOCUS_EXITS.with(|p| {
+                        let mut buf = p.borrow_mut();
+                        if buf.len() < MAX_PENDING_EVENTS {
+                            buf.push((window_id, direction));
+                        }
+                    });
+                }
+                Ok(())
+            })),
+            &mut token,
+        )
+    }
+}
+
+// ── macOS ──────────────────────────────────────────────────────
+
+/// Whether the WKWebView (or one of its subviews) is the first responder
+/// of its window.
+#[cfg(target_os = "macos")]
+pub fn has_focus(webview: &WebView) -> bool {
+    use objc2_app_kit::NSView;
+    use wry::WebViewExtMacOS;
+
+    let Some(responder) = webview.ns_window().firstResponder() else {
+        return false;
+    };
+    match responder.downcast::<NSView>() {
+        Ok(view) => view.isDescendantOf(&webview.webview()),
+        Err(_) => false,
+    }
+}
+
+/// Report a focus change found by polling [`has_focus`].
+#[cfg(target_os = "macos")]
+pub fn report(window_id: u32, focused: bool) {
+    push_focus_change(window_id, focused);
+}
+
+// ── Linux ──────────────────────────────────────────────────────
+
+#[cfg(target_os = "linux")]
+fn watch_linux(webview: &WebView, window_id: u32) {
+    use gtk::glib::Propagation;
+    use gtk::prelude::*;
+    use wry::WebViewExtUnix;
+
+    let widget = webview.webview();
+    widget.connect_focus_in_event(move |_, _| {
+        push_focus_change(window_id, true);
+        Propagation::Proceed
+    });
+    widget.connect_focus_out_event(move |_, _| {
+        push_focus_change(window_id, false);
+        Propagation::Proceed
+    });
+}
@@ -0,0 +1,134 @@
+/// Engine-level font settings, for `setFontOverrides()` and `setTextZoom()`.
+///
+/// Accessibility policies sometimes require a legible default font or a
+/// minimum text size on pages whose CSS the app does not control. WebKitGTK
+/// exposes these as settings; macOS has a public minimum font size and an
+/// SPI for the standard family; WebView2 goes through CDP, which has no
+/// minimum font size, so the default sizes are raised instead. Only Linux
+/// zooms text alone; elsewhere `setTextZoom()` zooms the whole page.
+use wry::WebView;
+
+/// Chromium's default font sizes, in CSS pixels.
+#[cfg(target_os = "windows")]
+const DEFAULT_FONT_SIZE: u32 = 16;
+#[cfg(target_os = "windows")]
+const DEFAULT_FIXED_FONT_SIZE: u32 = 13;
+
+/// Set the font used when a page does not name one, and the smallest font
+/// size text is drawn at. `None` restores the engine default.
+pub fn set_font_overrides(
+    webview: &WebView,
+    standard_family: Option<&str>,
+    minimum_font_size: Option<u32>,
+) -> Result<(), String> {
+    #[cfg(target_os = "windows")]
+    {
+        use crate::window_manager::json_escape;
+
+        let family = standard_family.unwrap_or("Times New Roman");
+        super::cdp::send(
+            webview,
+            "Page.setFontFamilies",
+            &format!(
+                "{{\"fontFamilies\":{{\"standard\":{}}}}}",
+                json_escape(family)
+            ),
+        )
+        .map_err(|e| e.to_string())?;
+        let minimum = minimum_font_size.unwrap_or(0);
+        super::cdp::send(
+            webview,
+            "Page.setFontSizes",
+            &format!(
+                "{{\"fontSizes\":{{\"standard\":{},\"fixed\":{}}}}}",
+                DEFAULT_FONT_SIZE.max(minimum),
+                DEFAULT_FIXED_FONT_SIZE.max(minimum)
+            ),
+        )
+        .map_err(|e| e.to_string())
+    }
+    #[cfg(target_os = "macos")]
+    {
+        set_font_overrides_macos(webview, standard_family, minimum_font_size);
+        Ok(())
+    }
+    #[cfg(target_os = "linux")]
+    {
+        set_font_overrides_linux(webview, standard_family, minimum_font_size);
+        Ok(())
+    }
+    #[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
+    {
+        let _ = (webview, standard_family, minimum_font_size);
+        Ok(())
+    }
+}
+
+/// Scale text to `percent` of its size.
+pub fn set_text_zoom(webview: &WebView, percent: f64) -> Result<(), String> {
+    #[cfg(target_os = "linux")]
+    {
+        use gtk::prelude::*;
+        use wry::WebViewExtUnix;
+
+        let view = webview.webview();
+        let settings = view.property::<gtk::glib::Object>("settings");
+        settings.set_property("zoom-text-only", true);
+    }
+    webview.zoom(percent / 100.0).map_err(|e| e.to_string())
+}
+
+// ── macOS ──────────────────────────────────────────────────────
+
+#[cfg(target_os = "macos")]
+fn set_font_overrides_macos(
+    webview: &WebView,
+    standard_family: Option<&str>,
+    minimum_font_size: Option<u32>,
+) {
+    use objc2::msg_send;
+    use objc2::rc::Retained;
+    use objc2::runtime::{AnyObject, Bool};
+    use objc2::sel;
+    use objc2_foundation::NSString;
+    use wry::WebViewExtMacOS;
+
+    let webview = webview.webview();
+    unsafe {
+        let configuration: Retained<AnyObject> = msg_send![&*webview, configuration];
+        let preferences: Retained<AnyObject> = msg_send![&*configuration, preferences];
+        let minimum = minimum_font_size.unwrap_or(0) as f64;
+        let _: () = msg_send![&*preferences, setMinimumFontSize: minimum];
+
+        // WebKit's own default when unset.
+        let family = NSString::from_str(standard_family.unwrap_or("Times"));
+        let responds: Bool =
+            msg_send![&*preferences, respondsToSelector: sel!(_setStandardFontFamily:)];
+        if responds.as_bool() {
+            let _: () = msg_send![&*preferences, _setStandardFontFamily: &*family];
+        } else if standard_family.is_some() {
+            eprintln!("[native-window] Standard font family is not supported by this WebKit");
+        }
+    }
+}
+
+// ── Linux ──────────────────────────────────────────────────────
+
+#[cfg(target_os = "linux")]
+fn set_font_overrides_linux(
+    webview: &WebView,
+    standard_family: Option<&str>,
+    minimum_font_size: Option<u32>,
+) {
+    use gtk::prelude::*;
+    use wry::WebViewExtUnix;
+
+    let view = webview.webview();
+    let settings = view.property::<gtk::glib::Object>("settings");
+    // WebKitGTK's own default when unset.
+    settings.set_property(
+        "default-font-family",
+        standard_family.unwrap_or("sans-serif"),
+    );
+    settings.set_property("minimum-font-size", minimum_font_size.unwrap_or(0));
+}
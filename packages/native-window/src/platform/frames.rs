@@ -0,0 +1,161 @@
+/// Iframe lifecycle, for `onFrameCreated()` and `onFrameDestroyed()`.
+///
+/// Windows reports frames natively: WebView2's `FrameCreated`, the frame's
+/// first `NavigationStarting` for its URL, and its `Destroyed` event. WebKit
+/// has no frame lifecycle API, so on macOS and Linux a script injected into
+/// subframes reports each document it runs in over IPC, and its `pagehide`
+/// as the end. Frame IDs are assigned here and unique within the process.
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+
+use wry::WebView;
+
+use crate::window_manager::{MAX_PENDING_EVENTS, PENDING_FRAME_EVENTS};
+
+/// IPC prefix of the subframe script's reports: `<prefix><kind>:<token>:<url>`.
+const FRAME_MESSAGE_PREFIX: &str = "__native_frame__:";
+
+/// Subframe script for WebKit. Each document gets a random token, which
+/// [`handle_message`] maps to a frame ID.
+#[cfg(not(target_os = "windows"))]
+pub const FRAME_SCRIPT: &str = r#"(function () {
+  if (window === window.top) return;
+  try {
+    var token = Math.random().toString(36).slice(2) + Date.now().toString(36);
+    var post = function (kind) {
+      window.webkit.messageHandlers.ipc.postMessage("__native_frame__:" + kind + ":" + token + ":" + location.href);
+    };
+    post("created");
+    window.addEventListener("pagehide", function () { post("destroyed"); });
+  } catch (e) {}
+})();"#;
+
+thread_local! {
+    static NEXT_FRAME_ID: Cell<u32> = const { Cell::new(1) };
+    /// (window_id, document token) → frame ID, for WebKit reports.
+    static TOKENS: RefCell<HashMap<(u32, String), u32>> = RefCell::new(HashMap::new());
+}
+
+fn next_frame_id() -> u32 {
+    NEXT_FRAME_ID.with(|n| {
+        let id = n.get();
+        n.set(id.wrapping_add(1).max(1));
+        id
+    })
+}
+
+/// Queue a created (`Some(url)`) or destroyed (`None`) event.
+fn push_event(window_id: u32, frame_id: u32, url: Option<String>) {
+    PENDING_FRAME_EVENTS.with(|p| {
+        let mut buf = p.borrow_mut();
+        if buf.len() < MAX_PENDING_EVENTS {
+            buf.push((window_id, frame_id, url));
+        }
+    });
+}
+
+/// Handle a subframe script report. Returns `false` for other messages.
+pub fn handle_message(window_id: u32, message: &str) -> bool {
+    let Some(report) = message.strip_prefix(FRAME_MESSAGE_PREFIX) else {
+        return false;
+    };
+    let mut parts = report.splitn(3, ':');
+    let (Some(kind), Some(token), Some(url)) = (parts.next(), parts.next(), parts.next()) else {
+        return true;
+    };
+    let key = (window_id, token.to_string());
+    match kind {
+        "created" => {
+            let known = TOKENS.with(|t| t.borrow().contains_key(&key));
+            if !known {
+                let frame_id = next_frame_id();
+                TOKENS.with(|t| t.borrow_mut().insert(key, frame_id));
+                push_event(window_id, frame_id, Some(url.to_string()));
+            }
+        }
+        "destroyed" => {
+            if let Some(frame_id) = TOKENS.with(|t| t.borrow_mut().remove(&key)) {
+                push_event(window_id, frame_id, None);
+            }
+        }
+        _ => {}
+    }
+    true
+}
+
+/// Start reporting the frames of a new webview.
+pub fn watch(webview: &WebView, window_id: u32) -> Result<(), String> {
+    #[cfg(target_os = "windows")]
+    {
+        watch_windows(webview, window_id).map_err(|e| e.to_string())
+    }
+    #[cfg(not(target_os = "windows"))]
+    {
+        // The subframe script does the work.
+        let _ = (webview, window_id);
+        Ok(())
+    }
+}
+
+/// Forget a destroyed window's frames.
+pub fn forget(window_id: u32) {
+    TOKENS.with(|t| t.borrow_mut().retain(|(id, _), _| *id != window_id));
+}
+
+// ── Windows ────────────────────────────────────────────────────
+
+#[cfg(target_os = "windows")]
+fn watch_windows(webview: &WebView, window_id: u32) -> windows::core::Result<()> {
+    use std::rc::Rc;
+    use webview2_com::Microsoft::Web::WebView2::Win32::{ICoreWebView2Frame2, ICoreWebView2_4};
+    use webview2_com::{
+        take_pwstr, FrameCreatedEventHandler, FrameDestroyedEventHandler,
+        FrameNavigationStartingEventHandler,
+    };
+    use windows::core::{Interface, PWSTR};
+    use wry::WebViewExtWindows;
+
+    let webview = webview.webview().cast::<ICoreWebView2_4>()?;
+    let mut token = 0i64;
+    unsafe {
+        webview.add_FrameCreated(
+            &FrameCreatedEventHandler::create(Box::new(move |_, args| {
+                let Some(args) = args else {
+                    return Ok(());
+                };
+                let frame = args.Frame()?;
+                let frame_id = next_frame_id();
+                // Reported once the first navigation gives the frame a URL.
+                let announced = Rc::new(Cell::new(false));
+                let mut token = 0i64;
+                if let Ok(frame) = frame.cast::<ICoreWebView2Frame2>() {
+                    let announced = announced.clone();
+                    frame.add_NavigationStarting(
+                        &FrameNavigationStartingEventHandler::create(Box::new(move |_, args| {
+                            let Some(args) = args else {
+                                return Ok(());
+                            };
+                            if !announced.replace(true) {
+                                let mut uri = PWSTR::null();
+                                args.Uri(&mut uri)?;
+                                push_event(window_id, frame_id, Some(take_pwstr(uri)));
+                            }
+                            Ok(())
+                        })),
+                        &mut token,
+                    )?;
+                }
+                frame.add_Destroyed(
+                    &FrameDestroyedEventHandler::create(Box::new(move |_, _| {
+                        if announced.get() {
+                            push_event(window_id, frame_id, None);
+                        }
+                        Ok(())
+                    })),
+                    &mut token,
+                )
+            })),
+            &mut token,
+        )
+    }
+}
@@ -0,0 +1,325 @@
+/// Iframe restrictions, for the `iframePolicy` window option.
+///
+/// wry's navigation handler only sees main-frame navigations on Windows, and
+/// on macOS it sees subframe ones without knowing they are. Subframe
+/// navigations are checked here instead: WebView2's
+/// `FrameNavigationStarting` on Windows, and the target frame of WebKit's
+/// navigation action on macOS. They must match `iframePolicy.allowedHosts`,
+/// or the window's `allowedHosts` when unset. WebKitGTK does not say which
+/// frame navigates, so on Linux iframe hosts are enforced only by the page
+/// script, which keeps `<iframe>` elements off other hosts; the navigation
+/// handler still checks every navigation against `allowedHosts` alone.
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use wry::WebView;
+
+use crate::options::IframePolicy;
+use crate::window_manager::json_escape;
+
+thread_local! {
+    /// `iframePolicy.allowedHosts` of each window that sets it.
+    static FRAME_HOSTS: RefCell<HashMap<u32, Vec<String>>> = RefCell::new(HashMap::new());
+}
+
+/// Start filtering the subframe navigations of a new webview.
+pub fn watch(
+    webview: &WebView,
+    window_id: u32,
+    policy: Option<&IframePolicy>,
+) -> Result<(), String> {
+    if let Some(hosts) = policy.and_then(|p| p.allowed_hosts.clone()) {
+        FRAME_HOSTS.with(|f| f.borrow_mut().insert(window_id, hosts));
+    }
+    #[cfg(target_os = "windows")]
+    {
+        watch_windows(webview, window_id).map_err(|e| e.to_string())
+    }
+    #[cfg(target_os = "macos")]
+    {
+        watch_macos(webview, window_id);
+        Ok(())
+    }
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    {
+        let _ = webview;
+        Ok(())
+    }
+}
+
+/// Stop tracking a destroyed window.
+pub fn forget(window_id: u32) {
+    FRAME_HOSTS.with(|f| f.borrow_mut().remove(&window_id));
+    #[cfg(target_os = "macos")]
+    WEBVIEWS.with(|w| w.borrow_mut().retain(|_, id| *id != window_id));
+}
+
+/// Whether `url` matches the window's `iframePolicy.allowedHosts`.
+#[cfg(target_os = "linux")]
+pub fn is_frame_host(window_id: u32, url: &str) -> bool {
+    FRAME_HOSTS.with(|f| {
+        f.borrow()
+            .get(&window_id)
+            .is_some_and(|hosts| host_matches(hosts, url))
+    })
+}
+
+/// Decide a subframe navigation. Host rejections fire `onNavigationBlocked`
+/// like main-frame ones.
+#[cfg(any(target_os = "windows", target_os = "macos"))]
+fn allow_frame_navigation(window_id: u32, url: &str) -> bool {
+    use crate::window_manager::{
+        host_matches, is_host_allowed, is_hostless, is_scheme_allowed, MAX_PENDING_EVENTS,
+        PENDING_NAVIGATION_BLOCKED,
+    };
+
+    let lower = url.to_lowercase();
+    // about:blank, about:srcdoc and our own content
+    if lower.starts_with("about:")
+        || lower.starts_with("nativewindow:")
+        || url::Url::parse(url).is_ok_and(|u| u.host_str() == Some("nativewindow.localhost"))
+    {
+        return true;
+    }
+    // allowedSchemes are exempt from scheme blocking, and hostless ones
+    // from the host check; URLs with a host are still matched.
+    let scheme_allowed = is_scheme_allowed(window_id, url);
+    if !scheme_allowed
+        && ["javascript:", "file:", "data:", "blob:"]
+            .iter()
+            .any(|scheme| lower.starts_with(scheme))
+    {
+        return false;
+    }
+    let allowed = (scheme_allowed && is_hostless(url))
+        || match FRAME_HOSTS.with(|f| f.borrow().get(&window_id).cloned()) {
+            Some(hosts) => host_matches(&hosts, url),
+            None => is_host_allowed(window_id, url),
+        };
+    if !allowed {
+        PENDING_NAVIGATION_BLOCKED.with(|p| {
+            let mut buf = p.borrow_mut();
+            if buf.len() < MAX_PENDING_EVENTS {
+                buf.push((window_id, url.to_string()));
+            }
+        });
+    }
+    allowed
+}
+
+/// Page script applying the policy to `<iframe>` elements: `src`
+/// attributes on other hosts are removed, and `sandbox` is forced and the
+/// frame reloaded so it takes effect. `None` when the policy sets neither.
+pub fn script(policy: &IframePolicy) -> Option<String> {
+    if policy.allowed_hosts.is_none() && policy.sandbox.is_none() {
+        return None;
+    }
+    let hosts = match &policy.allowed_hosts {
+        Some(hosts) => format!(
+            "[{}]",
+            hosts
+                .iter()
+                .map(|h| json_escape(h))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        None => "null".to_string(),
+    };
+    let sandbox = policy
+        .sandbox
+        .as_deref()
+        .map_or_else(|| "null".to_string(), json_escape);
+    Some(
+        r#"(function () {
+  try {
+    var HOSTS = __HOSTS__;
+    var SANDBOX = __SANDBOX__;
+    function hostAllowed(src) {
+      if (!HOSTS) return true;
+      var url;
+      try { url = new URL(src, location.href); } catch (e) { return false; }
+      if (url.protocol === "about:" || url.protocol === "nativewindow:" || url.hostname === "nativewindow.localhost") return true;
+      var host = url.hostname.toLowerCase();
+      return HOSTS.some(function (p) {
+        p = p.toLowerCase();
+        if (p.charAt(0) !== "*") return host === p;
+        var suffix = p.slice(1);
+        return host.slice(-suffix.length) === suffix || (suffix.charAt(0) === "." && host === suffix.slice(1));
+      });
+    }
+    function apply(frame) {
+      if (SANDBOX !== null && frame.getAttribute("sandbox") !== SANDBOX) {
+        frame.setAttribute("sandbox", SANDBOX);
+        // Sandbox flags only apply from the frame's next navigation.
+        if (frame.hasAttribute("src")) frame.setAttribute("src", frame.getAttribute("src"));
+      }
+      if (frame.hasAttribute("src") && !hostAllowed(frame.getAttribute("src"))) {
+        frame.removeAttribute("src");
+      }
+    }
+    new MutationObserver(function (mutations) {
+      mutations.forEach(function (m) {
+        if (m.type === "attributes") {
+          if (m.target.tagName === "IFRAME") apply(m.target);
+          return;
+        }
+        m.addedNodes.forEach(function (n) {
+          if (n.nodeType !== 1) return;
+          if (n.tagName === "IFRAME") apply(n);
+          if (n.querySelectorAll) n.querySelectorAll("iframe").forEach(apply);
+        });
+      });
+    }).observe(document.documentElement || document, {
+      childList: true,
+      subtree: true,
+      attributes: true,
+      attributeFilter: ["src", "sandbox"]
+    });
+  } catch (e) {}
+})();"#
+            .replace("__HOSTS__", &hosts)
+            .replace("__SANDBOX__", &sandbox),
+    )
+}
+
+// ── Windows ────────────────────────────────────────────────────
+
+#[cfg(target_os = "windows")]
+fn watch_windows(webview: &WebView, window_id: u32) -> windows::core::Result<()> {
+    use webview2_com::{take_pwstr, NavigationStartingEventHandler};
+    use windows::core::PWSTR;
+    use wry::WebViewExtWindows;
+
+    let mut token = 0i64;
+    unsafe {
+        webview.webview().add_FrameNavigationStarting(
+            &NavigationStartingEventHandler::create(Box::new(move |_, args| {
+                let Some(args) = args else {
+                    return Ok(());
+                };
+                let mut uri = PWSTR::null();
+                args.Uri(&mut uri)?;
+                if !allow_frame_navigation(window_id, &take_pwstr(uri)) {
+                    args.SetCancel(true)?;
+                }
+                Ok(())
+            })),
+            &mut token,
+        )
+    }
+}
+
+// ── macOS ──────────────────────────────────────────────────────
+
+#[cfg(target_os = "macos")]
+thread_local! {
+    /// WKWebView pointer → window ID, to find the window in the delegate.
+    static WEBVIEWS: RefCell<HashMap<usize, u32>> = RefCell::new(HashMap::new());
+}
+
+/// wry's own implementation, called for main-frame navigations.
+#[cfg(target_os = "macos")]
+static ORIGINAL_DECIDE_POLICY: std::sync::OnceLock<objc2::runtime::Imp> =
+    std::sync::OnceLock::new();
+
+/// wry's navigation delegate asks the navigation handler about every frame,
+/// so `webView:decidePolicyForNavigationAction:decisionHandler:` is wrapped
+/// once to decide subframe navigations here.
+#[cfg(target_os = "macos")]
+fn watch_macos(webview: &WebView, window_id: u32) {
+    use objc2::rc::Retained;
+    use objc2::runtime::{AnyClass, Imp};
+    use objc2::sel;
+    use std::sync::Once;
+    use wry::WebViewExtMacOS;
+
+    static WRAP_METHOD: Once = Once::new();
+    WRAP_METHOD.call_once(|| {
+        let method = AnyClass::get(c"WryNavigationDelegate").and_then(|class| {
+            class.instance_method(sel!(webView:decidePolicyForNavigationAction:decisionHandler:))
+        });
+        let Some(method) = method else {
+            eprintln!(
+                "[native-window] iframe navigations cannot be filtered: delegate method not found"
+            );
+            return;
+        };
+        let _ = ORIGINAL_DECIDE_POLICY.set(method.implementation());
+        unsafe {
+            let imp: Imp = std::mem::transmute(
+                decide_policy_for_navigation_action as unsafe extern "C-unwind" fn(_, _, _, _, _),
+            );
+            let _ = method.set_implementation(imp);
+        }
+    });
+
+    let key = Retained::as_ptr(&webview.webview()) as usize;
+    WEBVIEWS.with(|w| w.borrow_mut().insert(key, window_id));
+}
+
+#[cfg(target_os = "macos")]
+unsafe extern "C-unwind" fn decide_policy_for_navigation_action(
+    this: *mut objc2::runtime::AnyObject,
+    cmd: objc2::runtime::Sel,
+    webview: *mut objc2::runtime::AnyObject,
+    action: *mut objc2::runtime::AnyObject,
+    handler: *mut block2::Block<dyn Fn(isize)>,
+) {
+    use objc2::msg_send;
+    use objc2::rc::Retained;
+    use objc2::runtime::{AnyObject, Bool};
+    use objc2::sel;
+    use objc2_foundation::NSURL;
+
+    let window_id = WEBVIEWS.with(|w| w.borrow().get(&(webview as usize)).copied());
+    if let (Some(window_id), Some(action_ref), Some(handler_ref)) =
+        (window_id, unsafe { action.as_ref() }, unsafe {
+            handler.as_ref()
+        })
+    {
+        let frame: Option<Retained<AnyObject>> = unsafe { msg_send![action_ref, targetFrame] };
+        // A nil target frame is a new window, which wry's handler covers.
+        let is_main_frame = frame.as_ref().map_or(true, |frame| {
+            let main: Bool = unsafe { msg_send![&**frame, isMainFrame] };
+            main.as_bool()
+        });
+        // Subframe downloads go through wry's download handling.
+        let responds: Bool =
+            unsafe { msg_send![action_ref, respondsToSelector: sel!(shouldPerformDownload)] };
+        let is_download = responds.as_bool() && {
+            let download: Bool = unsafe { msg_send![action_ref, shouldPerformDownload] };
+            download.as_bool()
+        };
+        if !is_main_frame && !is_download {
+            let request: Option<Retained<AnyObject>> = unsafe { msg_send![action_ref, request] };
+            let url = request
+                .and_then(|request| {
+                    let url: Option<Retained<NSURL>> = unsafe { msg_send![&*request, URL] };
+                    url
+                })
+                .and_then(|url| url.absoluteString())
+                .map(|url| url.to_string())
+                .unwrap_or_default();
+            // WKNavigationActionPolicyCancel = 0, WKNavigationActionPolicyAllow = 1
+            let policy = if allow_frame_navigation(window_id, &url) {
+                1
+            } else {
+                0
+            };
+            handler_ref.call((policy,));
+            return;
+        }
+    }
+
+    let Some(original) = ORIGINAL_DECIDE_POLICY.get() else {
+        return;
+    };
+    let original: unsafe extern "C-unwind" fn(
+        *mut AnyObject,
+        objc2::runtime::Sel,
+        *mut AnyObject,
+        *mut AnyObject,
+        *mut block2::Block<dyn Fn(isize)>,
+    ) = unsafe { std::mem::transmute(*original) };
+    unsafe { original(this, cmd, webview, action, handler) };
+}
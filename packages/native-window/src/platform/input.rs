@@ -0,0 +1,365 @@
+/// Input event synthesis for `sendInputEvent()`.
+///
+/// - **Windows**: CDP `Input.dispatchMouseEvent` / `Input.dispatchKeyEvent`.
+///   Events are trusted and run through the full browser input pipeline.
+/// - **macOS**: mouse and key events are posted to the NSWindow as real
+///   `NSEvent`s. Wheel events fall back to DOM synthesis.
+/// - **Linux**: DOM synthesis only. Events have `isTrusted === false`, so
+///   gesture-gated web APIs will not treat them as user activation.
+use tao::window::Window;
+use wry::WebView;
+
+use crate::options::InputEvent;
+use crate::window_manager::json_escape;
+
+/// Dispatch a synthetic input event into a window's webview.
+///
+/// The event is assumed to be validated by `NativeWindow::send_input_event`.
+pub fn dispatch(window: &Window, webview: &WebView, event: &InputEvent) -> napi::Result<()> {
+    #[cfg(target_os = "windows")]
+    {
+        let _ = window;
+        dispatch_cdp(webview, event)
+    }
+    #[cfg(target_os = "macos")]
+    {
+        if event.kind == "wheel" {
+            return dispatch_dom(webview, event);
+        }
+        dispatch_ns_event(window, webview, event)
+    }
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    {
+        let _ = window;
+        dispatch_dom(webview, event)
+    }
+}
+
+/// Resolve the event action, falling back to the per-type default
+/// ("click" for mouse, "press" for key).
+fn action(event: &InputEvent) -> &str {
+    match event.action.as_deref() {
+        Some(a) => a,
+        None if event.kind == "key" => "press",
+        None => "click",
+    }
+}
+
+/// Whether a modifier key ("shift", "control", "alt", "meta") is held.
+fn has_modifier(event: &InputEvent, name: &str) -> bool {
+    event
+        .modifiers
+        .as_ref()
+        .map_or(false, |m| m.iter().any(|v| v.eq_ignore_ascii_case(name)))
+}
+
+/// Returns the key itself if it produces text (a single character).
+#[cfg(any(target_os = "windows", target_os = "macos"))]
+fn printable_text(key: &str) -> Option<&str> {
+    let mut chars = key.chars();
+    match (chars.next(), chars.next()) {
+        (Some(_), None) => Some(key),
+        _ => None,
+    }
+}
+
+// ── Windows: CDP ───────────────────────────────────────────────
+
+#[cfg(target_os = "windows")]
+fn dispatch_cdp(webview: &WebView, event: &InputEvent) -> napi::Result<()> {
+    use super::cdp;
+
+    let x = event.x.unwrap_or(0.0);
+    let y = event.y.unwrap_or(0.0);
+    // CDP modifier bitmask: Alt=1, Ctrl=2, Meta=4, Shift=8
+    let modifiers = (has_modifier(event, "alt") as u8)
+        | (has_modifier(event, "control") as u8) << 1
+        | (has_modifier(event, "meta") as u8) << 2
+        | (has_modifier(event, "shift") as u8) << 3;
+
+    match event.kind.as_str() {
+        "mouse" => {
+            let button = event.button.as_deref().unwrap_or("left");
+            let click_count = event.click_count.unwrap_or(1);
+            let types: &[&str] = match action(event) {
+                "down" => &["mousePressed"],
+                "up" => &["mouseReleased"],
+                "move" => &["mouseMoved"],
+                _ => &["mousePressed", "mouseReleased"],
+            };
+            for t in types {
+                let btn = if *t == "mouseMoved" { "none" } else { button };
+                let params = format!(
+                    "{{\"type\":\"{}\",\"x\":{},\"y\":{},\"button\":{},\"clickCount\":{},\"modifiers\":{}}}",
+                    t,
+                    x,
+                    y,
+                    json_escape(btn),
+                    click_count,
+                    modifiers
+                );
+                cdp::send(webview, "Input.dispatchMouseEvent", &params)?;
+            }
+        }
+        "wheel" => {
+            let params = format!(
+                "{{\"type\":\"mouseWheel\",\"x\":{},\"y\":{},\"deltaX\":{},\"deltaY\":{},\"modifiers\":{}}}",
+                x,
+                y,
+                event.delta_x.unwrap_or(0.0),
+                event.delta_y.unwrap_or(0.0),
+                modifiers
+            );
+            cdp::send(webview, "Input.dispatchMouseEvent", &params)?;
+        }
+        "key" => {
+            let key = event.key.as_deref().unwrap_or_default();
+            // Shortcuts (Ctrl/Meta held) must not insert text.
+            let text = printable_text(key).filter(|_| modifiers & 0b110 == 0);
+            let types: &[&str] = match action(event) {
+                "down" => &["keyDown"],
+                "up" => &["keyUp"],
+                _ => &["keyDown", "keyUp"],
+            };
+            for t in types {
+                let text_field = match text {
+                    Some(s) if *t == "keyDown" => format!(",\"text\":{}", json_escape(s)),
+                    _ => String::new(),
+                };
+                let params = format!(
+                    "{{\"type\":\"{}\",\"key\":{},\"windowsVirtualKeyCode\":{},\"modifiers\":{}{}}}",
+                    t,
+                    json_escape(key),
+                    windows_virtual_key_code(key),
+                    modifiers,
+                    text_field
+                );
+                cdp::send(webview, "Input.dispatchKeyEvent", &params)?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Map a DOM key value to a Windows virtual-key code (0 if unknown).
+#[cfg(target_os = "windows")]
+fn windows_virtual_key_code(key: &str) -> u32 {
+    match key {
+        "Backspace" => 0x08,
+        "Tab" => 0x09,
+        "Enter" => 0x0D,
+        "Escape" => 0x1B,
+        " " => 0x20,
+        "PageUp" => 0x21,
+        "PageDown" => 0x22,
+        "End" => 0x23,
+        "Home" => 0x24,
+        "ArrowLeft" => 0x25,
+        "ArrowUp" => 0x26,
+        "ArrowRight" => 0x27,
+        "ArrowDown" => 0x28,
+        "Delete" => 0x2E,
+        k => match printable_text(k).and_then(|s| s.chars().next()) {
+            Some(c) if c.is_ascii_alphanumeric() => c.to_ascii_uppercase() as u32,
+            _ => 0,
+        },
+    }
+}
+
+// ── macOS: NSEvent ─────────────────────────────────────────────
+
+#[cfg(target_os = "macos")]
+fn dispatch_ns_event(window: &Window, webview: &WebView, event: &InputEvent) -> napi::Result<()> {
+    use objc2_app_kit::{NSEvent, NSEventModifierFlags, NSEventType};
+    use objc2_foundation::{NSPoint, NSProcessInfo, NSString};
+    use wry::WebViewExtMacOS;
+
+    let ns_window = webview.ns_window();
+    let scale = window.scale_factor();
+    let content_height = window.inner_size().to_logical::<f64>(scale).height;
+    // NSWindow base coordinates have a bottom-left origin.
+    let location = NSPoint::new(
+        event.x.unwrap_or(0.0),
+        content_height - event.y.unwrap_or(0.0),
+    );
+    let mut flags = NSEventModifierFlags::empty();
+    if has_modifier(event, "shift") {
+        flags |= NSEventModifierFlags::Shift;
+    }
+    if has_modifier(event, "control") {
+        flags |= NSEventModifierFlags::Control;
+    }
+    if has_modifier(event, "alt") {
+        flags |= NSEventModifierFlags::Option;
+    }
+    if has_modifier(event, "meta") {
+        flags |= NSEventModifierFlags::Command;
+    }
+    let timestamp = NSProcessInfo::processInfo().systemUptime();
+    let window_number = ns_window.windowNumber();
+
+    let events: Vec<_> = match event.kind.as_str() {
+        "mouse" => {
+            let (down, up) = match event.button.as_deref() {
+                Some("right") => (NSEventType::RightMouseDown, NSEventType::RightMouseUp),
+                Some("middle") => (NSEventType::OtherMouseDown, NSEventType::OtherMouseUp),
+                _ => (NSEventType::LeftMouseDown, NSEventType::LeftMouseUp),
+            };
+            let types = match action(event) {
+                "down" => vec![down],
+                "up" => vec![up],
+                "move" => vec![NSEventType::MouseMoved],
+                _ => vec![down, up],
+            };
+            let click_count = event.click_count.unwrap_or(1) as isize;
+            types
+                .into_iter()
+                .map(|t| {
+                    NSEvent::mouseEventWithType_location_modifierFlags_timestamp_windowNumber_context_eventNumber_clickCount_pressure(
+                        t,
+                        location,
+                        flags,
+                        timestamp,
+                        window_number,
+                        None,
+                        0,
+                        click_count,
+                        if t == NSEventType::MouseMoved { 0.0 } else { 1.0 },
+                    )
+                })
+                .collect()
+        }
+        "key" => {
+            let key = event.key.as_deref().unwrap_or_default();
+            let characters = NSString::from_str(printable_text(key).unwrap_or(""));
+            let key_code = mac_virtual_key_code(key);
+            let types = match action(event) {
+                "down" => vec![NSEventType::KeyDown],
+                "up" => vec![NSEventType::KeyUp],
+                _ => vec![NSEventType::KeyDown, NSEventType::KeyUp],
+            };
+            types
+                .into_iter()
+                .map(|t| {
+                    NSEvent::keyEventWithType_location_modifierFlags_timestamp_windowNumber_context_characters_charactersIgnoringModifiers_isARepeat_keyCode(
+                        t,
+                        location,
+                        flags,
+                        timestamp,
+                        window_number,
+                        None,
+                        &characters,
+                        &characters,
+                        false,
+                        key_code,
+                    )
+                })
+                .collect()
+        }
+        _ => Vec::new(),
+    };
+
+    for ns_event in events {
+        let ns_event = ns_event
+            .ok_or_else(|| napi::Error::from_reason("Failed to create NSEvent for input"))?;
+        ns_window.sendEvent(&ns_event);
+    }
+    Ok(())
+}
+
+/// Map a DOM key value to a macOS virtual key code (0 if unknown).
+/// WebKit derives text from `characters`, so only non-text keys need codes.
+#[cfg(target_os = "macos")]
+fn mac_virtual_key_code(key: &str) -> u16 {
+    match key {
+        "Enter" => 36,
+        "Tab" => 48,
+        " " => 49,
+        "Backspace" => 51,
+        "Escape" => 53,
+        "Home" => 115,
+        "PageUp" => 116,
+        "Delete" => 117,
+        "End" => 119,
+        "PageDown" => 121,
+        "ArrowLeft" => 123,
+        "ArrowRight" => 124,
+        "ArrowDown" => 125,
+        "ArrowUp" => 126,
+        _ => 0,
+    }
+}
+
+// ── DOM fallback ───────────────────────────────────────────────
+
+/// Synthesize the event with DOM `dispatchEvent()` calls. Used where no
+/// engine-level input API is available. Printable keys are inserted into
+/// editable targets via `insertText` since untrusted key events don't
+/// produce text on their own; wheel events scroll the page unless the
+/// page calls `preventDefault()`.
+#[cfg(not(target_os = "windows"))]
+fn dispatch_dom(webview: &WebView, event: &InputEvent) -> napi::Result<()> {
+    let payload = format!(
+        "{{\"type\":{},\"action\":{},\"x\":{},\"y\":{},\"button\":{},\"clickCount\":{},\"key\":{},\"shift\":{},\"ctrl\":{},\"alt\":{},\"meta\":{},\"deltaX\":{},\"deltaY\":{}}}",
+        json_escape(&event.kind),
+        json_escape(action(event)),
+        event.x.unwrap_or(0.0),
+        event.y.unwrap_or(0.0),
+        json_escape(event.button.as_deref().unwrap_or("left")),
+        event.click_count.unwrap_or(1),
+        json_escape(event.key.as_deref().unwrap_or("")),
+        has_modifier(event, "shift"),
+        has_modifier(event, "control"),
+        has_modifier(event, "alt"),
+        has_modifier(event, "meta"),
+        event.delta_x.unwrap_or(0.0),
+        event.delta_y.unwrap_or(0.0),
+    );
+    let script = format!(
+        r#"(function (e) {{
+  try {{
+    function init(extra) {{
+      var o = {{ bubbles: true, cancelable: true, composed: true, view: window,
+        shiftKey: e.shift, ctrlKey: e.ctrl, altKey: e.alt, metaKey: e.meta }};
+      for (var k in extra) o[k] = extra[k];
+      return o;
+    }}
+    if (e.type === "mouse") {{
+      var target = document.elementFromPoint(e.x, e.y) || document.body;
+      var button = e.button === "right" ? 2 : e.button === "middle" ? 1 : 0;
+      var names = e.action === "down" ? ["mousedown"]
+        : e.action === "up" ? ["mouseup"]
+        : e.action === "move" ? ["mousemove"]
+        : ["mousedown", "mouseup", button === 2 ? "contextmenu" : "click"];
+      names.forEach(function (n) {{
+        target.dispatchEvent(new MouseEvent(n, init({{
+          clientX: e.x, clientY: e.y, button: button, detail: e.clickCount,
+        }})));
+      }});
+      if (names[0] === "mousedown" && typeof target.focus === "function") target.focus();
+    }} else if (e.type === "key") {{
+      var el = document.activeElement || document.body;
+      var k = init({{ key: e.key }});
+      var proceed = true;
+      if (e.action !== "up") proceed = el.dispatchEvent(new KeyboardEvent("keydown", k));
+      if (e.action !== "up" && proceed && e.key.length === 1 && !e.ctrl && !e.meta
+          && (el.isContentEditable || "value" in el)) {{
+        document.execCommand("insertText", false, e.key);
+      }}
+      if (e.action !== "down") el.dispatchEvent(new KeyboardEvent("keyup", k));
+    }} else if (e.type === "wheel") {{
+      var t = document.elementFromPoint(e.x, e.y) || document.body;
+      var ok = t.dispatchEvent(new WheelEvent("wheel", init({{
+        clientX: e.x, clientY: e.y, deltaX: e.deltaX, deltaY: e.deltaY, deltaMode: 0,
+      }})));
+      if (ok) window.scrollBy(e.deltaX, e.deltaY);
+    }}
+  }} catch (err) {{}}
+}})({});"#,
+        payload
+    );
+    webview
+        .evaluate_script(&script)
+        .map_err(|e| napi::Error::from_reason(format!("sendInputEvent failed: {}", e)))
+}
@@ -0,0 +1,174 @@
+/// Multi-window layout changes applied as one update, for `applyLayout()`.
+///
+/// Docking and tiling UIs rearrange several windows at once; moving them
+/// one by one paints every intermediate state. Windows batches the changes
+/// with `DeferWindowPos`, macOS groups them in one `NSAnimationContext`,
+/// and Linux applies them in sequence.
+use tao::dpi::{LogicalPosition, LogicalSize};
+use tao::window::Window;
+
+use crate::options::WindowLayout;
+
+/// Apply each window's layout entry in a single update.
+pub fn apply_layout(entries: &[(&Window, &WindowLayout)]) -> Result<(), String> {
+    #[cfg(target_os = "windows")]
+    {
+        apply_layout_windows(entries).map_err(|e| e.to_string())
+    }
+    #[cfg(target_os = "macos")]
+    {
+        apply_layout_macos(entries);
+        Ok(())
+    }
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    {
+        apply_layout_linux(entries);
+        Ok(())
+    }
+}
+
+/// Indices of the entries with a `z_order`, front to back. Hidden windows
+/// are left out so ordering them does not show them again.
+fn stacking_order(entries: &[(&Window, &WindowLayout)]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..entries.len())
+        .filter(|&i| entries[i].1.z_order.is_some() && entries[i].1.visible != Some(false))
+        .collect();
+    order.sort_by_key(|&i| entries[i].1.z_order);
+    order
+}
+
+/// Bounds and visibility through tao, one window at a time.
+#[cfg(not(target_os = "windows"))]
+fn apply_geometry(entries: &[(&Window, &WindowLayout)]) {
+    for (window, layout) in entries {
+        if let Some(bounds) = layout.bounds {
+            if window.is_maximized() {
+                window.set_maximized(false);
+            }
+            window.set_inner_size(LogicalSize::new(bounds.width, bounds.height));
+            window.set_outer_position(LogicalPosition::new(bounds.x, bounds.y));
+        }
+        if let Some(visible) = layout.visible {
+            window.set_visible(visible);
+        }
+    }
+}
+
+// ── Windows ────────────────────────────────────────────────────
+
+#[cfg(target_os = "windows")]
+fn apply_layout_windows(entries: &[(&Window, &WindowLayout)]) -> windows::core::Result<()> {
+    use tao::dpi::{PhysicalPosition, PhysicalSize};
+    use tao::platform::windows::WindowExtWindows;
+    use windows::Win32::Foundation::HWND;
+    use windows::Win32::UI::WindowsAndMessaging::{
+        BeginDeferWindowPos, DeferWindowPos, EndDeferWindowPos, HWND_TOP, SWP_HIDEWINDOW,
+        SWP_NOACTIVATE, SWP_NOMOVE, SWP_NOSIZE, SWP_NOZORDER, SWP_SHOWWINDOW,
+    };
+
+    let hwnd = |window: &Window| HWND(window.hwnd() as _);
+
+    // Each window is inserted after the one in front of it.
+    let order = stacking_order(entries);
+    let mut insert_after: Vec<Option<HWND>> = vec![None; entries.len()];
+    for (rank, &index) in order.iter().enumerate() {
+        insert_after[index] = Some(match rank {
+            0 => HWND_TOP,
+            _ => hwnd(entries[order[rank - 1]].0),
+        });
+    }
+
+    unsafe {
+        let mut batch = BeginDeferWindowPos(entries.len() as i32)?;
+        for (index, (window, layout)) in entries.iter().enumerate() {
+            let mut flags = SWP_NOACTIVATE;
+            let (mut x, mut y, mut width, mut height) = (0, 0, 0, 0);
+            match layout.bounds {
+                Some(bounds) => {
+                    if window.is_maximized() {
+                        window.set_maximized(false);
+                    }
+                    // Bounds size the client area — add the current frame.
+                    let scale = window.scale_factor();
+                    let position: PhysicalPosition<i32> =
+                        LogicalPosition::new(bounds.x, bounds.y).to_physical(scale);
+                    let inner: PhysicalSize<u32> =
+                        LogicalSize::new(bounds.width, bounds.height).to_physical(scale);
+                    let outer = window.outer_size();
+                    let current = window.inner_size();
+                    x = position.x;
+                    y = position.y;
+                    width = (inner.width + outer.width.saturating_sub(current.width)) as i32;
+                    height = (inner.height + outer.height.saturating_sub(current.height)) as i32;
+                }
+                None => flags |= SWP_NOMOVE | SWP_NOSIZE,
+            }
+            match layout.visible {
+                Some(true) => flags |= SWP_SHOWWINDOW,
+                Some(false) => flags |= SWP_HIDEWINDOW,
+                None => {}
+            }
+            if insert_after[index].is_none() {
+                flags |= SWP_NOZORDER;
+            }
+            batch = DeferWindowPos(
+                batch,
+                hwnd(window),
+                insert_after[index],
+                x,
+                y,
+                width,
+                height,
+                flags,
+            )?;
+        }
+        EndDeferWindowPos(batch)
+    }
+}
+
+// ── macOS ──────────────────────────────────────────────────────
+
+/// Frame and ordering changes inside one animation group are committed
+/// to the window server together.
+#[cfg(target_os = "macos")]
+fn apply_layout_macos(entries: &[(&Window, &WindowLayout)]) {
+    use objc2_app_kit::{NSAnimationContext, NSWindow, NSWindowOrderingMode};
+    use tao::platform::macos::WindowExtMacOS;
+
+    NSAnimationContext::beginGrouping();
+    NSAnimationContext::currentContext().setDuration(0.0);
+
+    apply_geometry(entries);
+
+    let mut above: Option<isize> = None;
+    for index in stacking_order(entries) {
+        let ns_window = entries[index].0.ns_window() as *const NSWindow;
+        let Some(ns_window) = (unsafe { ns_window.as_ref() }) else {
+            continue;
+        };
+        match above {
+            None => ns_window.orderFront(None),
+            Some(number) => ns_window.orderWindow_relativeTo(NSWindowOrderingMode::Below, number),
+        }
+        above = Some(ns_window.windowNumber());
+    }
+
+    NSAnimationContext::endGrouping();
+}
+
+// ── Linux ──────────────────────────────────────────────────────
+
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+fn apply_layout_linux(entries: &[(&Window, &WindowLayout)]) {
+    use gtk::prelude::*;
+    use tao::platform::unix::WindowExtUnix;
+
+    apply_geometry(entries);
+
+    // Raise back to front, so the frontmost window ends on top.
+    for index in stacking_order(entries).into_iter().rev() {
+        if let Some(gdk_window) = entries[index].0.gtk_window().window() {
+            gdk_window.raise();
+        }
+    }
+}
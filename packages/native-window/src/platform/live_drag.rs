@@ -0,0 +1,145 @@
+/// Whether the user is still resizing or moving a window, for
+/// `onResizeEnd()` and `onMoveEnd()`.
+///
+/// tao reports every intermediate size and position of a drag. Windows
+/// brackets drags with `WM_ENTERSIZEMOVE`/`WM_EXITSIZEMOVE`, watched here
+/// through a window subclass. macOS has `inLiveResize` for resizes; moves
+/// are driven by the window server, so a held mouse button marks one in
+/// progress. X11 and Wayland report neither, so Linux also waits for the
+/// pointer button and for the geometry to stop changing.
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+use std::time::Duration;
+use std::time::Instant;
+
+use tao::window::Window;
+
+/// How long a Linux window's geometry must stay unchanged to count as settled.
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+const SETTLE_TIME: Duration = Duration::from_millis(250);
+
+/// Start tracking drags of a new window.
+pub fn watch(window: &Window) {
+    #[cfg(target_os = "windows")]
+    {
+        use tao::platform::windows::WindowExtWindows;
+        size_move_loop::watch(window.hwnd());
+    }
+    #[cfg(not(target_os = "windows"))]
+    {
+        let _ = window;
+    }
+}
+
+/// Whether a drag that last changed the window at `last_change` may still
+/// be going on.
+pub fn in_progress(window: &Window, last_change: Instant) -> bool {
+    #[cfg(target_os = "windows")]
+    {
+        use tao::platform::windows::WindowExtWindows;
+        let _ = last_change;
+        size_move_loop::is_active(window.hwnd())
+    }
+    #[cfg(target_os = "macos")]
+    {
+        let _ = last_change;
+        in_progress_macos(window)
+    }
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    {
+        last_change.elapsed() < SETTLE_TIME || is_button_pressed_linux(window)
+    }
+}
+
+// ── Windows ────────────────────────────────────────────────────
+
+#[cfg(target_os = "windows")]
+mod size_move_loop {
+    use std::cell::RefCell;
+    use std::collections::HashSet;
+
+    use windows::Win32::Foundation::{HWND, LPARAM, LRESULT, WPARAM};
+    use windows::Win32::UI::Shell::{DefSubclassProc, RemoveWindowSubclass, SetWindowSubclass};
+    use windows::Win32::UI::WindowsAndMessaging::{
+        WM_ENTERSIZEMOVE, WM_EXITSIZEMOVE, WM_NCDESTROY,
+    };
+
+    /// Subclass ID for our window procedure hook.
+    const SUBCLASS_ID: usize = 0x6e77_6c64; // "nwld"
+
+    thread_local! {
+        /// Windows inside a modal size/move loop, by HWND.
+        static ACTIVE: RefCell<HashSet<isize>> = RefCell::new(HashSet::new());
+    }
+
+    pub fn watch(hwnd: isize) {
+        unsafe {
+            let _ = SetWindowSubclass(HWND(hwnd as _), Some(subclass_proc), SUBCLASS_ID, 0);
+        }
+    }
+
+    pub fn is_active(hwnd: isize) -> bool {
+        ACTIVE.with(|a| a.borrow().contains(&hwnd))
+    }
+
+    unsafe extern "system" fn subclass_proc(
+        hwnd: HWND,
+        msg: u32,
+        wparam: WPARAM,
+        lparam: LPARAM,
+        _subclass_id: usize,
+        _ref_data: usize,
+    ) -> LRESULT {
+        match msg {
+            WM_ENTERSIZEMOVE => {
+                ACTIVE.with(|a| a.borrow_mut().insert(hwnd.0 as isize));
+            }
+            WM_EXITSIZEMOVE => {
+                ACTIVE.with(|a| a.borrow_mut().remove(&(hwnd.0 as isize)));
+            }
+            WM_NCDESTROY => {
+                ACTIVE.with(|a| a.borrow_mut().remove(&(hwnd.0 as isize)));
+                let _ = unsafe { RemoveWindowSubclass(hwnd, Some(subclass_proc), SUBCLASS_ID) };
+            }
+            _ => {}
+        }
+        unsafe { DefSubclassProc(hwnd, msg, wparam, lparam) }
+    }
+}
+
+// ── macOS ──────────────────────────────────────────────────────
+
+#[cfg(target_os = "macos")]
+fn in_progress_macos(window: &Window) -> bool {
+    use objc2_app_kit::{NSEvent, NSWindow};
+    use tao::platform::macos::WindowExtMacOS;
+
+    let Some(ns_window) = (unsafe { (window.ns_window() as *const NSWindow).as_ref() }) else {
+        return false;
+    };
+    // Bit 0 is the left button, which title bar drags hold.
+    ns_window.inLiveResize() || NSEvent::pressedMouseButtons() & 1 != 0
+}
+
+// ── Linux ──────────────────────────────────────────────────────
+
+/// Whether the primary pointer button is held. Window managers grab the
+/// pointer during drags, but X11 still reports the button state.
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+fn is_button_pressed_linux(window: &Window) -> bool {
+    use gtk::gdk::ModifierType;
+    use gtk::prelude::*;
+    use tao::platform::unix::WindowExtUnix;
+
+    let Some(gdk_window) = window.gtk_window().window() else {
+        return false;
+    };
+    let Some(pointer) = gdk_window
+        .display()
+        .default_seat()
+        .and_then(|seat| seat.pointer())
+    else {
+        return false;
+    };
+    let (_, _, _, mask) = gdk_window.device_position(&pointer);
+    mask.contains(ModifierType::BUTTON1_MASK)
+}
@@ -0,0 +1,300 @@
+/// Main-frame navigation failures, for `setFallbackHtml()`.
+///
+/// When a page cannot be reached (offline, DNS, refused connection) each
+/// engine shows its own error page, or on macOS keeps the old page. Failures
+/// are collected here and picked up by the platform after each pump, which
+/// serves the window's fallback document through the custom protocol
+/// instead. Aborted navigations (a new navigation, a blocked URL) are not
+/// failures.
+use std::cell::RefCell;
+
+use wry::WebView;
+
+use crate::window_manager::MAX_PENDING_EVENTS;
+
+thread_local! {
+    /// Failures since the last pump: (window_id, url, error code).
+    static FAILURES: RefCell<Vec<(u32, String, &'static str)>> = const { RefCell::new(Vec::new()) };
+}
+
+fn push_failure(window_id: u32, url: String, code: &'static str) {
+    FAILURES.with(|f| {
+        let mut buf = f.borrow_mut();
+        if buf.len() < MAX_PENDING_EVENTS {
+            buf.push((window_id, url, code));
+        }
+    });
+}
+
+/// Drain the failures collected since the last call.
+pub fn take_failures() -> Vec<(u32, String, &'static str)> {
+    FAILURES.with(|f| std::mem::take(&mut *f.borrow_mut()))
+}
+
+/// Start reporting main-frame navigation failures of a new webview.
+pub fn watch(webview: &WebView, window_id: u32) -> Result<(), String> {
+    #[cfg(target_os = "windows")]
+    {
+        watch_windows(webview, window_id).map_err(|e| e.to_string())
+    }
+    #[cfg(target_os = "macos")]
+    {
+        watch_macos(webview, window_id);
+        Ok(())
+    }
+    #[cfg(target_os = "linux")]
+    {
+        watch_linux(webview, window_id);
+        Ok(())
+    }
+    #[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
+    {
+        let _ = (webview, window_id);
+        Ok(())
+    }
+}
+
+/// Stop tracking a destroyed window's webview.
+pub fn forget(window_id: u32) {
+    #[cfg(target_os = "macos")]
+    WEBVIEWS.with(|w| w.borrow_mut().retain(|_, id| *id != window_id));
+    #[cfg(not(target_os = "macos"))]
+    let _ = window_id;
+}
+
+/// Turn WebView2's built-in error page off while a fallback is set, so it
+/// does not flash before the fallback loads. Other engines need nothing.
+pub fn set_builtin_error_page(webview: &WebView, enabled: bool) -> Result<(), String> {
+    #[cfg(target_os = "windows")]
+    {
+        use wry::WebViewExtWindows;
+
+        unsafe {
+            webview
+                .webview()
+                .Settings()
+                .and_then(|settings| settings.SetIsBuiltInErrorPageEnabled(enabled))
+        }
+        .map_err(|e| e.to_string())
+    }
+    #[cfg(not(target_os = "windows"))]
+    {
+        let _ = (webview, enabled);
+        Ok(())
+    }
+}
+
+// ── Windows ────────────────────────────────────────────────────
+
+#[cfg(target_os = "windows")]
+fn watch_windows(webview: &WebView, window_id: u32) -> windows::core::Result<()> {
+    use webview2_com::Microsoft::Web::WebView2::Win32::COREWEBVIEW2_WEB_ERROR_STATUS;
+    use webview2_com::{take_pwstr, NavigationCompletedEventHandler};
+    use windows::core::{BOOL, PWSTR};
+    use wry::WebViewExtWindows;
+
+    let mut token = 0i64;
+    unsafe {
+        webview.webview().add_NavigationCompleted(
+            &NavigationCompletedEventHandler::create(Box::new(move |sender, args| {
+                let (Some(sender), Some(args)) = (sender, args) else {
+                    return Ok(());
+                };
+                let mut success = BOOL::default();
+                args.IsSuccess(&mut success)?;
+                if success.as_bool() {
+                    return Ok(());
+                }
+                let mut status = COREWEBVIEW2_WEB_ERROR_STATUS::default();
+                args.WebErrorStatus(&mut status)?;
+                let Some(code) = error_code_windows(status) else {
+                    return Ok(());
+                };
+                // Source is the URL that failed to load.
+                let mut uri = PWSTR::null();
+                sender.Source(&mut uri)?;
+                push_failure(window_id, take_pwstr(uri), code);
+                Ok(())
+            })),
+            &mut token,
+        )
+    }
+}
+
+/// Error code for a WebView2 error status, `None` for aborted navigations.
+#[cfg(target_os = "windows")]
+fn error_code_windows(
+    status: webview2_com::Microsoft::Web::WebView2::Win32::COREWEBVIEW2_WEB_ERROR_STATUS,
+) -> Option<&'static str> {
+    use webview2_com::Microsoft::Web::WebView2::Win32::*;
+
+    Some(match status {
+        COREWEBVIEW2_WEB_ERROR_STATUS_OPERATION_CANCELED => return None,
+        COREWEBVIEW2_WEB_ERROR_STATUS_DISCONNECTED => "disconnected",
+        COREWEBVIEW2_WEB_ERROR_STATUS_HOST_NAME_NOT_RESOLVED => "hostNotFound",
+        COREWEBVIEW2_WEB_ERROR_STATUS_TIMEOUT => "timeout",
+        COREWEBVIEW2_WEB_ERROR_STATUS_CANNOT_CONNECT
+        | COREWEBVIEW2_WEB_ERROR_STATUS_SERVER_UNREACHABLE
+        | COREWEBVIEW2_WEB_ERROR_STATUS_CONNECTION_ABORTED
+        | COREWEBVIEW2_WEB_ERROR_STATUS_CONNECTION_RESET => "connectionFailed",
+        COREWEBVIEW2_WEB_ERROR_STATUS_CERTIFICATE_COMMON_NAME_IS_INCORRECT
+        | COREWEBVIEW2_WEB_ERROR_STATUS_CERTIFICATE_EXPIRED
+        | COREWEBVIEW2_WEB_ERROR_STATUS_CERTIFICATE_IS_INVALID
+        | COREWEBVIEW2_WEB_ERROR_STATUS_CERTIFICATE_REVOKED
+        | COREWEBVIEW2_WEB_ERROR_STATUS_CLIENT_CERTIFICATE_CONTAINS_ERRORS => "certificateError",
+        _ => "other",
+    })
+}
+
+// ── macOS ──────────────────────────────────────────────────────
+
+#[cfg(target_os = "macos")]
+thread_local! {
+    /// WKWebView pointer → window ID, to find the window in the delegate.
+    static WEBVIEWS: RefCell<std::collections::HashMap<usize, u32>> =
+        RefCell::new(std::collections::HashMap::new());
+}
+
+/// wry's navigation delegate does not implement
+/// `webView:didFailProvisionalNavigation:withError:`, so the method is
+/// added to its class once.
+#[cfg(target_os = "macos")]
+fn watch_macos(webview: &WebView, window_id: u32) {
+    use objc2::rc::Retained;
+    use objc2::runtime::{AnyClass, Imp};
+    use objc2::sel;
+    use std::sync::Once;
+    use wry::WebViewExtMacOS;
+
+    static ADD_METHOD: Once = Once::new();
+    ADD_METHOD.call_once(|| {
+        let Some(class) = AnyClass::get(c"WryNavigationDelegate") else {
+            eprintln!(
+                "[native-window] Navigation failures cannot be detected: delegate class not found"
+            );
+            return;
+        };
+        unsafe {
+            let imp: Imp = std::mem::transmute(
+                did_fail_provisional_navigation as unsafe extern "C-unwind" fn(_, _, _, _, _),
+            );
+            objc2::ffi::class_addMethod(
+                class as *const AnyClass as *mut AnyClass,
+                sel!(webView:didFailProvisionalNavigation:withError:),
+                imp,
+                c"v@:@@@".as_ptr(),
+            );
+        }
+    });
+
+    let key = Retained::as_ptr(&webview.webview()) as usize;
+    WEBVIEWS.with(|w| w.borrow_mut().insert(key, window_id));
+}
+
+#[cfg(target_os = "macos")]
+unsafe extern "C-unwind" fn did_fail_provisional_navigation(
+    _this: *mut objc2::runtime::AnyObject,
+    _cmd: objc2::runtime::Sel,
+    webview: *mut objc2::runtime::AnyObject,
+    _navigation: *mut objc2::runtime::AnyObject,
+    error: *mut objc2_foundation::NSError,
+) {
+    use objc2_foundation::{ns_string, NSString};
+
+    let Some(window_id) = WEBVIEWS.with(|w| w.borrow().get(&(webview as usize)).copied()) else {
+        return;
+    };
+    let Some(error) = (unsafe { error.as_ref() }) else {
+        return;
+    };
+    let Some(code) = error_code_macos(&error.domain().to_string(), error.code()) else {
+        return;
+    };
+    let url = error
+        .userInfo()
+        .objectForKey(ns_string!("NSErrorFailingURLStringKey"))
+        .and_then(|url| url.downcast::<NSString>().ok())
+        .map(|url| url.to_string())
+        .unwrap_or_default();
+    push_failure(window_id, url, code);
+}
+
+/// Error code for an `NSURLErrorDomain` error, `None` for cancellations
+/// and WebKit policy interruptions (e.g. a blocked navigation).
+#[cfg(target_os = "macos")]
+fn error_code_macos(domain: &str, code: isize) -> Option<&'static str> {
+    if domain != "NSURLErrorDomain" {
+        return if domain == "WebKitErrorDomain" && code == 102 {
+            None
+        } else {
+            Some("other")
+        };
+    }
+    Some(match code {
+        -999 => return None,
+        -1009 | -1018 | -1020 => "disconnected",
+        -1003 | -1006 => "hostNotFound",
+        -1001 => "timeout",
+        -1004 | -1005 => "connectionFailed",
+        -1206..=-1200 => "certificateError",
+        _ => "other",
+    })
+}
+
+// ── Linux ──────────────────────────────────────────────────────
+
+/// WebKitGTK's `load-failed` signal. Returning `true` suppresses the
+/// built-in error page, which is only done when a fallback will replace it.
+#[cfg(target_os = "linux")]
+fn watch_linux(webview: &WebView, window_id: u32) {
+    use gtk::glib::{self, prelude::*};
+    use wry::WebViewExtUnix;
+
+    webview
+        .webview()
+        .connect_local("load-failed", false, move |values| {
+            let url = values.get(2).and_then(|v| v.get::<String>().ok());
+            let error = values.get(3).and_then(|v| v.get::<glib::Error>().ok());
+            let (Some(url), Some(error)) = (url, error) else {
+                return Some(false.to_value());
+            };
+            let Some(code) = error_code_linux(&error) else {
+                return Some(false.to_value());
+            };
+            push_failure(window_id, url, code);
+            let has_fallback = crate::window_manager::get_fallback_html(window_id).is_some();
+            Some(has_fallback.to_value())
+        });
+}
+
+/// Error code for a `load-failed` error, `None` for cancellations and
+/// WebKit policy interruptions (e.g. a blocked navigation).
+#[cfg(target_os = "linux")]
+fn error_code_linux(error: &gtk::glib::Error) -> Option<&'static str> {
+    use gtk::gio::{IOErrorEnum, ResolverError, TlsError};
+    use gtk::glib::translate::ToGlibPtr;
+
+    let raw: *const gtk::glib::ffi::GError = error.to_glib_none().0;
+    let code = unsafe { (*raw).code };
+    // WEBKIT_NETWORK_ERROR_CANCELLED and
+    // WEBKIT_POLICY_ERROR_FRAME_LOAD_INTERRUPTED_BY_POLICY_CHANGE
+    match (error.domain().as_str().as_str(), code) {
+        ("WebKitNetworkError", 302) | ("WebKitPolicyError", 102) => return None,
+        _ => {}
+    }
+    if error.kind::<ResolverError>().is_some() {
+        return Some("hostNotFound");
+    }
+    if error.kind::<TlsError>().is_some() {
+        return Some("certificateError");
+    }
+    Some(match error.kind::<IOErrorEnum>() {
+        Some(IOErrorEnum::Cancelled) => return None,
+        Some(IOErrorEnum::NetworkUnreachable) => "disconnected",
+        Some(IOErrorEnum::TimedOut) => "timeout",
+        Some(
+            IOErrorEnum::ConnectionRefused | IOErrorEnum::HostUnreachable | IOErrorEnum::BrokenPipe,
+        ) => "connectionFailed",
+        _ => "other",
+    })
+}
@@ -0,0 +1,482 @@
+/// Classic per-window menu bars for Windows (HMENU) and Linux (GtkMenuBar).
+///
+/// macOS has a single app-wide menu bar, so these are not compiled there.
+/// Clicks are reported through `PENDING_MENU_CLICKS` with the item's `id`.
+/// With auto-hide on, the bar stays hidden until Alt is pressed and
+/// released on its own, and hides again once a menu closes.
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use tao::window::Window;
+
+use crate::options::WindowMenuItem;
+use crate::window_manager::{MAX_PENDING_EVENTS, PENDING_MENU_CLICKS};
+
+/// Queue a click on `item_id` in `window_id`'s menu bar.
+fn report_click(window_id: u32, item_id: String) {
+    PENDING_MENU_CLICKS.with(|p| {
+        let mut buf = p.borrow_mut();
+        if buf.len() < MAX_PENDING_EVENTS {
+            buf.push((window_id, item_id));
+        }
+    });
+}
+
+fn is_separator(item: &WindowMenuItem) -> bool {
+    item.kind.as_deref() == Some("separator")
+}
+
+// ── Windows ────────────────────────────────────────────────────
+
+#[cfg(target_os = "windows")]
+pub use self::windows_menu::*;
+
+#[cfg(target_os = "windows")]
+mod windows_menu {
+    use super::*;
+    use std::cell::Cell;
+
+    use tao::platform::windows::WindowExtWindows;
+    use windows::core::{HSTRING, PCWSTR};
+    use windows::Win32::Foundation::{HWND, LPARAM, LRESULT, WPARAM};
+    use windows::Win32::UI::Input::KeyboardAndMouse::{
+        GetAsyncKeyState, VK_LMENU, VK_MENU, VK_RMENU,
+    };
+    use windows::Win32::UI::Shell::{DefSubclassProc, SetWindowSubclass};
+    use windows::Win32::UI::WindowsAndMessaging::{
+        AppendMenuW, CreateMenu, CreatePopupMenu, DestroyMenu, DrawMenuBar, GetForegroundWindow,
+        SetMenu, HMENU, MENU_ITEM_FLAGS, MF_GRAYED, MF_POPUP, MF_SEPARATOR, MF_STRING, WM_COMMAND,
+        WM_EXITMENULOOP,
+    };
+
+    /// Subclass ID for our window procedure hook.
+    const SUBCLASS_ID: usize = 0x6e77_6d6e; // "nwmn"
+
+    /// WM_COMMAND identifiers are 16-bit; 0 is reserved.
+    const MAX_ITEMS: usize = u16::MAX as usize;
+
+    #[derive(Default)]
+    struct MenuState {
+        hwnd: isize,
+        /// Current menu bar, attached to the window only while `visible`.
+        hmenu: Option<isize>,
+        /// Item IDs by command identifier - 1.
+        item_ids: Vec<String>,
+        auto_hide: bool,
+        visible: bool,
+        subclassed: bool,
+    }
+
+    thread_local! {
+        static MENUS: RefCell<HashMap<u32, MenuState>> = RefCell::new(HashMap::new());
+        /// Alt key tracking for auto-hide: (held, combined with another key).
+        static ALT_STATE: Cell<(bool, bool)> = const { Cell::new((false, false)) };
+    }
+
+    /// Replace `window`'s menu bar. An empty `items` removes it.
+    pub fn set_menu(
+        window: &Window,
+        window_id: u32,
+        items: &[WindowMenuItem],
+    ) -> Result<(), String> {
+        let hwnd = window.hwnd();
+        let mut item_ids = Vec::new();
+        let hmenu = if items.is_empty() {
+            None
+        } else {
+            Some(build(items, &mut item_ids, false)?)
+        };
+
+        let (old, visible) = MENUS.with(|m| {
+            let mut menus = m.borrow_mut();
+            let state = menus.entry(window_id).or_default();
+            state.hwnd = hwnd;
+            state.item_ids = item_ids;
+            state.visible = hmenu.is_some() && !state.auto_hide;
+            let old = std::mem::replace(&mut state.hmenu, hmenu.map(|h| h.0 as isize));
+            (old, state.visible)
+        });
+
+        ensure_subclassed(hwnd, window_id);
+        attach(hwnd, if visible { hmenu } else { None });
+        if let Some(old) = old {
+            let _ = unsafe { DestroyMenu(HMENU(old as _)) };
+        }
+        Ok(())
+    }
+
+    /// Turn auto-hide on or off for `window`'s menu bar.
+    pub fn set_auto_hide(window: &Window, window_id: u32, auto_hide: bool) {
+        let hwnd = window.hwnd();
+        let (hmenu, visible) = MENUS.with(|m| {
+            let mut menus = m.borrow_mut();
+            let state = menus.entry(window_id).or_default();
+            state.hwnd = hwnd;
+            state.auto_hide = auto_hide;
+            state.visible = state.hmenu.is_some() && !auto_hide;
+            (state.hmenu, state.visible)
+        });
+        attach(hwnd, hmenu.filter(|_| visible).map(|h| HMENU(h as _)));
+    }
+
+    /// Forget `window_id`'s menu. The window itself is already destroyed,
+    /// which destroys an attached menu; a hidden one is freed here.
+    pub fn remove(window_id: u32) {
+        let state = MENUS.with(|m| m.borrow_mut().remove(&window_id));
+        if let Some(MenuState {
+            hmenu: Some(hmenu),
+            visible: false,
+            ..
+        }) = state
+        {
+            let _ = unsafe { DestroyMenu(HMENU(hmenu as _)) };
+        }
+    }
+
+    /// Toggle the auto-hidden menu bar of the foreground window when Alt
+    /// is pressed and released on its own. Polled from `pump_events` since
+    /// key presses go to the WebView2 browser process, not our window.
+    pub fn poll_alt_key() {
+        let any_auto_hide = MENUS.with(|m| m.borrow().values().any(|s| s.auto_hide));
+        if !any_auto_hide {
+            return;
+        }
+
+        let pressed = |vk: u16| unsafe { GetAsyncKeyState(vk as i32) } < 0;
+        let alt = pressed(VK_MENU.0);
+        let (was_held, combined) = ALT_STATE.with(|s| s.get());
+        if alt {
+            let other = combined
+                || (1..=0xfe)
+                    .filter(|&vk| ![VK_MENU.0, VK_LMENU.0, VK_RMENU.0].contains(&vk))
+                    .any(pressed);
+            ALT_STATE.with(|s| s.set((true, other)));
+            return;
+        }
+        ALT_STATE.with(|s| s.set((false, false)));
+        if !was_held || combined {
+            return;
+        }
+
+        let foreground = unsafe { GetForegroundWindow() }.0 as isize;
+        let toggled = MENUS.with(|m| {
+            let mut menus = m.borrow_mut();
+            let state = menus
+                .values_mut()
+                .find(|s| s.hwnd == foreground && s.auto_hide && s.hmenu.is_some())?;
+            state.visible = !state.visible;
+            Some((state.hwnd, state.hmenu.filter(|_| state.visible)))
+        });
+        if let Some((hwnd, hmenu)) = toggled {
+            attach(hwnd, hmenu.map(|h| HMENU(h as _)));
+        }
+    }
+
+    fn attach(hwnd: isize, hmenu: Option<HMENU>) {
+        let hwnd = HWND(hwnd as _);
+        unsafe {
+            let _ = SetMenu(hwnd, hmenu);
+            let _ = DrawMenuBar(hwnd);
+        }
+    }
+
+    /// Build a menu bar (or a popup submenu) from `items`, recording each
+    /// clickable item's ID at index `command - 1`.
+    fn build(
+        items: &[WindowMenuItem],
+        item_ids: &mut Vec<String>,
+        popup: bool,
+    ) -> Result<HMENU, String> {
+        let menu = unsafe {
+            if popup {
+                CreatePopupMenu()
+            } else {
+                CreateMenu()
+            }
+        }
+        .map_err(|e| e.to_string())?;
+
+        for item in items {
+            let appended = if is_separator(item) {
+                unsafe { AppendMenuW(menu, MF_SEPARATOR, 0, PCWSTR::null()) }
+            } else {
+                let label = HSTRING::from(item.label.as_deref().unwrap_or_default());
+                let mut flags: MENU_ITEM_FLAGS = MF_STRING;
+                if item.enabled == Some(false) {
+                    flags |= MF_GRAYED;
+                }
+                let command = match item.submenu {
+                    Some(ref children) => {
+                        flags |= MF_POPUP;
+                        match build(children, item_ids, true) {
+                            Ok(sub) => sub.0 as usize,
+                            Err(e) => {
+                                let _ = unsafe { DestroyMenu(menu) };
+                                return Err(e);
+                            }
+                        }
+                    }
+                    None => {
+                        if item_ids.len() >= MAX_ITEMS - 1 {
+                            let _ = unsafe { DestroyMenu(menu) };
+                            return Err(format!("menu has more than {} items", MAX_ITEMS - 1));
+                        }
+                        item_ids.push(item.id.clone().unwrap_or_default());
+                        item_ids.len()
+                    }
+                };
+                unsafe { AppendMenuW(menu, flags, command, &label) }
+            };
+            if let Err(e) = appended {
+                let _ = unsafe { DestroyMenu(menu) };
+                return Err(e.to_string());
+            }
+        }
+        Ok(menu)
+    }
+
+    fn ensure_subclassed(hwnd: isize, window_id: u32) {
+        let subclassed = MENUS.with(|m| {
+            let mut menus = m.borrow_mut();
+            let state = menus.entry(window_id).or_default();
+            std::mem::replace(&mut state.subclassed, true)
+        });
+        if !subclassed {
+            unsafe {
+                let _ = SetWindowSubclass(
+                    HWND(hwnd as _),
+                    Some(subclass_proc),
+                    SUBCLASS_ID,
+                    window_id as usize,
+                );
+            }
+        }
+    }
+
+    /// Window procedure hook: reports menu commands and re-hides an
+    /// auto-hidden bar when its menu closes. Chains to tao's procedure.
+    unsafe extern "system" fn subclass_proc(
+        hwnd: HWND,
+        msg: u32,
+        wparam: WPARAM,
+        lparam: LPARAM,
+        _subclass_id: usize,
+        window_id: usize,
+    ) -> LRESULT {
+        let window_id = window_id as u32;
+        match msg {
+            // HIWORD(wParam) == 0 and lParam == 0: a menu item, not an
+            // accelerator or a child control notification.
+            WM_COMMAND if (wparam.0 >> 16) & 0xffff == 0 && lparam.0 == 0 => {
+                let command = wparam.0 & 0xffff;
+                let item_id = MENUS.with(|m| {
+                    m.try_borrow().ok().and_then(|menus| {
+                        let state = menus.get(&window_id)?;
+                        state.item_ids.get(command.checked_sub(1)?).cloned()
+                    })
+                });
+                if let Some(item_id) = item_id {
+                    report_click(window_id, item_id);
+                    return LRESULT(0);
+                }
+            }
+            WM_EXITMENULOOP => {
+                let hide = MENUS.with(|m| {
+                    m.try_borrow_mut().ok().is_some_and(|mut menus| {
+                        match menus.get_mut(&window_id) {
+                            Some(state) if state.auto_hide && state.visible => {
+                                state.visible = false;
+                                true
+                            }
+                            _ => false,
+                        }
+                    })
+                });
+                if hide {
+                    attach(hwnd.0 as isize, None);
+                }
+            }
+            _ => {}
+        }
+        unsafe { DefSubclassProc(hwnd, msg, wparam, lparam) }
+    }
+}
+
+// ── Linux ──────────────────────────────────────────────────────
+
+#[cfg(target_os = "linux")]
+pub use self::linux_menu::*;
+
+#[cfg(target_os = "linux")]
+mod linux_menu {
+    use super::*;
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    use gtk::gdk::keys::constants as key;
+    use gtk::prelude::*;
+    use tao::platform::unix::WindowExtUnix;
+
+    #[derive(Default)]
+    struct MenuState {
+        bar: Option<gtk::MenuBar>,
+        /// Shared with the key handlers installed on the window.
+        auto_hide: Rc<Cell<bool>>,
+        keys_connected: bool,
+    }
+
+    thread_local! {
+        static MENUS: RefCell<HashMap<u32, MenuState>> = RefCell::new(HashMap::new());
+    }
+
+    /// Replace `window`'s menu bar. An empty `items` removes it.
+    pub fn set_menu(
+        window: &Window,
+        window_id: u32,
+        items: &[WindowMenuItem],
+    ) -> Result<(), String> {
+        let vbox = window
+            .default_vbox()
+            .ok_or("window has no container for a menu bar")?;
+
+        let bar = if items.is_empty() {
+            None
+        } else {
+            let bar = gtk::MenuBar::new();
+            for item in items {
+                bar.append(&build_item(item, window_id));
+            }
+            vbox.pack_start(&bar, false, false, 0);
+            vbox.reorder_child(&bar, 0);
+            // Keep show_all() on the window from revealing a hidden bar.
+            bar.show_all();
+            bar.set_no_show_all(true);
+            Some(bar)
+        };
+
+        let auto_hide = MENUS.with(|m| {
+            let mut menus = m.borrow_mut();
+            let state = menus.entry(window_id).or_default();
+            if let Some(old) = std::mem::replace(&mut state.bar, bar.clone()) {
+                vbox.remove(&old);
+            }
+            state.auto_hide.clone()
+        });
+
+        if let Some(ref bar) = bar {
+            bar.set_visible(!auto_hide.get());
+            let hide_after_use = auto_hide.clone();
+            bar.connect_deactivate(move |bar| {
+                if hide_after_use.get() {
+                    bar.hide();
+                }
+            });
+        }
+        connect_alt_toggle(window, window_id);
+        Ok(())
+    }
+
+    /// Turn auto-hide on or off for `window`'s menu bar.
+    pub fn set_auto_hide(window: &Window, window_id: u32, auto_hide: bool) {
+        let bar = MENUS.with(|m| {
+            let mut menus = m.borrow_mut();
+            let state = menus.entry(window_id).or_default();
+            state.auto_hide.set(auto_hide);
+            state.bar.clone()
+        });
+        if let Some(bar) = bar {
+            bar.set_visible(!auto_hide);
+        }
+        connect_alt_toggle(window, window_id);
+    }
+
+    /// Forget `window_id`'s menu. Its widgets went away with the window.
+    pub fn remove(window_id: u32) {
+        MENUS.with(|m| m.borrow_mut().remove(&window_id));
+    }
+
+    /// Convert Windows-style `&` mnemonics to GTK's `_`.
+    fn mnemonic(label: &str) -> String {
+        let mut out = String::with_capacity(label.len());
+        let mut chars = label.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '&' if chars.peek() == Some(&'&') => {
+                    chars.next();
+                    out.push('&');
+                }
+                '&' => out.push('_'),
+                '_' => out.push_str("__"),
+                c => out.push(c),
+            }
+        }
+        out
+    }
+
+    fn build_item(item: &WindowMenuItem, window_id: u32) -> gtk::MenuItem {
+        if is_separator(item) {
+            return gtk::SeparatorMenuItem::new().upcast();
+        }
+        let menu_item =
+            gtk::MenuItem::with_mnemonic(&mnemonic(item.label.as_deref().unwrap_or_default()));
+        menu_item.set_sensitive(item.enabled != Some(false));
+        match item.submenu {
+            Some(ref children) => {
+                let submenu = gtk::Menu::new();
+                for child in children {
+                    submenu.append(&build_item(child, window_id));
+                }
+                menu_item.set_submenu(Some(&submenu));
+            }
+            None => {
+                let item_id = item.id.clone().unwrap_or_default();
+                menu_item.connect_activate(move |_| report_click(window_id, item_id.clone()));
+            }
+        }
+        menu_item
+    }
+
+    /// Show/hide an auto-hidden bar when Alt is pressed and released on
+    /// its own. The window sees key events before the focused webview.
+    fn connect_alt_toggle(window: &Window, window_id: u32) {
+        let auto_hide = MENUS.with(|m| {
+            let mut menus = m.borrow_mut();
+            let state = menus.entry(window_id).or_default();
+            if std::mem::replace(&mut state.keys_connected, true) {
+                None
+            } else {
+                Some(state.auto_hide.clone())
+            }
+        });
+        let Some(auto_hide) = auto_hide else {
+            return;
+        };
+
+        let is_alt = |k: &gtk::gdk::EventKey| {
+            let keyval = k.keyval();
+            keyval == key::Alt_L || keyval == key::Alt_R
+        };
+        // Alt held with no other key pressed since.
+        let alone = Rc::new(Cell::new(false));
+
+        let gtk_window = window.gtk_window();
+        let pressed = alone.clone();
+        gtk_window.connect_key_press_event(move |_, k| {
+            pressed.set(is_alt(k));
+            gtk::glib::Propagation::Proceed
+        });
+        gtk_window.connect_key_release_event(move |_, k| {
+            if is_alt(k) && alone.replace(false) && auto_hide.get() {
+                let bar = MENUS.with(|m| {
+                    m.try_borrow()
+                        .ok()
+                        .and_then(|menus| menus.get(&window_id)?.bar.clone())
+                });
+                if let Some(bar) = bar {
+                    bar.set_visible(!bar.is_visible());
+                }
+            }
+            gtk::glib::Propagation::Proceed
+        });
+    }
+}
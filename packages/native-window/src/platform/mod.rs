@@ -1,2 +1,37 @@
+mod aspect_ratio;
+mod audio_capture;
+mod backend;
+#[cfg(target_os = "windows")]
+mod badge;
+mod capture;
+#[cfg(target_os = "windows")]
+mod cdp;
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+mod color_picker;
+mod display;
+mod focus;
+mod fonts;
+mod frames;
+mod iframe_policy;
+mod input;
+mod layout;
+mod live_drag;
+mod load_errors;
+#[cfg(any(target_os = "windows", target_os = "linux"))]
+mod menu;
+mod popup;
+mod priority;
+pub(crate) mod protocol_client;
+mod readable;
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+mod share;
+mod stacking;
+mod ui_language;
 mod unified;
+#[cfg(target_os = "windows")]
+pub(crate) mod virtual_desktop;
+#[cfg(target_os = "windows")]
+pub(crate) mod webview2_env;
+pub use backend::PlatformBackend;
+pub use ui_language::set_ui_language;
 pub use unified::*;
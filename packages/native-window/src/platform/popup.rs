@@ -0,0 +1,142 @@
+/// Anchored popup placement.
+///
+/// Positions a popup window next to a rectangle — inside another window's
+/// webview, or on the screen — flipping to the opposite side when the
+/// preferred side would overflow the display's work area.
+use tao::dpi::PhysicalPosition;
+use tao::event_loop::EventLoopWindowTarget;
+use tao::monitor::MonitorHandle;
+use tao::window::Window;
+
+use super::display::{self, Rect};
+use crate::options::PopupAnchor;
+
+/// Where an anchor rectangle lives: the webview origin in physical screen
+/// pixels, the scale for its CSS pixels, and the monitor it is on.
+pub struct AnchorSpace {
+    pub origin: PhysicalPosition<i32>,
+    pub scale: f64,
+    pub monitor: Option<MonitorHandle>,
+}
+
+impl AnchorSpace {
+    /// Rect coordinates are CSS pixels relative to `window`'s webview.
+    pub fn window(window: &Window) -> Self {
+        Self {
+            origin: window.inner_position().unwrap_or_default(),
+            scale: window.scale_factor(),
+            monitor: window.current_monitor(),
+        }
+    }
+
+    /// Rect coordinates are logical screen pixels (no anchor window).
+    pub fn screen(target: &EventLoopWindowTarget<()>, x: f64, y: f64) -> Self {
+        let monitor = target
+            .available_monitors()
+            .find(|m| {
+                let b = display::bounds(m);
+                let (px, py) = (x * m.scale_factor(), y * m.scale_factor());
+                px >= b.x as f64
+                    && py >= b.y as f64
+                    && px < (b.x + b.width) as f64
+                    && py < (b.y + b.height) as f64
+            })
+            .or_else(|| target.primary_monitor());
+        Self {
+            origin: PhysicalPosition::new(0, 0),
+            scale: monitor.as_ref().map_or(1.0, |m| m.scale_factor()),
+            monitor,
+        }
+    }
+}
+
+/// Compute the outer position (physical pixels) for a popup of the given
+/// logical size, anchored to `anchor.rect` in `space`.
+pub fn place(
+    space: &AnchorSpace,
+    anchor: &PopupAnchor,
+    width: f64,
+    height: f64,
+) -> PhysicalPosition<i32> {
+    let AnchorSpace { origin, scale, .. } = *space;
+
+    // Anchor rect: CSS/logical pixels → physical screen pixels.
+    let target = Rect {
+        x: origin.x + (anchor.rect.x * scale).round() as i32,
+        y: origin.y + (anchor.rect.y * scale).round() as i32,
+        width: (anchor.rect.width * scale).round() as i32,
+        height: (anchor.rect.height * scale).round() as i32,
+    };
+    let w = (width * scale).round() as i32;
+    let h = (height * scale).round() as i32;
+
+    let work = space
+        .monitor
+        .as_ref()
+        .map(display::work_area)
+        // No monitor info: effectively unbounded, so no flipping or clamping.
+        .unwrap_or(Rect {
+            x: i32::MIN / 4,
+            y: i32::MIN / 4,
+            width: i32::MAX / 2,
+            height: i32::MAX / 2,
+        });
+    let right_edge = work.x + work.width;
+    let bottom_edge = work.y + work.height;
+
+    let (x, y) = match anchor.placement.as_deref().unwrap_or("below") {
+        "above" | "below" => {
+            let below = target.y + target.height;
+            let above = target.y - h;
+            let prefer_above = anchor.placement.as_deref() == Some("above");
+            let y = if prefer_above {
+                if above < work.y && below + h <= bottom_edge {
+                    below
+                } else {
+                    above
+                }
+            } else if below + h > bottom_edge && above >= work.y {
+                above
+            } else {
+                below
+            };
+            (clamp(target.x, work.x, right_edge - w), y)
+        }
+        _ => {
+            let right = target.x + target.width;
+            let left = target.x - w;
+            let prefer_left = anchor.placement.as_deref() == Some("left");
+            let x = if prefer_left {
+                if left < work.x && right + w <= right_edge {
+                    right
+                } else {
+                    left
+                }
+            } else if right + w > right_edge && left >= work.x {
+                left
+            } else {
+                right
+            };
+            (x, clamp(target.y, work.y, bottom_edge - h))
+        }
+    };
+
+    PhysicalPosition::new(x, y)
+}
+
+/// Clamp `value` into `[min, max]`, preferring `min` when the range is empty.
+fn clamp(value: i32, min: i32, max: i32) -> i32 {
+    value.min(max).max(min)
+}
+
+/// Whether a global physical cursor position lies inside a window's frame.
+pub fn contains_cursor(window: &Window, cursor: PhysicalPosition<f64>) -> bool {
+    let Ok(position) = window.outer_position() else {
+        return false;
+    };
+    let size = window.outer_size();
+    cursor.x >= position.x as f64
+        && cursor.y >= position.y as f64
+        && cursor.x < position.x as f64 + size.width as f64
+        && cursor.y < position.y as f64 + size.height as f64
+}
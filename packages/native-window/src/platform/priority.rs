@@ -0,0 +1,201 @@
+/// Scheduling priority of a webview's content process, for `setPriority()`.
+///
+/// Background windows of many-window apps keep their pages alive but
+/// should not compete with the foreground one for CPU or battery. Only the
+/// process rendering this webview is touched; shared browser/GPU/network
+/// processes keep their priority.
+use wry::WebView;
+
+/// Lower (`background == true`) or restore the webview's process priority.
+pub fn set_priority(webview: &WebView, background: bool) -> Result<(), String> {
+    #[cfg(target_os = "windows")]
+    {
+        set_priority_windows(webview, background)
+    }
+    #[cfg(target_os = "macos")]
+    {
+        set_priority_macos(webview, background)
+    }
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    {
+        // WebKitGTK does not expose its web process, so there is nothing
+        // to adjust.
+        let _ = (webview, background);
+        Ok(())
+    }
+}
+
+// ── Windows ────────────────────────────────────────────────────
+
+/// Sets WebView2's memory usage target, then finds the renderer processes
+/// hosting this webview's main frame and moves them to below-normal
+/// priority with EcoQoS power throttling.
+#[cfg(target_os = "windows")]
+fn set_priority_windows(webview: &WebView, background: bool) -> Result<(), String> {
+    use webview2_com::GetProcessExtendedInfosCompletedHandler;
+    use webview2_com::Microsoft::Web::WebView2::Win32::{
+        ICoreWebView2Environment13, ICoreWebView2_19, ICoreWebView2_2, ICoreWebView2_20,
+        COREWEBVIEW2_MEMORY_USAGE_TARGET_LEVEL_LOW, COREWEBVIEW2_MEMORY_USAGE_TARGET_LEVEL_NORMAL,
+        COREWEBVIEW2_PROCESS_KIND, COREWEBVIEW2_PROCESS_KIND_RENDERER,
+    };
+    use windows::core::Interface;
+    use wry::WebViewExtWindows;
+
+    let core = webview.webview();
+    let register = || -> windows::core::Result<()> {
+        unsafe {
+            // Lets the runtime trim caches of background webviews.
+            if let Ok(core19) = core.cast::<ICoreWebView2_19>() {
+                core19.SetMemoryUsageTargetLevel(if background {
+                    COREWEBVIEW2_MEMORY_USAGE_TARGET_LEVEL_LOW
+                } else {
+                    COREWEBVIEW2_MEMORY_USAGE_TARGET_LEVEL_NORMAL
+                })?;
+            }
+
+            let mut main_frame_id = 0u32;
+            core.cast::<ICoreWebView2_20>()?
+                .FrameId(&mut main_frame_id)?;
+            let environment = core
+                .cast::<ICoreWebView2_2>()?
+                .Environment()?
+                .cast::<ICoreWebView2Environment13>()?;
+
+            let handler =
+                GetProcessExtendedInfosCompletedHandler::create(Box::new(move |result, infos| {
+                    result?;
+                    let Some(infos) = infos else {
+                        return Ok(());
+                    };
+                    let mut count = 0u32;
+                    infos.Count(&mut count)?;
+                    for index in 0..count {
+                        let info = infos.GetValueAtIndex(index)?;
+                        let process = info.ProcessInfo()?;
+                        let mut kind = COREWEBVIEW2_PROCESS_KIND::default();
+                        process.Kind(&mut kind)?;
+                        if kind != COREWEBVIEW2_PROCESS_KIND_RENDERER
+                            || !hosts_frame(&info, main_frame_id)?
+                        {
+                            continue;
+                        }
+                        let mut pid = 0i32;
+                        process.ProcessId(&mut pid)?;
+                        if let Err(e) = set_process_priority(pid as u32, background) {
+                            eprintln!(
+                                "[native-window] setPriority: renderer {} not updated: {}",
+                                pid, e
+                            );
+                        }
+                    }
+                    Ok(())
+                }));
+            environment.GetProcessExtendedInfos(&handler)
+        }
+    };
+    register().map_err(|e| e.to_string())
+}
+
+/// Whether `info`'s renderer process runs the frame with `frame_id`.
+#[cfg(target_os = "windows")]
+unsafe fn hosts_frame(
+    info: &webview2_com::Microsoft::Web::WebView2::Win32::ICoreWebView2ProcessExtendedInfo,
+    frame_id: u32,
+) -> windows::core::Result<bool> {
+    use webview2_com::Microsoft::Web::WebView2::Win32::ICoreWebView2FrameInfo2;
+    use windows::core::{Interface, BOOL};
+
+    let frames = info.AssociatedFrameInfos()?.GetIterator()?;
+    let mut has_current = BOOL::default();
+    frames.HasCurrent(&mut has_current)?;
+    while has_current.as_bool() {
+        let mut id = 0u32;
+        frames
+            .GetCurrent()?
+            .cast::<ICoreWebView2FrameInfo2>()?
+            .FrameId(&mut id)?;
+        if id == frame_id {
+            return Ok(true);
+        }
+        frames.MoveNext(&mut has_current)?;
+    }
+    Ok(false)
+}
+
+#[cfg(target_os = "windows")]
+fn set_process_priority(pid: u32, background: bool) -> windows::core::Result<()> {
+    use windows::Win32::Foundation::CloseHandle;
+    use windows::Win32::System::Threading::{
+        OpenProcess, ProcessPowerThrottling, SetPriorityClass, SetProcessInformation,
+        BELOW_NORMAL_PRIORITY_CLASS, NORMAL_PRIORITY_CLASS,
+        PROCESS_POWER_THROTTLING_CURRENT_VERSION, PROCESS_POWER_THROTTLING_EXECUTION_SPEED,
+        PROCESS_POWER_THROTTLING_STATE, PROCESS_SET_INFORMATION,
+    };
+
+    unsafe {
+        let process = OpenProcess(PROCESS_SET_INFORMATION, false, pid)?;
+        let result = SetPriorityClass(
+            process,
+            if background {
+                BELOW_NORMAL_PRIORITY_CLASS
+            } else {
+                NORMAL_PRIORITY_CLASS
+            },
+        )
+        .and_then(|_| {
+            // EcoQoS: run on efficiency cores at reduced clock speed (Windows 11).
+            let throttling = PROCESS_POWER_THROTTLING_STATE {
+                Version: PROCESS_POWER_THROTTLING_CURRENT_VERSION,
+                ControlMask: PROCESS_POWER_THROTTLING_EXECUTION_SPEED,
+                StateMask: if background {
+                    PROCESS_POWER_THROTTLING_EXECUTION_SPEED
+                } else {
+                    0
+                },
+            };
+            SetProcessInformation(
+                process,
+                ProcessPowerThrottling,
+                &throttling as *const _ as *const std::ffi::c_void,
+                std::mem::size_of::<PROCESS_POWER_THROTTLING_STATE>() as u32,
+            )
+        });
+        let _ = CloseHandle(process);
+        result
+    }
+}
+
+// ── macOS ──────────────────────────────────────────────────────
+
+/// Moves the WebContent process into the Darwin background band, which
+/// throttles its CPU, I/O and timer coalescing like App Nap.
+#[cfg(target_os = "macos")]
+fn set_priority_macos(webview: &WebView, background: bool) -> Result<(), String> {
+    use objc2::{msg_send, sel};
+    use wry::WebViewExtMacOS;
+
+    // libc's `setpriority`; libSystem is always linked on macOS.
+    extern "C" {
+        fn setpriority(which: i32, who: u32, prio: i32) -> i32;
+    }
+    const PRIO_DARWIN_PROCESS: i32 = 4;
+    const PRIO_DARWIN_BG: i32 = 0x1000;
+
+    let wk_webview = webview.webview();
+    // WKWebView has no public accessor for its web process.
+    let responds: bool =
+        unsafe { msg_send![&*wk_webview, respondsToSelector: sel!(_webProcessIdentifier)] };
+    if !responds {
+        return Err("WebKit does not expose the web process on this macOS version".to_string());
+    }
+    let pid: i32 = unsafe { msg_send![&*wk_webview, _webProcessIdentifier] };
+    if pid <= 0 {
+        // No page loaded yet; the process starts with the next navigation.
+        return Err("the webview has no web process yet".to_string());
+    }
+    let prio = if background { PRIO_DARWIN_BG } else { 0 };
+    if unsafe { setpriority(PRIO_DARWIN_PROCESS, pid as u32, prio) } != 0 {
+        return Err(std::io::Error::last_os_error().to_string());
+    }
+    Ok(())
+}
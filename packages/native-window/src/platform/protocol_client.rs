@@ -0,0 +1,290 @@
+/// Registration as the OS handler of a URL scheme, for
+/// `setAsDefaultProtocolClient()` and friends.
+///
+/// - **macOS**: LaunchServices, keyed by the app's bundle identifier. The
+///   scheme must also be declared in the bundle's `CFBundleURLTypes`.
+/// - **Windows**: `HKCU\Software\Classes\<scheme>`, opening
+///   `"<path>" <args> "%1"`.
+/// - **Linux**: a `native-window-<scheme>.desktop` entry in the user's
+///   applications directory, made the default with `xdg-mime`.
+#[cfg(target_os = "macos")]
+mod launch_services {
+    use objc2::rc::Retained;
+    use objc2_foundation::{NSArray, NSBundle, NSString};
+
+    #[link(name = "CoreServices", kind = "framework")]
+    extern "C" {
+        fn LSSetDefaultHandlerForURLScheme(scheme: &NSString, bundle_id: &NSString) -> i32;
+        fn LSCopyDefaultHandlerForURLScheme(scheme: &NSString) -> *mut NSString;
+        fn LSCopyAllHandlersForURLScheme(scheme: &NSString) -> *mut NSArray<NSString>;
+    }
+
+    fn bundle_id() -> Result<Retained<NSString>, String> {
+        NSBundle::mainBundle()
+            .bundleIdentifier()
+            .ok_or_else(|| "the app has no bundle identifier".to_string())
+    }
+
+    pub fn register(scheme: &str, _path: &str, _args: &[String]) -> Result<(), String> {
+        let status =
+            unsafe { LSSetDefaultHandlerForURLScheme(&NSString::from_str(scheme), &bundle_id()?) };
+        match status {
+            0 => Ok(()),
+            status => Err(format!("LaunchServices error {}", status)),
+        }
+    }
+
+    /// LaunchServices has no "unset", so hand the scheme to another
+    /// registered handler, if any.
+    pub fn unregister(scheme: &str, path: &str, args: &[String]) -> Result<(), String> {
+        if !is_default(scheme, path, args) {
+            return Ok(());
+        }
+        let ours = bundle_id()?.to_string();
+        let scheme = NSString::from_str(scheme);
+        // Copy functions return a +1 reference; CF types are toll-free
+        // bridged to their Foundation counterparts.
+        let handlers = unsafe { Retained::from_raw(LSCopyAllHandlersForURLScheme(&scheme)) };
+        let other = handlers
+            .iter()
+            .flat_map(|h| h.iter())
+            .find(|h| !h.to_string().eq_ignore_ascii_case(&ours));
+        if let Some(other) = other {
+            let status = unsafe { LSSetDefaultHandlerForURLScheme(&scheme, &other) };
+            if status != 0 {
+                return Err(format!("LaunchServices error {}", status));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn is_default(scheme: &str, _path: &str, _args: &[String]) -> bool {
+        let Ok(ours) = bundle_id() else {
+            return false;
+        };
+        let handler = unsafe {
+            Retained::from_raw(LSCopyDefaultHandlerForURLScheme(&NSString::from_str(
+                scheme,
+            )))
+        };
+        // LaunchServices may report the identifier lowercased.
+        let ours = ours.to_string();
+        handler.is_some_and(|h| h.to_string().eq_ignore_ascii_case(&ours))
+    }
+}
+
+#[cfg(target_os = "macos")]
+pub use launch_services::{is_default, register, unregister};
+
+#[cfg(target_os = "windows")]
+mod registry {
+    use windows::core::{HSTRING, PCWSTR};
+    use windows::Win32::Foundation::ERROR_SUCCESS;
+    use windows::Win32::System::Registry::{
+        RegCloseKey, RegCreateKeyExW, RegDeleteTreeW, RegGetValueW, RegSetValueExW, HKEY,
+        HKEY_CURRENT_USER, KEY_WRITE, REG_OPTION_NON_VOLATILE, REG_SZ, RRF_RT_REG_SZ,
+    };
+
+    fn class_key(scheme: &str) -> String {
+        format!("Software\\Classes\\{}", scheme)
+    }
+
+    fn quote(arg: &str) -> String {
+        format!("\"{}\"", arg.replace('"', "\\\""))
+    }
+
+    /// The `shell\open\command` registered for `path` and `args`.
+    fn command(path: &str, args: &[String]) -> String {
+        let mut parts = vec![quote(path)];
+        parts.extend(args.iter().map(|a| quote(a)));
+        parts.push("\"%1\"".to_string());
+        parts.join(" ")
+    }
+
+    /// Create (or open) a HKCU key and set string values on it.
+    fn write_key(key: &str, values: &[(&str, &str)]) -> Result<(), String> {
+        let key = HSTRING::from(key);
+        let mut hkey = HKEY::default();
+        let status = unsafe {
+            RegCreateKeyExW(
+                HKEY_CURRENT_USER,
+                PCWSTR(key.as_ptr()),
+                None,
+                PCWSTR::null(),
+                REG_OPTION_NON_VOLATILE,
+                KEY_WRITE,
+                None,
+                &mut hkey,
+                None,
+            )
+        };
+        if status != ERROR_SUCCESS {
+            return Err(format!("failed to create registry key: {:?}", status));
+        }
+        let mut result = Ok(());
+        for (name, value) in values {
+            let name = HSTRING::from(*name);
+            let data: Vec<u8> = value
+                .encode_utf16()
+                .chain(Some(0))
+                .flat_map(u16::to_le_bytes)
+                .collect();
+            let status =
+                unsafe { RegSetValueExW(hkey, PCWSTR(name.as_ptr()), None, REG_SZ, Some(&data)) };
+            if status != ERROR_SUCCESS {
+                result = Err(format!("failed to set registry value: {:?}", status));
+                break;
+            }
+        }
+        unsafe {
+            let _ = RegCloseKey(hkey);
+        }
+        result
+    }
+
+    /// The registered `shell\open\command` of `scheme`, if any.
+    fn registered_command(scheme: &str) -> Option<String> {
+        let key = HSTRING::from(format!("{}\\shell\\open\\command", class_key(scheme)));
+        let mut size = 0u32;
+        let read = |data: Option<*mut std::ffi::c_void>, size: &mut u32| unsafe {
+            RegGetValueW(
+                HKEY_CURRENT_USER,
+                PCWSTR(key.as_ptr()),
+                PCWSTR::null(),
+                RRF_RT_REG_SZ,
+                None,
+                data,
+                Some(size),
+            )
+        };
+        if read(None, &mut size) != ERROR_SUCCESS || size == 0 {
+            return None;
+        }
+        let mut data = vec![0u16; (size as usize).div_ceil(2)];
+        if read(Some(data.as_mut_ptr().cast()), &mut size) != ERROR_SUCCESS {
+            return None;
+        }
+        let len = data.iter().position(|&c| c == 0).unwrap_or(data.len());
+        Some(String::from_utf16_lossy(&data[..len]))
+    }
+
+    pub fn register(scheme: &str, path: &str, args: &[String]) -> Result<(), String> {
+        let key = class_key(scheme);
+        write_key(
+            &key,
+            &[("", &format!("URL:{}", scheme)), ("URL Protocol", "")],
+        )?;
+        write_key(
+            &format!("{}\\shell\\open\\command", key),
+            &[("", &command(path, args))],
+        )
+    }
+
+    /// Remove the registration, only if it opens `path` with `args`.
+    pub fn unregister(scheme: &str, path: &str, args: &[String]) -> Result<(), String> {
+        if !is_default(scheme, path, args) {
+            return Ok(());
+        }
+        let key = HSTRING::from(class_key(scheme));
+        let status = unsafe { RegDeleteTreeW(HKEY_CURRENT_USER, PCWSTR(key.as_ptr())) };
+        if status != ERROR_SUCCESS {
+            return Err(format!("failed to delete registry key: {:?}", status));
+        }
+        Ok(())
+    }
+
+    pub fn is_default(scheme: &str, path: &str, args: &[String]) -> bool {
+        registered_command(scheme).is_some_and(|c| c.eq_ignore_ascii_case(&command(path, args)))
+    }
+}
+
+#[cfg(target_os = "windows")]
+pub use registry::{is_default, register, unregister};
+
+#[cfg(target_os = "linux")]
+mod xdg {
+    use std::path::PathBuf;
+    use std::process::Command;
+
+    fn desktop_file_name(scheme: &str) -> String {
+        format!("native-window-{}.desktop", scheme)
+    }
+
+    fn applications_dir() -> Option<PathBuf> {
+        std::env::var_os("XDG_DATA_HOME")
+            .filter(|d| !d.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|h| PathBuf::from(h).join(".local/share")))
+            .map(|d| d.join("applications"))
+    }
+
+    /// Quote an `Exec` argument as the desktop entry spec requires.
+    fn quote(arg: &str) -> String {
+        let mut quoted = String::from("\"");
+        for c in arg.chars() {
+            if matches!(c, '"' | '`' | '$' | '\\') {
+                quoted.push('\\');
+            }
+            quoted.push(c);
+        }
+        quoted.push('"');
+        quoted
+    }
+
+    fn mime_type(scheme: &str) -> String {
+        format!("x-scheme-handler/{}", scheme)
+    }
+
+    pub fn register(scheme: &str, path: &str, args: &[String]) -> Result<(), String> {
+        let dir = applications_dir().ok_or("cannot find the applications directory")?;
+        std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+        let mut exec: Vec<String> = vec![quote(path)];
+        exec.extend(args.iter().map(|a| quote(a)));
+        exec.push("%u".to_string());
+        let entry = format!(
+            "[Desktop Entry]\nType=Application\nName={scheme}\nExec={}\nMimeType={};\nNoDisplay=true\n",
+            exec.join(" "),
+            mime_type(scheme),
+        );
+        std::fs::write(dir.join(desktop_file_name(scheme)), entry).map_err(|e| e.to_string())?;
+
+        let status = Command::new("xdg-mime")
+            .args(["default", &desktop_file_name(scheme), &mime_type(scheme)])
+            .status()
+            .map_err(|e| format!("failed to run xdg-mime: {}", e))?;
+        if !status.success() {
+            return Err(format!("xdg-mime exited with {}", status));
+        }
+        Ok(())
+    }
+
+    /// Remove the desktop entry; the desktop falls back to other handlers.
+    pub fn unregister(scheme: &str, _path: &str, _args: &[String]) -> Result<(), String> {
+        let Some(file) = applications_dir().map(|d| d.join(desktop_file_name(scheme))) else {
+            return Ok(());
+        };
+        match std::fs::remove_file(file) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.to_string()),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn is_default(scheme: &str, _path: &str, _args: &[String]) -> bool {
+        Command::new("xdg-mime")
+            .args(["query", "default", &mime_type(scheme)])
+            .output()
+            .is_ok_and(|out| {
+                String::from_utf8_lossy(&out.stdout).trim() == desktop_file_name(scheme)
+            })
+    }
+}
+
+#[cfg(target_os = "linux")]
+pub use xdg::{is_default, register, unregister};
+
+/// Whether `scheme` is a valid URL scheme (RFC 3986).
+pub fn is_valid_scheme(scheme: &str) -> bool {
+    let mut chars = scheme.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+}
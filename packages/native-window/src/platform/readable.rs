@@ -0,0 +1,327 @@
+/// Reader-mode extraction, for `extractReadableContent()`.
+///
+/// A compact port of the scoring approach of Mozilla's Readability runs in
+/// an isolated script world, so page scripts can neither observe it nor
+/// tamper with the DOM APIs it uses: a CDP isolated world on WebView2, the
+/// default client `WKContentWorld` on macOS, and a named script world on
+/// WebKitGTK. The result is a JSON object `{ title, html, text }`.
+use wry::WebView;
+
+/// Name of the isolated world the extraction runs in.
+#[cfg(not(target_os = "macos"))]
+const WORLD_NAME: &str = "native-window-reader";
+
+/// Extract the main content of the page and return it as a JSON object.
+/// Paragraph-like blocks score their ancestors by text length and commas;
+/// the best ancestor, with link-heavy and boilerplate parts removed, and
+/// its qualifying siblings form the article.
+const READABILITY_SCRIPT: &str = r#"(function () {
+  var UNLIKELY = /banner|breadcrumb|combx|comment|community|cookie|disqus|footer|header|menu|modal|nav|popup|promo|related|remark|share|shoutbox|sidebar|social|sponsor|subscribe|ad-break|agegate|pagination/i;
+  var MAYBE = /and|article|body|column|content|main|shadow/i;
+  var POSITIVE = /article|body|content|entry|hentry|main|page|post|story|text|blog/i;
+  var NEGATIVE = /hidden|banner|combx|comment|contact|footer|footnote|masthead|media|meta|outbrain|promo|related|scroll|share|shoutbox|sidebar|skyscraper|sponsor|shopping|tags|tool|widget/i;
+  var REMOVE = "script,style,noscript,template,iframe,object,embed,form,button,input,select,textarea,nav,aside,footer,svg,canvas,link,meta";
+  var BLOCKS = "p,pre,td,blockquote,li,h2,h3";
+
+  function textOf(el) { return (el.textContent || "").replace(/\s+/g, " ").trim(); }
+  function classWeight(el) {
+    var names = (el.className && el.className.baseVal !== undefined ? el.className.baseVal : el.className || "") + " " + (el.id || "");
+    var weight = 0;
+    if (NEGATIVE.test(names)) weight -= 25;
+    if (POSITIVE.test(names)) weight += 25;
+    return weight;
+  }
+  function linkDensity(el) {
+    var length = textOf(el).length;
+    if (!length) return 0;
+    var links = 0;
+    el.querySelectorAll("a").forEach(function (a) { links += textOf(a).length; });
+    return links / length;
+  }
+  function tagScore(el) {
+    switch (el.tagName) {
+      case "ARTICLE": case "MAIN": return 10;
+      case "DIV": case "SECTION": return 5;
+      case "PRE": case "TD": case "BLOCKQUOTE": return 3;
+      case "OL": case "UL": case "DL": case "DD": case "DT": case "LI": case "FORM": return -3;
+      case "H1": case "H2": case "H3": case "H4": case "H5": case "H6": case "TH": return -5;
+      default: return 0;
+    }
+  }
+
+  function title() {
+    var meta = document.querySelector('meta[property="og:title"],meta[name="twitter:title"]');
+    var t = (meta && meta.getAttribute("content")) || document.title || "";
+    t = t.trim();
+    // Drop a trailing " | Site" or " - Site" when enough title remains.
+    var parts = t.split(/\s[|\-–—]\s/);
+    if (parts.length > 1 && parts[0].split(/\s+/).length >= 3) t = parts[0];
+    if (!t) {
+      var h1 = document.querySelector("h1");
+      t = h1 ? textOf(h1) : "";
+    }
+    return t;
+  }
+
+  var root = (document.body || document.documentElement).cloneNode(true);
+  root.querySelectorAll(REMOVE).forEach(function (el) { el.remove(); });
+  root.querySelectorAll("*").forEach(function (el) {
+    var names = (typeof el.className === "string" ? el.className : "") + " " + (el.id || "");
+    if (el.tagName !== "BODY" && el.tagName !== "ARTICLE" && el.tagName !== "MAIN" &&
+        UNLIKELY.test(names) && !MAYBE.test(names)) {
+      el.remove();
+    } else if (el.getAttribute("aria-hidden") === "true" || el.hidden ||
+               /display:\s*none|visibility:\s*hidden/i.test(el.getAttribute("style") || "")) {
+      el.remove();
+    }
+  });
+
+  var scores = new Map();
+  function addScore(el, points) {
+    if (!el || el === root.parentNode) return;
+    if (!scores.has(el)) scores.set(el, tagScore(el) + classWeight(el));
+    scores.set(el, scores.get(el) + points);
+  }
+  root.querySelectorAll(BLOCKS).forEach(function (block) {
+    var text = textOf(block);
+    if (text.length < 25) return;
+    var points = 1 + text.split(/[,،，]/).length - 1 + Math.min(Math.floor(text.length / 100), 3);
+    var parent = block.parentNode;
+    addScore(parent, points);
+    if (parent && parent !== root) {
+      addScore(parent.parentNode, points / 2);
+      if (parent.parentNode && parent.parentNode !== root) addScore(parent.parentNode.parentNode, points / 3);
+    }
+  });
+
+  var best = null;
+  var bestScore = 0;
+  scores.forEach(function (score, el) {
+    var adjusted = score * (1 - linkDensity(el));
+    scores.set(el, adjusted);
+    if (adjusted > bestScore) { best = el; bestScore = adjusted; }
+  });
+  if (!best) best = root;
+
+  var article = document.createElement("div");
+  var threshold = Math.max(10, bestScore * 0.2);
+  var siblings = best.parentNode && best !== root ? Array.prototype.slice.call(best.parentNode.children) : [best];
+  siblings.forEach(function (sibling) {
+    var keep = sibling === best || (scores.get(sibling) || 0) >= threshold;
+    if (!keep && sibling.tagName === "P") {
+      var text = textOf(sibling);
+      var density = linkDensity(sibling);
+      keep = (text.length > 80 && density < 0.25) || (text.length > 0 && density === 0 && /\.( |$)/.test(text));
+    }
+    if (keep) article.appendChild(sibling.cloneNode(true));
+  });
+
+  // Drop link lists and other boilerplate left inside the article.
+  article.querySelectorAll("div,section,ul,ol,table").forEach(function (el) {
+    if (!article.contains(el)) return;
+    var text = textOf(el);
+    var images = el.querySelectorAll("img").length;
+    if ((linkDensity(el) > 0.5 && text.length < 1000) || (text.length < 25 && images === 0 && !el.querySelector("pre,code"))) {
+      el.remove();
+    }
+  });
+  // Keep the markup plain and the links absolute.
+  article.querySelectorAll("*").forEach(function (el) {
+    Array.prototype.slice.call(el.attributes).forEach(function (attr) {
+      var name = attr.name.toLowerCase();
+      if (name === "href" || name === "src") {
+        try { el.setAttribute(name, new URL(attr.value, document.baseURI).href); } catch (e) { el.removeAttribute(name); }
+        if (/^\s*javascript:/i.test(el.getAttribute(name) || "")) el.removeAttribute(name);
+      } else if (name !== "alt" && name !== "title" && name !== "colspan" && name !== "rowspan") {
+        el.removeAttribute(attr.name);
+      }
+    });
+  });
+
+  // Plain text with a blank line between blocks.
+  var paragraphs = [];
+  article.querySelectorAll("h1,h2,h3,h4,h5,h6,p,pre,li,blockquote,td").forEach(function (el) {
+    if (el.querySelector("p,pre,li,blockquote")) return;
+    var text = el.tagName === "PRE" ? (el.textContent || "").trim() : textOf(el);
+    if (text) paragraphs.push(text);
+  });
+
+  return {
+    title: title(),
+    html: article.innerHTML.trim(),
+    text: paragraphs.length ? paragraphs.join("\n\n") : textOf(article)
+  };
+})()"#;
+
+/// Run the extraction on `webview`'s page. `on_result` receives the JSON
+/// object, or `None` if the script failed; it runs during a later pump.
+pub fn extract<F>(webview: &WebView, on_result: F) -> Result<(), String>
+where
+    F: FnOnce(Option<String>) + 'static,
+{
+    #[cfg(target_os = "windows")]
+    {
+        extract_windows(webview, on_result)
+    }
+    #[cfg(target_os = "macos")]
+    {
+        extract_macos(webview, on_result);
+        Ok(())
+    }
+    #[cfg(target_os = "linux")]
+    {
+        extract_linux(webview, on_result);
+        Ok(())
+    }
+    #[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
+    {
+        let _ = (webview, on_result);
+        Err("not supported on this platform".to_string())
+    }
+}
+
+// ── Windows ────────────────────────────────────────────────────
+
+/// `Page.getFrameTree` for the main frame's ID, `Page.createIsolatedWorld`
+/// in it, then `Runtime.evaluate` in the new world's context.
+#[cfg(target_os = "windows")]
+fn extract_windows<F>(webview: &WebView, on_result: F) -> Result<(), String>
+where
+    F: FnOnce(Option<String>) + 'static,
+{
+    use wry::WebViewExtWindows;
+
+    let core = webview.webview();
+    super::cdp::call_method(webview, "Page.getFrameTree", "{}", move |tree| {
+        // `{"frameTree":{"frame":{"id":"…",…`: the first ID is the main frame's.
+        let frame_id = tree.ok().and_then(|json| {
+            let rest = json.split_once("\"id\":\"")?.1;
+            Some(rest[..rest.find('"')?].to_string())
+        });
+        let Some(frame_id) = frame_id else {
+            on_result(None);
+            return;
+        };
+        let params = format!(
+            "{{\"frameId\":{},\"worldName\":\"{}\"}}",
+            crate::window_manager::json_escape(&frame_id),
+            WORLD_NAME
+        );
+        let evaluate_core = core.clone();
+        let result = super::cdp::call_core_method(
+            &core,
+            "Page.createIsolatedWorld",
+            &params,
+            move |world| {
+                // `{"executionContextId":N}`
+                let context_id = world.ok().and_then(|json| {
+                    let rest = json.split_once("\"executionContextId\":")?.1;
+                    let digits: String = rest.chars().take_while(|c| c.is_ascii_digit()).collect();
+                    digits.parse::<u64>().ok()
+                });
+                let Some(context_id) = context_id else {
+                    on_result(None);
+                    return;
+                };
+                let params = format!(
+                    "{{\"expression\":{},\"contextId\":{},\"returnByValue\":true}}",
+                    crate::window_manager::json_escape(READABILITY_SCRIPT),
+                    context_id
+                );
+                let result = super::cdp::call_core_method(
+                    &evaluate_core,
+                    "Runtime.evaluate",
+                    &params,
+                    move |evaluated| {
+                        // `{"result":{"type":"object","value":{…}}}`; exceptions have no value.
+                        let value = evaluated.ok().and_then(|json| {
+                            let start = json.find("\"value\":")? + "\"value\":".len();
+                            let end = json.strip_suffix("}}")?.len();
+                            (start < end).then(|| json[start..end].to_string())
+                        });
+                        on_result(value);
+                    },
+                );
+                if let Err(e) = result {
+                    eprintln!("[native-window] extractReadableContent failed: {}", e);
+                }
+            },
+        );
+        if let Err(e) = result {
+            eprintln!("[native-window] extractReadableContent failed: {}", e);
+        }
+    })
+    .map_err(|e| e.to_string())
+}
+
+// ── macOS ──────────────────────────────────────────────────────
+
+/// `evaluateJavaScript:inFrame:inContentWorld:completionHandler:` in the
+/// default client world (macOS 11+).
+#[cfg(target_os = "macos")]
+fn extract_macos<F>(webview: &WebView, on_result: F)
+where
+    F: FnOnce(Option<String>) + 'static,
+{
+    use std::cell::Cell;
+
+    use block2::RcBlock;
+    use objc2::msg_send;
+    use objc2::rc::Retained;
+    use objc2::runtime::{AnyClass, AnyObject};
+    use objc2_foundation::{NSError, NSString};
+    use wry::WebViewExtMacOS;
+
+    let Some(world_class) = AnyClass::get(c"WKContentWorld") else {
+        on_result(None);
+        return;
+    };
+    let webview = webview.webview();
+    let script = NSString::from_str(&format!("JSON.stringify({})", READABILITY_SCRIPT));
+    let on_result = Cell::new(Some(on_result));
+    let handler = RcBlock::new(move |result: *mut AnyObject, _error: *mut NSError| {
+        let json = unsafe { result.as_ref() }
+            .and_then(|result| result.downcast_ref::<NSString>())
+            .map(|json| json.to_string());
+        if let Some(on_result) = on_result.take() {
+            on_result(json);
+        }
+    });
+    unsafe {
+        let world: Retained<AnyObject> = msg_send![world_class, defaultClientWorld];
+        let _: () = msg_send![
+            &*webview,
+            evaluateJavaScript: &*script,
+            inFrame: std::ptr::null::<AnyObject>(),
+            inContentWorld: &*world,
+            completionHandler: &*handler
+        ];
+    }
+}
+
+// ── Linux ──────────────────────────────────────────────────────
+
+#[cfg(target_os = "linux")]
+fn extract_linux<F>(webview: &WebView, on_result: F)
+where
+    F: FnOnce(Option<String>) + 'static,
+{
+    use javascriptcore::ValueExt;
+    use webkit2gtk::WebViewExt;
+    use wry::WebViewExtUnix;
+
+    let script = format!("JSON.stringify({})", READABILITY_SCRIPT);
+    webview.webview().run_javascript_in_world(
+        &script,
+        WORLD_NAME,
+        None::<&webkit2gtk::gio::Cancellable>,
+        move |result| {
+            let json = result
+                .ok()
+                .and_then(|result| result.js_value())
+                .filter(|value| value.is_string())
+                .map(|value| value.to_str().to_string());
+            on_result(json);
+        },
+    );
+}
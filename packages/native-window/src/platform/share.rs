@@ -0,0 +1,138 @@
+/// Native sharing for `share()`.
+///
+/// macOS shows an `NSSharingServicePicker` anchored to the middle of the
+/// window. Windows shows the Share UI through `DataTransferManager`, which
+/// asks for the data with a `DataRequested` event once the user opens it.
+/// Not supported on Linux.
+use crate::options::ShareOptions;
+
+/// Show the share sheet for `window`.
+#[cfg(target_os = "macos")]
+pub fn show(window: &tao::window::Window, options: &ShareOptions) -> Result<(), String> {
+    use objc2::rc::Retained;
+    use objc2::runtime::AnyObject;
+    use objc2_app_kit::{NSSharingServicePicker, NSWindow};
+    use objc2_foundation::{NSArray, NSPoint, NSRect, NSRectEdge, NSSize, NSString, NSURL};
+    use tao::platform::macos::WindowExtMacOS;
+
+    let ns_window = window.ns_window() as *const NSWindow;
+    let view = unsafe { ns_window.as_ref() }
+        .and_then(|w| w.contentView())
+        .ok_or("window has no content view")?;
+
+    // The picker takes pasteboard items; there is no item for a title.
+    let mut items: Vec<Retained<AnyObject>> = Vec::new();
+    if let Some(text) = &options.text {
+        items.push(Retained::into_super(Retained::into_super(
+            NSString::from_str(text),
+        )));
+    }
+    if let Some(url) = &options.url {
+        let url = NSURL::URLWithString(&NSString::from_str(url))
+            .ok_or_else(|| format!("invalid URL: {}", url))?;
+        items.push(Retained::into_super(Retained::into_super(url)));
+    }
+    for path in options.files.iter().flatten() {
+        let url = NSURL::fileURLWithPath(&NSString::from_str(path));
+        items.push(Retained::into_super(Retained::into_super(url)));
+    }
+
+    let items = NSArray::from_retained_slice(&items);
+    let picker =
+        unsafe { NSSharingServicePicker::initWithItems(NSSharingServicePicker::alloc(), &items) };
+    let bounds = view.bounds();
+    let anchor = NSRect::new(
+        NSPoint::new(bounds.size.width / 2.0, bounds.size.height / 2.0),
+        NSSize::new(1.0, 1.0),
+    );
+    picker.showRelativeToRect_ofView_preferredEdge(anchor, &view, NSRectEdge::MinY);
+    Ok(())
+}
+
+#[cfg(target_os = "windows")]
+pub use data_transfer::{remove, show};
+
+#[cfg(target_os = "windows")]
+mod data_transfer {
+    use std::cell::RefCell;
+    use std::collections::HashMap;
+
+    use tao::platform::windows::WindowExtWindows;
+    use windows::core::{factory, Interface, Ref, HSTRING};
+    use windows::ApplicationModel::DataTransfer::{DataRequestedEventArgs, DataTransferManager};
+    use windows::Foundation::{TypedEventHandler, Uri};
+    use windows::Storage::{IStorageItem, StorageFile};
+    use windows::Win32::Foundation::HWND;
+    use windows::Win32::UI::Shell::IDataTransferManagerInterop;
+    use windows_collections::IIterable;
+
+    use crate::options::ShareOptions;
+
+    thread_local! {
+        /// The `DataRequested` registration of each window that has shared,
+        /// by window ID. Replaced on every `share()`.
+        static HANDLERS: RefCell<HashMap<u32, (DataTransferManager, i64)>> =
+            RefCell::new(HashMap::new());
+    }
+
+    /// Show the Share UI for window `id`.
+    pub fn show(
+        id: u32,
+        window: &tao::window::Window,
+        options: &ShareOptions,
+    ) -> Result<(), String> {
+        let hwnd = HWND(window.hwnd() as _);
+        let interop = factory::<DataTransferManager, IDataTransferManagerInterop>()
+            .map_err(|e| e.to_string())?;
+        let manager: DataTransferManager =
+            unsafe { interop.GetForWindow(hwnd) }.map_err(|e| e.to_string())?;
+
+        // The handler must be Send, so it captures the options rather than
+        // storage items; files are resolved when the data is requested.
+        let options = options.clone();
+        let handler = TypedEventHandler::new(
+            move |_, args: Ref<DataRequestedEventArgs>| -> windows::core::Result<()> {
+                let request = args.ok()?.Request()?;
+                let data = request.Data()?;
+                // The Share UI refuses data without a title.
+                let title = options
+                    .title
+                    .as_deref()
+                    .or(options.url.as_deref())
+                    .unwrap_or(" ");
+                data.Properties()?.SetTitle(&HSTRING::from(title))?;
+                if let Some(text) = &options.text {
+                    data.SetText(&HSTRING::from(text))?;
+                }
+                if let Some(url) = &options.url {
+                    data.SetWebLink(&Uri::CreateUri(&HSTRING::from(url))?)?;
+                }
+                if let Some(files) = options.files.as_ref().filter(|f| !f.is_empty()) {
+                    let mut items = Vec::with_capacity(files.len());
+                    for path in files {
+                        let file =
+                            StorageFile::GetFileFromPathAsync(&HSTRING::from(path))?.get()?;
+                        items.push(Some(file.cast::<IStorageItem>()?));
+                    }
+                    data.SetStorageItemsReadOnly(&IIterable::<IStorageItem>::from(items))?;
+                }
+                Ok(())
+            },
+        );
+        let token = manager.DataRequested(&handler).map_err(|e| e.to_string())?;
+        if let Some((previous, token)) =
+            HANDLERS.with(|h| h.borrow_mut().insert(id, (manager, token)))
+        {
+            let _ = previous.RemoveDataRequested(token);
+        }
+
+        unsafe { interop.ShowShareUIForWindow(hwnd) }.map_err(|e| e.to_string())
+    }
+
+    /// Drop the `DataRequested` registration of a closed window.
+    pub fn remove(id: u32) {
+        if let Some((manager, token)) = HANDLERS.with(|h| h.borrow_mut().remove(&id)) {
+            let _ = manager.RemoveDataRequested(token);
+        }
+    }
+}
@@ -0,0 +1,107 @@
+/// Relative z-order between the app's windows, for `moveAbove()` and
+/// `moveBelow()`.
+///
+/// Tool palettes and pickers need to sit just above (or below) another
+/// window of the app without going always-on-top. Windows inserts the
+/// window next to the other in the z-order with `SetWindowPos`, macOS uses
+/// `orderWindow:relativeTo:`, and Linux restacks the GDK window, which
+/// X11 window managers honor and Wayland ignores. Windows stay on their
+/// own level, so a normal window never rises above an always-on-top one.
+use tao::window::Window;
+
+/// Place `window` directly above `other` (`above`), or directly below it.
+/// Hidden windows stay hidden.
+pub fn move_relative(window: &Window, other: &Window, above: bool) -> Result<(), String> {
+    #[cfg(target_os = "windows")]
+    {
+        use tao::platform::windows::WindowExtWindows;
+        move_relative_windows(window.hwnd(), other.hwnd(), above).map_err(|e| e.to_string())
+    }
+    #[cfg(target_os = "macos")]
+    {
+        move_relative_macos(window, other, above);
+        Ok(())
+    }
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    {
+        move_relative_linux(window, other, above);
+        Ok(())
+    }
+}
+
+// ── Windows ────────────────────────────────────────────────────
+
+#[cfg(target_os = "windows")]
+fn move_relative_windows(hwnd: isize, other: isize, above: bool) -> windows::core::Result<()> {
+    use windows::Win32::Foundation::HWND;
+    use windows::Win32::UI::WindowsAndMessaging::{
+        GetWindow, SetWindowPos, GW_HWNDPREV, SWP_NOACTIVATE, SWP_NOMOVE, SWP_NOSIZE,
+    };
+
+    let hwnd = HWND(hwnd as _);
+    let other = HWND(other as _);
+    // `SetWindowPos` places a window *after* (below) another, so going
+    // above `other` means going below the window just above it, or to the
+    // top when there is none.
+    let insert_after = if above {
+        match unsafe { GetWindow(other, GW_HWNDPREV) } {
+            Ok(prev) if prev == hwnd => return Ok(()),
+            Ok(prev) => Some(prev),
+            Err(_) => None, // HWND_TOP
+        }
+    } else {
+        Some(other)
+    };
+    unsafe {
+        SetWindowPos(
+            hwnd,
+            insert_after,
+            0,
+            0,
+            0,
+            0,
+            SWP_NOMOVE | SWP_NOSIZE | SWP_NOACTIVATE,
+        )
+    }
+}
+
+// ── macOS ──────────────────────────────────────────────────────
+
+#[cfg(target_os = "macos")]
+fn move_relative_macos(window: &Window, other: &Window, above: bool) {
+    use objc2_app_kit::{NSWindow, NSWindowOrderingMode};
+    use tao::platform::macos::WindowExtMacOS;
+
+    let (Some(ns_window), Some(other)) = (
+        unsafe { (window.ns_window() as *const NSWindow).as_ref() },
+        unsafe { (other.ns_window() as *const NSWindow).as_ref() },
+    ) else {
+        return;
+    };
+    // Ordering a window in would also show it.
+    if !ns_window.isVisible() {
+        return;
+    }
+    let place = if above {
+        NSWindowOrderingMode::Above
+    } else {
+        NSWindowOrderingMode::Below
+    };
+    ns_window.orderWindow_relativeTo(place, other.windowNumber());
+}
+
+// ── Linux ──────────────────────────────────────────────────────
+
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+fn move_relative_linux(window: &Window, other: &Window, above: bool) {
+    use gtk::prelude::*;
+    use tao::platform::unix::WindowExtUnix;
+
+    // Unrealized (never shown) windows have no GDK window to restack.
+    let (Some(gdk_window), Some(other)) =
+        (window.gtk_window().window(), other.gtk_window().window())
+    else {
+        return;
+    };
+    gdk_window.restack(Some(&other), above);
+}
@@ -0,0 +1,54 @@
+/// Language of the webview engine's own UI, for `init({ uiLanguage })`.
+///
+/// Context menus, authentication prompts and the PDF viewer are localized
+/// by the engine, which follows the OS language unless told otherwise.
+/// WebView2 takes the language as an environment option, so it must be set
+/// before the shared environment is created. WebKit on macOS follows the
+/// `AppleLanguages` default, which is overridden for this process only.
+/// WebKitGTK reads the process locale and is left alone.
+
+/// Use `language` (a BCP 47 tag such as `"de"` or `"pt-BR"`) for engine UI.
+pub fn set_ui_language(language: &str) -> Result<(), String> {
+    let valid = !language.is_empty()
+        && language
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if !valid {
+        return Err(format!("Invalid uiLanguage '{}'", language));
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        super::webview2_env::set_language(language.to_string())
+    }
+    #[cfg(target_os = "macos")]
+    {
+        set_ui_language_macos(language);
+        Ok(())
+    }
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    {
+        Ok(())
+    }
+}
+
+// ── macOS ──────────────────────────────────────────────────────
+
+/// Put `AppleLanguages` in the argument domain, as the `-AppleLanguages`
+/// launch argument would. The domain is volatile, so nothing is written
+/// to the app's preferences.
+#[cfg(target_os = "macos")]
+fn set_ui_language_macos(language: &str) {
+    use objc2::runtime::AnyObject;
+    use objc2_foundation::{
+        ns_string, NSArgumentDomain, NSArray, NSMutableDictionary, NSString, NSUserDefaults,
+    };
+
+    let defaults = NSUserDefaults::standardUserDefaults();
+    let domain_name = unsafe { NSArgumentDomain };
+    let domain: objc2::rc::Retained<NSMutableDictionary<NSString, AnyObject>> =
+        NSMutableDictionary::dictionaryWithDictionary(&defaults.volatileDomainForName(domain_name));
+    let languages = NSArray::from_retained_slice(&[NSString::from_str(language)]);
+    domain.insert(ns_string!("AppleLanguages"), &**languages);
+    unsafe { defaults.setVolatileDomain_forName(&domain, domain_name) };
+}
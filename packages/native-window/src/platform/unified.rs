@@ -3,13 +3,17 @@
 /// Replaces the platform-specific `macos.rs` and `windows.rs` modules with a
 /// single implementation that works on macOS, Windows, and Linux.
 use std::borrow::Cow;
-use std::collections::HashMap;
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
 
-use tao::dpi::{LogicalPosition, LogicalSize};
-use tao::event::{Event, WindowEvent};
+use napi::bindgen_prelude::Either;
+use tao::dpi::{LogicalPosition, LogicalSize, PhysicalPosition};
+use tao::event::{DeviceEvent, ElementState, Event, WindowEvent};
 use tao::event_loop::{ControlFlow, EventLoop};
+use tao::monitor::MonitorHandle;
 use tao::platform::run_return::EventLoopExtRunReturn;
-use tao::window::{Window, WindowBuilder};
+use tao::window::{Theme, UserAttentionType, Window, WindowBuilder};
 
 #[cfg(target_os = "linux")]
 use wry::WebViewBuilderExtUnix;
@@ -18,20 +22,43 @@ use wry::WebViewBuilderExtWindows;
 use wry::{WebView, WebViewBuilder};
 
 use crate::events::WindowEventHandlers;
-use crate::options::WindowOptions;
+use crate::options::{Point, Rect, WindowOptions, WindowState};
 use crate::window_manager::{
-    is_host_allowed, is_origin_trusted, json_escape, Command, EVENT_LOOP, MAX_PENDING_EVENTS,
-    PENDING_BLURS, PENDING_CLOSES, PENDING_COOKIES, PENDING_FOCUSES, PENDING_MESSAGES,
-    PENDING_MOVES, PENDING_NAVIGATION_BLOCKED, PENDING_PAGE_LOADS, PENDING_RESIZE_CALLBACKS,
-    PENDING_TITLE_CHANGES,
+    is_event_enabled, is_host_allowed, is_hostless, is_origin_trusted, is_scheme_allowed,
+    json_escape, Command, CLOSE_INTERCEPTED, EVENT_BLUR, EVENT_FOCUS, EVENT_LOOP, EVENT_MESSAGE,
+    EVENT_MOVE, EVENT_RESIZE, EVENT_TITLE_CHANGED, LAST_GOOD_BOUNDS, MAX_PENDING_EVENTS,
+    MINIMIZE_TO_TRAY, PENDING_ACCESSIBILITY_SNAPSHOTS, PENDING_AUDIO_CAPTURES, PENDING_BLURS,
+    PENDING_BOUNDS, PENDING_CLOSES, PENDING_CLOSE_REQUESTS, PENDING_COOKIES,
+    PENDING_FALLBACKS_SHOWN, PENDING_FOCUSES, PENDING_FRAMES, PENDING_GLOBAL_MOUSE_MOVES,
+    PENDING_HEAP_SNAPSHOTS, PENDING_MATCH_COUNTS, PENDING_MESSAGES, PENDING_MOVES,
+    PENDING_MOVE_ENDS, PENDING_NAVIGATION_BLOCKED, PENDING_NAVIGATION_THROTTLED,
+    PENDING_NETWORK_CAPTURES, PENDING_PAGE_CAPTURES, PENDING_PAGE_LOADS,
+    PENDING_PERFORMANCE_METRICS, PENDING_READABLE_CONTENTS, PENDING_READY_TO_SHOW, PENDING_RESCUES,
+    PENDING_RESIZE_CALLBACKS, PENDING_RESIZE_ENDS, PENDING_SCALE_FACTOR_CHANGES,
+    PENDING_THEME_CHANGES, PENDING_THUMBNAILS, PENDING_TITLE_CHANGES, PENDING_TRACES,
+    PENDING_TRAY_RESTORES, PENDING_WINDOW_CAPTURES, PENDING_WINDOW_STATES,
+    PENDING_WORKSPACE_CHANGES,
 };
 
+#[cfg(target_os = "windows")]
+use super::webview2_env;
+use super::PlatformBackend;
+
 /// Maximum IPC message size (10 MB).
 const MAX_MESSAGE_SIZE: usize = 10 * 1024 * 1024;
 
 /// Maximum pending IPC messages per window before new messages are dropped.
 const MAX_PENDING_MESSAGES_PER_WINDOW: usize = 10_000;
 
+thread_local! {
+    /// Windows whose page pressed the mouse on a `data-native-drag-region`
+    /// element during this pump; dragged once the pump ends.
+    static DRAG_REQUESTS: RefCell<Vec<u32>> = const { RefCell::new(Vec::new()) };
+    /// Document titles reported during this pump; applied to windows with
+    /// a `setTitleTemplate()` once the pump ends.
+    static DOCUMENT_TITLES: RefCell<Vec<(u32, String)>> = const { RefCell::new(Vec::new()) };
+}
+
 /// Push an item to a thread-local pending buffer, enforcing MAX_PENDING_EVENTS.
 /// Silently drops the item (with a one-time warning) if the buffer is full.
 macro_rules! capped_push {
@@ -95,6 +122,8 @@ fn load_icon_from_path(path: &str) -> napi::Result<tao::window::Icon> {
 struct WindowEntry {
     window: Window,
     webview: WebView,
+    /// Use borderless (non-Space) fullscreen on macOS.
+    borderless_fullscreen: bool,
 }
 
 /// Unified platform state backed by tao + wry.
@@ -102,6 +131,201 @@ pub struct Platform {
     windows: HashMap<u32, WindowEntry>,
     /// Reverse map: tao WindowId → our u32 window ID.
     window_id_map: HashMap<tao::window::WindowId, u32>,
+    /// Anchored popups: popup window ID → anchor window ID
+    /// (`None` for popups anchored to a screen position).
+    popups: HashMap<u32, Option<u32>>,
+    /// Owned windows: child window ID → owner window ID (`parentId`).
+    owners: HashMap<u32, u32>,
+    /// When windows were last checked for being off-screen.
+    last_rescue_check: Instant,
+    /// Time zone / locale / network overrides per window, re-applied after
+    /// each page load. WebView2 keeps CDP emulation across navigations by itself.
+    #[cfg(not(target_os = "windows"))]
+    emulation: HashMap<u32, Emulation>,
+    /// Windows set to `setPriority("background")`. Re-applied after each
+    /// page load, as a navigation can move the page to a new process.
+    background_windows: HashSet<u32>,
+    /// Windows whose webview had keyboard focus at the last pump. WebKit
+    /// has no focus notification on macOS, so focus is polled.
+    #[cfg(target_os = "macos")]
+    webview_focus: HashSet<u32>,
+    /// Windows with an `onGlobalMouseMove()` handler.
+    mouse_tracking: HashMap<u32, MouseTracking>,
+    /// Resizes and moves not yet reported as ended, by window.
+    geometry_changes: HashMap<u32, GeometryChange>,
+    /// `setTitleTemplate()` templates, by window.
+    title_templates: HashMap<u32, String>,
+    /// Latest document title of each window, for templates set later.
+    document_titles: HashMap<u32, String>,
+    /// Whether each window was on the active Space / virtual desktop at the
+    /// last poll. Neither OS notifies per window, so it is polled.
+    #[cfg(any(target_os = "macos", target_os = "windows"))]
+    active_space: HashMap<u32, bool>,
+    /// Virtual desktop queries go through Explorer, so they are throttled.
+    #[cfg(target_os = "windows")]
+    last_desktop_poll: Instant,
+    /// Theme of each window at the last poll. tao reports theme changes
+    /// on macOS and Windows only, so Linux polls the GTK theme.
+    #[cfg(target_os = "linux")]
+    themes: HashMap<u32, Theme>,
+    #[cfg(target_os = "linux")]
+    last_theme_poll: Instant,
+    /// Active `startNetworkCapture()` recordings.
+    network_captures: HashMap<u32, NetworkCapture>,
+    /// `startTracing()` sessions, kept until the trace completes or the
+    /// window is destroyed.
+    #[cfg(target_os = "windows")]
+    traces: HashMap<u32, TraceSession>,
+    /// `startFrameCapture()` screencasts, as their `Page.screencastFrame`
+    /// subscriptions.
+    #[cfg(target_os = "windows")]
+    frame_captures: HashMap<u32, super::cdp::Subscription>,
+    /// Windows waiting for the shared WebView2 environment, in creation
+    /// order. Their native window does not exist yet.
+    #[cfg(target_os = "windows")]
+    pending_windows: Vec<PendingWindow>,
+}
+
+/// Emulated settings for a window (`None` = host default).
+#[cfg(not(target_os = "windows"))]
+#[derive(Default)]
+struct Emulation {
+    timezone: Option<String>,
+    locale: Option<String>,
+    network: Option<crate::options::NetworkConditions>,
+}
+
+/// Most CDP network events kept per capture; later events are dropped.
+#[cfg(target_os = "windows")]
+const MAX_CAPTURE_EVENTS: usize = 100_000;
+
+/// CDP network events recorded for HAR export.
+#[cfg(target_os = "windows")]
+const CAPTURED_NETWORK_EVENTS: [&str; 4] = [
+    "Network.requestWillBeSent",
+    "Network.responseReceived",
+    "Network.loadingFinished",
+    "Network.loadingFailed",
+];
+
+/// A `startNetworkCapture()` recording.
+struct NetworkCapture {
+    /// Wall-clock start, in milliseconds since the Unix epoch.
+    started_ms: f64,
+    /// Recorded CDP events as `{"method":…,"params":…}` JSON objects.
+    #[cfg(target_os = "windows")]
+    events: std::rc::Rc<std::cell::RefCell<Vec<String>>>,
+    #[cfg(target_os = "windows")]
+    _subscriptions: Vec<super::cdp::Subscription>,
+}
+
+/// Cursor polling for an `onGlobalMouseMove()` handler.
+struct MouseTracking {
+    /// The handler's `throttleMs`.
+    interval: Duration,
+    last_poll: Option<Instant>,
+    /// Last reported position, so an idle cursor is not reported again.
+    position: Option<(f64, f64)>,
+}
+
+/// A resize or move awaiting `onResizeEnd()` / `onMoveEnd()`.
+struct GeometryChange {
+    resized: bool,
+    moved: bool,
+    last_change: Instant,
+}
+
+/// A `startTracing()` session.
+#[cfg(target_os = "windows")]
+struct TraceSession {
+    /// Whether `Tracing.end` has been sent.
+    stopping: bool,
+    _subscriptions: [super::cdp::Subscription; 2],
+}
+
+/// A window whose creation is deferred until the WebView2 environment is
+/// ready, together with the commands sent to it in the meantime.
+#[cfg(target_os = "windows")]
+struct PendingWindow {
+    id: u32,
+    options: WindowOptions,
+    queued: Vec<Command>,
+}
+
+// ── Backend ────────────────────────────────────────────────────
+
+impl PlatformBackend for Platform {
+    fn process_command(
+        &mut self,
+        cmd: Command,
+        event_handlers: &mut HashMap<u32, WindowEventHandlers>,
+    ) -> napi::Result<()> {
+        Platform::process_command(self, cmd, event_handlers)
+    }
+
+    fn pump_events(&mut self) {
+        Platform::pump_events(self)
+    }
+
+    fn finish_pump(
+        &mut self,
+        event_handlers: &mut HashMap<u32, WindowEventHandlers>,
+    ) -> napi::Result<()> {
+        // Windows: build windows whose WebView2 environment became ready
+        // while pumping, then replay the commands queued for them.
+        #[cfg(target_os = "windows")]
+        let result = self.create_pending_windows(event_handlers);
+        #[cfg(not(target_os = "windows"))]
+        let result = {
+            let _ = event_handlers;
+            Ok(())
+        };
+
+        // Destroy native resources for windows that received OS-initiated
+        // CloseRequested.  This ensures tao::Window and wry::WebView are
+        // properly dropped before the JS on_close callback fires — an
+        // abrupt process.exit() in the callback would otherwise leave live
+        // native objects whose teardown fails on Windows.
+        self.destroy_pending_closes();
+        result
+    }
+
+    fn has_window(&self, id: u32) -> bool {
+        self.windows.contains_key(&id)
+    }
+
+    fn bounds(&self, id: u32) -> Option<Rect> {
+        Platform::bounds(self, id)
+    }
+
+    fn window_state(&self, id: u32) -> Option<WindowState> {
+        Platform::window_state(self, id)
+    }
+
+    fn scale_factor(&self, id: u32) -> Option<f64> {
+        self.windows
+            .get(&id)
+            .map(|entry| entry.window.scale_factor())
+    }
+
+    fn client_origin(&self, id: u32) -> Option<Point> {
+        self.windows.get(&id).map(|entry| {
+            let scale = entry.window.scale_factor();
+            let origin = entry
+                .window
+                .inner_position()
+                .unwrap_or_default()
+                .to_logical::<f64>(scale);
+            Point {
+                x: origin.x,
+                y: origin.y,
+            }
+        })
+    }
+
+    fn shutdown(&mut self) {
+        Platform::shutdown(self)
+    }
 }
 
 // ── Platform initialization ────────────────────────────────────
@@ -122,9 +346,78 @@ impl Platform {
         Ok(Self {
             windows: HashMap::new(),
             window_id_map: HashMap::new(),
+            popups: HashMap::new(),
+            owners: HashMap::new(),
+            last_rescue_check: Instant::now(),
+            #[cfg(not(target_os = "windows"))]
+            emulation: HashMap::new(),
+            background_windows: HashSet::new(),
+            #[cfg(target_os = "macos")]
+            webview_focus: HashSet::new(),
+            mouse_tracking: HashMap::new(),
+            geometry_changes: HashMap::new(),
+            title_templates: HashMap::new(),
+            document_titles: HashMap::new(),
+            #[cfg(any(target_os = "macos", target_os = "windows"))]
+            active_space: HashMap::new(),
+            #[cfg(target_os = "windows")]
+            last_desktop_poll: Instant::now(),
+            #[cfg(target_os = "linux")]
+            themes: HashMap::new(),
+            #[cfg(target_os = "linux")]
+            last_theme_poll: Instant::now(),
+            network_captures: HashMap::new(),
+            #[cfg(target_os = "windows")]
+            traces: HashMap::new(),
+            #[cfg(target_os = "windows")]
+            frame_captures: HashMap::new(),
+            #[cfg(target_os = "windows")]
+            pending_windows: Vec::new(),
+        })
+    }
+
+    /// Outer position and inner size in logical pixels — the spaces of
+    /// setPosition() / setSize(). Wayland reports no position.
+    fn bounds(&self, id: u32) -> Option<Rect> {
+        let entry = self.windows.get(&id)?;
+        let scale = entry.window.scale_factor();
+        let position = entry
+            .window
+            .outer_position()
+            .unwrap_or_default()
+            .to_logical::<f64>(scale);
+        let size = entry.window.inner_size().to_logical::<f64>(scale);
+        Some(Rect {
+            x: position.x,
+            y: position.y,
+            width: size.width,
+            height: size.height,
         })
     }
 
+    fn window_state(&self, id: u32) -> Option<WindowState> {
+        let window = &self.windows.get(&id)?.window;
+        Some(WindowState {
+            maximized: window.is_maximized(),
+            minimized: window.is_minimized(),
+            visible: window.is_visible(),
+            focused: window.is_focused(),
+            on_active_workspace: is_on_active_workspace(window),
+            visible_on_all_workspaces: is_visible_on_all_workspaces(window),
+        })
+    }
+
+    /// Place window `id` directly above or below window `other_id`. A no-op
+    /// if either window is gone.
+    fn move_relative(&self, id: u32, other_id: u32, above: bool) -> napi::Result<()> {
+        if let (Some(entry), Some(other)) = (self.windows.get(&id), self.windows.get(&other_id)) {
+            super::stacking::move_relative(&entry.window, &other.window, above).map_err(|e| {
+                napi::Error::from_reason(format!("Failed to reorder window: {}", e))
+            })?;
+        }
+        Ok(())
+    }
+
     // ── Command processing ─────────────────────────────────────
 
     /// Process a single command from the command queue.
@@ -133,6 +426,11 @@ impl Platform {
         cmd: Command,
         _event_handlers: &mut HashMap<u32, WindowEventHandlers>,
     ) -> napi::Result<()> {
+        #[cfg(target_os = "windows")]
+        let Some(cmd) = self.defer_until_environment_ready(cmd) else {
+            return Ok(());
+        };
+
         match cmd {
             Command::CreateWindow { id, options } => {
                 self.create_window(id, &options)?;
@@ -143,18 +441,23 @@ impl Platform {
                         .webview
                         .load_url(&url)
                         .map_err(|e| napi::Error::from_reason(format!("load_url failed: {}", e)))?;
-                    // Clear any stored HTML to prevent stale custom protocol responses
-                    crate::window_manager::remove_html_content(id);
+                    // Clear any stored HTML to prevent stale custom protocol
+                    // responses, unless the URL is one of the stored documents.
+                    if !url.starts_with(custom_protocol_url()) && !url.starts_with("nativewindow:")
+                    {
+                        crate::window_manager::remove_html_content(id);
+                    }
                 }
             }
-            Command::LoadHTML { id, html } => {
+            Command::LoadHTML { id, html, path } => {
                 if let Some(entry) = self.windows.get(&id) {
                     // Store HTML for the custom protocol handler, then navigate
                     // to the custom protocol URL which triggers the handler.
                     // This gives the page a proper origin (secure context) and
                     // makes Cmd+R / browser-native reload work correctly.
-                    crate::window_manager::set_html_content(id, html);
-                    entry.webview.load_url(custom_protocol_url()).map_err(|e| {
+                    crate::window_manager::set_html_content(id, &path, html);
+                    let url = format!("{}{}", custom_protocol_url(), path.trim_start_matches('/'));
+                    entry.webview.load_url(&url).map_err(|e| {
                         napi::Error::from_reason(format!("load_url (html) failed: {}", e))
                     })?;
                 }
@@ -164,11 +467,52 @@ impl Platform {
                     let _ = entry.webview.evaluate_script(&script);
                 }
             }
+            Command::EvaluateJSWithUserGesture { id, script } => {
+                if let Some(entry) = self.windows.get(&id) {
+                    // WebView2 runs ExecuteScript without user activation;
+                    // CDP Runtime.evaluate can grant it.
+                    #[cfg(target_os = "windows")]
+                    {
+                        let params = format!(
+                            r#"{{"expression":{},"userGesture":true}}"#,
+                            crate::window_manager::json_escape(&script)
+                        );
+                        if let Err(e) =
+                            super::cdp::send(&entry.webview, "Runtime.evaluate", &params)
+                        {
+                            eprintln!("[native-window] Window {}: {}", id, e);
+                        }
+                    }
+                    // WKWebView and WebKitGTK already evaluate app scripts
+                    // as if triggered by a user gesture.
+                    #[cfg(not(target_os = "windows"))]
+                    {
+                        let _ = entry.webview.evaluate_script(&script);
+                    }
+                }
+            }
             Command::SetTitle { id, title } => {
                 if let Some(entry) = self.windows.get(&id) {
                     entry.window.set_title(&title);
                 }
             }
+            Command::SetTitleTemplate { id, template } => {
+                if let Some(entry) = self.windows.get(&id) {
+                    match template {
+                        Some(template) => {
+                            let page_title =
+                                self.document_titles.get(&id).map_or("", String::as_str);
+                            entry
+                                .window
+                                .set_title(&apply_title_template(&template, page_title));
+                            self.title_templates.insert(id, template);
+                        }
+                        None => {
+                            self.title_templates.remove(&id);
+                        }
+                    }
+                }
+            }
             Command::SetSize { id, width, height } => {
                 if let Some(entry) = self.windows.get(&id) {
                     let _ = entry.window.set_inner_size(LogicalSize::new(width, height));
@@ -188,16 +532,65 @@ impl Platform {
                         .set_max_inner_size(Some(LogicalSize::new(width, height)));
                 }
             }
+            Command::SetAspectRatio { id, ratio } => {
+                if let Some(entry) = self.windows.get(&id) {
+                    super::aspect_ratio::set_aspect_ratio(&entry.window, ratio);
+                }
+            }
             Command::SetPosition { id, x, y } => {
                 if let Some(entry) = self.windows.get(&id) {
                     entry.window.set_outer_position(LogicalPosition::new(x, y));
                 }
             }
+            Command::SnapTo {
+                id,
+                position,
+                display,
+            } => {
+                if let Some(entry) = self.windows.get(&id) {
+                    let monitor = super::display::resolve_monitor(&entry.window, display)?;
+                    let work = super::display::work_area(&monitor);
+                    if let Some(rect) = super::display::snap_rect(work, &position) {
+                        super::display::set_outer_bounds(&entry.window, rect);
+                    }
+                }
+            }
+            Command::Center { id, display } => {
+                if let Some(entry) = self.windows.get(&id) {
+                    let monitor = super::display::resolve_monitor(&entry.window, display)?;
+                    let work = super::display::work_area(&monitor);
+                    let outer = entry.window.outer_size();
+                    let position =
+                        super::display::centered(work, outer.width as i32, outer.height as i32);
+                    entry.window.set_outer_position(position);
+                }
+            }
+            Command::MoveToDisplay { id, display } => {
+                if let Some(entry) = self.windows.get(&id) {
+                    let monitor = super::display::resolve_monitor(&entry.window, Some(display))?;
+                    move_to_display(&entry.window, &monitor)?;
+                }
+            }
             Command::SetResizable { id, resizable } => {
                 if let Some(entry) = self.windows.get(&id) {
                     entry.window.set_resizable(resizable);
                 }
             }
+            Command::SetClosable { id, closable } => {
+                if let Some(entry) = self.windows.get(&id) {
+                    entry.window.set_closable(closable);
+                }
+            }
+            Command::SetMinimizable { id, minimizable } => {
+                if let Some(entry) = self.windows.get(&id) {
+                    entry.window.set_minimizable(minimizable);
+                }
+            }
+            Command::SetMaximizable { id, maximizable } => {
+                if let Some(entry) = self.windows.get(&id) {
+                    entry.window.set_maximizable(maximizable);
+                }
+            }
             Command::SetDecorations { id, decorations } => {
                 if let Some(entry) = self.windows.get(&id) {
                     entry.window.set_decorations(decorations);
@@ -205,9 +598,126 @@ impl Platform {
             }
             Command::SetAlwaysOnTop { id, always_on_top } => {
                 if let Some(entry) = self.windows.get(&id) {
+                    // The two levels are exclusive; turning one on clears the other.
+                    if always_on_top {
+                        entry.window.set_always_on_bottom(false);
+                    }
                     entry.window.set_always_on_top(always_on_top);
                 }
             }
+            Command::SetAlwaysOnBottom {
+                id,
+                always_on_bottom,
+            } => {
+                if let Some(entry) = self.windows.get(&id) {
+                    if always_on_bottom {
+                        entry.window.set_always_on_top(false);
+                    }
+                    entry.window.set_always_on_bottom(always_on_bottom);
+                }
+            }
+            Command::MoveAbove { id, other_id } => self.move_relative(id, other_id, true)?,
+            Command::MoveBelow { id, other_id } => self.move_relative(id, other_id, false)?,
+            Command::SetSkipTaskbar { id, skip } => {
+                if let Some(entry) = self.windows.get(&id) {
+                    set_skip_taskbar(&entry.window, skip);
+                }
+            }
+            Command::SetVisibleOnAllWorkspaces { id, visible } => {
+                if let Some(entry) = self.windows.get(&id) {
+                    // canJoinAllSpaces on macOS, sticky on Linux.
+                    #[cfg(not(target_os = "windows"))]
+                    entry.window.set_visible_on_all_workspaces(visible);
+                    #[cfg(target_os = "windows")]
+                    {
+                        use tao::platform::windows::WindowExtWindows;
+                        super::virtual_desktop::set_pinned(entry.window.hwnd(), visible);
+                    }
+                }
+            }
+            Command::SetTheme { id, theme } => {
+                if let Some(entry) = self.windows.get(&id) {
+                    entry.window.set_theme(parse_theme(&theme));
+                    // WebView2 follows the OS for `prefers-color-scheme`
+                    // unless told otherwise.
+                    #[cfg(target_os = "windows")]
+                    {
+                        use wry::WebViewExtWindows;
+                        if let Err(e) = entry.webview.set_theme(webview_theme(&theme)) {
+                            eprintln!(
+                                "[native-window] Window {}: failed to set webview theme: {}",
+                                id, e
+                            );
+                        }
+                    }
+                }
+            }
+            Command::MoveToVirtualDesktop { id, desktop_id } => {
+                #[cfg(target_os = "windows")]
+                if let Some(entry) = self.windows.get(&id) {
+                    use tao::platform::windows::WindowExtWindows;
+                    // Validated by moveToVirtualDesktop().
+                    if let Some(guid) = super::virtual_desktop::parse_id(&desktop_id) {
+                        super::virtual_desktop::move_to(entry.window.hwnd(), &guid).map_err(
+                            |e| {
+                                napi::Error::from_reason(format!(
+                                    "Failed to move window to desktop {}: {}",
+                                    desktop_id, e
+                                ))
+                            },
+                        )?;
+                    }
+                }
+                #[cfg(not(target_os = "windows"))]
+                let _ = (id, desktop_id);
+            }
+            Command::TrackGlobalMouse { id, throttle_ms } => {
+                if self.windows.contains_key(&id) {
+                    self.mouse_tracking.insert(
+                        id,
+                        MouseTracking {
+                            interval: Duration::from_secs_f64(throttle_ms / 1000.0),
+                            last_poll: None,
+                            position: None,
+                        },
+                    );
+                }
+            }
+            Command::StartDragging { id } => {
+                if let Some(entry) = self.windows.get(&id) {
+                    // WM_NCLBUTTONDOWN/HTCAPTION on Windows,
+                    // performWindowDragWithEvent: on macOS, a GTK move drag on Linux.
+                    if let Err(e) = entry.window.drag_window() {
+                        eprintln!("[native-window] Window {}: failed to start drag: {}", id, e);
+                    }
+                }
+            }
+            Command::SetIgnoreCursorEvents { id, ignore } => {
+                if let Some(entry) = self.windows.get(&id) {
+                    // WS_EX_TRANSPARENT | WS_EX_LAYERED on Windows,
+                    // setIgnoresMouseEvents: on macOS, an empty input shape on Linux.
+                    if let Err(e) = entry.window.set_ignore_cursor_events(ignore) {
+                        eprintln!(
+                            "[native-window] Window {}: failed to set click-through: {}",
+                            id, e
+                        );
+                    }
+                }
+            }
+            Command::SetFullscreen {
+                id,
+                fullscreen,
+                mode,
+            } => {
+                if let Some(entry) = self.windows.get_mut(&id) {
+                    if let Some(mode) = mode {
+                        // Leave the current style before switching to another.
+                        set_fullscreen(&entry.window, false, entry.borderless_fullscreen);
+                        entry.borderless_fullscreen = mode == "borderless";
+                    }
+                    set_fullscreen(&entry.window, fullscreen, entry.borderless_fullscreen);
+                }
+            }
             Command::Show { id } => {
                 if let Some(entry) = self.windows.get(&id) {
                     entry.window.set_visible(true);
@@ -219,6 +729,13 @@ impl Platform {
                 }
             }
             Command::Close { id } => {
+                // Popups and owned windows never outlive the window they
+                // are anchored to or owned by.
+                for child_id in self.popups_of(id).into_iter().chain(self.owned_by(id)) {
+                    if self.destroy_window_entry(child_id) {
+                        capped_push!(PENDING_CLOSES, child_id, "PENDING_CLOSES");
+                    }
+                }
                 self.destroy_window_entry(id);
                 // Event handlers are NOT removed here — they are cleaned
                 // up after flush_pending_callbacks so the JS on_close
@@ -230,6 +747,57 @@ impl Platform {
                     entry.window.set_focus();
                 }
             }
+            Command::RequestAttention { id, critical } => {
+                if let Some(entry) = self.windows.get(&id) {
+                    // FlashWindowEx on Windows, a Dock bounce on macOS, the
+                    // urgency hint on Linux. No-op if already focused.
+                    entry.window.request_user_attention(Some(if critical {
+                        UserAttentionType::Critical
+                    } else {
+                        UserAttentionType::Informational
+                    }));
+                }
+            }
+            Command::FocusWebview { id } => {
+                if let Some(entry) = self.windows.get(&id) {
+                    super::focus::focus(&entry.webview).map_err(|e| {
+                        napi::Error::from_reason(format!("Failed to focus webview: {}", e))
+                    })?;
+                }
+            }
+            Command::ShowEmojiPicker { id } => {
+                #[cfg(any(target_os = "macos", target_os = "windows"))]
+                if let Some(entry) = self.windows.get(&id) {
+                    entry.window.set_focus();
+                    super::focus::show_emoji_picker(&entry.webview).map_err(|e| {
+                        napi::Error::from_reason(format!("Failed to show emoji picker: {}", e))
+                    })?;
+                }
+                #[cfg(target_os = "linux")]
+                let _ = id;
+            }
+            Command::BlurWebview { id } => {
+                if let Some(entry) = self.windows.get(&id) {
+                    super::focus::blur(&entry.webview).map_err(|e| {
+                        napi::Error::from_reason(format!("Failed to blur webview: {}", e))
+                    })?;
+                }
+            }
+            Command::MoveFocusToWebview { id, last } => {
+                if let Some(entry) = self.windows.get(&id) {
+                    super::focus::focus_edge(&entry.webview, last).map_err(|e| {
+                        napi::Error::from_reason(format!("Failed to move focus to webview: {}", e))
+                    })?;
+                }
+            }
+            Command::RestoreFromTray { id } => {
+                if let Some(entry) = self.windows.get(&id) {
+                    entry.window.set_visible(true);
+                    entry.window.set_minimized(false);
+                    entry.window.set_focus();
+                    capped_push!(PENDING_TRAY_RESTORES, id, "PENDING_TRAY_RESTORES");
+                }
+            }
             Command::Maximize { id } => {
                 if let Some(entry) = self.windows.get(&id) {
                     entry.window.set_maximized(true);
@@ -286,61 +854,1033 @@ impl Platform {
                     }
                 }
             }
-        }
-        Ok(())
-    }
+            Command::SetIconRgba {
+                id,
+                rgba,
+                width,
+                height,
+            } => {
+                // macOS doesn't support per-window icons; silently ignore.
+                let _ = (&id, &rgba, width, height);
+                #[cfg(not(target_os = "macos"))]
+                if let Some(entry) = self.windows.get(&id) {
+                    match tao::window::Icon::from_rgba(rgba, width, height) {
+                        Ok(icon) => {
+                            entry.window.set_window_icon(Some(icon));
+                        }
+                        Err(e) => {
+                            eprintln!("[native-window] Warning: Failed to create icon: {}", e)
+                        }
+                    }
+                }
+            }
+            Command::SetOverlayIcon {
+                id,
+                path,
+                description,
+            } => {
+                // Taskbar overlays only exist on Windows; silently ignore.
+                let _ = (&id, &path, &description);
+                #[cfg(target_os = "windows")]
+                if let Some(entry) = self.windows.get(&id) {
+                    use tao::platform::windows::WindowExtWindows;
+                    let image = match path.as_deref().map(image::open).transpose() {
+                        Ok(image) => image.map(|img| img.into_rgba8()),
+                        Err(e) => {
+                            eprintln!(
+                                "[native-window] Warning: Failed to load overlay icon: {}",
+                                e
+                            );
+                            return Ok(());
+                        }
+                    };
+                    let icon = image
+                        .as_ref()
+                        .map(|img| (img.as_raw().as_slice(), img.width(), img.height()));
+                    if let Err(e) =
+                        super::badge::set_overlay_icon(entry.window.hwnd(), icon, &description)
+                    {
+                        eprintln!("[native-window] Warning: Failed to set overlay icon: {}", e);
+                    }
+                }
+            }
+            Command::SetBadgeLabel { id, text } => {
+                // The Dock badge is macOS only; silently ignore.
+                let _ = (&id, &text);
+                #[cfg(target_os = "macos")]
+                if let Some(entry) = self.windows.get(&id) {
+                    use tao::platform::macos::WindowExtMacOS;
+                    entry.window.set_badge_label(text);
+                }
+            }
+            Command::SetProgressBar {
+                id,
+                progress,
+                state,
+            } => {
+                if let Some(entry) = self.windows.get(&id) {
+                    use tao::window::{ProgressBarState, ProgressState};
+                    let state = match (progress, state.as_str()) {
+                        (None, _) => ProgressState::None,
+                        (Some(_), "indeterminate") => ProgressState::Indeterminate,
+                        (Some(_), "paused") => ProgressState::Paused,
+                        (Some(_), "error") => ProgressState::Error,
+                        (Some(_), _) => ProgressState::Normal,
+                    };
+                    entry.window.set_progress_bar(ProgressBarState {
+                        state: Some(state),
+                        progress,
+                        desktop_filename: None,
+                    });
+                }
+            }
+            Command::SetTaskbarBadge { id, count } => {
+                if let Some(entry) = self.windows.get(&id) {
+                    // 0 clears the badge, like null.
+                    let count = count.filter(|&c| c > 0);
 
-    // ── Window destruction ──────────────────────────────────────
+                    #[cfg(target_os = "macos")]
+                    {
+                        use tao::platform::macos::WindowExtMacOS;
+                        entry.window.set_badge_label(count.map(|c| c.to_string()));
+                    }
 
-    /// Remove and destroy a window's native resources (tao Window + wry
-    /// WebView) and clean up associated platform state.  Does NOT touch
-    /// `event_handlers` — those must survive until after
-    /// `flush_pending_callbacks` so the JS `on_close` callback still fires.
-    ///
-    /// Returns `true` if the window existed and was destroyed.
-    fn destroy_window_entry(&mut self, id: u32) -> bool {
-        if let Some(entry) = self.windows.remove(&id) {
-            let tao_id = entry.window.id();
-            self.window_id_map.remove(&tao_id);
-            // Drop entry — this closes the window and destroys the webview
-            drop(entry);
-            // Clean up security config
-            crate::window_manager::TRUSTED_ORIGINS_MAP.with(|o| {
-                o.borrow_mut().remove(&id);
-            });
-            crate::window_manager::ALLOWED_HOSTS_MAP.with(|h| {
-                h.borrow_mut().remove(&id);
-            });
-            crate::window_manager::PERMISSIONS_MAP.with(|p| {
-                p.borrow_mut().remove(&id);
-            });
-            crate::window_manager::remove_html_content(id);
-            true
-        } else {
-            false
-        }
-    }
+                    #[cfg(target_os = "windows")]
+                    {
+                        use tao::platform::windows::WindowExtWindows;
+                        let icon = match count {
+                            Some(c) => {
+                                let size = super::badge::SIZE;
+                                let rgba = super::badge::render(&super::badge::label(c));
+                                Some(tao::window::Icon::from_rgba(rgba, size, size).map_err(
+                                    |e| {
+                                        napi::Error::from_reason(format!(
+                                            "Failed to create badge icon: {}",
+                                            e
+                                        ))
+                                    },
+                                )?)
+                            }
+                            None => None,
+                        };
+                        entry.window.set_overlay_icon(icon.as_ref());
+                    }
 
-    /// Destroy native resources for windows that received an OS-initiated
-    /// `CloseRequested` but weren't already destroyed by `Command::Close`.
-    ///
-    /// This ensures the tao Window and wry WebView are properly dropped
-    /// **before** the JS `on_close` callback fires.  Without this, an
-    /// abrupt `process.exit()` in the callback leaves live native objects
-    /// whose teardown fails (e.g. WebView2 "Failed to unregister class"
-    /// on Windows).
-    pub fn destroy_pending_closes(&mut self) {
-        PENDING_CLOSES.with(|p| {
-            let pending = p.borrow();
-            for &id in pending.iter() {
-                // No-op if already destroyed by Command::Close
-                self.destroy_window_entry(id);
+                    #[cfg(target_os = "linux")]
+                    {
+                        use tao::platform::unix::WindowExtUnix;
+                        // Unity LauncherEntry API; shown by docks that support it.
+                        entry.window.set_badge_count(count.map(i64::from), None);
+                    }
+                }
             }
-        });
-    }
-
-    // ── Window creation ────────────────────────────────────────
-
+            Command::SetWindowMenu { id, items } => {
+                // macOS has a single app-wide menu bar; nothing to attach.
+                let _ = (&id, &items);
+                #[cfg(any(target_os = "windows", target_os = "linux"))]
+                if let Some(entry) = self.windows.get(&id) {
+                    if let Err(e) = super::menu::set_menu(&entry.window, id, &items) {
+                        eprintln!(
+                            "[native-window] Window {}: failed to set menu bar: {}",
+                            id, e
+                        );
+                    }
+                }
+            }
+            Command::SetAutoHideMenuBar { id, auto_hide } => {
+                let _ = (&id, &auto_hide);
+                #[cfg(any(target_os = "windows", target_os = "linux"))]
+                if let Some(entry) = self.windows.get(&id) {
+                    super::menu::set_auto_hide(&entry.window, id, auto_hide);
+                }
+            }
+            Command::CountMatches { id, text } => {
+                if let Some(entry) = self.windows.get(&id) {
+                    let script = count_matches_script(&text);
+                    let result =
+                        entry
+                            .webview
+                            .evaluate_script_with_callback(&script, move |raw: String| {
+                                // The script returns null when it throws.
+                                let count = raw.trim().parse::<f64>().ok();
+                                PENDING_MATCH_COUNTS.with(|p| {
+                                    // Always push — countMatches() promises need a response.
+                                    p.borrow_mut().push((id, count));
+                                });
+                            });
+                    if let Err(e) = result {
+                        eprintln!("[native-window] countMatches failed: {}", e);
+                        PENDING_MATCH_COUNTS.with(|p| p.borrow_mut().push((id, None)));
+                    }
+                }
+            }
+            Command::SendInputEvent { id, event } => {
+                if let Some(entry) = self.windows.get(&id) {
+                    super::input::dispatch(&entry.window, &entry.webview, &event)?;
+                }
+            }
+            Command::SetAccessibilityEnabled { id, enabled } => {
+                #[cfg(target_os = "windows")]
+                if let Some(entry) = self.windows.get(&id) {
+                    let method = if enabled {
+                        "Accessibility.enable"
+                    } else {
+                        "Accessibility.disable"
+                    };
+                    super::cdp::send(&entry.webview, method, "{}")?;
+                }
+                // WebKit builds its accessibility tree on demand whenever
+                // assistive technology queries it; nothing to force there.
+                #[cfg(not(target_os = "windows"))]
+                let _ = (id, enabled);
+            }
+            Command::GetAccessibilitySnapshot { id } => {
+                if let Some(entry) = self.windows.get(&id) {
+                    // Snapshot requests always push — the promise needs a response.
+                    let on_result = move |json: String| {
+                        PENDING_ACCESSIBILITY_SNAPSHOTS.with(|p| p.borrow_mut().push((id, json)));
+                    };
+
+                    #[cfg(target_os = "windows")]
+                    let result = super::cdp::call_method(
+                        &entry.webview,
+                        "Accessibility.getFullAXTree",
+                        "{}",
+                        move |res| on_result(res.unwrap_or_else(|_| "null".to_string())),
+                    );
+
+                    #[cfg(not(target_os = "windows"))]
+                    let result = entry
+                        .webview
+                        .evaluate_script_with_callback(ACCESSIBILITY_SNAPSHOT_SCRIPT, on_result)
+                        .map_err(|e| napi::Error::from_reason(e.to_string()));
+
+                    if let Err(e) = result {
+                        eprintln!("[native-window] getAccessibilitySnapshot failed: {}", e);
+                        PENDING_ACCESSIBILITY_SNAPSHOTS
+                            .with(|p| p.borrow_mut().push((id, "null".to_string())));
+                    }
+                }
+            }
+            Command::GetPagePerformanceMetrics { id } => {
+                if let Some(entry) = self.windows.get(&id) {
+                    // Metrics requests always push — the promise needs a response.
+                    #[cfg(target_os = "windows")]
+                    let result = {
+                        // Page timings come from the same script as elsewhere
+                        // (via Runtime.evaluate); CDP Performance metrics add
+                        // engine counters. Push once both replies are in.
+                        let parts = std::rc::Rc::new(std::cell::RefCell::new([None, None]));
+                        let store = |index: usize| {
+                            let parts = parts.clone();
+                            move |res: Result<String, String>| {
+                                let mut parts = parts.borrow_mut();
+                                parts[index] = Some(res.unwrap_or_else(|_| "null".to_string()));
+                                if let [Some(timing), Some(metrics)] = &*parts {
+                                    let json = format!(
+                                        "{{\"timing\":{},\"metrics\":{}}}",
+                                        timing, metrics
+                                    );
+                                    PENDING_PERFORMANCE_METRICS
+                                        .with(|p| p.borrow_mut().push((id, json)));
+                                }
+                            }
+                        };
+                        let expression = format!(
+                            "{{\"expression\":{},\"returnByValue\":true}}",
+                            json_escape(PERFORMANCE_METRICS_SCRIPT)
+                        );
+                        super::cdp::send(&entry.webview, "Performance.enable", "{}")
+                            .and_then(|_| {
+                                super::cdp::call_method(
+                                    &entry.webview,
+                                    "Runtime.evaluate",
+                                    &expression,
+                                    store(0),
+                                )
+                            })
+                            .and_then(|_| {
+                                super::cdp::call_method(
+                                    &entry.webview,
+                                    "Performance.getMetrics",
+                                    "{}",
+                                    store(1),
+                                )
+                            })
+                    };
+
+                    #[cfg(not(target_os = "windows"))]
+                    let result = entry
+                        .webview
+                        .evaluate_script_with_callback(PERFORMANCE_METRICS_SCRIPT, move |timing| {
+                            let json = format!("{{\"timing\":{},\"metrics\":null}}", timing);
+                            PENDING_PERFORMANCE_METRICS.with(|p| p.borrow_mut().push((id, json)));
+                        })
+                        .map_err(|e| napi::Error::from_reason(e.to_string()));
+
+                    if let Err(e) = result {
+                        eprintln!("[native-window] getPagePerformanceMetrics failed: {}", e);
+                        PENDING_PERFORMANCE_METRICS
+                            .with(|p| p.borrow_mut().push((id, "null".to_string())));
+                    }
+                }
+            }
+            Command::ExtractReadableContent { id } => {
+                if let Some(entry) = self.windows.get(&id) {
+                    // Always push — extractReadableContent() promises need a response.
+                    let result = super::readable::extract(&entry.webview, move |json| {
+                        let json = json.unwrap_or_else(|| "null".to_string());
+                        PENDING_READABLE_CONTENTS.with(|p| p.borrow_mut().push((id, json)));
+                    });
+                    if let Err(e) = result {
+                        eprintln!("[native-window] extractReadableContent failed: {}", e);
+                        PENDING_READABLE_CONTENTS
+                            .with(|p| p.borrow_mut().push((id, "null".to_string())));
+                    }
+                }
+            }
+            Command::SetEmulatedTimezone { id, timezone } => {
+                if let Some(entry) = self.windows.get(&id) {
+                    #[cfg(target_os = "windows")]
+                    {
+                        // An empty ID clears the override.
+                        let params = format!(
+                            "{{\"timezoneId\":{}}}",
+                            json_escape(timezone.as_deref().unwrap_or(""))
+                        );
+                        super::cdp::send(&entry.webview, "Emulation.setTimezoneOverride", &params)?;
+                    }
+
+                    #[cfg(not(target_os = "windows"))]
+                    {
+                        let emulation = self.emulation.entry(id).or_default();
+                        emulation.timezone = timezone;
+                        apply_emulation(&entry.webview, emulation);
+                    }
+                }
+            }
+            Command::SetEmulatedLocale { id, locale } => {
+                if let Some(entry) = self.windows.get(&id) {
+                    #[cfg(target_os = "windows")]
+                    {
+                        // Omitting `locale` clears the override.
+                        let params = match locale {
+                            Some(ref locale) => format!("{{\"locale\":{}}}", json_escape(locale)),
+                            None => "{}".to_string(),
+                        };
+                        super::cdp::send(&entry.webview, "Emulation.setLocaleOverride", &params)?;
+                    }
+
+                    #[cfg(not(target_os = "windows"))]
+                    {
+                        let emulation = self.emulation.entry(id).or_default();
+                        emulation.locale = locale;
+                        apply_emulation(&entry.webview, emulation);
+                    }
+                }
+            }
+            Command::SetFontOverrides {
+                id,
+                standard_family,
+                minimum_font_size,
+            } => {
+                if let Some(entry) = self.windows.get(&id) {
+                    super::fonts::set_font_overrides(
+                        &entry.webview,
+                        standard_family.as_deref(),
+                        minimum_font_size,
+                    )
+                    .map_err(|e| {
+                        napi::Error::from_reason(format!("Failed to set font overrides: {}", e))
+                    })?;
+                }
+            }
+            Command::SetTextZoom { id, percent } => {
+                if let Some(entry) = self.windows.get(&id) {
+                    super::fonts::set_text_zoom(&entry.webview, percent).map_err(|e| {
+                        napi::Error::from_reason(format!("Failed to set text zoom: {}", e))
+                    })?;
+                }
+            }
+            Command::SetNetworkConditions { id, conditions } => {
+                if let Some(entry) = self.windows.get(&id) {
+                    #[cfg(target_os = "windows")]
+                    {
+                        // -1 disables throttling in the DevTools protocol.
+                        let c = conditions.unwrap_or_default();
+                        let params = format!(
+                            "{{\"offline\":{},\"latency\":{},\"downloadThroughput\":{},\"uploadThroughput\":{}}}",
+                            c.offline.unwrap_or(false),
+                            c.latency_ms.unwrap_or(0.0),
+                            c.download_throughput.unwrap_or(-1.0),
+                            c.upload_throughput.unwrap_or(-1.0)
+                        );
+                        super::cdp::send(&entry.webview, "Network.enable", "{}")?;
+                        super::cdp::send(
+                            &entry.webview,
+                            "Network.emulateNetworkConditions",
+                            &params,
+                        )?;
+                    }
+
+                    #[cfg(not(target_os = "windows"))]
+                    {
+                        let emulation = self.emulation.entry(id).or_default();
+                        emulation.network = conditions;
+                        apply_emulation(&entry.webview, emulation);
+                    }
+                }
+            }
+            Command::StartAudioCapture { id } => {
+                if let Some(entry) = self.windows.get(&id) {
+                    if let Err(e) = super::audio_capture::start(&entry.webview, id) {
+                        // End the capture right away so the JS side learns why.
+                        super::audio_capture::push(
+                            id,
+                            super::audio_capture::AudioEvent::End(Some(e)),
+                        );
+                    }
+                }
+            }
+            Command::StopAudioCapture { id } => {
+                super::audio_capture::stop(id);
+            }
+            Command::StartNetworkCapture { id } => {
+                if let Some(entry) = self.windows.get(&id) {
+                    let started_ms = std::time::SystemTime::now()
+                        .duration_since(std::time::UNIX_EPOCH)
+                        .map_or(0.0, |d| d.as_secs_f64() * 1000.0);
+
+                    #[cfg(target_os = "windows")]
+                    let capture = {
+                        let events = std::rc::Rc::new(std::cell::RefCell::new(Vec::new()));
+                        let mut subscriptions = Vec::new();
+                        for method in CAPTURED_NETWORK_EVENTS {
+                            let events = events.clone();
+                            subscriptions.push(super::cdp::subscribe(
+                                &entry.webview,
+                                method,
+                                move |params| {
+                                    let mut events = events.borrow_mut();
+                                    if events.len() == MAX_CAPTURE_EVENTS {
+                                        eprintln!(
+                                            "[native-window] Network capture full ({} events), dropping events.",
+                                            MAX_CAPTURE_EVENTS
+                                        );
+                                    }
+                                    if events.len() <= MAX_CAPTURE_EVENTS {
+                                        events.push(format!(
+                                            "{{\"method\":{},\"params\":{}}}",
+                                            json_escape(method),
+                                            params
+                                        ));
+                                    }
+                                },
+                            )?);
+                        }
+                        super::cdp::send(&entry.webview, "Network.enable", "{}")?;
+                        NetworkCapture {
+                            started_ms,
+                            events,
+                            _subscriptions: subscriptions,
+                        }
+                    };
+
+                    #[cfg(not(target_os = "windows"))]
+                    let capture = NetworkCapture { started_ms };
+
+                    // Restarting discards the previous recording.
+                    self.network_captures.insert(id, capture);
+                }
+            }
+            Command::StopNetworkCapture { id } => {
+                if let Some(entry) = self.windows.get(&id) {
+                    // Always push — stopNetworkCapture() promises need a response.
+                    let push = move |json: Option<String>| {
+                        PENDING_NETWORK_CAPTURES.with(|p| p.borrow_mut().push((id, json)));
+                    };
+                    match self.network_captures.remove(&id) {
+                        None => push(None),
+                        #[cfg(target_os = "windows")]
+                        Some(capture) => {
+                            let events = capture.events.borrow().join(",");
+                            push(Some(format!(
+                                "{{\"startedMs\":{},\"events\":[{}]}}",
+                                capture.started_ms, events
+                            )));
+                        }
+                        #[cfg(not(target_os = "windows"))]
+                        Some(capture) => {
+                            // WebKit has no network event stream; read the
+                            // page's Resource Timing entries instead.
+                            let started_ms = capture.started_ms;
+                            let script = format!("{}({})", RESOURCE_TIMING_SCRIPT, started_ms);
+                            let result = entry.webview.evaluate_script_with_callback(
+                                &script,
+                                move |resources| {
+                                    push(Some(format!(
+                                        "{{\"startedMs\":{},\"resources\":{}}}",
+                                        started_ms, resources
+                                    )))
+                                },
+                            );
+                            if let Err(e) = result {
+                                eprintln!("[native-window] stopNetworkCapture failed: {}", e);
+                                push(None);
+                            }
+                        }
+                    }
+                    #[cfg(target_os = "windows")]
+                    let _ = entry;
+                }
+            }
+            #[cfg(target_os = "windows")]
+            Command::StartTracing { id, categories } => {
+                if let Some(entry) = self.windows.get(&id) {
+                    // `Tracing.dataCollected` params (`{"value":[...]}`) in arrival order.
+                    let chunks = std::rc::Rc::new(std::cell::RefCell::new(Vec::new()));
+                    let collect = {
+                        let chunks = chunks.clone();
+                        super::cdp::subscribe(
+                            &entry.webview,
+                            "Tracing.dataCollected",
+                            move |params| chunks.borrow_mut().push(params),
+                        )?
+                    };
+                    let complete = super::cdp::subscribe(
+                        &entry.webview,
+                        "Tracing.tracingComplete",
+                        move |_| {
+                            let json = format!(
+                                "[{}]",
+                                std::mem::take(&mut *chunks.borrow_mut()).join(",")
+                            );
+                            PENDING_TRACES.with(|p| p.borrow_mut().push((id, Some(json))));
+                        },
+                    )?;
+
+                    let params = if categories.is_empty() {
+                        "{\"transferMode\":\"ReportEvents\"}".to_string()
+                    } else {
+                        let included: Vec<String> =
+                            categories.iter().map(|c| json_escape(c)).collect();
+                        format!(
+                            "{{\"transferMode\":\"ReportEvents\",\"traceConfig\":{{\"includedCategories\":[{}]}}}}",
+                            included.join(",")
+                        )
+                    };
+                    super::cdp::send(&entry.webview, "Tracing.start", &params)?;
+
+                    // Restarting discards the previous session.
+                    self.traces.insert(
+                        id,
+                        TraceSession {
+                            stopping: false,
+                            _subscriptions: [collect, complete],
+                        },
+                    );
+                }
+            }
+            #[cfg(target_os = "windows")]
+            Command::StopTracing { id } => {
+                if let Some(entry) = self.windows.get(&id) {
+                    match self.traces.get_mut(&id) {
+                        // The trace is pushed once `Tracing.tracingComplete` arrives.
+                        Some(session) if !session.stopping => {
+                            session.stopping = true;
+                            if let Err(e) = super::cdp::send(&entry.webview, "Tracing.end", "{}") {
+                                eprintln!("[native-window] stopTracing failed: {}", e);
+                                PENDING_TRACES.with(|p| p.borrow_mut().push((id, None)));
+                            }
+                        }
+                        _ => PENDING_TRACES.with(|p| p.borrow_mut().push((id, None))),
+                    }
+                }
+            }
+            #[cfg(target_os = "windows")]
+            Command::TakeHeapSnapshot { id } => {
+                if let Some(entry) = self.windows.get(&id) {
+                    // `HeapProfiler.addHeapSnapshotChunk` params (`{"chunk":"..."}`);
+                    // all chunks arrive before the method completes.
+                    let chunks = std::rc::Rc::new(std::cell::RefCell::new(Vec::new()));
+                    let result = {
+                        let chunks = chunks.clone();
+                        super::cdp::subscribe(
+                            &entry.webview,
+                            "HeapProfiler.addHeapSnapshotChunk",
+                            move |params| chunks.borrow_mut().push(params),
+                        )
+                    }
+                    .and_then(|subscription| {
+                        super::cdp::call_method(
+                            &entry.webview,
+                            "HeapProfiler.takeHeapSnapshot",
+                            "{\"reportProgress\":false}",
+                            move |res| {
+                                drop(subscription);
+                                let json = match res {
+                                    Ok(_) => Some(format!(
+                                        "[{}]",
+                                        std::mem::take(&mut *chunks.borrow_mut()).join(",")
+                                    )),
+                                    Err(e) => {
+                                        eprintln!("[native-window] takeHeapSnapshot failed: {}", e);
+                                        None
+                                    }
+                                };
+                                PENDING_HEAP_SNAPSHOTS.with(|p| p.borrow_mut().push((id, json)));
+                            },
+                        )
+                    });
+                    if let Err(e) = result {
+                        eprintln!("[native-window] takeHeapSnapshot failed: {}", e);
+                        PENDING_HEAP_SNAPSHOTS.with(|p| p.borrow_mut().push((id, None)));
+                    }
+                }
+            }
+            #[cfg(target_os = "windows")]
+            Command::StartFrameCapture { id, fps, format } => {
+                if let Some(entry) = self.windows.get(&id) {
+                    use wry::WebViewExtWindows;
+
+                    // Chromium sends the next frame only once the previous one
+                    // is acknowledged, so every frame is acked, even dropped ones.
+                    let core = entry.webview.webview();
+                    let interval = Duration::from_secs_f64(1.0 / fps);
+                    let mut last_frame: Option<Instant> = None;
+                    let subscription = super::cdp::subscribe(
+                        &entry.webview,
+                        "Page.screencastFrame",
+                        move |params| {
+                            if let Some(session) = screencast_session_id(&params) {
+                                let _ = super::cdp::send_to(
+                                    &core,
+                                    "Page.screencastFrameAck",
+                                    &format!("{{\"sessionId\":{}}}", session),
+                                );
+                            }
+                            let now = Instant::now();
+                            if last_frame.is_some_and(|t| now.duration_since(t) < interval) {
+                                return;
+                            }
+                            last_frame = Some(now);
+                            PENDING_FRAMES.with(|p| {
+                                let mut frames = p.borrow_mut();
+                                match frames.iter_mut().find(|(w, _)| *w == id) {
+                                    Some(frame) => frame.1 = params,
+                                    None => frames.push((id, params)),
+                                }
+                            });
+                        },
+                    )?;
+                    let params = if format == "png" {
+                        "{\"format\":\"png\"}"
+                    } else {
+                        "{\"format\":\"jpeg\",\"quality\":80}"
+                    };
+                    super::cdp::send(&entry.webview, "Page.startScreencast", params)?;
+                    // Restarting replaces the previous subscription and settings.
+                    self.frame_captures.insert(id, subscription);
+                }
+            }
+            #[cfg(target_os = "windows")]
+            Command::StopFrameCapture { id } => {
+                if let Some(entry) = self.windows.get(&id) {
+                    if self.frame_captures.remove(&id).is_some() {
+                        super::cdp::send(&entry.webview, "Page.stopScreencast", "{}")?;
+                    }
+                }
+            }
+            // Tracing, heap snapshots and frame capture need the DevTools
+            // protocol; the JS methods reject before queueing on other platforms.
+            #[cfg(not(target_os = "windows"))]
+            Command::StartFrameCapture { .. } | Command::StopFrameCapture { .. } => {}
+            #[cfg(not(target_os = "windows"))]
+            Command::StartTracing { .. } => {}
+            #[cfg(not(target_os = "windows"))]
+            Command::StopTracing { id } => {
+                PENDING_TRACES.with(|p| p.borrow_mut().push((id, None)));
+            }
+            #[cfg(not(target_os = "windows"))]
+            Command::TakeHeapSnapshot { id } => {
+                PENDING_HEAP_SNAPSHOTS.with(|p| p.borrow_mut().push((id, None)));
+            }
+            Command::UnregisterServiceWorkers { id } => {
+                if let Some(entry) = self.windows.get(&id) {
+                    entry
+                        .webview
+                        .evaluate_script(UNREGISTER_SERVICE_WORKERS_SCRIPT)
+                        .map_err(|e| {
+                            napi::Error::from_reason(format!(
+                                "unregisterServiceWorkers failed: {}",
+                                e
+                            ))
+                        })?;
+                }
+            }
+            Command::SetFallbackHtml { id, html } => {
+                if let Some(entry) = self.windows.get(&id) {
+                    super::load_errors::set_builtin_error_page(&entry.webview, html.is_none())
+                        .map_err(|e| {
+                            napi::Error::from_reason(format!("setFallbackHtml failed: {}", e))
+                        })?;
+                    crate::window_manager::set_fallback_html(id, html);
+                }
+            }
+            Command::AddResourceHints { id, rel, urls } => {
+                if let Some(entry) = self.windows.get(&id) {
+                    let urls: Vec<String> = urls.iter().map(|u| json_escape(u)).collect();
+                    let script = format!(
+                        "{}({},[{}])",
+                        RESOURCE_HINTS_SCRIPT,
+                        json_escape(rel),
+                        urls.join(",")
+                    );
+                    entry
+                        .webview
+                        .evaluate_script(&script)
+                        .map_err(|e| napi::Error::from_reason(format!("{} failed: {}", rel, e)))?;
+                }
+            }
+            Command::ApplyLayout { layout } => {
+                let entries: Vec<_> = layout
+                    .iter()
+                    .filter_map(|item| {
+                        self.windows
+                            .get(&item.window_id)
+                            .map(|entry| (&entry.window, item))
+                    })
+                    .collect();
+                super::layout::apply_layout(&entries).map_err(|e| {
+                    napi::Error::from_reason(format!("Failed to apply layout: {}", e))
+                })?;
+            }
+            Command::SetPriority { id, background } => {
+                if let Some(entry) = self.windows.get(&id) {
+                    if background {
+                        self.background_windows.insert(id);
+                    } else {
+                        self.background_windows.remove(&id);
+                    }
+                    // Best effort: without a page the process may not exist
+                    // yet; the next page load applies it.
+                    if let Err(e) = super::priority::set_priority(&entry.webview, background) {
+                        eprintln!("[native-window] setPriority failed: {}", e);
+                    }
+                }
+            }
+            Command::CaptureWindow { id } => {
+                if let Some(entry) = self.windows.get(&id) {
+                    let png = match super::capture::capture_window(&entry.window) {
+                        Ok(png) => Some(png),
+                        Err(e) => {
+                            eprintln!("[native-window] captureWindow failed: {}", e);
+                            None
+                        }
+                    };
+                    // Always push — captureWindow() promises need a response.
+                    PENDING_WINDOW_CAPTURES.with(|p| p.borrow_mut().push((id, png)));
+                }
+            }
+            Command::CapturePage { id } => {
+                if let Some(entry) = self.windows.get(&id) {
+                    super::capture::capture_page(&entry.webview, move |result| {
+                        let png = result
+                            .map_err(|e| eprintln!("[native-window] capturePage failed: {}", e))
+                            .ok();
+                        PENDING_PAGE_CAPTURES.with(|p| p.borrow_mut().push((id, png)));
+                    });
+                }
+            }
+            Command::GetThumbnail {
+                id,
+                max_width,
+                max_height,
+            } => {
+                if let Some(entry) = self.windows.get(&id) {
+                    let png = match super::capture::capture_thumbnail(
+                        &entry.window,
+                        max_width,
+                        max_height,
+                    ) {
+                        Ok(png) => Some(png),
+                        Err(e) => {
+                            eprintln!("[native-window] getThumbnail failed: {}", e);
+                            None
+                        }
+                    };
+                    PENDING_THUMBNAILS.with(|p| p.borrow_mut().push((id, png)));
+                }
+            }
+            Command::PickColor { id } => {
+                #[cfg(any(target_os = "macos", target_os = "windows"))]
+                if self.windows.contains_key(&id) {
+                    if let Err(e) = super::color_picker::start(id) {
+                        eprintln!("[native-window] pickColorFromScreen failed: {}", e);
+                        super::color_picker::report(id, None);
+                    }
+                }
+                #[cfg(target_os = "linux")]
+                let _ = id;
+            }
+            Command::Share { id, options } => {
+                #[cfg(any(target_os = "macos", target_os = "windows"))]
+                if let Some(entry) = self.windows.get(&id) {
+                    #[cfg(target_os = "macos")]
+                    let result = super::share::show(&entry.window, &options);
+                    #[cfg(target_os = "windows")]
+                    let result = super::share::show(id, &entry.window, &options);
+                    result.map_err(|e| {
+                        napi::Error::from_reason(format!("Failed to show share sheet: {}", e))
+                    })?;
+                }
+                #[cfg(target_os = "linux")]
+                let _ = (id, options);
+            }
+            Command::GetBounds { id } => {
+                if let Some(bounds) = Platform::bounds(self, id) {
+                    // Always push — getBounds() promises need a response.
+                    PENDING_BOUNDS.with(|p| p.borrow_mut().push((id, bounds)));
+                }
+            }
+            Command::GetWindowState { id } => {
+                if let Some(state) = Platform::window_state(self, id) {
+                    // Always push — isMaximized() etc. need a response.
+                    PENDING_WINDOW_STATES.with(|p| p.borrow_mut().push((id, state)));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // ── Window destruction ──────────────────────────────────────
+
+    /// Remove and destroy a window's native resources (tao Window + wry
+    /// WebView) and clean up associated platform state.  Does NOT touch
+    /// `event_handlers` — those must survive until after
+    /// `flush_pending_callbacks` so the JS `on_close` callback still fires.
+    ///
+    /// Returns `true` if the window existed and was destroyed.
+    fn destroy_window_entry(&mut self, id: u32) -> bool {
+        if let Some(entry) = self.windows.remove(&id) {
+            let tao_id = entry.window.id();
+            self.window_id_map.remove(&tao_id);
+            self.popups.remove(&id);
+            self.owners.remove(&id);
+            // Unpin while the window still exists.
+            #[cfg(target_os = "windows")]
+            {
+                use tao::platform::windows::WindowExtWindows;
+                super::virtual_desktop::set_pinned(entry.window.hwnd(), false);
+            }
+            // Drop entry — this closes the window and destroys the webview
+            drop(entry);
+            // Clean up security config
+            crate::window_manager::TRUSTED_ORIGINS_MAP.with(|o| {
+                o.borrow_mut().remove(&id);
+            });
+            crate::window_manager::ALLOWED_HOSTS_MAP.with(|h| {
+                h.borrow_mut().remove(&id);
+            });
+            crate::window_manager::ALLOWED_SCHEMES_MAP.with(|s| {
+                s.borrow_mut().remove(&id);
+            });
+            crate::window_manager::PERMISSIONS_MAP.with(|p| {
+                p.borrow_mut().remove(&id);
+            });
+            crate::window_manager::remove_html_content(id);
+            crate::window_manager::set_fallback_html(id, None);
+            super::load_errors::forget(id);
+            super::iframe_policy::forget(id);
+            super::frames::forget(id);
+            super::audio_capture::stop(id);
+            CLOSE_INTERCEPTED.with(|c| {
+                c.borrow_mut().remove(&id);
+            });
+            MINIMIZE_TO_TRAY.with(|t| {
+                t.borrow_mut().remove(&id);
+            });
+            LAST_GOOD_BOUNDS.with(|b| {
+                b.borrow_mut().remove(&id);
+            });
+            #[cfg(not(target_os = "windows"))]
+            self.emulation.remove(&id);
+            self.network_captures.remove(&id);
+            self.background_windows.remove(&id);
+            #[cfg(target_os = "macos")]
+            self.webview_focus.remove(&id);
+            self.mouse_tracking.remove(&id);
+            self.geometry_changes.remove(&id);
+            self.title_templates.remove(&id);
+            self.document_titles.remove(&id);
+            #[cfg(any(target_os = "macos", target_os = "windows"))]
+            self.active_space.remove(&id);
+            #[cfg(target_os = "linux")]
+            self.themes.remove(&id);
+            #[cfg(target_os = "windows")]
+            self.traces.remove(&id);
+            #[cfg(target_os = "windows")]
+            self.frame_captures.remove(&id);
+            #[cfg(any(target_os = "windows", target_os = "linux"))]
+            super::menu::remove(id);
+            #[cfg(target_os = "windows")]
+            super::share::remove(id);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Destroy native resources for windows that received an OS-initiated
+    /// `CloseRequested` but weren't already destroyed by `Command::Close`.
+    ///
+    /// This ensures the tao Window and wry WebView are properly dropped
+    /// **before** the JS `on_close` callback fires.  Without this, an
+    /// abrupt `process.exit()` in the callback leaves live native objects
+    /// whose teardown fails (e.g. WebView2 "Failed to unregister class"
+    /// on Windows).
+    pub fn destroy_pending_closes(&mut self) {
+        let pending: Vec<u32> = PENDING_CLOSES.with(|p| p.borrow().clone());
+        for id in pending {
+            // The OS destroys owned windows along with their owner, so
+            // release them first and report their close too.
+            for child_id in self.owned_by(id) {
+                if self.destroy_window_entry(child_id) {
+                    capped_push!(PENDING_CLOSES, child_id, "PENDING_CLOSES");
+                }
+            }
+            // No-op if already destroyed by Command::Close
+            self.destroy_window_entry(id);
+        }
+    }
+
+    /// Destroy every window, owned windows before their owners, and stop
+    /// the audio capture threads. Called when the Node environment exits,
+    /// so no close events are reported.
+    pub fn shutdown(&mut self) {
+        #[cfg(target_os = "windows")]
+        self.pending_windows.clear();
+        let roots: Vec<u32> = self
+            .windows
+            .keys()
+            .copied()
+            .filter(|id| !self.owners.contains_key(id))
+            .collect();
+        for id in roots {
+            for child_id in self.owned_by(id).into_iter().rev() {
+                self.destroy_window_entry(child_id);
+            }
+            self.destroy_window_entry(id);
+        }
+        // Windows whose owner closed before them.
+        let rest: Vec<u32> = self.windows.keys().copied().collect();
+        for id in rest {
+            self.destroy_window_entry(id);
+        }
+        super::audio_capture::shutdown();
+    }
+
+    // ── Deferred creation (Windows) ────────────────────────────
+
+    /// Hold back window creation until the shared WebView2 environment is
+    /// ready, so `pumpEvents()` never blocks on browser process startup.
+    /// Commands for a pending window are queued and replayed once it exists.
+    /// Returns the command if it should run now.
+    #[cfg(target_os = "windows")]
+    fn defer_until_environment_ready(&mut self, cmd: Command) -> Option<Command> {
+        let id = cmd.window_id();
+        if let Some(index) = self.pending_windows.iter().position(|p| p.id == id) {
+            if let Command::Close { .. } = cmd {
+                // Never created — nothing to destroy, just report the close.
+                self.pending_windows.remove(index);
+                capped_push!(PENDING_CLOSES, id, "PENDING_CLOSES");
+            } else {
+                // Never dropped, so loads sent right after creation always run.
+                let queued = &mut self.pending_windows[index].queued;
+                if queued.len() == crate::window_manager::MAX_COMMAND_QUEUE {
+                    eprintln!(
+                        "[native-window] Window {}: {} commands queued while waiting for \
+                         WebView2. Possible runaway loop.",
+                        id,
+                        queued.len()
+                    );
+                }
+                queued.push(cmd);
+            }
+            return None;
+        }
+        match cmd {
+            // Keep creation order: queue behind earlier pending windows even
+            // if the environment has just become ready.
+            Command::CreateWindow { id, options }
+                if !self.pending_windows.is_empty()
+                    || matches!(webview2_env::poll(), webview2_env::Environment::Pending) =>
+            {
+                self.pending_windows.push(PendingWindow {
+                    id,
+                    options,
+                    queued: Vec::new(),
+                });
+                None
+            }
+            cmd => Some(cmd),
+        }
+    }
+
+    /// Create windows that were waiting for the WebView2 environment and
+    /// replay their queued commands. Called once per pump; a no-op until
+    /// the environment settles. Each window's controller is still created
+    /// synchronously by wry. If environment creation failed, windows fall
+    /// back to wry's own (blocking) environment setup.
+    #[cfg(target_os = "windows")]
+    pub fn create_pending_windows(
+        &mut self,
+        event_handlers: &mut HashMap<u32, WindowEventHandlers>,
+    ) -> napi::Result<()> {
+        if self.pending_windows.is_empty() {
+            return Ok(());
+        }
+        match webview2_env::poll() {
+            webview2_env::Environment::Pending => return Ok(()),
+            webview2_env::Environment::Failed(e) => {
+                eprintln!(
+                    "[native-window] WebView2 environment creation failed: {}. \
+                     Falling back to per-window setup.",
+                    e
+                );
+            }
+            webview2_env::Environment::Ready(_) => {}
+        }
+
+        let mut first_err: Option<napi::Error> = None;
+        for pending in std::mem::take(&mut self.pending_windows) {
+            if let Err(e) = self.create_window(pending.id, &pending.options) {
+                eprintln!("[native-window] Command failed: {}", e);
+                if !pending.queued.is_empty() {
+                    eprintln!(
+                        "[native-window] Window {}: discarding {} queued commands.",
+                        pending.id,
+                        pending.queued.len()
+                    );
+                }
+                first_err.get_or_insert(e);
+                continue;
+            }
+            for cmd in pending.queued {
+                if let Err(e) = self.process_command(cmd, event_handlers) {
+                    eprintln!("[native-window] Command failed: {}", e);
+                    first_err.get_or_insert(e);
+                }
+            }
+        }
+        match first_err {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
+    }
+
+    // ── Window creation ────────────────────────────────────────
+
     /// Create a new tao window + wry webview.
     fn create_window(&mut self, id: u32, options: &WindowOptions) -> napi::Result<()> {
         EVENT_LOOP.with(|el| {
@@ -357,11 +1897,87 @@ impl Platform {
                 .with_title(options.title.as_deref().unwrap_or(""))
                 .with_inner_size(LogicalSize::new(width, height))
                 .with_resizable(options.resizable.unwrap_or(true))
+                .with_closable(options.closable.unwrap_or(true))
+                .with_minimizable(options.minimizable.unwrap_or(true))
+                .with_maximizable(options.maximizable.unwrap_or(true))
                 .with_decorations(options.decorations.unwrap_or(true))
                 .with_always_on_top(options.always_on_top.unwrap_or(false))
-                .with_visible(options.visible.unwrap_or(true));
+                .with_always_on_bottom(
+                    options.always_on_bottom.unwrap_or(false)
+                        && !options.always_on_top.unwrap_or(false),
+                )
+                .with_visible_on_all_workspaces(options.visible_on_all_workspaces.unwrap_or(false))
+                .with_visible(options.visible.unwrap_or(true))
+                .with_focused(options.focused.unwrap_or(true));
+
+            // Custom title bars: drop the native one but keep resize borders
+            // (tao hit-tests borders of undecorated windows itself).
+            if let Some(style @ ("hidden" | "hiddenInset" | "overlay")) =
+                options.title_bar_style.as_deref()
+            {
+                #[cfg(target_os = "macos")]
+                {
+                    use tao::platform::macos::WindowBuilderExtMacOS;
+                    win_builder = win_builder
+                        .with_titlebar_transparent(true)
+                        .with_fullsize_content_view(true)
+                        .with_title_hidden(style != "overlay");
+                    if let Some(p) = options.traffic_light_position {
+                        win_builder = win_builder.with_traffic_light_inset(LogicalPosition::new(p.x, p.y));
+                    } else if style == "hiddenInset" {
+                        win_builder =
+                            win_builder.with_traffic_light_inset(LogicalPosition::new(20.0, 20.0));
+                    }
+                }
+                #[cfg(target_os = "windows")]
+                {
+                    use tao::platform::windows::WindowBuilderExtWindows;
+                    let _ = style;
+                    win_builder = win_builder
+                        .with_decorations(false)
+                        .with_undecorated_shadow(true);
+                }
+                #[cfg(target_os = "linux")]
+                {
+                    let _ = style;
+                    win_builder = win_builder.with_decorations(false);
+                }
+            }
 
-            if let (Some(x), Some(y)) = (options.x, options.y) {
+            let center = options.center.unwrap_or(false);
+            // Monitor chosen by the `display` option, for `fullscreen`.
+            let mut display_monitor = None;
+            if options.display.is_some() || center {
+                // x/y are logical offsets into the target monitor's work area;
+                // a missing coordinate (or `center`) centers the window on
+                // that axis.
+                let monitor = match options.display {
+                    Some(ref display) => super::display::resolve_display(event_loop, display)?,
+                    None => super::display::resolve_display(
+                        event_loop,
+                        &Either::B("primary".to_string()),
+                    )?,
+                };
+                let work = super::display::work_area(&monitor);
+                let scale = monitor.scale_factor();
+                let x = options
+                    .x
+                    .filter(|_| !center)
+                    .map(|x| x * scale)
+                    .unwrap_or_else(|| ((work.width as f64 - width * scale) / 2.0).max(0.0));
+                let y = options
+                    .y
+                    .filter(|_| !center)
+                    .map(|y| y * scale)
+                    .unwrap_or_else(|| ((work.height as f64 - height * scale) / 2.0).max(0.0));
+                win_builder = win_builder.with_position(PhysicalPosition::new(
+                    work.x as f64 + x,
+                    work.y as f64 + y,
+                ));
+                if options.display.is_some() {
+                    display_monitor = Some(monitor);
+                }
+            } else if let (Some(x), Some(y)) = (options.x, options.y) {
                 win_builder = win_builder.with_position(LogicalPosition::new(x, y));
             }
             if let (Some(min_w), Some(min_h)) = (options.min_width, options.min_height) {
@@ -370,13 +1986,85 @@ impl Platform {
             if let (Some(max_w), Some(max_h)) = (options.max_width, options.max_height) {
                 win_builder = win_builder.with_max_inner_size(LogicalSize::new(max_w, max_h));
             }
+            if let Some(ref theme) = options.theme {
+                win_builder = win_builder.with_theme(parse_theme(theme));
+            }
             if options.transparent.unwrap_or(false) {
                 win_builder = win_builder.with_transparent(true);
             }
 
+            if let Some(ref anchor) = options.anchor {
+                let space = match anchor.window_id {
+                    Some(anchor_id) => {
+                        let anchor_window =
+                            self.windows.get(&anchor_id).map(|e| &e.window).ok_or_else(|| {
+                                napi::Error::from_reason(format!(
+                                    "Popup anchor window {} not found",
+                                    anchor_id
+                                ))
+                            })?;
+                        super::popup::AnchorSpace::window(anchor_window)
+                    }
+                    None => super::popup::AnchorSpace::screen(
+                        event_loop,
+                        anchor.rect.x,
+                        anchor.rect.y,
+                    ),
+                };
+                let position = super::popup::place(&space, anchor, width, height);
+                win_builder = win_builder
+                    .with_position(position)
+                    .with_decorations(false)
+                    .with_always_on_top(true)
+                    .with_focused(false);
+                #[cfg(target_os = "windows")]
+                {
+                    use tao::platform::windows::WindowBuilderExtWindows;
+                    win_builder = win_builder.with_skip_taskbar(true);
+                }
+                #[cfg(target_os = "linux")]
+                {
+                    use tao::platform::unix::WindowBuilderExtUnix;
+                    win_builder = win_builder.with_skip_taskbar(true);
+                }
+            }
+
+            if let Some(parent_id) = options.parent_id {
+                let parent = self.windows.get(&parent_id).map(|e| &e.window).ok_or_else(|| {
+                    napi::Error::from_reason(format!("Parent window {} not found", parent_id))
+                })?;
+                #[cfg(target_os = "windows")]
+                {
+                    use tao::platform::windows::{WindowBuilderExtWindows, WindowExtWindows};
+                    win_builder = win_builder.with_owner_window(parent.hwnd());
+                }
+                #[cfg(target_os = "macos")]
+                {
+                    use tao::platform::macos::{WindowBuilderExtMacOS, WindowExtMacOS};
+                    win_builder = win_builder.with_parent_window(parent.ns_window());
+                }
+                #[cfg(target_os = "linux")]
+                {
+                    use tao::platform::unix::{WindowBuilderExtUnix, WindowExtUnix};
+                    win_builder = win_builder.with_transient_for(parent.gtk_window());
+                }
+            }
+
             let window = win_builder.build(event_loop)
                 .map_err(|e| napi::Error::from_reason(format!("Failed to create window: {}", e)))?;
 
+            #[cfg(target_os = "windows")]
+            if let Some(ref style) = options.corner_style {
+                use tao::platform::windows::WindowExtWindows;
+                set_corner_style(window.hwnd(), style);
+            }
+
+            #[cfg(target_os = "windows")]
+            if options.visible_on_all_workspaces == Some(true) {
+                use tao::platform::windows::WindowExtWindows;
+                super::virtual_desktop::set_pinned(window.hwnd(), true);
+            }
+
             // Set window icon from file path (Windows/Linux only; no-op on macOS)
             #[cfg(not(target_os = "macos"))]
             if let Some(ref icon_path) = options.icon {
@@ -402,7 +2090,20 @@ impl Platform {
                 }
                 let source_url = req.uri().to_string();
 
-                if !is_origin_trusted(window_id, &source_url) {
+                if super::frames::handle_message(window_id, &message) {
+                    return;
+                }
+
+                if message == DRAG_REGION_MESSAGE {
+                    if is_origin_trusted(window_id, &source_url) {
+                        DRAG_REQUESTS.with(|d| d.borrow_mut().push(window_id));
+                    }
+                    return;
+                }
+
+                if !is_origin_trusted(window_id, &source_url)
+                    || !is_event_enabled(window_id, EVENT_MESSAGE)
+                {
                     return;
                 }
 
@@ -434,9 +2135,16 @@ impl Platform {
             //
             // Each section is wrapped in its own try/catch so that a failure
             // in one patch never disables subsequent protections.
+            //
+            // Schemes in `allowedSchemes` are left out of the list.
+            let blocked_schemes: Vec<String> = ["data", "file", "blob"]
+                .iter()
+                .filter(|s| !options.allowed_schemes.iter().flatten().any(|a| a == *s))
+                .map(|s| format!("\"{}:\"", s))
+                .collect();
             wv_builder = wv_builder.with_initialization_script(
-                r#"(function () {
-  var BLOCKED_SCHEMES = ["data:", "file:", "blob:"];
+                &r#"(function () {
+  var BLOCKED_SCHEMES = [__BLOCKED_SCHEMES__];
 
   function isBlocked(url) {
     var lower = (url + "").trim().toLowerCase();
@@ -586,9 +2294,13 @@ impl Platform {
     }).observe(root, { childList: true, subtree: true });
   } catch (e) {}
 })();"#
+                    .replace("__BLOCKED_SCHEMES__", &blocked_schemes.join(", ")),
             );
 
-            // Navigation handler — block dangerous schemes + enforce allowedHosts
+            // Navigation handler — block dangerous schemes, enforce allowedHosts
+            // and maxNavigationsPerMinute
+            let nav_limit = options.max_navigations_per_minute;
+            let nav_times = std::cell::RefCell::new(std::collections::VecDeque::<Instant>::new());
             wv_builder = wv_builder.with_navigation_handler(move |url: String| {
                 let lower = url.to_lowercase();
                 // Always allow our custom protocol for HTML content.
@@ -602,27 +2314,59 @@ impl Platform {
                         return true;
                     }
                 }
+                // allowedSchemes are exempt from scheme blocking
+                let scheme_allowed = is_scheme_allowed(window_id, &url);
                 // Block dangerous URL schemes
-                if lower.starts_with("javascript:")
-                    || lower.starts_with("file:")
-                    || lower.starts_with("data:")
-                    || lower.starts_with("blob:")
+                if !scheme_allowed
+                    && (lower.starts_with("javascript:")
+                        || lower.starts_with("file:")
+                        || lower.starts_with("data:")
+                        || lower.starts_with("blob:"))
                 {
                     return false;
                 }
-                // Enforce allowedHosts
-                if !is_host_allowed(window_id, &url) {
+                // Enforce allowedHosts; only hostless allowedSchemes URLs skip it
+                let host_allowed =
+                    is_host_allowed(window_id, &url) || (scheme_allowed && is_hostless(&url));
+                // WebKitGTK does not say which frame navigates, so iframe hosts
+                // are accepted too.
+                #[cfg(target_os = "linux")]
+                let host_allowed = host_allowed || super::iframe_policy::is_frame_host(window_id, &url);
+                if !host_allowed {
                     capped_push!(PENDING_NAVIGATION_BLOCKED, (window_id, url), "PENDING_NAVIGATION_BLOCKED");
                     return false;
                 }
+                // Rolling one-minute window of allowed navigations
+                if let Some(limit) = nav_limit {
+                    let now = Instant::now();
+                    let mut times = nav_times.borrow_mut();
+                    while times
+                        .front()
+                        .is_some_and(|t| now.duration_since(*t) >= Duration::from_secs(60))
+                    {
+                        times.pop_front();
+                    }
+                    if times.len() >= limit as usize {
+                        capped_push!(PENDING_NAVIGATION_THROTTLED, (window_id, url), "PENDING_NAVIGATION_THROTTLED");
+                        return false;
+                    }
+                    times.push_back(now);
+                }
                 true
             });
 
-            // Page load handler — fires on navigation start and finish
+            // Page load handler — fires on navigation start and finish.
+            // The first finished load also fires the one-time ready-to-show event.
+            let ready_to_show_fired = std::cell::Cell::new(false);
             wv_builder = wv_builder.with_on_page_load_handler(move |event, url| {
                 let event_str = match event {
                     wry::PageLoadEvent::Started => "started".to_string(),
-                    wry::PageLoadEvent::Finished => "finished".to_string(),
+                    wry::PageLoadEvent::Finished => {
+                        if !ready_to_show_fired.replace(true) {
+                            capped_push!(PENDING_READY_TO_SHOW, window_id, "PENDING_READY_TO_SHOW");
+                        }
+                        "finished".to_string()
+                    }
                 };
                 PENDING_PAGE_LOADS.with(|p| {
                     let mut buf = p.borrow_mut();
@@ -634,20 +2378,48 @@ impl Platform {
 
             // Title changed handler
             wv_builder = wv_builder.with_document_title_changed_handler(move |title| {
-                capped_push!(PENDING_TITLE_CHANGES, (window_id, title), "PENDING_TITLE_CHANGES");
+                DOCUMENT_TITLES.with(|t| t.borrow_mut().push((window_id, title.clone())));
+                if is_event_enabled(window_id, EVENT_TITLE_CHANGED) {
+                    capped_push!(PENDING_TITLE_CHANGES, (window_id, title), "PENDING_TITLE_CHANGES");
+                }
             });
 
-            // Custom protocol handler — serves stored HTML content at nativewindow://localhost/
+            // Custom protocol handler — serves stored HTML documents at
+            // nativewindow://localhost/<path>.
             // This gives HTML pages a proper origin (secure context) so APIs like
             // navigator.mediaDevices are available, and makes browser-native reload
             // (Cmd+R) work correctly instead of showing a blank page.
-            wv_builder = wv_builder.with_custom_protocol("nativewindow".into(), move |_webview_id, _request| {
-                let html = crate::window_manager::get_html_content(window_id)
-                    .unwrap_or_default();
-                http::Response::builder()
-                    .header("Content-Type", "text/html; charset=utf-8")
-                    .header("Cache-Control", "no-store")
-                    .body(Cow::Owned(html.into_bytes()))
+            // The service worker script from setServiceWorker() is served with a
+            // JavaScript MIME type and root scope, as registration requires.
+            wv_builder = wv_builder.with_custom_protocol("nativewindow".into(), move |_webview_id, request| {
+                let response = if request.uri().path() == crate::window_manager::SERVICE_WORKER_PATH {
+                    match crate::window_manager::get_service_worker_script(window_id) {
+                        Some(script) => http::Response::builder()
+                            .header("Content-Type", "text/javascript; charset=utf-8")
+                            .header("Service-Worker-Allowed", "/")
+                            .header("Cache-Control", "no-cache")
+                            .body(Cow::Owned(script.into_bytes())),
+                        None => http::Response::builder()
+                            .status(404)
+                            .body(Cow::Owned(Vec::new())),
+                    }
+                } else {
+                    // Other paths get the root document, so client-side routes
+                    // survive a reload.
+                    let path = request.uri().path();
+                    let html = crate::window_manager::get_html_content(window_id, path)
+                        .or_else(|| crate::window_manager::get_html_content(window_id, "/"));
+                    match html {
+                        Some(html) => http::Response::builder()
+                            .header("Content-Type", "text/html; charset=utf-8")
+                            .header("Cache-Control", "no-store")
+                            .body(Cow::Owned(html.into_bytes())),
+                        None => http::Response::builder()
+                            .status(404)
+                            .body(Cow::Owned(Vec::new())),
+                    }
+                };
+                response
                     .unwrap_or_else(|_| {
                         http::Response::builder()
                             .body(Cow::Owned(Vec::new()))
@@ -660,6 +2432,19 @@ impl Platform {
                 wry::NewWindowResponse::Deny
             });
 
+            wv_builder = wv_builder.with_initialization_script(DRAG_REGION_SCRIPT);
+
+            // WebKit has no frame lifecycle API; subframes report themselves.
+            #[cfg(not(target_os = "windows"))]
+            {
+                wv_builder = wv_builder
+                    .with_initialization_script_for_main_only(super::frames::FRAME_SCRIPT, false);
+            }
+
+            if let Some(script) = options.iframe_policy.as_ref().and_then(super::iframe_policy::script) {
+                wv_builder = wv_builder.with_initialization_script(&script);
+            }
+
             // CSP injection via initialization script.
             // Uses json_escape() to safely embed the CSP value as a JSON string,
             // preventing injection via newlines, quotes, null bytes, etc.
@@ -711,119 +2496,744 @@ document.addEventListener('DOMContentLoaded', function () {{
             #[cfg(target_os = "windows")]
             {
                 wv_builder = wv_builder.with_https_scheme(true);
+                if let Some(ref theme) = options.theme {
+                    wv_builder = wv_builder.with_theme(webview_theme(theme));
+                }
+                if let webview2_env::Environment::Ready(env) = webview2_env::poll() {
+                    wv_builder = wv_builder.with_environment(env);
+                }
             }
 
             // Build the webview — platform-specific build method
+            // On Linux, pack into tao's default vbox so a menu bar from
+            // setWindowMenu() can sit above the webview.
             #[cfg(target_os = "linux")]
             let webview = {
                 use tao::platform::unix::WindowExtUnix;
-                let gtk_window = window.gtk_window();
-                wv_builder.build_gtk(gtk_window)
-                    .map_err(|e| napi::Error::from_reason(format!("Failed to create webview: {}", e)))?
+                let built = match window.default_vbox() {
+                    Some(vbox) => wv_builder.build_gtk(vbox),
+                    None => wv_builder.build_gtk(window.gtk_window()),
+                };
+                built.map_err(|e| napi::Error::from_reason(format!("Failed to create webview: {}", e)))?
             };
 
             #[cfg(not(target_os = "linux"))]
             let webview = wv_builder.build(&window)
                 .map_err(|e| napi::Error::from_reason(format!("Failed to create webview: {}", e)))?;
 
-            // Store the window + webview
-            let tao_window_id = window.id();
-            self.window_id_map.insert(tao_window_id, id);
-            self.windows.insert(id, WindowEntry {
-                window,
-                webview,
-            });
+            super::live_drag::watch(&window);
+            if let Err(e) = super::focus::watch(&webview, id) {
+                eprintln!("[native-window] Window {}: webview focus events unavailable: {}", id, e);
+            }
+            if let Err(e) = super::load_errors::watch(&webview, id) {
+                eprintln!("[native-window] Window {}: navigation failures unavailable: {}", id, e);
+            }
+            if let Err(e) = super::frames::watch(&webview, id) {
+                eprintln!("[native-window] Window {}: frame events unavailable: {}", id, e);
+            }
+            if let Err(e) = super::iframe_policy::watch(&webview, id, options.iframe_policy.as_ref()) {
+                eprintln!("[native-window] Window {}: iframe navigations unfiltered: {}", id, e);
+            }
+
+            // Store the window + webview
+            let tao_window_id = window.id();
+            self.window_id_map.insert(tao_window_id, id);
+            let borderless_fullscreen = options.fullscreen_mode.as_deref() == Some("borderless");
+            if options.fullscreen == Some(true) {
+                // Name the `display` monitor, as the window may not be
+                // mapped on it yet.
+                #[cfg(not(target_os = "macos"))]
+                {
+                    if let Some(monitor) = display_monitor {
+                        window.set_fullscreen(Some(tao::window::Fullscreen::Borderless(Some(
+                            monitor,
+                        ))));
+                    } else {
+                        set_fullscreen(&window, true, borderless_fullscreen);
+                    }
+                }
+                // macOS has already placed the window on it.
+                #[cfg(target_os = "macos")]
+                {
+                    let _ = display_monitor;
+                    set_fullscreen(&window, true, borderless_fullscreen);
+                }
+            }
+            if options.skip_taskbar == Some(true) {
+                set_skip_taskbar(&window, true);
+            }
+            #[cfg(target_os = "macos")]
+            if let Some(ref material) = options.vibrancy {
+                apply_vibrancy(&window, &webview, material);
+            }
+            #[cfg(any(target_os = "windows", target_os = "linux"))]
+            if options.auto_hide_menu_bar == Some(true) {
+                super::menu::set_auto_hide(&window, id, true);
+            }
+            self.windows.insert(id, WindowEntry {
+                window,
+                webview,
+                borderless_fullscreen,
+            });
+            if let Some(ref anchor) = options.anchor {
+                self.popups.insert(id, anchor.window_id);
+            }
+            if let Some(parent_id) = options.parent_id {
+                self.owners.insert(id, parent_id);
+            }
+
+            Ok(())
+        })
+    }
+
+    // ── Popups ─────────────────────────────────────────────────
+
+    /// All popups anchored (directly or through other popups) to `anchor_id`.
+    fn popups_of(&self, anchor_id: u32) -> Vec<u32> {
+        let mut result = Vec::new();
+        let mut stack = vec![anchor_id];
+        while let Some(parent) = stack.pop() {
+            for (&popup, &anchor) in &self.popups {
+                if anchor == Some(parent) {
+                    result.push(popup);
+                    stack.push(popup);
+                }
+            }
+        }
+        result
+    }
+
+    /// All windows owned (directly or through other owned windows) by
+    /// `owner_id`.
+    fn owned_by(&self, owner_id: u32) -> Vec<u32> {
+        let mut result = Vec::new();
+        let mut stack = vec![owner_id];
+        while let Some(owner) = stack.pop() {
+            for (&child, &parent) in &self.owners {
+                if parent == owner {
+                    result.push(child);
+                    stack.push(child);
+                }
+            }
+        }
+        result
+    }
+
+    /// The window at the root of a popup chain (the popup itself when it
+    /// is anchored to a screen position).
+    fn popup_root(&self, mut id: u32) -> u32 {
+        while let Some(&Some(anchor)) = self.popups.get(&id) {
+            id = anchor;
+        }
+        id
+    }
+
+    /// Decide which popups to close after an event loop pass.
+    ///
+    /// A popup is dismissed when its anchor moves, resizes, or closes; when
+    /// the mouse is pressed outside it (and outside any popup chained to it);
+    /// or when focus leaves its window family entirely (e.g. the user
+    /// switched to another app).
+    fn popups_to_dismiss(
+        &self,
+        anchors_changed: &[u32],
+        blurred: &[u32],
+        clicks: &[PhysicalPosition<f64>],
+    ) -> Vec<u32> {
+        if self.popups.is_empty() {
+            return Vec::new();
+        }
+        let mut dismiss = Vec::new();
+
+        for &id in anchors_changed {
+            dismiss.extend(self.popups_of(id));
+        }
+
+        for &click in clicks {
+            // Keep popups under the cursor along with every popup they hang off.
+            let mut keep = HashSet::new();
+            for &popup in self.popups.keys() {
+                let hit = self
+                    .windows
+                    .get(&popup)
+                    .is_some_and(|e| super::popup::contains_cursor(&e.window, click));
+                if hit {
+                    let mut current = Some(popup);
+                    while let Some(id) = current {
+                        keep.insert(id);
+                        current = self.popups.get(&id).copied().flatten();
+                    }
+                }
+            }
+            dismiss.extend(self.popups.keys().filter(|id| !keep.contains(*id)));
+        }
+
+        if !blurred.is_empty() {
+            let is_focused = |id: &u32| self.windows.get(id).is_some_and(|e| e.window.is_focused());
+            for &popup in self.popups.keys() {
+                let root = self.popup_root(popup);
+                let mut family = self.popups_of(root);
+                family.push(root);
+                let lost_focus = family.iter().any(|id| blurred.contains(id));
+                if lost_focus && !family.iter().any(is_focused) {
+                    dismiss.push(popup);
+                }
+            }
+        }
+
+        dismiss.sort_unstable();
+        dismiss.dedup();
+        dismiss
+    }
+
+    // ── Event loop pumping ─────────────────────────────────────
+
+    /// Pump the tao event loop (non-blocking). Processes all pending OS events
+    /// and pushes them to PENDING_* deferred callback buffers.
+    ///
+    /// Uses a two-phase approach:
+    /// - Phase A: `run_return` dispatches tao-level events (WindowEvent, etc.)
+    /// - Phase B (macOS): raw NSApp event drain for cascading WebKit events
+    ///
+    /// Phase B is needed because tao's `run_return` processes one CFRunLoop
+    /// iteration via `[NSApp run]`, but WebKit operations (network → parse →
+    /// render) generate cascading events that need additional iterations.
+    /// Without the drain, each step waits 16ms for the next pump call.
+    pub fn pump_events(&mut self) {
+        // Popup dismissal triggers collected during dispatch.
+        let mut anchors_changed: Vec<u32> = Vec::new();
+        let mut blurred: Vec<u32> = Vec::new();
+        let mut clicks: Vec<PhysicalPosition<f64>> = Vec::new();
+
+        // Phase A: tao event dispatch
+        EVENT_LOOP.with(|el| {
+            let mut event_loop_opt = el.borrow_mut().take();
+            if let Some(ref mut event_loop) = event_loop_opt {
+                let window_id_map = &self.window_id_map;
+                let windows = &self.windows;
+                let geometry_changes = &mut self.geometry_changes;
+                let track_popups = !self.popups.is_empty();
+
+                event_loop.run_return(|event, _target, control_flow| {
+                    // Ensure non-blocking from the start, regardless of any
+                    // stale ControlFlow persisted in tao's global Handler.
+                    *control_flow = ControlFlow::Poll;
+
+                    match event {
+                        Event::WindowEvent {
+                            window_id,
+                            event: ref win_event,
+                            ..
+                        } => {
+                            if let Some(&id) = window_id_map.get(&window_id) {
+                                match win_event {
+                                    WindowEvent::Resized(size) => {
+                                        let scale = windows
+                                            .get(&id)
+                                            .map(|e| e.window.scale_factor())
+                                            .unwrap_or(1.0);
+                                        let logical: LogicalSize<f64> = size.to_logical(scale);
+                                        if is_event_enabled(id, EVENT_RESIZE) {
+                                            capped_push!(
+                                                PENDING_RESIZE_CALLBACKS,
+                                                (id, logical.width, logical.height),
+                                                "PENDING_RESIZE_CALLBACKS"
+                                            );
+                                        }
+                                        note_geometry_change(geometry_changes, id, true);
+                                        if track_popups {
+                                            anchors_changed.push(id);
+                                        }
+                                    }
+                                    WindowEvent::Moved(pos) => {
+                                        let scale = windows
+                                            .get(&id)
+                                            .map(|e| e.window.scale_factor())
+                                            .unwrap_or(1.0);
+                                        let logical: LogicalPosition<f64> = pos.to_logical(scale);
+                                        if is_event_enabled(id, EVENT_MOVE) {
+                                            capped_push!(
+                                                PENDING_MOVES,
+                                                (id, logical.x, logical.y),
+                                                "PENDING_MOVES"
+                                            );
+                                        }
+                                        note_geometry_change(geometry_changes, id, false);
+                                        if track_popups {
+                                            anchors_changed.push(id);
+                                        }
+                                    }
+                                    WindowEvent::ScaleFactorChanged { scale_factor, .. } => {
+                                        capped_push!(
+                                            PENDING_SCALE_FACTOR_CHANGES,
+                                            (id, *scale_factor),
+                                            "PENDING_SCALE_FACTOR_CHANGES"
+                                        );
+                                    }
+                                    WindowEvent::Focused(focused) => {
+                                        if *focused {
+                                            if is_event_enabled(id, EVENT_FOCUS) {
+                                                capped_push!(
+                                                    PENDING_FOCUSES,
+                                                    id,
+                                                    "PENDING_FOCUSES"
+                                                );
+                                            }
+                                        } else {
+                                            if is_event_enabled(id, EVENT_BLUR) {
+                                                capped_push!(PENDING_BLURS, id, "PENDING_BLURS");
+                                            }
+                                            if track_popups {
+                                                blurred.push(id);
+                                            }
+                                        }
+                                    }
+                                    WindowEvent::ThemeChanged(theme) => {
+                                        capped_push!(
+                                            PENDING_THEME_CHANGES,
+                                            (id, theme_name(*theme)),
+                                            "PENDING_THEME_CHANGES"
+                                        );
+                                    }
+                                    WindowEvent::CloseRequested => {
+                                        // Intercepted: JS decides whether to close.
+                                        let intercepted =
+                                            CLOSE_INTERCEPTED.with(|c| c.borrow().contains(&id));
+                                        let to_tray =
+                                            MINIMIZE_TO_TRAY.with(|t| t.borrow().contains(&id));
+                                        if to_tray {
+                                            if let Some(entry) = windows.get(&id) {
+                                                entry.window.set_visible(false);
+                                            }
+                                        } else if intercepted {
+                                            capped_push!(
+                                                PENDING_CLOSE_REQUESTS,
+                                                id,
+                                                "PENDING_CLOSE_REQUESTS"
+                                            );
+                                        } else {
+                                            capped_push!(PENDING_CLOSES, id, "PENDING_CLOSES");
+                                            if track_popups {
+                                                anchors_changed.push(id);
+                                            }
+                                        }
+                                    }
+                                    _ => {}
+                                }
+                            }
+                        }
+                        Event::DeviceEvent {
+                            event:
+                                DeviceEvent::Button {
+                                    state: ElementState::Pressed,
+                                    ..
+                                },
+                            ..
+                        } if track_popups => {
+                            // Any window can report the global cursor position.
+                            if let Some(entry) = windows.values().next() {
+                                if let Ok(cursor) = entry.window.cursor_position() {
+                                    clicks.push(cursor);
+                                }
+                            }
+                        }
+                        Event::MainEventsCleared => {
+                            *control_flow = ControlFlow::Exit;
+                        }
+                        _ => {}
+                    }
+                });
+            }
+            // Put the event loop back
+            *el.borrow_mut() = event_loop_opt;
+        });
+
+        for id in self.popups_to_dismiss(&anchors_changed, &blurred, &clicks) {
+            // Closed like an OS close request: destroyed by
+            // destroy_pending_closes() and reported via onClose.
+            let pending = PENDING_CLOSES.with(|p| p.borrow().contains(&id));
+            if !pending {
+                capped_push!(PENDING_CLOSES, id, "PENDING_CLOSES");
+            }
+        }
+
+        self.report_geometry_ends();
+        self.apply_title_templates();
+        self.rescue_offscreen_windows();
+        self.hide_minimized_to_tray();
+        self.show_fallbacks();
+        self.deliver_audio();
+        self.start_drags();
+        #[cfg(not(target_os = "windows"))]
+        self.reapply_emulation();
+        self.reapply_priority();
+        self.poll_global_mouse();
+        #[cfg(target_os = "macos")]
+        self.poll_webview_focus();
+        #[cfg(any(target_os = "macos", target_os = "windows"))]
+        self.poll_active_space();
+        #[cfg(target_os = "linux")]
+        self.poll_themes();
+
+        #[cfg(target_os = "windows")]
+        super::menu::poll_alt_key();
+
+        // Phase B: drain remaining platform events for WebKit processing
+        #[cfg(target_os = "macos")]
+        self.drain_macos_events();
+    }
+
+    /// Report resizes and moves whose drag has ended, with the final size
+    /// or position.
+    fn report_geometry_ends(&mut self) {
+        let windows = &self.windows;
+        self.geometry_changes.retain(|&id, change| {
+            let Some(entry) = windows.get(&id) else {
+                return false;
+            };
+            if super::live_drag::in_progress(&entry.window, change.last_change) {
+                return true;
+            }
+            let scale = entry.window.scale_factor();
+            if change.resized {
+                let size: LogicalSize<f64> = entry.window.inner_size().to_logical(scale);
+                capped_push!(
+                    PENDING_RESIZE_ENDS,
+                    (id, size.width, size.height),
+                    "PENDING_RESIZE_ENDS"
+                );
+            }
+            if change.moved {
+                if let Ok(position) = entry.window.outer_position() {
+                    let position: LogicalPosition<f64> = position.to_logical(scale);
+                    capped_push!(
+                        PENDING_MOVE_ENDS,
+                        (id, position.x, position.y),
+                        "PENDING_MOVE_ENDS"
+                    );
+                }
+            }
+            false
+        });
+    }
+
+    /// Record the document titles reported during this pump, and retitle
+    /// windows that have a title template.
+    fn apply_title_templates(&mut self) {
+        for (id, title) in DOCUMENT_TITLES.with(|t| std::mem::take(&mut *t.borrow_mut())) {
+            let Some(entry) = self.windows.get(&id) else {
+                continue;
+            };
+            if let Some(template) = self.title_templates.get(&id) {
+                entry
+                    .window
+                    .set_title(&apply_title_template(template, &title));
+            }
+            self.document_titles.insert(id, title);
+        }
+    }
+
+    /// Report webviews that gained or lost keyboard focus since the last pump.
+    #[cfg(target_os = "macos")]
+    fn poll_webview_focus(&mut self) {
+        for (&id, entry) in &self.windows {
+            let focused = super::focus::has_focus(&entry.webview);
+            if focused != self.webview_focus.contains(&id) {
+                if focused {
+                    self.webview_focus.insert(id);
+                } else {
+                    self.webview_focus.remove(&id);
+                }
+                super::focus::report(id, focused);
+            }
+        }
+    }
+
+    /// Report the global cursor position to windows tracking it, when it
+    /// moved and the window's throttle interval has passed.
+    fn poll_global_mouse(&mut self) {
+        let now = Instant::now();
+        for (&id, tracking) in &mut self.mouse_tracking {
+            if tracking
+                .last_poll
+                .is_some_and(|t| now.duration_since(t) < tracking.interval)
+            {
+                continue;
+            }
+            let Some(entry) = self.windows.get(&id) else {
+                continue;
+            };
+            tracking.last_poll = Some(now);
+            let Ok(cursor) = entry.window.cursor_position() else {
+                continue;
+            };
+            // Use the scale of the display under the cursor, which may not be
+            // the one showing the window.
+            let scale = entry
+                .window
+                .monitor_from_point(cursor.x, cursor.y)
+                .map(|m| m.scale_factor())
+                .unwrap_or_else(|| entry.window.scale_factor());
+            let logical: LogicalPosition<f64> = cursor.to_logical(scale);
+            let position = (logical.x, logical.y);
+            if tracking.position.replace(position) != Some(position) {
+                capped_push!(
+                    PENDING_GLOBAL_MOUSE_MOVES,
+                    (id, logical.x, logical.y),
+                    "PENDING_GLOBAL_MOUSE_MOVES"
+                );
+            }
+        }
+    }
+
+    /// Report windows that entered or left the active Space / virtual
+    /// desktop since the last poll. A window's first poll only records its
+    /// state. On Windows, pinned windows first follow the user to the
+    /// current desktop.
+    #[cfg(any(target_os = "macos", target_os = "windows"))]
+    fn poll_active_space(&mut self) {
+        #[cfg(target_os = "windows")]
+        {
+            const INTERVAL: Duration = Duration::from_millis(250);
+            if self.last_desktop_poll.elapsed() < INTERVAL {
+                return;
+            }
+            self.last_desktop_poll = Instant::now();
+            super::virtual_desktop::follow_current();
+        }
+        for (&id, entry) in &self.windows {
+            let active = is_on_active_workspace(&entry.window);
+            if let Some(was) = self.active_space.insert(id, active) {
+                if was != active {
+                    capped_push!(
+                        PENDING_WORKSPACE_CHANGES,
+                        (id, active),
+                        "PENDING_WORKSPACE_CHANGES"
+                    );
+                }
+            }
+        }
+    }
+
+    /// Report GTK theme changes, which tao does not on Linux.
+    #[cfg(target_os = "linux")]
+    fn poll_themes(&mut self) {
+        const INTERVAL: Duration = Duration::from_secs(1);
+        if self.last_theme_poll.elapsed() < INTERVAL {
+            return;
+        }
+        self.last_theme_poll = Instant::now();
+        for (&id, entry) in &self.windows {
+            let theme = entry.window.theme();
+            if let Some(was) = self.themes.insert(id, theme) {
+                if was != theme {
+                    capped_push!(
+                        PENDING_THEME_CHANGES,
+                        (id, theme_name(theme)),
+                        "PENDING_THEME_CHANGES"
+                    );
+                }
+            }
+        }
+    }
+
+    // ── Audio capture ──────────────────────────────────────────
+
+    /// Move audio recorded by capture threads to the event buffer.
+    fn deliver_audio(&self) {
+        use super::audio_capture::AudioEvent;
+
+        for (id, event) in super::audio_capture::take_events() {
+            let item = match event {
+                AudioEvent::Data(pcm) => (id, Some(pcm), None),
+                AudioEvent::End(error) => (id, None, error),
+            };
+            capped_push!(PENDING_AUDIO_CAPTURES, item, "PENDING_AUDIO_CAPTURES");
+        }
+    }
+
+    /// Start the window drags requested by drag regions during this pump.
+    /// The mouse button is still down, so the OS move loop takes over.
+    fn start_drags(&self) {
+        for id in DRAG_REQUESTS.with(|d| std::mem::take(&mut *d.borrow_mut())) {
+            if let Some(entry) = self.windows.get(&id) {
+                if let Err(e) = entry.window.drag_window() {
+                    eprintln!("[native-window] Window {}: failed to start drag: {}", id, e);
+                }
+            }
+        }
+    }
+
+    // ── Fallback pages ─────────────────────────────────────────
+
+    /// Replace pages that failed to load during this pump with the
+    /// window's `setFallbackHtml()` document, served like `loadHtml()`.
+    fn show_fallbacks(&self) {
+        for (id, url, code) in super::load_errors::take_failures() {
+            let (Some(entry), Some(html)) = (
+                self.windows.get(&id),
+                crate::window_manager::get_fallback_html(id),
+            ) else {
+                continue;
+            };
+            // The fallback itself is served locally and cannot fail, but
+            // never loop on it.
+            if url.starts_with(custom_protocol_url()) {
+                continue;
+            }
+            crate::window_manager::set_html_content(id, "/", html);
+            if let Err(e) = entry.webview.load_url(custom_protocol_url()) {
+                eprintln!(
+                    "[native-window] Window {}: failed to show fallback: {}",
+                    id, e
+                );
+                continue;
+            }
+            capped_push!(
+                PENDING_FALLBACKS_SHOWN,
+                (id, url, code.to_string()),
+                "PENDING_FALLBACKS_SHOWN"
+            );
+        }
+    }
+
+    // ── Emulation ──────────────────────────────────────────────
+
+    /// Re-apply emulation shims to pages that finished loading
+    /// during this pump (a new document starts without them).
+    #[cfg(not(target_os = "windows"))]
+    fn reapply_emulation(&self) {
+        if self.emulation.is_empty() {
+            return;
+        }
+        PENDING_PAGE_LOADS.with(|p| {
+            for (id, event, _) in p.borrow().iter() {
+                if event != "finished" {
+                    continue;
+                }
+                if let (Some(entry), Some(emulation)) =
+                    (self.windows.get(id), self.emulation.get(id))
+                {
+                    apply_emulation(&entry.webview, emulation);
+                }
+            }
+        });
+    }
+
+    // ── Process priority ───────────────────────────────────────
+
+    /// Re-apply background priority to windows whose page finished loading
+    /// during this pump.
+    fn reapply_priority(&self) {
+        if self.background_windows.is_empty() {
+            return;
+        }
+        PENDING_PAGE_LOADS.with(|p| {
+            for (id, event, _) in p.borrow().iter() {
+                if event != "finished" || !self.background_windows.contains(id) {
+                    continue;
+                }
+                if let Some(entry) = self.windows.get(id) {
+                    if let Err(e) = super::priority::set_priority(&entry.webview, true) {
+                        eprintln!("[native-window] setPriority failed: {}", e);
+                    }
+                }
+            }
+        });
+    }
+
+    // ── Minimize to tray ───────────────────────────────────────
 
-            Ok(())
-        })
+    /// Hide `minimizeToTray` windows that the user minimized. They stay
+    /// minimized while hidden, so `restoreFromTray()` un-minimizes them.
+    fn hide_minimized_to_tray(&self) {
+        MINIMIZE_TO_TRAY.with(|t| {
+            for id in t.borrow().iter() {
+                if let Some(entry) = self.windows.get(id) {
+                    if entry.window.is_visible() && entry.window.is_minimized() {
+                        entry.window.set_visible(false);
+                    }
+                }
+            }
+        });
     }
 
-    // ── Event loop pumping ─────────────────────────────────────
+    // ── Off-screen rescue ──────────────────────────────────────
 
-    /// Pump the tao event loop (non-blocking). Processes all pending OS events
-    /// and pushes them to PENDING_* deferred callback buffers.
-    ///
-    /// Uses a two-phase approach:
-    /// - Phase A: `run_return` dispatches tao-level events (WindowEvent, etc.)
-    /// - Phase B (macOS): raw NSApp event drain for cascading WebKit events
-    ///
-    /// Phase B is needed because tao's `run_return` processes one CFRunLoop
-    /// iteration via `[NSApp run]`, but WebKit operations (network → parse →
-    /// render) generate cascading events that need additional iterations.
-    /// Without the drain, each step waits 16ms for the next pump call.
-    pub fn pump_events(&mut self) {
-        // Phase A: tao event dispatch
-        EVENT_LOOP.with(|el| {
-            let mut event_loop_opt = el.borrow_mut().take();
-            if let Some(ref mut event_loop) = event_loop_opt {
-                let window_id_map = &self.window_id_map;
-                let windows = &self.windows;
+    /// Move windows that ended up entirely outside every display (a monitor
+    /// was unplugged, or the resolution dropped) back to their last visible
+    /// bounds, or centered on the primary display's work area if those are
+    /// gone too. Checked at most once per second.
+    fn rescue_offscreen_windows(&mut self) {
+        const INTERVAL: Duration = Duration::from_secs(1);
+        if self.windows.is_empty() || self.last_rescue_check.elapsed() < INTERVAL {
+            return;
+        }
+        self.last_rescue_check = Instant::now();
 
-                event_loop.run_return(|event, _target, control_flow| {
-                    // Ensure non-blocking from the start, regardless of any
-                    // stale ControlFlow persisted in tao's global Handler.
-                    *control_flow = ControlFlow::Poll;
+        for (&id, entry) in &self.windows {
+            let window = &entry.window;
+            // Minimized windows report a parked off-screen position on Windows.
+            if !window.is_visible() || window.is_minimized() {
+                continue;
+            }
+            let Some(bounds) = super::display::outer_bounds(window) else {
+                continue;
+            };
+            let monitors: Vec<_> = window
+                .available_monitors()
+                .map(|m| super::display::bounds(&m))
+                .collect();
+            // No monitor info at all (e.g. display asleep): leave it be.
+            if monitors.is_empty() {
+                continue;
+            }
+            let visible = |r: super::display::Rect| {
+                monitors.iter().any(|&m| super::display::overlap(r, m) > 0)
+            };
 
-                    match event {
-                        Event::WindowEvent {
-                            window_id,
-                            event: ref win_event,
-                            ..
-                        } => {
-                            if let Some(&id) = window_id_map.get(&window_id) {
-                                match win_event {
-                                    WindowEvent::Resized(size) => {
-                                        let scale = windows
-                                            .get(&id)
-                                            .map(|e| e.window.scale_factor())
-                                            .unwrap_or(1.0);
-                                        let logical: LogicalSize<f64> = size.to_logical(scale);
-                                        capped_push!(
-                                            PENDING_RESIZE_CALLBACKS,
-                                            (id, logical.width, logical.height),
-                                            "PENDING_RESIZE_CALLBACKS"
-                                        );
-                                    }
-                                    WindowEvent::Moved(pos) => {
-                                        let scale = windows
-                                            .get(&id)
-                                            .map(|e| e.window.scale_factor())
-                                            .unwrap_or(1.0);
-                                        let logical: LogicalPosition<f64> = pos.to_logical(scale);
-                                        capped_push!(
-                                            PENDING_MOVES,
-                                            (id, logical.x, logical.y),
-                                            "PENDING_MOVES"
-                                        );
-                                    }
-                                    WindowEvent::Focused(focused) => {
-                                        if *focused {
-                                            capped_push!(PENDING_FOCUSES, id, "PENDING_FOCUSES");
-                                        } else {
-                                            capped_push!(PENDING_BLURS, id, "PENDING_BLURS");
-                                        }
-                                    }
-                                    WindowEvent::CloseRequested => {
-                                        capped_push!(PENDING_CLOSES, id, "PENDING_CLOSES");
-                                    }
-                                    _ => {}
-                                }
-                            }
-                        }
-                        Event::MainEventsCleared => {
-                            *control_flow = ControlFlow::Exit;
-                        }
-                        _ => {}
-                    }
+            if visible(bounds) {
+                LAST_GOOD_BOUNDS.with(|b| {
+                    b.borrow_mut()
+                        .insert(id, (bounds.x, bounds.y, bounds.width, bounds.height));
                 });
+                continue;
             }
-            // Put the event loop back
-            *el.borrow_mut() = event_loop_opt;
-        });
 
-        // Phase B: drain remaining platform events for WebKit processing
-        #[cfg(target_os = "macos")]
-        self.drain_macos_events();
+            let last_good = LAST_GOOD_BOUNDS
+                .with(|b| b.borrow().get(&id).copied())
+                .map(|(x, y, width, height)| super::display::Rect {
+                    x,
+                    y,
+                    width,
+                    height,
+                })
+                .filter(|&r| visible(r));
+            let target = match last_good {
+                Some(r) => PhysicalPosition::new(r.x, r.y),
+                None => {
+                    let Some(monitor) = window
+                        .primary_monitor()
+                        .or_else(|| window.available_monitors().next())
+                    else {
+                        continue;
+                    };
+                    let work = super::display::work_area(&monitor);
+                    PhysicalPosition::new(
+                        work.x + ((work.width - bounds.width) / 2).max(0),
+                        work.y + ((work.height - bounds.height) / 2).max(0),
+                    )
+                }
+            };
+            window.set_outer_position(target);
+
+            let logical: LogicalPosition<f64> = target.to_logical(window.scale_factor());
+            capped_push!(
+                PENDING_RESCUES,
+                (id, logical.x, logical.y),
+                "PENDING_RESCUES"
+            );
+        }
     }
 
     /// Drain remaining events and run-loop sources after `run_return`.
@@ -903,6 +3313,326 @@ document.addEventListener('DOMContentLoaded', function () {{
     }
 }
 
+// ── Resize and move ends ───────────────────────────────────────
+
+/// Record a resize (`resized`) or move of window `id`, to report its end.
+fn note_geometry_change(changes: &mut HashMap<u32, GeometryChange>, id: u32, resized: bool) {
+    let change = changes.entry(id).or_insert(GeometryChange {
+        resized: false,
+        moved: false,
+        last_change: Instant::now(),
+    });
+    if resized {
+        change.resized = true;
+    } else {
+        change.moved = true;
+    }
+    change.last_change = Instant::now();
+}
+
+// ── Title templates ────────────────────────────────────────────
+
+/// Placeholder replaced with the document title in `setTitleTemplate()`.
+const PAGE_TITLE_PLACEHOLDER: &str = "{{pageTitle}}";
+
+/// Compose a native window title from a template and the page's title.
+fn apply_title_template(template: &str, page_title: &str) -> String {
+    template.replace(PAGE_TITLE_PLACEHOLDER, page_title)
+}
+
+// ── Fullscreen ─────────────────────────────────────────────────
+
+/// Enter or leave fullscreen on the window's current monitor.
+///
+/// On macOS, tao's borderless fullscreen is the native Spaces fullscreen
+/// (`toggleFullScreen:`); `borderless` selects "simple" fullscreen instead,
+/// which covers the screen without creating a Space. Other platforms have
+/// no such distinction and ignore it.
+fn set_fullscreen(window: &Window, fullscreen: bool, borderless: bool) {
+    #[cfg(target_os = "macos")]
+    {
+        use tao::platform::macos::WindowExtMacOS;
+        if borderless {
+            window.set_simple_fullscreen(fullscreen);
+            return;
+        }
+    }
+    #[cfg(not(target_os = "macos"))]
+    let _ = borderless;
+
+    window.set_fullscreen(fullscreen.then_some(tao::window::Fullscreen::Borderless(None)));
+}
+
+// ── Moving between displays ────────────────────────────────────
+
+/// Move a window to `monitor`. Maximized and fullscreen windows are
+/// maximized or made fullscreen there; others keep their place in the
+/// work area (see [`place_on_display`]).
+fn move_to_display(window: &Window, monitor: &MonitorHandle) -> napi::Result<()> {
+    #[cfg(target_os = "macos")]
+    {
+        use tao::platform::macos::WindowExtMacOS;
+        if window.simple_fullscreen() {
+            window.set_simple_fullscreen(false);
+            place_on_display(window, monitor);
+            window.set_simple_fullscreen(true);
+            return Ok(());
+        }
+        // A Spaces fullscreen window owns a Space on its display; tao can
+        // only choose the display when entering fullscreen.
+        if window.fullscreen().is_some() {
+            return Err(napi::Error::from_reason(
+                "A window in native fullscreen cannot move to another display; leave fullscreen first",
+            ));
+        }
+    }
+    #[cfg(not(target_os = "macos"))]
+    {
+        if window.fullscreen().is_some() {
+            window.set_fullscreen(Some(tao::window::Fullscreen::Borderless(Some(
+                monitor.clone(),
+            ))));
+            return Ok(());
+        }
+    }
+
+    let maximized = window.is_maximized();
+    if maximized {
+        window.set_maximized(false);
+    }
+    place_on_display(window, monitor);
+    if maximized {
+        window.set_maximized(true);
+    }
+    Ok(())
+}
+
+/// Place a window at the same offset into `monitor`'s work area as it has
+/// into its current monitor's, scaled by the DPI ratio and kept inside.
+fn place_on_display(window: &Window, monitor: &MonitorHandle) {
+    let Some(bounds) = super::display::outer_bounds(window) else {
+        return;
+    };
+    let target = super::display::work_area(monitor);
+    let (dx, dy) = match window.current_monitor() {
+        Some(current) => {
+            let work = super::display::work_area(&current);
+            let ratio = monitor.scale_factor() / current.scale_factor();
+            (
+                ((bounds.x - work.x) as f64 * ratio).round() as i32,
+                ((bounds.y - work.y) as f64 * ratio).round() as i32,
+            )
+        }
+        None => (0, 0),
+    };
+    let x = target.x + dx.clamp(0, (target.width - bounds.width).max(0));
+    let y = target.y + dy.clamp(0, (target.height - bounds.height).max(0));
+    window.set_outer_position(PhysicalPosition::new(x, y));
+}
+
+// ── Taskbar ────────────────────────────────────────────────────
+
+/// Remove the window's taskbar button (Windows/Linux), or exclude it from
+/// window cycling with Cmd+` (macOS). The Dock icon and Cmd+Tab entry
+/// belong to the app, not the window, so macOS keeps showing them.
+fn set_skip_taskbar(window: &Window, skip: bool) {
+    #[cfg(target_os = "windows")]
+    {
+        use tao::platform::windows::WindowExtWindows;
+        if let Err(e) = window.set_skip_taskbar(skip) {
+            eprintln!("[native-window] setSkipTaskbar failed: {}", e);
+        }
+    }
+    #[cfg(target_os = "linux")]
+    {
+        use tao::platform::unix::WindowExtUnix;
+        if let Err(e) = window.set_skip_taskbar(skip) {
+            eprintln!("[native-window] setSkipTaskbar failed: {}", e);
+        }
+    }
+    #[cfg(target_os = "macos")]
+    {
+        use objc2_app_kit::{NSWindow, NSWindowCollectionBehavior};
+        use tao::platform::macos::WindowExtMacOS;
+
+        let ns_window = window.ns_window() as *const NSWindow;
+        if let Some(ns_window) = unsafe { ns_window.as_ref() } {
+            let mut behavior = ns_window.collectionBehavior();
+            behavior.set(NSWindowCollectionBehavior::IgnoresCycle, skip);
+            ns_window.setCollectionBehavior(behavior);
+        }
+    }
+}
+
+/// Put an `NSVisualEffectView` with `material` behind the webview and
+/// stop the webview drawing its white background, so transparent parts
+/// of the page show the blurred material. `drawsBackground` is private
+/// WebKit API (wry's `transparent` feature uses the same key), so it is
+/// only touched when `vibrancy` is set.
+#[cfg(target_os = "macos")]
+fn apply_vibrancy(window: &Window, webview: &WebView, material: &str) {
+    use objc2::msg_send;
+    use objc2_app_kit::{
+        NSAutoresizingMaskOptions, NSVisualEffectBlendingMode, NSVisualEffectMaterial,
+        NSVisualEffectState, NSVisualEffectView, NSWindow, NSWindowOrderingMode,
+    };
+    use objc2_foundation::{ns_string, MainThreadMarker, NSNumber};
+    use tao::platform::macos::WindowExtMacOS;
+    use wry::WebViewExtMacOS;
+
+    let Some(mtm) = MainThreadMarker::new() else {
+        return;
+    };
+    let ns_window = window.ns_window() as *const NSWindow;
+    let Some(content) = unsafe { ns_window.as_ref() }.and_then(|w| w.contentView()) else {
+        return;
+    };
+    // Names follow Electron's `vibrancy` values.
+    let material = match material {
+        "titlebar" => NSVisualEffectMaterial::Titlebar,
+        "selection" => NSVisualEffectMaterial::Selection,
+        "menu" => NSVisualEffectMaterial::Menu,
+        "popover" => NSVisualEffectMaterial::Popover,
+        "sidebar" => NSVisualEffectMaterial::Sidebar,
+        "header" => NSVisualEffectMaterial::HeaderView,
+        "sheet" => NSVisualEffectMaterial::Sheet,
+        "window" => NSVisualEffectMaterial::WindowBackground,
+        "hud" => NSVisualEffectMaterial::HUDWindow,
+        "fullscreen-ui" => NSVisualEffectMaterial::FullScreenUI,
+        "tooltip" => NSVisualEffectMaterial::ToolTip,
+        "content" => NSVisualEffectMaterial::ContentBackground,
+        "under-page" => NSVisualEffectMaterial::UnderPageBackground,
+        _ => NSVisualEffectMaterial::UnderWindowBackground,
+    };
+
+    let effect =
+        NSVisualEffectView::initWithFrame(NSVisualEffectView::alloc(mtm), content.bounds());
+    effect.setMaterial(material);
+    effect.setBlendingMode(NSVisualEffectBlendingMode::BehindWindow);
+    effect.setState(NSVisualEffectState::FollowsWindowActiveState);
+    effect.setAutoresizingMask(
+        NSAutoresizingMaskOptions::ViewWidthSizable | NSAutoresizingMaskOptions::ViewHeightSizable,
+    );
+    content.addSubview_positioned_relativeTo(&effect, NSWindowOrderingMode::Below, None);
+
+    let no = NSNumber::new_bool(false);
+    let _: () = unsafe {
+        msg_send![&*webview.webview(), setValue: &*no, forKey: ns_string!("drawsBackground")]
+    };
+}
+
+/// tao's preference for a validated `theme` value; `None` follows the OS.
+fn parse_theme(theme: &str) -> Option<Theme> {
+    match theme {
+        "light" => Some(Theme::Light),
+        "dark" => Some(Theme::Dark),
+        _ => None,
+    }
+}
+
+/// The WebView2 color scheme for a validated `theme` value.
+#[cfg(target_os = "windows")]
+fn webview_theme(theme: &str) -> wry::Theme {
+    match theme {
+        "light" => wry::Theme::Light,
+        "dark" => wry::Theme::Dark,
+        _ => wry::Theme::Auto,
+    }
+}
+
+/// The name `onThemeChanged` reports for a tao theme.
+fn theme_name(theme: Theme) -> &'static str {
+    match theme {
+        Theme::Dark => "dark",
+        _ => "light",
+    }
+}
+
+/// Apply a `cornerStyle` through DWM. Windows 10 does not know the
+/// attribute and rejects it, which leaves its square corners as they are.
+#[cfg(target_os = "windows")]
+fn set_corner_style(hwnd: isize, style: &str) {
+    use windows::Win32::Foundation::HWND;
+    use windows::Win32::Graphics::Dwm::{
+        DwmSetWindowAttribute, DWMWA_WINDOW_CORNER_PREFERENCE, DWMWCP_DEFAULT, DWMWCP_DONOTROUND,
+        DWMWCP_ROUND,
+    };
+
+    let preference = match style {
+        "round" => DWMWCP_ROUND,
+        "square" => DWMWCP_DONOTROUND,
+        _ => DWMWCP_DEFAULT,
+    };
+    let _ = unsafe {
+        DwmSetWindowAttribute(
+            HWND(hwnd as _),
+            DWMWA_WINDOW_CORNER_PREFERENCE,
+            &preference as *const _ as *const std::ffi::c_void,
+            std::mem::size_of_val(&preference) as u32,
+        )
+    };
+}
+
+// ── Workspaces ─────────────────────────────────────────────────
+
+/// Whether `window` is on the macOS Space / Windows virtual desktop the
+/// user is looking at. Linux does not report workspace membership, so it
+/// is always `true` there.
+fn is_on_active_workspace(window: &Window) -> bool {
+    #[cfg(target_os = "macos")]
+    {
+        use objc2_app_kit::NSWindow;
+        use tao::platform::macos::WindowExtMacOS;
+
+        let ns_window = window.ns_window() as *const NSWindow;
+        match unsafe { ns_window.as_ref() } {
+            Some(ns_window) => ns_window.isOnActiveSpace(),
+            None => true,
+        }
+    }
+    #[cfg(target_os = "windows")]
+    {
+        use tao::platform::windows::WindowExtWindows;
+        super::virtual_desktop::is_on_current(window.hwnd())
+    }
+    #[cfg(target_os = "linux")]
+    {
+        let _ = window;
+        true
+    }
+}
+
+/// Whether `window` is shown on every Space (macOS), workspace (Linux) or
+/// virtual desktop (Windows, `pinToAllDesktops()`).
+fn is_visible_on_all_workspaces(window: &Window) -> bool {
+    #[cfg(target_os = "macos")]
+    {
+        use objc2_app_kit::{NSWindow, NSWindowCollectionBehavior};
+        use tao::platform::macos::WindowExtMacOS;
+
+        let ns_window = window.ns_window() as *const NSWindow;
+        unsafe { ns_window.as_ref() }.is_some_and(|w| {
+            w.collectionBehavior()
+                .contains(NSWindowCollectionBehavior::CanJoinAllSpaces)
+        })
+    }
+    #[cfg(target_os = "linux")]
+    {
+        use gtk::prelude::*;
+        use tao::platform::unix::WindowExtUnix;
+
+        window
+            .gtk_window()
+            .window()
+            .is_some_and(|w| w.state().contains(gtk::gdk::WindowState::STICKY))
+    }
+    #[cfg(target_os = "windows")]
+    {
+        use tao::platform::windows::WindowExtWindows;
+        super::virtual_desktop::is_pinned(window.hwnd())
+    }
+}
+
 // ── macOS Edit menu setup ──────────────────────────────────────
 
 /// On macOS, set up the Edit menu so standard keyboard shortcuts
@@ -915,6 +3645,399 @@ fn setup_macos_menu() {
     // macOS-only dependency and create the NSMenu directly.
 }
 
+// ── Text search ────────────────────────────────────────────────
+
+/// Build a script that counts case-insensitive, non-overlapping occurrences
+/// of `text` across the page's rendered text nodes.
+///
+/// Walks text nodes with a `TreeWalker` instead of using `window.find()` so
+/// the viewport and selection are left untouched. `<script>` / `<style>`
+/// contents are skipped. The script evaluates to a number (0 on error).
+fn count_matches_script(text: &str) -> String {
+    format!(
+        r#"(function (q) {{
+  try {{
+    q = q.toLowerCase();
+    if (!q || !document.body) return 0;
+    var n = 0;
+    var walker = document.createTreeWalker(document.body, NodeFilter.SHOW_TEXT, {{
+      acceptNode: function (node) {{
+        var p = node.parentNode;
+        var tag = p && p.nodeName;
+        return tag === "SCRIPT" || tag === "STYLE" || tag === "NOSCRIPT"
+          ? NodeFilter.FILTER_REJECT
+          : NodeFilter.FILTER_ACCEPT;
+      }},
+    }});
+    while (walker.nextNode()) {{
+      var s = walker.currentNode.nodeValue.toLowerCase();
+      var i = s.indexOf(q);
+      while (i !== -1) {{
+        n++;
+        i = s.indexOf(q, i + q.length);
+      }}
+    }}
+    return n;
+  }} catch (e) {{
+    return null;
+  }}
+}})({});"#,
+        json_escape(text)
+    )
+}
+
+// ── Emulation ──────────────────────────────────────────────────
+
+/// Install (once per document) and update the emulation shims.
+/// `null` restores the host default for that setting.
+#[cfg(not(target_os = "windows"))]
+fn apply_emulation(webview: &WebView, emulation: &Emulation) {
+    let json = |v: &Option<String>| v.as_deref().map_or("null".to_string(), json_escape);
+    let network = match emulation.network {
+        Some(ref c) => format!(
+            "{{\"offline\":{},\"latency\":{}}}",
+            c.offline.unwrap_or(false),
+            c.latency_ms.unwrap_or(0.0)
+        ),
+        None => "null".to_string(),
+    };
+    let script = format!(
+        "{}({}, {});{}({});",
+        EMULATION_SCRIPT,
+        json(&emulation.timezone),
+        json(&emulation.locale),
+        NETWORK_SCRIPT,
+        network
+    );
+    if let Err(e) = webview.evaluate_script(&script) {
+        eprintln!("[native-window] Failed to apply emulation: {}", e);
+    }
+}
+
+/// JS shim for engines without CDP emulation (WebKit). Defaults the time
+/// zone and locale of `Intl` formatters and `Date`/`Number` locale methods,
+/// derives `getTimezoneOffset()` from the emulated zone, and overrides
+/// `navigator.language(s)`. Other `Date` getters keep the host zone.
+#[cfg(not(target_os = "windows"))]
+const EMULATION_SCRIPT: &str = r#"(function (tz, locale) {
+  var state = window.__nativeWindowEmulation;
+  if (!state) {
+    state = { tz: null, locale: null };
+    Object.defineProperty(window, "__nativeWindowEmulation", { value: state });
+    var DateTimeFormat = Intl.DateTimeFormat;
+    var nav = Object.getOwnPropertyDescriptors(Navigator.prototype);
+    var getTimezoneOffset = Date.prototype.getTimezoneOffset;
+    var loc = function (l) { return l === undefined && state.locale ? state.locale : l; };
+    var withZone = function (options) {
+      if (!state.tz || (options && options.timeZone)) return options;
+      return Object.assign({}, options, { timeZone: state.tz });
+    };
+    ["DateTimeFormat", "NumberFormat", "Collator", "PluralRules", "RelativeTimeFormat", "ListFormat"]
+      .forEach(function (name) {
+        var Original = Intl[name];
+        if (!Original) return;
+        var zoned = name === "DateTimeFormat";
+        var Wrapped = function (l, options) {
+          return new Original(loc(l), zoned ? withZone(options) : options);
+        };
+        Wrapped.prototype = Original.prototype;
+        Wrapped.supportedLocalesOf = Original.supportedLocalesOf;
+        Intl[name] = Wrapped;
+      });
+    ["toLocaleString", "toLocaleDateString", "toLocaleTimeString"].forEach(function (name) {
+      var original = Date.prototype[name];
+      Date.prototype[name] = function (l, options) {
+        return original.call(this, loc(l), withZone(options));
+      };
+    });
+    var numberToLocaleString = Number.prototype.toLocaleString;
+    Number.prototype.toLocaleString = function (l, options) {
+      return numberToLocaleString.call(this, loc(l), options);
+    };
+    Date.prototype.getTimezoneOffset = function () {
+      var time = this.getTime();
+      if (!state.tz || isNaN(time)) return getTimezoneOffset.call(this);
+      var parts = {};
+      new DateTimeFormat("en-US", {
+        timeZone: state.tz, hourCycle: "h23", year: "numeric", month: "numeric",
+        day: "numeric", hour: "numeric", minute: "numeric", second: "numeric",
+      }).formatToParts(this).forEach(function (p) { parts[p.type] = +p.value; });
+      var local = Date.UTC(parts.year, parts.month - 1, parts.day, parts.hour, parts.minute, parts.second);
+      return Math.round((Math.floor(time / 1000) * 1000 - local) / 60000);
+    };
+    Object.defineProperty(Navigator.prototype, "language", {
+      configurable: true,
+      get: function () { return state.locale || nav.language.get.call(this); },
+    });
+    Object.defineProperty(Navigator.prototype, "languages", {
+      configurable: true,
+      get: function () {
+        return state.locale ? Object.freeze([state.locale]) : nav.languages.get.call(this);
+      },
+    });
+  }
+  state.tz = tz;
+  state.locale = locale;
+})"#;
+
+/// Best-effort network shim for engines without CDP (WebKit): offline
+/// mode rejects `fetch()`/XHR, flips `navigator.onLine` and fires
+/// `offline`/`online`; latency delays requests. Throughput is not emulated.
+#[cfg(not(target_os = "windows"))]
+const NETWORK_SCRIPT: &str = r#"(function (conditions) {
+  var state = window.__nativeWindowNetwork;
+  if (!state) {
+    state = { conditions: null };
+    Object.defineProperty(window, "__nativeWindowNetwork", { value: state });
+    var offline = function () { return !!(state.conditions && state.conditions.offline); };
+    var latency = function () { return state.conditions ? state.conditions.latency : 0; };
+    var onLine = Object.getOwnPropertyDescriptor(Navigator.prototype, "onLine");
+    Object.defineProperty(Navigator.prototype, "onLine", {
+      configurable: true,
+      get: function () { return offline() ? false : onLine.get.call(this); },
+    });
+    var fetch = window.fetch;
+    window.fetch = function () {
+      var self = this, args = arguments;
+      if (offline()) return Promise.reject(new TypeError("Failed to fetch"));
+      if (!latency()) return fetch.apply(self, args);
+      return new Promise(function (resolve) { setTimeout(resolve, latency()); })
+        .then(function () { return fetch.apply(self, args); });
+    };
+    var send = XMLHttpRequest.prototype.send;
+    XMLHttpRequest.prototype.send = function () {
+      var xhr = this, args = arguments;
+      if (offline()) {
+        setTimeout(function () {
+          xhr.dispatchEvent(new ProgressEvent("error"));
+          xhr.dispatchEvent(new ProgressEvent("loadend"));
+        });
+        return;
+      }
+      if (!latency()) return send.apply(xhr, args);
+      setTimeout(function () { send.apply(xhr, args); }, latency());
+    };
+  }
+  var wasOffline = !!(state.conditions && state.conditions.offline);
+  state.conditions = conditions;
+  var isOffline = !!(conditions && conditions.offline);
+  if (wasOffline !== isOffline) window.dispatchEvent(new Event(isOffline ? "offline" : "online"));
+})"#;
+
+// ── Network capture ────────────────────────────────────────────
+
+/// Resource Timing entries (navigation + subresources) that started at or
+/// after the given epoch time in milliseconds, for HAR export on WebKit.
+#[cfg(not(target_os = "windows"))]
+const RESOURCE_TIMING_SCRIPT: &str = r#"(function (since) {
+  try {
+    var origin = performance.timeOrigin;
+    return performance.getEntriesByType("navigation")
+      .concat(performance.getEntriesByType("resource"))
+      .filter(function (e) { return origin + e.startTime >= since; })
+      .map(function (e) {
+        return {
+          url: e.name,
+          startedMs: origin + e.startTime,
+          duration: e.duration,
+          domainLookupStart: e.domainLookupStart,
+          domainLookupEnd: e.domainLookupEnd,
+          connectStart: e.connectStart,
+          connectEnd: e.connectEnd,
+          secureConnectionStart: e.secureConnectionStart,
+          requestStart: e.requestStart,
+          responseStart: e.responseStart,
+          responseEnd: e.responseEnd,
+          startTime: e.startTime,
+          status: e.responseStatus || 0,
+          protocol: e.nextHopProtocol || "",
+          transferSize: e.transferSize || 0,
+          bodySize: e.encodedBodySize || 0,
+          mimeType: e.contentType || "",
+        };
+      });
+  } catch (e) {
+    return [];
+  }
+})"#;
+
+// ── Drag regions ───────────────────────────────────────────────
+
+/// IPC message the drag region script sends instead of a page message.
+const DRAG_REGION_MESSAGE: &str = "__native_drag__";
+
+/// Starts a window drag on a primary-button press inside an element with
+/// `data-native-drag-region`, unless the press is on a control or inside
+/// a `data-native-no-drag` element.
+const DRAG_REGION_SCRIPT: &str = r#"(function () {
+  document.addEventListener("mousedown", function (e) {
+    if (e.button !== 0 || !(e.target instanceof Element)) return;
+    var region = e.target.closest("[data-native-drag-region], [data-native-no-drag]");
+    if (!region || !region.hasAttribute("data-native-drag-region")) return;
+    if (e.target.closest("input, textarea, select, button, a[href], [contenteditable]")) return;
+    e.preventDefault();
+    window.ipc.postMessage("__native_drag__");
+  }, true);
+})();"#;
+
+// ── Service workers ────────────────────────────────────────────
+
+/// Unregisters every service worker of the page's origin and deletes its
+/// Cache Storage, resetting an offline-capable app to a clean state.
+const UNREGISTER_SERVICE_WORKERS_SCRIPT: &str = r#"(function () {
+  if (navigator.serviceWorker) {
+    navigator.serviceWorker.getRegistrations().then(function (registrations) {
+      registrations.forEach(function (r) { r.unregister(); });
+    });
+  }
+  if (window.caches) {
+    caches.keys().then(function (keys) {
+      keys.forEach(function (key) { caches.delete(key); });
+    });
+  }
+})();"#;
+
+// ── Resource hints ─────────────────────────────────────────────
+
+/// Adds `<link rel="preconnect">` / `<link rel="prefetch">` hints to the
+/// current document. Engines without prefetch support (WebKit) warm the
+/// HTTP cache with a low-priority `fetch()` instead.
+const RESOURCE_HINTS_SCRIPT: &str = r#"(function (rel, urls) {
+  var head = document.head || document.documentElement;
+  var probe = document.createElement("link");
+  var supported = probe.relList && probe.relList.supports && probe.relList.supports(rel);
+  urls.forEach(function (url) {
+    if (rel === "prefetch" && !supported) {
+      fetch(url, { mode: "no-cors", credentials: "include", priority: "low" }).catch(function () {});
+      return;
+    }
+    var link = document.createElement("link");
+    link.rel = rel;
+    link.href = url;
+    head.appendChild(link);
+    if (rel === "preconnect") {
+      var dns = document.createElement("link");
+      dns.rel = "dns-prefetch";
+      dns.href = url;
+      head.appendChild(dns);
+    }
+  });
+})"#;
+
+// ── Performance ────────────────────────────────────────────────
+
+/// Page load timings (milliseconds since navigation start) from the
+/// Navigation and Paint Timing APIs, plus JS heap usage where the engine
+/// exposes `performance.memory`. Unavailable values are `null`.
+const PERFORMANCE_METRICS_SCRIPT: &str = r#"(function () {
+  try {
+    var nav = performance.getEntriesByType("navigation")[0];
+    var paint = {};
+    performance.getEntriesByType("paint").forEach(function (e) {
+      paint[e.name] = e.startTime;
+    });
+    var ms = function (v) { return typeof v === "number" && v > 0 ? v : null; };
+    var memory = performance.memory;
+    return {
+      timeToFirstByte: nav ? ms(nav.responseStart) : null,
+      firstPaint: ms(paint["first-paint"]),
+      firstContentfulPaint: ms(paint["first-contentful-paint"]),
+      domInteractive: nav ? ms(nav.domInteractive) : null,
+      domContentLoaded: nav ? ms(nav.domContentLoadedEventEnd) : null,
+      load: nav ? ms(nav.loadEventEnd) : null,
+      transferSize: nav && nav.transferSize ? nav.transferSize : null,
+      resourceCount: performance.getEntriesByType("resource").length,
+      domElements: document.getElementsByTagName("*").length,
+      jsHeapUsed: memory ? memory.usedJSHeapSize : null,
+      jsHeapTotal: memory ? memory.totalJSHeapSize : null,
+    };
+  } catch (e) {
+    return null;
+  }
+})()"#;
+
+// ── Accessibility ──────────────────────────────────────────────
+
+/// DOM-derived accessibility snapshot for engines without a DevTools
+/// `Accessibility` domain (WebKit). Approximates the computed tree from
+/// explicit ARIA roles/labels and implicit HTML semantics, and returns it in
+/// the same `{ nodes: [{ nodeId, ignored, role, name, childIds }] }` shape as
+/// CDP's `Accessibility.getFullAXTree`.
+#[cfg(not(target_os = "windows"))]
+const ACCESSIBILITY_SNAPSHOT_SCRIPT: &str = r#"(function () {
+  try {
+    var IMPLICIT = {
+      A: "link", BUTTON: "button", TEXTAREA: "textbox", SELECT: "combobox",
+      IMG: "img", UL: "list", OL: "list", LI: "listitem", NAV: "navigation",
+      MAIN: "main", HEADER: "banner", FOOTER: "contentinfo", FORM: "form",
+      TABLE: "table", TR: "row", TD: "cell", TH: "columnheader", DIALOG: "dialog",
+      H1: "heading", H2: "heading", H3: "heading", H4: "heading", H5: "heading", H6: "heading",
+    };
+    var INPUT = { checkbox: "checkbox", radio: "radio", range: "slider", button: "button", submit: "button" };
+    var NAMED_FROM_CONTENT = { link: 1, button: 1, heading: 1, listitem: 1, cell: 1, columnheader: 1, checkbox: 1, radio: 1 };
+    var nodes = [];
+    var nextId = 1;
+
+    function roleOf(el) {
+      var explicit = el.getAttribute("role");
+      if (explicit) return explicit.split(" ")[0];
+      if (el.tagName === "INPUT") return INPUT[(el.type || "").toLowerCase()] || "textbox";
+      if (el.tagName === "A" && !el.hasAttribute("href")) return "generic";
+      return IMPLICIT[el.tagName] || "generic";
+    }
+    function nameOf(el, role) {
+      var label = el.getAttribute("aria-label");
+      if (label) return label.trim();
+      var by = el.getAttribute("aria-labelledby");
+      if (by) {
+        return by.split(/\s+/).map(function (id) {
+          var ref = document.getElementById(id);
+          return ref ? ref.textContent.trim() : "";
+        }).join(" ").trim();
+      }
+      if (el.labels && el.labels.length) return el.labels[0].textContent.trim();
+      var alt = el.getAttribute("alt") || el.getAttribute("title") || el.getAttribute("placeholder");
+      if (alt) return alt.trim();
+      if (NAMED_FROM_CONTENT[role]) return (el.textContent || "").trim().replace(/\s+/g, " ").slice(0, 200);
+      return "";
+    }
+    function visit(el) {
+      var role = roleOf(el);
+      var node = {
+        nodeId: String(nextId++),
+        ignored: el.getAttribute("aria-hidden") === "true" || role === "presentation" || role === "none",
+        role: { type: "role", value: role },
+        name: { type: "computedString", value: nameOf(el, role) },
+        childIds: [],
+      };
+      nodes.push(node);
+      for (var c = el.firstElementChild; c; c = c.nextElementSibling) {
+        if (c.tagName === "SCRIPT" || c.tagName === "STYLE" || c.tagName === "TEMPLATE") continue;
+        node.childIds.push(visit(c));
+      }
+      return node.nodeId;
+    }
+    var root = visit(document.body || document.documentElement);
+    nodes[0].role.value = "RootWebArea";
+    nodes[0].name.value = document.title;
+    return { nodes: nodes, rootId: root };
+  } catch (e) {
+    return null;
+  }
+})();"#;
+
+/// The `sessionId` of `Page.screencastFrame` params, needed to acknowledge
+/// the frame. The other fields (base64 data, numbers) cannot contain the key.
+#[cfg(target_os = "windows")]
+fn screencast_session_id(params: &str) -> Option<u64> {
+    let rest = &params[params.find("\"sessionId\":")? + "\"sessionId\":".len()..];
+    let digits = rest.trim_start();
+    let end = digits
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(digits.len());
+    digits[..end].parse().ok()
+}
+
 // ── Cookie serialization ───────────────────────────────────────
 
 /// Serialize a list of wry cookies to a JSON array string.
@@ -0,0 +1,207 @@
+/// Windows virtual desktops, for `moveToVirtualDesktop()`,
+/// `getVirtualDesktops()` and `pinToAllDesktops()`.
+///
+/// The public `IVirtualDesktopManager` can only query a window's desktop
+/// and move windows of this process. Desktops are listed from Explorer's
+/// registry state, and pinned windows are moved to the current desktop
+/// after each switch rather than pinned by the shell (which needs
+/// undocumented interfaces that change between Windows builds).
+use std::cell::RefCell;
+use std::collections::HashSet;
+
+use windows::core::{GUID, HSTRING, PCWSTR};
+use windows::Win32::Foundation::{ERROR_SUCCESS, HWND};
+use windows::Win32::System::Com::{CoCreateInstance, CLSCTX_ALL};
+use windows::Win32::System::Registry::{
+    RegGetValueW, HKEY_CURRENT_USER, REG_ROUTINE_FLAGS, RRF_RT_REG_BINARY, RRF_RT_REG_SZ,
+};
+use windows::Win32::UI::Shell::{IVirtualDesktopManager, VirtualDesktopManager};
+
+const VIRTUAL_DESKTOPS_KEY: &str =
+    "Software\\Microsoft\\Windows\\CurrentVersion\\Explorer\\VirtualDesktops";
+
+thread_local! {
+    /// Created on first use; `None` inside if the shell does not provide it.
+    static MANAGER: RefCell<Option<Option<IVirtualDesktopManager>>> = const { RefCell::new(None) };
+    /// Windows pinned with `pinToAllDesktops(true)`, by HWND.
+    static PINNED: RefCell<HashSet<isize>> = RefCell::new(HashSet::new());
+}
+
+/// A virtual desktop as listed by Explorer.
+pub struct Desktop {
+    pub id: String,
+    pub name: String,
+    pub current: bool,
+}
+
+fn with_manager<T>(f: impl FnOnce(&IVirtualDesktopManager) -> T) -> Option<T> {
+    MANAGER.with(|m| {
+        m.borrow_mut()
+            .get_or_insert_with(|| unsafe {
+                CoCreateInstance(&VirtualDesktopManager, None, CLSCTX_ALL).ok()
+            })
+            .as_ref()
+            .map(f)
+    })
+}
+
+/// Format a desktop GUID the way `moveToVirtualDesktop()` accepts it.
+fn format_id(guid: &GUID) -> String {
+    format!("{:?}", guid)
+}
+
+/// Parse a desktop ID, with or without braces.
+pub fn parse_id(id: &str) -> Option<GUID> {
+    let id = id.trim();
+    let id = id
+        .strip_prefix('{')
+        .and_then(|s| s.strip_suffix('}'))
+        .unwrap_or(id);
+    GUID::try_from(id).ok()
+}
+
+/// Whether `hwnd` is on the desktop the user is looking at. `true` when
+/// the shell cannot tell, e.g. for a window that is not shown yet.
+pub fn is_on_current(hwnd: isize) -> bool {
+    with_manager(|m| unsafe { m.IsWindowOnCurrentVirtualDesktop(HWND(hwnd as _)) })
+        .and_then(|r| r.ok())
+        .map(|on| on.as_bool())
+        != Some(false)
+}
+
+/// Move `hwnd` to the desktop `id`.
+pub fn move_to(hwnd: isize, id: &GUID) -> Result<(), String> {
+    with_manager(|m| unsafe { m.MoveWindowToDesktop(HWND(hwnd as _), id) })
+        .ok_or("virtual desktops are not available")?
+        .map_err(|e| e.to_string())
+}
+
+/// Pin or unpin `hwnd`; see [`follow_current`].
+pub fn set_pinned(hwnd: isize, pinned: bool) {
+    PINNED.with(|p| {
+        let mut p = p.borrow_mut();
+        if pinned {
+            p.insert(hwnd);
+        } else {
+            p.remove(&hwnd);
+        }
+    });
+}
+
+pub fn is_pinned(hwnd: isize) -> bool {
+    PINNED.with(|p| p.borrow().contains(&hwnd))
+}
+
+/// Move pinned windows left behind by a desktop switch to the current
+/// desktop.
+pub fn follow_current() {
+    let pinned: Vec<isize> = PINNED.with(|p| p.borrow().iter().copied().collect());
+    if pinned.is_empty() {
+        return;
+    }
+    let Some(current) = current_desktop() else {
+        return;
+    };
+    for hwnd in pinned {
+        if !is_on_current(hwnd) {
+            if let Err(e) = move_to(hwnd, &current) {
+                eprintln!(
+                    "[native-window] pinToAllDesktops: failed to follow desktop: {}",
+                    e
+                );
+            }
+        }
+    }
+}
+
+/// List the user's virtual desktops in taskbar order. Empty when Explorer
+/// has not recorded any (a single desktop that was never switched away from).
+pub fn list() -> Vec<Desktop> {
+    let Some(ids) = read_binary(VIRTUAL_DESKTOPS_KEY, "VirtualDesktopIDs") else {
+        return Vec::new();
+    };
+    let current = current_desktop();
+    ids.chunks_exact(16)
+        .map(guid_from_bytes)
+        .enumerate()
+        .map(|(i, guid)| {
+            let key = format!("{}\\Desktops\\{{{:?}}}", VIRTUAL_DESKTOPS_KEY, guid);
+            Desktop {
+                id: format_id(&guid),
+                // Unnamed desktops are shown as "Desktop N".
+                name: read_string(&key, "Name").unwrap_or_else(|| format!("Desktop {}", i + 1)),
+                current: current == Some(guid),
+            }
+        })
+        .collect()
+}
+
+/// The desktop the user is looking at. Windows 11 keeps it under the
+/// VirtualDesktops key; Windows 10 per logon session.
+fn current_desktop() -> Option<GUID> {
+    use windows::Win32::System::RemoteDesktop::ProcessIdToSessionId;
+    use windows::Win32::System::Threading::GetCurrentProcessId;
+
+    let value = read_binary(VIRTUAL_DESKTOPS_KEY, "CurrentVirtualDesktop").or_else(|| {
+        let mut session = 0u32;
+        unsafe { ProcessIdToSessionId(GetCurrentProcessId(), &mut session) }.ok()?;
+        let key = format!(
+            "Software\\Microsoft\\Windows\\CurrentVersion\\Explorer\\SessionInfo\\{}\\VirtualDesktops",
+            session
+        );
+        read_binary(&key, "CurrentVirtualDesktop")
+    })?;
+    (value.len() == 16).then(|| guid_from_bytes(&value))
+}
+
+/// Registry GUIDs are stored in their in-memory (little-endian) layout.
+fn guid_from_bytes(b: &[u8]) -> GUID {
+    GUID::from_values(
+        u32::from_le_bytes([b[0], b[1], b[2], b[3]]),
+        u16::from_le_bytes([b[4], b[5]]),
+        u16::from_le_bytes([b[6], b[7]]),
+        [b[8], b[9], b[10], b[11], b[12], b[13], b[14], b[15]],
+    )
+}
+
+fn read_binary(key: &str, value: &str) -> Option<Vec<u8>> {
+    read_value(key, value, RRF_RT_REG_BINARY)
+}
+
+fn read_string(key: &str, value: &str) -> Option<String> {
+    let bytes = read_value(key, value, RRF_RT_REG_SZ)?;
+    let wide: Vec<u16> = bytes
+        .chunks_exact(2)
+        .map(|c| u16::from_le_bytes([c[0], c[1]]))
+        .take_while(|&c| c != 0)
+        .collect();
+    let name = String::from_utf16_lossy(&wide);
+    (!name.is_empty()).then_some(name)
+}
+
+/// Read a HKCU value, sizing the buffer with a first call.
+fn read_value(key: &str, value: &str, flags: REG_ROUTINE_FLAGS) -> Option<Vec<u8>> {
+    let key = HSTRING::from(key);
+    let value = HSTRING::from(value);
+    let mut size = 0u32;
+    let read = |data: Option<*mut std::ffi::c_void>, size: &mut u32| unsafe {
+        RegGetValueW(
+            HKEY_CURRENT_USER,
+            PCWSTR(key.as_ptr()),
+            PCWSTR(value.as_ptr()),
+            flags,
+            None,
+            data,
+            Some(size),
+        )
+    };
+    if read(None, &mut size) != ERROR_SUCCESS || size == 0 {
+        return None;
+    }
+    let mut data = vec![0u8; size as usize];
+    if read(Some(data.as_mut_ptr().cast()), &mut size) != ERROR_SUCCESS {
+        return None;
+    }
+    data.truncate(size as usize);
+    Some(data)
+}
@@ -0,0 +1,183 @@
+/// Asynchronous, shared WebView2 environment for the Windows backend.
+///
+/// wry creates the WebView2 environment synchronously inside
+/// `WebViewBuilder::build`, spinning a nested message loop until the
+/// browser process is up — which freezes every other window during
+/// `pumpEvents()`. Instead we start the environment once, asynchronously,
+/// and let the completion handler fire on a later pump (tao's event loop
+/// dispatches the COM callback). Windows created before it is ready stay
+/// pending and are built with `with_environment()` once it settles.
+///
+/// Only the environment is asynchronous. wry still creates each window's
+/// controller inside `build()`, waiting in a nested message loop, and
+/// offers no way to adopt a controller created elsewhere. That wait is
+/// short once the browser process runs, but it does block the pump.
+///
+/// A locked or corrupted user data folder fails environment creation for
+/// every window. In that case creation is retried once with a profile in
+/// the temp directory, and `onProfileFallback` reports it.
+use std::cell::RefCell;
+use std::path::PathBuf;
+
+use webview2_com::Microsoft::Web::WebView2::Win32::{
+    CreateCoreWebView2EnvironmentWithOptions, ICoreWebView2Environment,
+    ICoreWebView2EnvironmentOptions,
+};
+use webview2_com::{CoreWebView2EnvironmentOptions, CreateCoreWebView2EnvironmentCompletedHandler};
+use windows::core::{HRESULT, HSTRING, PCWSTR};
+
+use crate::runtime::ProfileFallback;
+use crate::window_manager::PENDING_PROFILE_FALLBACKS;
+
+/// `HRESULT_FROM_WIN32(ERROR_FILE_NOT_FOUND)`: the WebView2 runtime is not
+/// installed. Every other creation failure is blamed on the profile.
+const RUNTIME_NOT_FOUND: HRESULT = HRESULT(0x8007_0002_u32 as i32);
+
+/// Lifecycle of the shared environment: idle → pending → ready or failed.
+/// Controllers are not tracked here: wry creates each window's controller
+/// synchronously in `build()`, once this reaches `Ready`.
+enum EnvironmentState {
+    /// Creation has not been requested yet.
+    Idle,
+    /// `CreateCoreWebView2EnvironmentWithOptions` is in flight.
+    Pending,
+    Ready(ICoreWebView2Environment),
+    Failed(windows::core::Error),
+}
+
+thread_local! {
+    static ENVIRONMENT: RefCell<EnvironmentState> = const { RefCell::new(EnvironmentState::Idle) };
+    /// Whether creation has been retried with the temp profile.
+    static FELL_BACK: RefCell<bool> = const { RefCell::new(false) };
+    /// Browser UI language from `init({ uiLanguage })`.
+    static LANGUAGE: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Outcome of [`poll`].
+pub enum Environment {
+    /// Still being created — try again on a later pump.
+    Pending,
+    Ready(ICoreWebView2Environment),
+    Failed(String),
+}
+
+/// Return the shared environment, starting its creation on first use.
+pub fn poll() -> Environment {
+    let start = ENVIRONMENT.with(|e| matches!(*e.borrow(), EnvironmentState::Idle));
+    if start {
+        start_creation(None);
+    }
+
+    // A profile failure gets one retry with a fresh profile in temp.
+    let failure = ENVIRONMENT.with(|e| match &*e.borrow() {
+        EnvironmentState::Failed(err) if err.code() != RUNTIME_NOT_FOUND => Some(err.message()),
+        _ => None,
+    });
+    if let Some(reason) = failure {
+        if !FELL_BACK.with(|f| f.replace(true)) {
+            let folder = fallback_folder();
+            eprintln!(
+                "[native-window] WebView2 environment creation failed: {}. \
+                 Retrying with a temporary profile in {}.",
+                reason,
+                folder.display()
+            );
+            PENDING_PROFILE_FALLBACKS.with(|p| {
+                p.borrow_mut().push(ProfileFallback {
+                    reason,
+                    user_data_folder: folder.to_string_lossy().into_owned(),
+                })
+            });
+            start_creation(Some(folder));
+        }
+    }
+
+    ENVIRONMENT.with(|e| match &*e.borrow() {
+        EnvironmentState::Idle | EnvironmentState::Pending => Environment::Pending,
+        EnvironmentState::Ready(env) => Environment::Ready(env.clone()),
+        EnvironmentState::Failed(err) => Environment::Failed(err.to_string()),
+    })
+}
+
+/// Set the browser UI language. Only possible before the environment is
+/// created, since it is an environment option.
+pub fn set_language(language: String) -> Result<(), String> {
+    if started() {
+        return Err("uiLanguage must be set before the first window is created".to_string());
+    }
+    LANGUAGE.with(|l| *l.borrow_mut() = Some(language));
+    Ok(())
+}
+
+/// Whether environment creation has started in this process, after which
+/// the user data folder is in use.
+pub fn started() -> bool {
+    ENVIRONMENT.with(|e| !matches!(*e.borrow(), EnvironmentState::Idle))
+}
+
+/// WebView2's default user data folder for unpackaged apps:
+/// `<executable>.WebView2` next to the executable.
+pub fn default_folder() -> Option<PathBuf> {
+    let exe = std::env::current_exe().ok()?;
+    let mut name = exe.file_name()?.to_os_string();
+    name.push(".WebView2");
+    Some(exe.with_file_name(name))
+}
+
+/// Profile used when the default one cannot be opened. Shared by all
+/// instances of the app, like the default.
+fn fallback_folder() -> PathBuf {
+    let app = std::env::current_exe()
+        .ok()
+        .and_then(|exe| exe.file_stem().map(|s| s.to_string_lossy().into_owned()))
+        .unwrap_or_else(|| "app".to_string());
+    std::env::temp_dir().join(format!("native-window-{}.WebView2", app))
+}
+
+fn start_creation(user_data_folder: Option<PathBuf>) {
+    ENVIRONMENT.with(|e| *e.borrow_mut() = EnvironmentState::Pending);
+    if let Err(e) = create(user_data_folder) {
+        ENVIRONMENT.with(|s| *s.borrow_mut() = EnvironmentState::Failed(e));
+    }
+}
+
+/// Kick off asynchronous environment creation with the same browser
+/// arguments wry would use for a default webview. `None` uses WebView2's
+/// default user data folder.
+fn create(user_data_folder: Option<PathBuf>) -> windows::core::Result<()> {
+    let options = CoreWebView2EnvironmentOptions::default();
+    unsafe {
+        // Matches wry's defaults: no "mini menu", PDF OOUI, or SmartScreen.
+        options.set_additional_browser_arguments(
+            "--disable-features=msWebOOUI,msPdfOOUI,msSmartScreenProtection".to_string(),
+        );
+        if let Some(language) = LANGUAGE.with(|l| l.borrow().clone()) {
+            options.set_language(language);
+        }
+    }
+
+    let handler = CreateCoreWebView2EnvironmentCompletedHandler::create(Box::new(
+        move |result, environment| {
+            let state = match (result, environment) {
+                (Ok(()), Some(env)) => EnvironmentState::Ready(env),
+                (Err(e), _) => EnvironmentState::Failed(e),
+                (Ok(()), None) => EnvironmentState::Failed(windows::core::Error::new(
+                    windows::Win32::Foundation::E_FAIL,
+                    "no environment returned",
+                )),
+            };
+            ENVIRONMENT.with(|e| *e.borrow_mut() = state);
+            Ok(())
+        },
+    ));
+
+    let user_data_folder = user_data_folder.map(|f| HSTRING::from(f.as_os_str()));
+    unsafe {
+        CreateCoreWebView2EnvironmentWithOptions(
+            PCWSTR::null(),
+            user_data_folder.as_ref().unwrap_or(&HSTRING::new()),
+            &ICoreWebView2EnvironmentOptions::from(options),
+            &handler,
+        )
+    }
+}
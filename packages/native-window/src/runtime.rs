@@ -9,8 +9,12 @@ use windows::core::PWSTR;
 #[cfg(target_os = "windows")]
 use webview2_com::Microsoft::Web::WebView2::Win32::GetAvailableCoreWebView2BrowserVersionString;
 
+use napi::threadsafe_function::{ErrorStrategy, ThreadSafeCallContext, ThreadsafeFunction};
+use napi::JsFunction;
 use napi_derive::napi;
 
+use crate::window_manager::with_manager;
+
 /// Information about the native webview runtime.
 #[napi(object)]
 #[derive(Debug, Clone)]
@@ -229,41 +233,18 @@ fn ensure_runtime_windows() -> napi::Result<RuntimeInfo> {
     // Verify Authenticode signature before executing.
     // Ensures the downloaded file is signed by Microsoft Corporation.
     // If signature verification fails, the file is deleted and an error is returned.
-    let verify_result = std::process::Command::new("powershell")
-        .args([
-            "-NoProfile",
-            "-NonInteractive",
-            "-Command",
-            &format!(
-                "$sig = Get-AuthenticodeSignature -FilePath '{}'; \
-                 if ($sig.Status -ne 'Valid') {{ \
-                   Write-Error \"Authenticode signature is not valid: $($sig.Status). \
-                   StatusMessage: $($sig.StatusMessage)\"; \
-                   exit 1 \
-                 }} \
-                 $signer = $sig.SignerCertificate.Subject; \
-                 if ($signer -notlike '*O=Microsoft Corporation*') {{ \
-                   Write-Error \"Unexpected signer: $signer\"; \
-                   exit 1 \
-                 }}",
-                installer_path_str.replace('\'', "''")
-            ),
-        ])
-        .output();
-
-    match verify_result {
-        Ok(output) if output.status.success() => {
+    match verify_authenticode(&installer_path, "Microsoft Corporation") {
+        Ok(()) => {
             // Signature valid and signer is Microsoft — proceed
         }
-        Ok(output) => {
-            let stderr = String::from_utf8_lossy(&output.stderr);
+        Err(AuthenticodeError::Invalid(reason)) => {
             let _ = std::fs::remove_file(&installer_path);
             return Err(napi::Error::from_reason(format!(
                 "WebView2 bootstrapper failed signature verification: {}",
-                stderr.trim()
+                reason
             )));
         }
-        Err(e) => {
+        Err(AuthenticodeError::Unavailable(e)) => {
             // PowerShell not available for verification — refuse to execute
             // an unverified binary to prevent potential supply-chain attacks.
             let _ = std::fs::remove_file(&installer_path);
@@ -311,3 +292,129 @@ fn ensure_runtime_windows() -> napi::Result<RuntimeInfo> {
         ))
     }
 }
+
+/// Why [`verify_authenticode`] rejected a file.
+#[cfg(target_os = "windows")]
+pub(crate) enum AuthenticodeError {
+    /// The signature is missing, invalid, or from another organization.
+    Invalid(String),
+    /// PowerShell could not be run, so nothing was verified.
+    Unavailable(String),
+}
+
+/// Check that `path` carries a valid Authenticode signature whose signer
+/// certificate's `O=` field is exactly `organization`. Used for the WebView2
+/// bootstrapper and for `downloadUpdate()` installers. Callers must treat
+/// every error, including [`AuthenticodeError::Unavailable`], as a reason
+/// not to run the file.
+#[cfg(target_os = "windows")]
+pub(crate) fn verify_authenticode(
+    path: &std::path::Path,
+    organization: &str,
+) -> Result<(), AuthenticodeError> {
+    // Both values are interpolated into single-quoted PowerShell strings.
+    // The subject is split into its RDNs, one per line, so "Acme" does not
+    // match a certificate for "Acme Evil Ltd".
+    let quote = |s: &str| s.replace('\'', "''");
+    let output = std::process::Command::new("powershell")
+        .args([
+            "-NoProfile",
+            "-NonInteractive",
+            "-Command",
+            &format!(
+                "$sig = Get-AuthenticodeSignature -FilePath '{}'; \
+                 if ($sig.Status -ne 'Valid') {{ \
+                   Write-Error \"Authenticode signature is not valid: $($sig.Status). \
+                   StatusMessage: $($sig.StatusMessage)\"; \
+                   exit 1 \
+                 }} \
+                 $signer = $sig.SignerCertificate.SubjectName; \
+                 $orgs = @($signer.Format($true) -split \"`r?`n\" | \
+                   ForEach-Object {{ $_.Trim() }} | \
+                   Where-Object {{ $_.StartsWith('O=') }} | \
+                   ForEach-Object {{ $_.Substring(2).Trim('\"') }}); \
+                 if ($orgs -cnotcontains '{}') {{ \
+                   Write-Error \"Unexpected signer: $($signer.Name)\"; \
+                   exit 1 \
+                 }}",
+                quote(&path.to_string_lossy()),
+                quote(organization)
+            ),
+        ])
+        .output()
+        .map_err(|e| AuthenticodeError::Unavailable(e.to_string()))?;
+
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(AuthenticodeError::Invalid(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ))
+    }
+}
+
+/// Reported by `onProfileFallback` when the WebView2 user data folder could
+/// not be used and a temporary profile was created instead.
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct ProfileFallback {
+    /// Why the default profile failed, e.g. "Access is denied."
+    pub reason: String,
+    /// The temporary user data folder now in use.
+    pub user_data_folder: String,
+}
+
+/// Delete the WebView2 user data folder (`<executable>.WebView2` next to
+/// the executable) so the next window starts with a fresh profile. Cookies,
+/// storage and caches are lost. Returns `true` if a folder was removed.
+///
+/// Must be called before the first window is created, while the folder is
+/// not in use. A no-op returning `false` on macOS and Linux.
+#[napi]
+pub fn repair_runtime_profile() -> napi::Result<bool> {
+    #[cfg(target_os = "windows")]
+    {
+        use crate::platform::webview2_env;
+
+        if webview2_env::started() {
+            return Err(napi::Error::from_reason(
+                "repairRuntimeProfile() must be called before the first window is created",
+            ));
+        }
+        let Some(folder) = webview2_env::default_folder() else {
+            return Ok(false);
+        };
+        if !folder.exists() {
+            return Ok(false);
+        }
+        std::fs::remove_dir_all(&folder).map_err(|e| {
+            napi::Error::from_reason(format!(
+                "Failed to remove WebView2 profile {}: {}",
+                folder.display(),
+                e
+            ))
+        })?;
+        Ok(true)
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        Ok(false)
+    }
+}
+
+/// Register a handler fired when WebView2 could not open its user data
+/// folder (locked or corrupted) and windows fell back to a temporary
+/// profile. Windows only; never fires elsewhere.
+#[napi(ts_args_type = "callback: (fallback: ProfileFallback) => void")]
+pub fn on_profile_fallback(callback: JsFunction) -> napi::Result<()> {
+    let tsfn: ThreadsafeFunction<ProfileFallback, ErrorStrategy::Fatal> = callback
+        .create_threadsafe_function(0, |ctx: ThreadSafeCallContext<ProfileFallback>| {
+            Ok(vec![ctx.value])
+        })?;
+
+    with_manager(|mgr| {
+        mgr.app_handlers.on_profile_fallback = Some(tsfn);
+    });
+    Ok(())
+}
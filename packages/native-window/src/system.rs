@@ -0,0 +1,766 @@
+/// System-wide (app-level) queries and change notifications.
+///
+/// Unlike `NativeWindow` methods these are not tied to a window. Change
+/// events are detected by polling from `pumpEvents()`, so they only fire
+/// while the event pump is running.
+use std::cell::RefCell;
+use std::time::{Duration, Instant};
+
+use napi::bindgen_prelude::*;
+use napi::threadsafe_function::{
+    ErrorStrategy, ThreadSafeCallContext, ThreadsafeFunction, ThreadsafeFunctionCallMode,
+};
+use napi::JsFunction;
+use napi_derive::napi;
+
+use crate::events::AppEventHandlers;
+use crate::window_manager::with_manager;
+
+/// Minimum time between two polls of system settings.
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+thread_local! {
+    /// Time of the last system settings poll.
+    static LAST_POLL: RefCell<Option<Instant>> = RefCell::new(None);
+    /// Last accessibility preferences seen by `poll()`, used to detect changes.
+    static LAST_ACCESSIBILITY_PREFERENCES: RefCell<Option<AccessibilityPreferences>> =
+        RefCell::new(None);
+    /// Whether the user was idle at the last poll, used to detect changes.
+    static LAST_IDLE: RefCell<Option<bool>> = RefCell::new(None);
+    /// Last accent color seen by `poll()`, used to detect changes.
+    static LAST_ACCENT_COLOR: RefCell<Option<String>> = RefCell::new(None);
+    /// ID of the default audio output device at the last poll, used to
+    /// detect changes.
+    static LAST_AUDIO_DEVICE: RefCell<Option<String>> = RefCell::new(None);
+    /// Whether `ensure_com()` initialized COM on this thread, so teardown
+    /// can balance it.
+    #[cfg(target_os = "windows")]
+    static COM_INITIALIZED: std::cell::Cell<bool> = const { std::cell::Cell::new(false) };
+}
+
+/// Initialize COM on the JS thread for system queries, once. Succeeds
+/// (`S_FALSE`) if tao already did; either way one `CoUninitialize()` is owed.
+#[cfg(target_os = "windows")]
+fn ensure_com() {
+    use windows::Win32::System::Com::{CoInitializeEx, COINIT_APARTMENTTHREADED};
+
+    if !COM_INITIALIZED.get() {
+        let initialized = unsafe { CoInitializeEx(None, COINIT_APARTMENTTHREADED) }.is_ok();
+        COM_INITIALIZED.set(initialized);
+    }
+}
+
+/// Balance `ensure_com()` on teardown.
+#[cfg(target_os = "windows")]
+pub(crate) fn uninitialize_com() {
+    if COM_INITIALIZED.replace(false) {
+        unsafe { windows::Win32::System::Com::CoUninitialize() };
+    }
+}
+
+// ── Accessibility preferences ──────────────────────────────────
+
+/// User accessibility display preferences set at the OS level.
+#[napi(object)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AccessibilityPreferences {
+    /// High contrast mode / "Increase contrast" is enabled.
+    pub high_contrast: bool,
+    /// The user asked for reduced motion (animations disabled).
+    pub reduced_motion: bool,
+    /// The user asked for reduced transparency.
+    pub reduced_transparency: bool,
+}
+
+/// Read the current OS accessibility display preferences.
+///
+/// - **macOS**: `NSWorkspace` accessibility display options.
+/// - **Windows**: High Contrast, "Show animations in Windows" and
+///   "Transparency effects" settings.
+/// - **Linux**: GTK theme name (HighContrast themes) and
+///   `gtk-enable-animations`. Reduced transparency is always `false`.
+#[napi]
+pub fn get_accessibility_preferences() -> AccessibilityPreferences {
+    #[cfg(target_os = "macos")]
+    {
+        use objc2_app_kit::NSWorkspace;
+
+        let workspace = NSWorkspace::sharedWorkspace();
+        AccessibilityPreferences {
+            high_contrast: workspace.accessibilityDisplayShouldIncreaseContrast(),
+            reduced_motion: workspace.accessibilityDisplayShouldReduceMotion(),
+            reduced_transparency: workspace.accessibilityDisplayShouldReduceTransparency(),
+        }
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        use windows::core::{w, BOOL};
+        use windows::Win32::Foundation::ERROR_SUCCESS;
+        use windows::Win32::System::Registry::{RegGetValueW, HKEY_CURRENT_USER, RRF_RT_REG_DWORD};
+        use windows::Win32::UI::Accessibility::{HCF_HIGHCONTRASTON, HIGHCONTRASTW};
+        use windows::Win32::UI::WindowsAndMessaging::{
+            SystemParametersInfoW, SPI_GETCLIENTAREAANIMATION, SPI_GETHIGHCONTRAST,
+            SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS,
+        };
+
+        let mut hc = HIGHCONTRASTW {
+            cbSize: std::mem::size_of::<HIGHCONTRASTW>() as u32,
+            ..Default::default()
+        };
+        let high_contrast = unsafe {
+            SystemParametersInfoW(
+                SPI_GETHIGHCONTRAST,
+                hc.cbSize,
+                Some(&mut hc as *mut _ as *mut _),
+                SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS(0),
+            )
+        }
+        .is_ok()
+            && (hc.dwFlags.0 & HCF_HIGHCONTRASTON.0) != 0;
+
+        let mut animations = BOOL(1);
+        let reduced_motion = unsafe {
+            SystemParametersInfoW(
+                SPI_GETCLIENTAREAANIMATION,
+                0,
+                Some(&mut animations as *mut _ as *mut _),
+                SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS(0),
+            )
+        }
+        .is_ok()
+            && !animations.as_bool();
+
+        let mut transparency: u32 = 1;
+        let mut size = std::mem::size_of::<u32>() as u32;
+        let status = unsafe {
+            RegGetValueW(
+                HKEY_CURRENT_USER,
+                w!("Software\\Microsoft\\Windows\\CurrentVersion\\Themes\\Personalize"),
+                w!("EnableTransparency"),
+                RRF_RT_REG_DWORD,
+                None,
+                Some(&mut transparency as *mut _ as *mut _),
+                Some(&mut size),
+            )
+        };
+        let reduced_transparency = status == ERROR_SUCCESS && transparency == 0;
+
+        AccessibilityPreferences {
+            high_contrast,
+            reduced_motion,
+            reduced_transparency,
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        use gtk::prelude::*;
+
+        // GTK is initialized by the event loop in `init()`; querying
+        // settings before that would abort.
+        if !gtk::is_initialized_main_thread() {
+            return AccessibilityPreferences::default();
+        }
+        match gtk::Settings::default() {
+            Some(settings) => AccessibilityPreferences {
+                high_contrast: settings
+                    .gtk_theme_name()
+                    .map(|name| name.to_lowercase().contains("highcontrast"))
+                    .unwrap_or(false),
+                reduced_motion: !settings.is_gtk_enable_animations(),
+                reduced_transparency: false,
+            },
+            None => AccessibilityPreferences::default(),
+        }
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+    {
+        AccessibilityPreferences::default()
+    }
+}
+
+/// Register a handler fired when the OS accessibility display preferences
+/// change. Replaces any previously registered handler.
+#[napi(ts_args_type = "callback: (preferences: AccessibilityPreferences) => void")]
+pub fn on_accessibility_preferences_changed(callback: JsFunction) -> Result<()> {
+    let tsfn: ThreadsafeFunction<AccessibilityPreferences, ErrorStrategy::Fatal> = callback
+        .create_threadsafe_function(0, |ctx: ThreadSafeCallContext<AccessibilityPreferences>| {
+            Ok(vec![ctx.value])
+        })?;
+
+    // Baseline against the current state so the first poll does not
+    // report a change that happened before registration.
+    let current = get_accessibility_preferences();
+    LAST_ACCESSIBILITY_PREFERENCES.with(|p| *p.borrow_mut() = Some(current));
+
+    with_manager(|mgr| {
+        mgr.app_handlers.on_accessibility_preferences_changed = Some(tsfn);
+    });
+    Ok(())
+}
+
+// ── Accent color ───────────────────────────────────────────────
+
+/// Fallback when the OS reports no accent color (macOS/Windows default blue).
+const DEFAULT_ACCENT_COLOR: &str = "#0078d4";
+
+/// The OS accent color as a `#rrggbb` hex string.
+///
+/// - **macOS**: `NSColor.controlAccentColor` (follows the "Accent color"
+///   setting, including per-app overrides), converted to sRGB.
+/// - **Windows**: `DwmGetColorizationColor` — the color used for window
+///   frames, which follows the personalization accent color.
+/// - **Linux**: the GTK theme's `theme_selected_bg_color`.
+#[napi]
+pub fn get_accent_color() -> String {
+    #[cfg(target_os = "macos")]
+    {
+        use objc2_app_kit::{NSColor, NSColorSpace};
+
+        let accent = NSColor::controlAccentColor();
+        match accent.colorUsingColorSpace(&NSColorSpace::sRGBColorSpace()) {
+            Some(rgb) => hex_color(
+                rgb.redComponent(),
+                rgb.greenComponent(),
+                rgb.blueComponent(),
+            ),
+            None => DEFAULT_ACCENT_COLOR.to_string(),
+        }
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        use windows::core::BOOL;
+        use windows::Win32::Graphics::Dwm::DwmGetColorizationColor;
+
+        let mut color: u32 = 0;
+        let mut opaque = BOOL(0);
+        if unsafe { DwmGetColorizationColor(&mut color, &mut opaque) }.is_err() {
+            return DEFAULT_ACCENT_COLOR.to_string();
+        }
+        // 0xAARRGGBB — drop the alpha.
+        format!("#{:06x}", color & 0x00ff_ffff)
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        use gtk::prelude::*;
+
+        // Same guard as get_accessibility_preferences().
+        if !gtk::is_initialized_main_thread() {
+            return DEFAULT_ACCENT_COLOR.to_string();
+        }
+        let Some(screen) = gtk::gdk::Screen::default() else {
+            return DEFAULT_ACCENT_COLOR.to_string();
+        };
+        let context = gtk::StyleContext::new();
+        context.set_screen(&screen);
+        match context.lookup_color("theme_selected_bg_color") {
+            Some(rgba) => hex_color(rgba.red(), rgba.green(), rgba.blue()),
+            None => DEFAULT_ACCENT_COLOR.to_string(),
+        }
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+    {
+        DEFAULT_ACCENT_COLOR.to_string()
+    }
+}
+
+/// Format 0.0–1.0 color components as `#rrggbb`.
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+fn hex_color(red: f64, green: f64, blue: f64) -> String {
+    let channel = |c: f64| (c.clamp(0.0, 1.0) * 255.0).round() as u8;
+    format!(
+        "#{:02x}{:02x}{:02x}",
+        channel(red),
+        channel(green),
+        channel(blue)
+    )
+}
+
+/// Register a handler fired with the new `#rrggbb` color when the OS
+/// accent color changes. Replaces any previously registered handler.
+#[napi(ts_args_type = "callback: (color: string) => void")]
+pub fn on_accent_color_changed(callback: JsFunction) -> Result<()> {
+    let tsfn: ThreadsafeFunction<String, ErrorStrategy::Fatal> = callback
+        .create_threadsafe_function(0, |ctx: ThreadSafeCallContext<String>| {
+            ctx.env.create_string(ctx.value.as_str()).map(|v| vec![v])
+        })?;
+
+    // Baseline against the current state, as for accessibility preferences.
+    let current = get_accent_color();
+    LAST_ACCENT_COLOR.with(|c| *c.borrow_mut() = Some(current));
+
+    with_manager(|mgr| {
+        mgr.app_handlers.on_accent_color_changed = Some(tsfn);
+    });
+    Ok(())
+}
+
+// ── Idle time ──────────────────────────────────────────────────
+
+/// Seconds since the last keyboard or mouse input anywhere on the system.
+///
+/// - **macOS**: `CGEventSourceSecondsSinceLastEventType`.
+/// - **Windows**: `GetLastInputInfo`.
+/// - **Linux**: the X11 screen saver extension (`libXss`). Returns `0` when
+///   it is unavailable, e.g. on Wayland without XWayland.
+#[napi]
+pub fn get_system_idle_time() -> f64 {
+    #[cfg(target_os = "macos")]
+    {
+        use objc2_core_graphics::{CGEventSource, CGEventSourceStateID, CGEventType};
+
+        // kCGAnyInputEventType (~0) is not exposed as a named constant.
+        CGEventSource::seconds_since_last_event_type(
+            CGEventSourceStateID::CombinedSessionState,
+            CGEventType(u32::MAX),
+        )
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        use windows::Win32::System::SystemInformation::GetTickCount;
+        use windows::Win32::UI::Input::KeyboardAndMouse::{GetLastInputInfo, LASTINPUTINFO};
+
+        let mut info = LASTINPUTINFO {
+            cbSize: std::mem::size_of::<LASTINPUTINFO>() as u32,
+            dwTime: 0,
+        };
+        if !unsafe { GetLastInputInfo(&mut info) }.as_bool() {
+            return 0.0;
+        }
+        // Both are 32-bit tick counts; wrapping_sub handles the 49-day rollover.
+        let now = unsafe { GetTickCount() };
+        now.wrapping_sub(info.dwTime) as f64 / 1000.0
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        use x11_dl::xlib::Xlib;
+        use x11_dl::xss::Xss;
+
+        let (Ok(xlib), Ok(xss)) = (Xlib::open(), Xss::open()) else {
+            return 0.0;
+        };
+        unsafe {
+            let display = (xlib.XOpenDisplay)(std::ptr::null());
+            if display.is_null() {
+                return 0.0;
+            }
+            let info = (xss.XScreenSaverAllocInfo)();
+            let mut idle_ms = 0;
+            if !info.is_null() {
+                let root = (xlib.XDefaultRootWindow)(display);
+                if (xss.XScreenSaverQueryInfo)(display, root, info) != 0 {
+                    idle_ms = (*info).idle;
+                }
+                (xlib.XFree)(info.cast());
+            }
+            (xlib.XCloseDisplay)(display);
+            idle_ms as f64 / 1000.0
+        }
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+    {
+        0.0
+    }
+}
+
+/// Register a handler fired when the user becomes idle (no input for
+/// `threshold` seconds) or active again. Replaces any previously
+/// registered handler.
+#[napi(ts_args_type = "threshold: number, callback: (state: 'idle' | 'active') => void")]
+pub fn on_idle_state_changed(threshold: f64, callback: JsFunction) -> Result<()> {
+    if !threshold.is_finite() || threshold <= 0.0 {
+        return Err(napi::Error::from_reason(
+            "Idle threshold must be a positive number of seconds.",
+        ));
+    }
+    let tsfn: ThreadsafeFunction<String, ErrorStrategy::Fatal> = callback
+        .create_threadsafe_function(0, |ctx: ThreadSafeCallContext<String>| {
+            ctx.env.create_string(ctx.value.as_str()).map(|v| vec![v])
+        })?;
+
+    // Baseline against the current state, as for accessibility preferences.
+    let idle = get_system_idle_time() >= threshold;
+    LAST_IDLE.with(|l| *l.borrow_mut() = Some(idle));
+
+    with_manager(|mgr| {
+        mgr.app_handlers.on_idle_state_changed = Some((threshold, tsfn));
+    });
+    Ok(())
+}
+
+// ── Audio devices ──────────────────────────────────────────────
+
+/// An audio endpoint as reported by the OS.
+#[napi(object)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AudioDevice {
+    /// Stable OS identifier: the endpoint ID on Windows, the device UID
+    /// on macOS.
+    pub id: String,
+    /// Human-readable name, e.g. "Headphones (USB Audio)".
+    pub name: String,
+}
+
+/// The default audio output device, or `null` when there is none.
+///
+/// - **macOS**: CoreAudio `kAudioHardwarePropertyDefaultOutputDevice`.
+/// - **Windows**: the default console render endpoint (`IMMDeviceEnumerator`).
+/// - **Linux**: not supported; always returns `null`.
+#[napi]
+pub fn get_default_audio_device() -> Option<AudioDevice> {
+    #[cfg(target_os = "macos")]
+    {
+        default_audio_device_macos()
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        default_audio_device_windows().ok()
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    {
+        None
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn default_audio_device_windows() -> windows::core::Result<AudioDevice> {
+    use windows::Win32::Devices::FunctionDiscovery::PKEY_Device_FriendlyName;
+    use windows::Win32::Media::Audio::{
+        eConsole, eRender, IMMDeviceEnumerator, MMDeviceEnumerator,
+    };
+    use windows::Win32::System::Com::StructuredStorage::{
+        PropVariantClear, PropVariantToStringAlloc,
+    };
+    use windows::Win32::System::Com::{CoCreateInstance, CoTaskMemFree, CLSCTX_ALL, STGM_READ};
+
+    // COM may not be initialized yet if no window has been created.
+    ensure_com();
+    unsafe {
+        let enumerator: IMMDeviceEnumerator =
+            CoCreateInstance(&MMDeviceEnumerator, None, CLSCTX_ALL)?;
+        let device = enumerator.GetDefaultAudioEndpoint(eRender, eConsole)?;
+
+        let raw_id = device.GetId()?;
+        let id = raw_id.to_string().unwrap_or_default();
+        CoTaskMemFree(Some(raw_id.0 as *const _));
+
+        let store = device.OpenPropertyStore(STGM_READ)?;
+        let mut value = store.GetValue(&PKEY_Device_FriendlyName)?;
+        let name = match PropVariantToStringAlloc(&value) {
+            Ok(raw_name) => {
+                let name = raw_name.to_string().unwrap_or_default();
+                CoTaskMemFree(Some(raw_name.0 as *const _));
+                name
+            }
+            Err(_) => String::new(),
+        };
+        let _ = PropVariantClear(&mut value);
+
+        Ok(AudioDevice { id, name })
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn default_audio_device_macos() -> Option<AudioDevice> {
+    use std::ffi::c_void;
+
+    use objc2::rc::Retained;
+    use objc2_foundation::NSString;
+
+    #[repr(C)]
+    struct AudioObjectPropertyAddress {
+        selector: u32,
+        scope: u32,
+        element: u32,
+    }
+
+    #[link(name = "CoreAudio", kind = "framework")]
+    extern "C" {
+        fn AudioObjectGetPropertyData(
+            object_id: u32,
+            address: *const AudioObjectPropertyAddress,
+            qualifier_size: u32,
+            qualifier: *const c_void,
+            data_size: *mut u32,
+            data: *mut c_void,
+        ) -> i32;
+    }
+
+    const SYSTEM_OBJECT: u32 = 1;
+    const SCOPE_GLOBAL: u32 = u32::from_be_bytes(*b"glob");
+    const DEFAULT_OUTPUT_DEVICE: u32 = u32::from_be_bytes(*b"dOut");
+    const DEVICE_UID: u32 = u32::from_be_bytes(*b"uid ");
+    const OBJECT_NAME: u32 = u32::from_be_bytes(*b"lnam");
+
+    let address = |selector| AudioObjectPropertyAddress {
+        selector,
+        scope: SCOPE_GLOBAL,
+        element: 0,
+    };
+
+    // Reads a CFString property. The caller owns the returned reference,
+    // and CFString is toll-free bridged to NSString.
+    let string_property = |object_id: u32, selector: u32| -> Option<String> {
+        let mut value: *mut NSString = std::ptr::null_mut();
+        let mut size = std::mem::size_of::<*mut NSString>() as u32;
+        let status = unsafe {
+            AudioObjectGetPropertyData(
+                object_id,
+                &address(selector),
+                0,
+                std::ptr::null(),
+                &mut size,
+                (&mut value as *mut *mut NSString).cast(),
+            )
+        };
+        if status != 0 {
+            return None;
+        }
+        unsafe { Retained::from_raw(value) }.map(|s| s.to_string())
+    };
+
+    let mut device_id: u32 = 0;
+    let mut size = std::mem::size_of::<u32>() as u32;
+    let status = unsafe {
+        AudioObjectGetPropertyData(
+            SYSTEM_OBJECT,
+            &address(DEFAULT_OUTPUT_DEVICE),
+            0,
+            std::ptr::null(),
+            &mut size,
+            (&mut device_id as *mut u32).cast(),
+        )
+    };
+    // kAudioObjectUnknown (0) means there is no output device.
+    if status != 0 || device_id == 0 {
+        return None;
+    }
+
+    Some(AudioDevice {
+        id: string_property(device_id, DEVICE_UID)?,
+        name: string_property(device_id, OBJECT_NAME).unwrap_or_default(),
+    })
+}
+
+/// Register a handler fired when the default audio output device changes,
+/// e.g. when headphones are plugged in. Receives the new device, or `null`
+/// when none is left. Replaces any previously registered handler.
+#[napi(ts_args_type = "callback: (device: AudioDevice | null) => void")]
+pub fn on_audio_devices_changed(callback: JsFunction) -> Result<()> {
+    let tsfn: ThreadsafeFunction<Option<AudioDevice>, ErrorStrategy::Fatal> = callback
+        .create_threadsafe_function(0, |ctx: ThreadSafeCallContext<Option<AudioDevice>>| {
+            Ok(vec![ctx.value])
+        })?;
+
+    // Baseline against the current state, as for accessibility preferences.
+    let current = get_default_audio_device().map(|d| d.id);
+    LAST_AUDIO_DEVICE.with(|d| *d.borrow_mut() = current);
+
+    with_manager(|mgr| {
+        mgr.app_handlers.on_audio_devices_changed = Some(tsfn);
+    });
+    Ok(())
+}
+
+// ── Virtual desktops ───────────────────────────────────────────
+
+/// A Windows virtual desktop, as listed by `getVirtualDesktops()`.
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct VirtualDesktop {
+    /// Desktop GUID, for `moveToVirtualDesktop()`.
+    pub id: String,
+    /// Name shown in Task View, or "Desktop N" when unnamed.
+    pub name: String,
+    /// Whether the user is looking at this desktop.
+    pub current: bool,
+}
+
+/// The user's virtual desktops in Task View order.
+///
+/// - **Windows**: read from Explorer's state. Empty until the user has
+///   created a second desktop.
+/// - **macOS / Linux**: always empty; see `getWindowWorkspace()`.
+#[napi]
+pub fn get_virtual_desktops() -> Vec<VirtualDesktop> {
+    #[cfg(target_os = "windows")]
+    {
+        crate::platform::virtual_desktop::list()
+            .into_iter()
+            .map(|d| VirtualDesktop {
+                id: d.id,
+                name: d.name,
+                current: d.current,
+            })
+            .collect()
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        Vec::new()
+    }
+}
+
+// ── Protocol clients ───────────────────────────────────────────
+
+/// Resolve the scheme, executable and arguments of a protocol client call.
+/// `path` defaults to the current executable.
+fn protocol_client_target(
+    name: &str,
+    scheme: &str,
+    path: Option<String>,
+    args: Option<Vec<String>>,
+) -> Result<(String, Vec<String>)> {
+    if !crate::platform::protocol_client::is_valid_scheme(scheme) {
+        return Err(napi::Error::from_reason(format!(
+            "{}(): invalid URL scheme \"{}\".",
+            name, scheme
+        )));
+    }
+    let path = match path {
+        Some(path) => path,
+        None => std::env::current_exe()
+            .map_err(|e| {
+                napi::Error::from_reason(format!(
+                    "{}(): cannot resolve the current executable: {}",
+                    name, e
+                ))
+            })?
+            .to_string_lossy()
+            .into_owned(),
+    };
+    Ok((path, args.unwrap_or_default()))
+}
+
+/// Register the app as the default handler of `scheme` links, e.g. `myapp`
+/// for `myapp://...`.
+///
+/// - **macOS**: through LaunchServices, for the app's bundle. The bundle
+///   must declare the scheme in `CFBundleURLTypes`; `path` and `args` are
+///   ignored.
+/// - **Windows**: under `HKCU\Software\Classes`. Links start
+///   `"<path>" <args> "<url>"`.
+/// - **Linux**: a desktop entry in the user's applications directory, set
+///   as the default with `xdg-mime`. Links start `<path> <args> <url>`.
+///
+/// `path` defaults to the current executable; pass it (and `args`, e.g. the
+/// entry script) when the app runs under a shared runtime.
+#[napi]
+pub fn set_as_default_protocol_client(
+    scheme: String,
+    path: Option<String>,
+    args: Option<Vec<String>>,
+) -> Result<()> {
+    let (path, args) = protocol_client_target("setAsDefaultProtocolClient", &scheme, path, args)?;
+    crate::platform::protocol_client::register(&scheme, &path, &args)
+        .map_err(|e| napi::Error::from_reason(format!("Failed to register protocol client: {}", e)))
+}
+
+/// Undo `setAsDefaultProtocolClient()`. Does nothing when the app is not
+/// the registered handler. On macOS the scheme is handed to another app
+/// that declares it, if any.
+#[napi]
+pub fn remove_as_default_protocol_client(
+    scheme: String,
+    path: Option<String>,
+    args: Option<Vec<String>>,
+) -> Result<()> {
+    let (path, args) =
+        protocol_client_target("removeAsDefaultProtocolClient", &scheme, path, args)?;
+    crate::platform::protocol_client::unregister(&scheme, &path, &args)
+        .map_err(|e| napi::Error::from_reason(format!("Failed to remove protocol client: {}", e)))
+}
+
+/// Whether the app is the default handler of `scheme` links. On Windows
+/// the registered command must match `path` and `args` as well.
+#[napi]
+pub fn is_default_protocol_client(
+    scheme: String,
+    path: Option<String>,
+    args: Option<Vec<String>>,
+) -> Result<bool> {
+    let (path, args) = protocol_client_target("isDefaultProtocolClient", &scheme, path, args)?;
+    Ok(crate::platform::protocol_client::is_default(
+        &scheme, &path, &args,
+    ))
+}
+
+// ── Change polling ─────────────────────────────────────────────
+
+/// Poll system settings and fire app-level change handlers.
+/// Called from `pumpEvents()`; throttled to [`POLL_INTERVAL`].
+pub fn poll(handlers: &AppEventHandlers) {
+    let now = Instant::now();
+    let due = LAST_POLL.with(|last| {
+        let mut last = last.borrow_mut();
+        match *last {
+            Some(t) if now.duration_since(t) < POLL_INTERVAL => false,
+            _ => {
+                *last = Some(now);
+                true
+            }
+        }
+    });
+    if !due {
+        return;
+    }
+
+    if let Some(ref cb) = handlers.on_accessibility_preferences_changed {
+        let current = get_accessibility_preferences();
+        let changed = LAST_ACCESSIBILITY_PREFERENCES
+            .with(|p| p.borrow_mut().replace(current) != Some(current));
+        if changed {
+            cb.call(current, ThreadsafeFunctionCallMode::NonBlocking);
+        }
+    }
+
+    if let Some(ref cb) = handlers.on_accent_color_changed {
+        let current = get_accent_color();
+        let changed = LAST_ACCENT_COLOR.with(|c| {
+            let mut last = c.borrow_mut();
+            if last.as_deref() == Some(current.as_str()) {
+                return false;
+            }
+            *last = Some(current.clone());
+            true
+        });
+        if changed {
+            cb.call(current, ThreadsafeFunctionCallMode::NonBlocking);
+        }
+    }
+
+    if let Some((threshold, ref cb)) = handlers.on_idle_state_changed {
+        let idle = get_system_idle_time() >= threshold;
+        let changed = LAST_IDLE.with(|l| l.borrow_mut().replace(idle) != Some(idle));
+        if changed {
+            let state = if idle { "idle" } else { "active" };
+            cb.call(state.to_string(), ThreadsafeFunctionCallMode::NonBlocking);
+        }
+    }
+
+    if let Some(ref cb) = handlers.on_audio_devices_changed {
+        let current = get_default_audio_device();
+        let id = current.as_ref().map(|d| d.id.clone());
+        let changed = LAST_AUDIO_DEVICE.with(|d| {
+            let mut last = d.borrow_mut();
+            if *last == id {
+                return false;
+            }
+            *last = id;
+            true
+        });
+        if changed {
+            cb.call(current, ThreadsafeFunctionCallMode::NonBlocking);
+        }
+    }
+}
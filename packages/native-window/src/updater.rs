@@ -0,0 +1,510 @@
+/// Minimal self-update support: download an installer or archive, verify
+/// it, and relaunch into it.
+///
+/// Downloads run `curl` on a background thread (bundled with macOS,
+/// Windows 10 1803+ and virtually every Linux distribution), so no HTTP
+/// stack is linked into the addon. Every download must be verifiable: by
+/// SHA-256, by the platform code signature (Authenticode on Windows,
+/// `codesign` / `pkgutil` on macOS), or both. A file that fails
+/// verification is deleted before the promise settles.
+use std::cell::RefCell;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread::JoinHandle;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use napi::threadsafe_function::{
+    ErrorStrategy, ThreadSafeCallContext, ThreadsafeFunction, ThreadsafeFunctionCallMode,
+};
+use napi::JsFunction;
+use napi_derive::napi;
+use sha2::{Digest, Sha256};
+
+/// How often the size of a running download is reported.
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Set on shutdown: running downloads kill `curl` and end without
+/// settling their promise.
+static CANCELLED: AtomicBool = AtomicBool::new(false);
+
+thread_local! {
+    /// Download threads, joined on shutdown. Finished ones are dropped
+    /// when the next download starts.
+    static THREADS: RefCell<Vec<JoinHandle<()>>> = const { RefCell::new(Vec::new()) };
+}
+
+/// What to download with `downloadUpdate()` and how to verify it. At least
+/// one of `sha256` and `signer` is required.
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct UpdateDownloadOptions {
+    /// `https://` URL of the installer or archive.
+    pub url: String,
+    /// Expected SHA-256 of the file, as hex.
+    pub sha256: Option<String>,
+    /// Organization the file must be signed by, matched exactly: the `O=`
+    /// name of the Authenticode certificate on Windows, or the Developer ID
+    /// name or team ID on macOS (`.pkg`, `.dmg` and `.app` only). Not
+    /// supported on Linux.
+    pub signer: Option<String>,
+    /// Where to save the file. Default: a file named after the last segment
+    /// of the URL, in a new private directory under the temp directory.
+    pub path: Option<String>,
+}
+
+/// Progress of a `downloadUpdate()` call, in bytes.
+#[napi(object)]
+#[derive(Debug, Clone, Copy)]
+pub struct UpdateProgress {
+    pub received: f64,
+    /// Size reported by the server, if any.
+    pub total: Option<f64>,
+}
+
+/// Outcome of a `downloadUpdate()` call: the verified file, or why the
+/// download failed.
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct UpdateDownloadResult {
+    pub path: Option<String>,
+    pub error: Option<String>,
+}
+
+/// Download an update on a background thread. `on_progress` fires while
+/// the file downloads and `on_done` once with the verified path or an
+/// error. Use the promise-based `downloadUpdate()` wrapper from JS.
+#[napi(
+    ts_args_type = "options: UpdateDownloadOptions, onProgress: (progress: UpdateProgress) => void, onDone: (result: UpdateDownloadResult) => void"
+)]
+pub fn download_update(
+    options: UpdateDownloadOptions,
+    on_progress: JsFunction,
+    on_done: JsFunction,
+) -> napi::Result<()> {
+    if !options.url.starts_with("https://") {
+        return Err(napi::Error::from_reason(
+            "downloadUpdate() only accepts https:// URLs.",
+        ));
+    }
+    if options.sha256.is_none() && options.signer.is_none() {
+        return Err(napi::Error::from_reason(
+            "downloadUpdate() requires sha256 or signer so the download can be verified.",
+        ));
+    }
+    if let Some(ref hash) = options.sha256 {
+        if hash.len() != 64 || !hash.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(napi::Error::from_reason(
+                "downloadUpdate() sha256 must be 64 hex characters.",
+            ));
+        }
+    }
+    #[cfg(target_os = "linux")]
+    if options.signer.is_some() {
+        return Err(napi::Error::from_reason(
+            "downloadUpdate() signer is not supported on Linux; use sha256.",
+        ));
+    }
+
+    let on_progress: ThreadsafeFunction<UpdateProgress, ErrorStrategy::Fatal> = on_progress
+        .create_threadsafe_function(0, |ctx: ThreadSafeCallContext<UpdateProgress>| {
+            Ok(vec![ctx.value])
+        })?;
+    let on_done: ThreadsafeFunction<UpdateDownloadResult, ErrorStrategy::Fatal> = on_done
+        .create_threadsafe_function(0, |ctx: ThreadSafeCallContext<UpdateDownloadResult>| {
+            Ok(vec![ctx.value])
+        })?;
+
+    let (path, private_dir) = match options.path {
+        Some(ref path) => (PathBuf::from(path), None),
+        None => {
+            let dir = private_temp_dir().map_err(|e| {
+                napi::Error::from_reason(format!("Failed to create a download directory: {}", e))
+            })?;
+            (dir.join(file_name(&options.url)), Some(dir))
+        }
+    };
+
+    let thread = std::thread::Builder::new()
+        .name("native-window-updater".to_string())
+        .spawn(move || {
+            let result = match download(&options, &path, &on_progress)
+                .and_then(|()| verify(&options, &path))
+            {
+                Ok(()) => UpdateDownloadResult {
+                    path: Some(path.to_string_lossy().into_owned()),
+                    error: None,
+                },
+                Err(e) => {
+                    let _ = std::fs::remove_file(&path);
+                    if let Some(ref dir) = private_dir {
+                        let _ = std::fs::remove_dir(dir);
+                    }
+                    UpdateDownloadResult {
+                        path: None,
+                        error: Some(e),
+                    }
+                }
+            };
+            if !CANCELLED.load(Ordering::Relaxed) {
+                on_done.call(result, ThreadsafeFunctionCallMode::Blocking);
+            }
+        })
+        .map_err(|e| napi::Error::from_reason(format!("Failed to start update download: {}", e)))?;
+    THREADS.with(|t| {
+        let mut threads = t.borrow_mut();
+        threads.retain(|t| !t.is_finished());
+        threads.push(thread);
+    });
+    Ok(())
+}
+
+/// Cancel running downloads and wait for their threads to exit.
+pub(crate) fn shutdown() {
+    CANCELLED.store(true, Ordering::Relaxed);
+    for thread in THREADS.with(|t| std::mem::take(&mut *t.borrow_mut())) {
+        let _ = thread.join();
+    }
+    CANCELLED.store(false, Ordering::Relaxed);
+}
+
+/// Wait for `child`, calling `tick` every [`PROGRESS_INTERVAL`]. Kills it
+/// on shutdown.
+fn wait_cancellable(child: &mut Child, mut tick: impl FnMut()) -> Result<ExitStatus, String> {
+    loop {
+        if let Some(status) = child.try_wait().map_err(|e| e.to_string())? {
+            return Ok(status);
+        }
+        if CANCELLED.load(Ordering::Relaxed) {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err("download cancelled".to_string());
+        }
+        std::thread::sleep(PROGRESS_INTERVAL);
+        tick();
+    }
+}
+
+/// Create a new directory under the temp directory that only this user can
+/// access. `curl -o` follows symlinks, so a predictable path in a shared
+/// `/tmp` would let another local user redirect or swap the download.
+fn private_temp_dir() -> std::io::Result<PathBuf> {
+    let base = std::env::temp_dir();
+    let nonce = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or_default();
+    for attempt in 0..16u32 {
+        let dir = base.join(format!(
+            "native-window-update-{}-{:x}-{}",
+            std::process::id(),
+            nonce,
+            attempt
+        ));
+        let mut builder = std::fs::DirBuilder::new();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::DirBuilderExt;
+            builder.mode(0o700);
+        }
+        // Fails if the path exists, so the directory is always our own.
+        match builder.create(&dir) {
+            Ok(()) => return Ok(dir),
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => continue,
+            Err(e) => return Err(e),
+        }
+    }
+    Err(std::io::Error::new(
+        std::io::ErrorKind::AlreadyExists,
+        "no unused directory name",
+    ))
+}
+
+/// A file name for the download from the last URL path segment, limited
+/// to characters that are safe on every platform.
+fn file_name(url: &str) -> String {
+    let path = url.split(['?', '#']).next().unwrap_or(url);
+    let name: String = path
+        .rsplit('/')
+        .next()
+        .unwrap_or_default()
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_'))
+        .collect();
+    let name = name.trim_start_matches('.');
+    if name.is_empty() {
+        "native-window-update".to_string()
+    } else {
+        format!("native-window-update-{}", name)
+    }
+}
+
+/// Size announced by the server, from a HEAD request that follows redirects.
+fn content_length(url: &str) -> Option<f64> {
+    let mut child = Command::new("curl")
+        .args(["-sSIL", "--proto", "=https", "--max-time", "30", url])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .ok()?;
+    // Headers are small enough not to fill the pipe before curl exits.
+    wait_cancellable(&mut child, || {}).ok()?;
+    let mut headers = String::new();
+    child.stdout.take()?.read_to_string(&mut headers).ok()?;
+    // One header block per redirect; the last length wins.
+    headers
+        .lines()
+        .filter_map(|line| {
+            let (name, value) = line.split_once(':')?;
+            if !name.trim().eq_ignore_ascii_case("content-length") {
+                return None;
+            }
+            value.trim().parse().ok()
+        })
+        .next_back()
+}
+
+/// Download `options.url` to `path`, reporting the growing file size.
+fn download(
+    options: &UpdateDownloadOptions,
+    path: &Path,
+    on_progress: &ThreadsafeFunction<UpdateProgress, ErrorStrategy::Fatal>,
+) -> Result<(), String> {
+    let total = content_length(&options.url);
+    let mut child = Command::new("curl")
+        .args(["-sSfL", "--proto", "=https", "--tlsv1.2", "-o"])
+        .arg(path)
+        .arg(&options.url)
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("failed to run curl: {}", e))?;
+
+    let mut reported = 0u64;
+    let status = wait_cancellable(&mut child, || {
+        let received = std::fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+        if received != reported {
+            reported = received;
+            on_progress.call(
+                UpdateProgress {
+                    received: received as f64,
+                    total,
+                },
+                ThreadsafeFunctionCallMode::NonBlocking,
+            );
+        }
+    })?;
+    if !status.success() {
+        let mut stderr = String::new();
+        if let Some(mut pipe) = child.stderr.take() {
+            let _ = pipe.read_to_string(&mut stderr);
+        }
+        return Err(format!("download failed: {}", stderr.trim()));
+    }
+
+    let received = std::fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+    if received == 0 {
+        return Err("the downloaded file is empty".to_string());
+    }
+    on_progress.call(
+        UpdateProgress {
+            received: received as f64,
+            total: total.or(Some(received as f64)),
+        },
+        ThreadsafeFunctionCallMode::NonBlocking,
+    );
+    Ok(())
+}
+
+/// Apply every check requested in `options` to the downloaded file.
+fn verify(options: &UpdateDownloadOptions, path: &Path) -> Result<(), String> {
+    if let Some(ref expected) = options.sha256 {
+        let actual = sha256_file(path)?;
+        if !actual.eq_ignore_ascii_case(expected) {
+            return Err(format!(
+                "SHA-256 mismatch: expected {}, got {}",
+                expected.to_ascii_lowercase(),
+                actual
+            ));
+        }
+    }
+    if let Some(ref signer) = options.signer {
+        verify_signature(path, signer)?;
+    }
+    Ok(())
+}
+
+fn sha256_file(path: &Path) -> Result<String, String> {
+    let mut file = std::fs::File::open(path).map_err(|e| e.to_string())?;
+    let mut hasher = Sha256::new();
+    let mut buf = vec![0u8; 64 * 1024];
+    loop {
+        let n = file.read(&mut buf).map_err(|e| e.to_string())?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buf[..n]);
+    }
+    Ok(hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect())
+}
+
+#[cfg(target_os = "windows")]
+fn verify_signature(path: &Path, signer: &str) -> Result<(), String> {
+    use crate::runtime::{verify_authenticode, AuthenticodeError};
+
+    verify_authenticode(path, signer).map_err(|e| match e {
+        AuthenticodeError::Invalid(reason) => {
+            format!("signature verification failed: {}", reason)
+        }
+        AuthenticodeError::Unavailable(reason) => {
+            format!("could not verify the Authenticode signature: {}", reason)
+        }
+    })
+}
+
+/// Installer packages are checked with `pkgutil`, everything else with
+/// `codesign`. Either way the leaf certificate's developer name or team ID
+/// must equal `signer`.
+#[cfg(target_os = "macos")]
+fn verify_signature(path: &Path, signer: &str) -> Result<(), String> {
+    let is_pkg = path
+        .extension()
+        .is_some_and(|e| e.eq_ignore_ascii_case("pkg"));
+    if is_pkg {
+        verify_pkg_signature(path, signer)
+    } else {
+        verify_code_signature(path, signer)
+    }
+}
+
+/// `codesign` checks the signer itself against a requirement: an Apple-issued
+/// chain whose leaf has `signer` as its organization (developer name) or
+/// organizational unit (team ID).
+#[cfg(target_os = "macos")]
+fn verify_code_signature(path: &Path, signer: &str) -> Result<(), String> {
+    let quoted = format!("\"{}\"", signer.replace('\\', "\\\\").replace('"', "\\\""));
+    let requirement = format!(
+        "=anchor apple generic and (certificate leaf[subject.O] = {0} or certificate leaf[subject.OU] = {0})",
+        quoted
+    );
+    let output = Command::new("codesign")
+        .args(["--verify", "--strict", "-R"])
+        .arg(requirement)
+        .arg(path)
+        .output()
+        .map_err(|e| format!("failed to run codesign: {}", e))?;
+    if !output.status.success() {
+        // codesign prints why to stderr.
+        let reason = String::from_utf8_lossy(&output.stderr);
+        return Err(format!(
+            "the file is not validly signed by {}: {}",
+            signer,
+            reason.trim()
+        ));
+    }
+    Ok(())
+}
+
+/// `pkgutil` lists the certificate chain leaf first, as
+/// `1. Developer ID Installer: <name> (<team ID>)`.
+#[cfg(target_os = "macos")]
+fn verify_pkg_signature(path: &Path, signer: &str) -> Result<(), String> {
+    let output = Command::new("pkgutil")
+        .arg("--check-signature")
+        .arg(path)
+        .output()
+        .map_err(|e| format!("failed to run pkgutil: {}", e))?;
+    let report = String::from_utf8_lossy(&output.stdout);
+    if !output.status.success() || !report.contains("Status: signed") {
+        return Err("the file is not validly signed".to_string());
+    }
+    let leaf = report
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("1. "))
+        .and_then(|leaf| leaf.split_once(": ").map(|(_, identity)| identity));
+    let signed_by_signer = leaf.is_some_and(|identity| {
+        match identity
+            .strip_suffix(')')
+            .and_then(|rest| rest.rsplit_once(" ("))
+        {
+            Some((name, team)) => name == signer || team == signer,
+            None => identity == signer,
+        }
+    });
+    if !signed_by_signer {
+        return Err(format!("the file is not signed by {}", signer));
+    }
+    Ok(())
+}
+
+#[cfg(target_os = "linux")]
+fn verify_signature(_path: &Path, _signer: &str) -> Result<(), String> {
+    // Rejected by download_update() before the download starts.
+    Err("signature verification is not supported on Linux".to_string())
+}
+
+/// What `relaunch()` starts.
+#[napi(object)]
+#[derive(Debug, Clone, Default)]
+pub struct RelaunchOptions {
+    /// Executable to start. Default: the current executable.
+    pub exec_path: Option<String>,
+    /// Arguments for the new process. Default: the current process's
+    /// arguments when `exec_path` is omitted, none otherwise.
+    pub args: Option<Vec<String>>,
+}
+
+/// Start a new instance of the app, or an installer from
+/// `downloadUpdate()`, detached from this process. The current process
+/// keeps running; exit it once the handoff is done.
+#[napi]
+pub fn relaunch(options: Option<RelaunchOptions>) -> napi::Result<()> {
+    let options = options.unwrap_or_default();
+    let (exec_path, args) = match options.exec_path {
+        Some(path) => (PathBuf::from(path), options.args.unwrap_or_default()),
+        None => {
+            let exe = std::env::current_exe().map_err(|e| {
+                napi::Error::from_reason(format!(
+                    "relaunch(): cannot resolve the current executable: {}",
+                    e
+                ))
+            })?;
+            let args = options
+                .args
+                .unwrap_or_else(|| std::env::args().skip(1).collect());
+            (exe, args)
+        }
+    };
+
+    let mut command = Command::new(&exec_path);
+    command
+        .args(&args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+    // Keep the new process alive when this one exits.
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        use windows::Win32::System::Threading::{CREATE_NEW_PROCESS_GROUP, DETACHED_PROCESS};
+        command.creation_flags((DETACHED_PROCESS | CREATE_NEW_PROCESS_GROUP).0);
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        command.process_group(0);
+    }
+    command.spawn().map_err(|e| {
+        napi::Error::from_reason(format!(
+            "relaunch(): failed to start {}: {}",
+            exec_path.display(),
+            e
+        ))
+    })?;
+    Ok(())
+}
@@ -3,16 +3,23 @@ use napi::threadsafe_function::{ErrorStrategy, ThreadSafeCallContext, Threadsafe
 use napi::JsFunction;
 use napi_derive::napi;
 
-use crate::options::WindowOptions;
+use crate::options::{
+    FontOverrides, InputEvent, LoadHtmlOptions, NetworkConditions, PickedColor, Point, Rect,
+    ShareOptions, WindowMenuItem, WindowOptions, WindowState,
+};
+use crate::platform::PlatformBackend;
 use crate::window_manager::{
-    extract_origin, with_manager, Command, PermissionFlags, ALLOWED_HOSTS_MAP, PERMISSIONS_MAP,
-    TRUSTED_ORIGINS_MAP,
+    extract_origin, with_manager, Command, CommandRecord, PermissionFlags, ALLOWED_HOSTS_MAP,
+    ALLOWED_SCHEMES_MAP, CLOSE_INTERCEPTED, COMMAND_HISTORY, EVENT_MASKS, MASKABLE_EVENTS,
+    MINIMIZE_TO_TRAY, PERMISSIONS_MAP, TRUSTED_ORIGINS_MAP,
 };
 
 /// A native OS window with an embedded webview.
 #[napi]
 pub struct NativeWindow {
     id: u32,
+    /// Whether garbage collection of this object closes the window.
+    close_on_drop: bool,
 }
 
 #[napi]
@@ -21,7 +28,88 @@ impl NativeWindow {
     /// The window is created asynchronously during the next `pumpEvents()` call.
     #[napi(constructor)]
     pub fn new(options: Option<WindowOptions>) -> Result<Self> {
-        let opts = options.unwrap_or_default();
+        let mut opts = options.unwrap_or_default();
+
+        if let Some(ref anchor) = opts.anchor {
+            let placement = anchor.placement.as_deref().unwrap_or("below");
+            if !["below", "above", "right", "left"].contains(&placement) {
+                return Err(napi::Error::from_reason(format!(
+                    "Invalid popup placement '{}'. Expected 'below', 'above', 'right', or 'left'.",
+                    placement
+                )));
+            }
+            let r = anchor.rect;
+            if ![r.x, r.y, r.width, r.height].iter().all(|v| v.is_finite()) {
+                return Err(napi::Error::from_reason(
+                    "Popup anchor rect must contain finite numbers.",
+                ));
+            }
+        }
+        if let Some(ref mode) = opts.fullscreen_mode {
+            validate_fullscreen_mode(mode)?;
+        }
+        if let Some(ref style) = opts.title_bar_style {
+            if !matches!(
+                style.as_str(),
+                "default" | "hidden" | "hiddenInset" | "overlay"
+            ) {
+                return Err(napi::Error::from_reason(format!(
+                    "Invalid titleBarStyle '{}'. Expected 'default', 'hidden', 'hiddenInset', or 'overlay'.",
+                    style
+                )));
+            }
+        }
+        if let Some(ref material) = opts.vibrancy {
+            if !VIBRANCY_MATERIALS.contains(&material.as_str()) {
+                return Err(napi::Error::from_reason(format!(
+                    "Invalid vibrancy '{}'. Expected one of: {}.",
+                    material,
+                    VIBRANCY_MATERIALS.join(", ")
+                )));
+            }
+        }
+        if let Some(ref theme) = opts.theme {
+            validate_theme(theme)?;
+        }
+        if let Some(ref style) = opts.corner_style {
+            if !matches!(style.as_str(), "default" | "round" | "square") {
+                return Err(napi::Error::from_reason(format!(
+                    "Invalid cornerStyle '{}'. Expected 'default', 'round', or 'square'.",
+                    style
+                )));
+            }
+        }
+        if let Some(p) = opts.traffic_light_position {
+            if !p.x.is_finite() || !p.y.is_finite() || p.x < 0.0 || p.y < 0.0 {
+                return Err(napi::Error::from_reason(
+                    "trafficLightPosition must contain finite, non-negative numbers.",
+                ));
+            }
+        }
+        if let Some(ref mut schemes) = opts.allowed_schemes {
+            validate_allowed_schemes(schemes)?;
+        }
+        if let Some(hosts) = opts
+            .iframe_policy
+            .as_ref()
+            .and_then(|p| p.allowed_hosts.as_ref())
+        {
+            if hosts.iter().any(|h| h.trim().is_empty()) {
+                return Err(napi::Error::from_reason(
+                    "iframePolicy.allowedHosts must not contain empty hosts.",
+                ));
+            }
+        }
+        if let Some(Either::B(ref display)) = opts.display {
+            if display != "primary" && display != "cursor" {
+                return Err(napi::Error::from_reason(format!(
+                    "Invalid display '{}'. Expected a monitor index, 'primary', or 'cursor'.",
+                    display
+                )));
+            }
+        }
+
+        let close_on_drop = opts.persist_after_gc != Some(true);
 
         let id = with_manager(|mgr| {
             if !mgr.initialized {
@@ -29,6 +117,15 @@ impl NativeWindow {
                     "Native window system not initialized. Call init() first.",
                 ));
             }
+            if mgr.strict_options {
+                let unsupported = crate::capabilities::unsupported_options(&opts);
+                if !unsupported.is_empty() {
+                    return Err(napi::Error::from_reason(format!(
+                        "Unsupported window options on this platform: {}",
+                        unsupported.join(", ")
+                    )));
+                }
+            }
             let id = mgr.allocate_id()?;
             // Store trusted origins for native-layer IPC filtering.
             // Normalize each origin through extract_origin() so that
@@ -52,6 +149,13 @@ impl NativeWindow {
                     });
                 }
             }
+            if let Some(ref schemes) = opts.allowed_schemes {
+                if !schemes.is_empty() {
+                    ALLOWED_SCHEMES_MAP.with(|s| {
+                        s.borrow_mut().insert(id, schemes.clone());
+                    });
+                }
+            }
             // Store permission flags for platform callbacks
             // (separate thread-local so macOS WKUIDelegate / Windows PermissionRequested
             // handlers can read while MANAGER is borrowed)
@@ -63,11 +167,16 @@ impl NativeWindow {
             PERMISSIONS_MAP.with(|p| {
                 p.borrow_mut().insert(id, permissions);
             });
+            if opts.minimize_to_tray == Some(true) {
+                MINIMIZE_TO_TRAY.with(|t| {
+                    t.borrow_mut().insert(id);
+                });
+            }
             mgr.push_command(Command::CreateWindow { id, options: opts });
             Ok(id)
         })?;
 
-        Ok(Self { id })
+        Ok(Self { id, close_on_drop })
     }
 
     /// Get the unique window ID.
@@ -79,7 +188,8 @@ impl NativeWindow {
     // ---- Content loading ----
 
     /// Load a URL in the webview.
-    /// Only `http:`, `https:`, and internal `nativewindow:` schemes are allowed.
+    /// Only `http:`, `https:`, internal `nativewindow:` and the window's
+    /// `allowedSchemes` are allowed.
     #[napi]
     pub fn load_url(&self, url: String) -> Result<()> {
         let trimmed = url.trim().to_string();
@@ -88,10 +198,11 @@ impl NativeWindow {
         if !lower.starts_with("http://")
             && !lower.starts_with("https://")
             && !lower.starts_with("nativewindow:")
+            && !crate::window_manager::is_scheme_allowed(self.id, &trimmed)
         {
             return Err(napi::Error::from_reason(
-                "Blocked: only http:, https:, and nativewindow: URLs are allowed in loadUrl(). \
-                 Use evaluateJs() for script execution or loadHtml() for HTML content.",
+                "Blocked: only http:, https:, nativewindow: and allowedSchemes URLs are allowed \
+                 in loadUrl(). Use evaluateJs() for script execution or loadHtml() for HTML content.",
             ));
         }
         with_manager(|mgr| {
@@ -103,11 +214,78 @@ impl NativeWindow {
         Ok(())
     }
 
-    /// Load an HTML string directly in the webview.
+    /// Load an HTML string directly in the webview, served at `options.path`
+    /// (default `/`) under the custom protocol origin.
+    #[napi]
+    pub fn load_html(&self, html: String, options: Option<LoadHtmlOptions>) -> Result<()> {
+        let path = options
+            .and_then(|o| o.path)
+            .unwrap_or_else(|| "/".to_string());
+        crate::window_manager::validate_html_path(&path).map_err(napi::Error::from_reason)?;
+        with_manager(|mgr| {
+            mgr.push_command(Command::LoadHTML {
+                id: self.id,
+                html,
+                path,
+            });
+        });
+        Ok(())
+    }
+
+    /// Serve `script` as the service worker of `loadHtml()` content, at
+    /// `/service-worker.js` under the custom protocol origin. `null` stops
+    /// serving it; already-registered workers stay until unregistered.
+    #[napi(ts_args_type = "script: string | null")]
+    pub fn set_service_worker(&self, script: Option<String>) -> Result<()> {
+        crate::window_manager::set_service_worker_script(self.id, script);
+        Ok(())
+    }
+
+    /// Serve `html` through the custom protocol in place of any main-frame
+    /// page that fails to load (offline, DNS, refused connection), then
+    /// fire `onFallbackShown`. `null` restores the engine's error page.
+    #[napi(ts_args_type = "html: string | null")]
+    pub fn set_fallback_html(&self, html: Option<String>) -> Result<()> {
+        with_manager(|mgr| {
+            mgr.push_command(Command::SetFallbackHtml { id: self.id, html });
+        });
+        Ok(())
+    }
+
+    /// Unregister all service workers of the current page's origin and
+    /// clear its Cache Storage.
+    #[napi]
+    pub fn unregister_service_workers(&self) -> Result<()> {
+        with_manager(|mgr| {
+            mgr.push_command(Command::UnregisterServiceWorkers { id: self.id });
+        });
+        Ok(())
+    }
+
+    /// Warm up DNS, TCP and TLS for origins the page will load from next.
+    #[napi]
+    pub fn preconnect(&self, origins: Vec<String>) -> Result<()> {
+        let origins = validate_resource_hints("preconnect", origins, true)?;
+        with_manager(|mgr| {
+            mgr.push_command(Command::AddResourceHints {
+                id: self.id,
+                rel: "preconnect",
+                urls: origins,
+            });
+        });
+        Ok(())
+    }
+
+    /// Fetch resources into the HTTP cache ahead of an upcoming navigation.
     #[napi]
-    pub fn load_html(&self, html: String) -> Result<()> {
+    pub fn prefetch(&self, urls: Vec<String>) -> Result<()> {
+        let urls = validate_resource_hints("prefetch", urls, false)?;
         with_manager(|mgr| {
-            mgr.push_command(Command::LoadHTML { id: self.id, html });
+            mgr.push_command(Command::AddResourceHints {
+                id: self.id,
+                rel: "prefetch",
+                urls,
+            });
         });
         Ok(())
     }
@@ -125,6 +303,20 @@ impl NativeWindow {
         Ok(())
     }
 
+    /// Execute JavaScript with a transient user activation, so the script
+    /// may call APIs that require a user gesture (clipboard writes,
+    /// fullscreen, `window.open`). Fire-and-forget like `evaluate_js`.
+    #[napi]
+    pub fn evaluate_js_with_user_gesture(&self, script: String) -> Result<()> {
+        with_manager(|mgr| {
+            mgr.push_command(Command::EvaluateJSWithUserGesture {
+                id: self.id,
+                script,
+            });
+        });
+        Ok(())
+    }
+
     /// Send a message to the webview.
     /// This calls `window.__native_message__(msg)` in the webview context.
     #[napi]
@@ -145,6 +337,46 @@ impl NativeWindow {
         Ok(())
     }
 
+    /// Synthesize a mouse, key, or wheel event into the webview.
+    /// Coordinates are CSS pixels relative to the webview's top-left corner.
+    #[napi]
+    pub fn send_input_event(&self, event: InputEvent) -> Result<()> {
+        let actions: &[&str] = match event.kind.as_str() {
+            "mouse" => &["down", "up", "move", "click"],
+            "key" => &["down", "up", "press"],
+            "wheel" => &[],
+            other => {
+                return Err(napi::Error::from_reason(format!(
+                    "Invalid input event type '{}'. Expected 'mouse', 'key', or 'wheel'.",
+                    other
+                )));
+            }
+        };
+        if let Some(ref action) = event.action {
+            if !actions.contains(&action.as_str()) {
+                return Err(napi::Error::from_reason(format!(
+                    "Invalid action '{}' for '{}' input event.",
+                    action, event.kind
+                )));
+            }
+        }
+        if event.kind == "key" && event.key.as_deref().map_or(true, str::is_empty) {
+            return Err(napi::Error::from_reason(
+                "Key input events require a non-empty 'key'.",
+            ));
+        }
+        let coords = [event.x, event.y, event.delta_x, event.delta_y];
+        if coords.iter().flatten().any(|v| !v.is_finite()) {
+            return Err(napi::Error::from_reason(
+                "Input event coordinates and deltas must be finite numbers.",
+            ));
+        }
+        with_manager(|mgr| {
+            mgr.push_command(Command::SendInputEvent { id: self.id, event });
+        });
+        Ok(())
+    }
+
     // ---- Window control ----
 
     /// Set the window title.
@@ -156,6 +388,20 @@ impl NativeWindow {
         Ok(())
     }
 
+    /// Compose the window title from the page's document title, replacing
+    /// `{{pageTitle}}` in `template` on every title change. `null` stops
+    /// composing and leaves the current title.
+    #[napi]
+    pub fn set_title_template(&self, template: Option<String>) -> Result<()> {
+        with_manager(|mgr| {
+            mgr.push_command(Command::SetTitleTemplate {
+                id: self.id,
+                template,
+            });
+        });
+        Ok(())
+    }
+
     /// Set the window size in logical pixels.
     #[napi]
     pub fn set_size(&self, width: f64, height: f64) -> Result<()> {
@@ -195,6 +441,26 @@ impl NativeWindow {
         Ok(())
     }
 
+    /// Keep the content area at `width`:`height` while the user resizes
+    /// the window. `setAspectRatio(0, 0)` removes the constraint.
+    #[napi]
+    pub fn set_aspect_ratio(&self, width: f64, height: f64) -> Result<()> {
+        let ratio = if width == 0.0 && height == 0.0 {
+            None
+        } else if width.is_finite() && height.is_finite() && width > 0.0 && height > 0.0 {
+            Some(width / height)
+        } else {
+            return Err(napi::Error::from_reason(format!(
+                "Invalid aspect ratio {}:{}. Expected positive numbers, or 0:0 to remove it.",
+                width, height
+            )));
+        };
+        with_manager(|mgr| {
+            mgr.push_command(Command::SetAspectRatio { id: self.id, ratio });
+        });
+        Ok(())
+    }
+
     /// Set the window position in screen coordinates.
     #[napi]
     pub fn set_position(&self, x: f64, y: f64) -> Result<()> {
@@ -204,217 +470,1192 @@ impl NativeWindow {
         Ok(())
     }
 
-    /// Set whether the window is resizable.
-    #[napi]
-    pub fn set_resizable(&self, resizable: bool) -> Result<()> {
+    /// Tile the window to a half or quarter of a display's work area
+    /// (excluding taskbar/dock/menu bar), or fill it entirely.
+    /// `display` is a monitor index; defaults to the window's current monitor.
+    #[napi(
+        ts_args_type = "position: 'left' | 'right' | 'top' | 'bottom' | 'top-left' | 'top-right' | 'bottom-left' | 'bottom-right' | 'fill', display?: number"
+    )]
+    pub fn snap_to(&self, position: String, display: Option<u32>) -> Result<()> {
+        const POSITIONS: &[&str] = &[
+            "left",
+            "right",
+            "top",
+            "bottom",
+            "top-left",
+            "top-right",
+            "bottom-left",
+            "bottom-right",
+            "fill",
+        ];
+        if !POSITIONS.contains(&position.as_str()) {
+            return Err(napi::Error::from_reason(format!(
+                "Invalid snap position '{}'. Expected one of: {}.",
+                position,
+                POSITIONS.join(", ")
+            )));
+        }
         with_manager(|mgr| {
-            mgr.push_command(Command::SetResizable {
+            mgr.push_command(Command::SnapTo {
                 id: self.id,
-                resizable,
+                position,
+                display,
             });
         });
         Ok(())
     }
 
-    /// Set whether the window has decorations (title bar, borders).
+    /// Center the window in a display's work area (excluding taskbar/dock/
+    /// menu bar). `display` is a monitor index; defaults to the window's
+    /// current monitor.
     #[napi]
-    pub fn set_decorations(&self, decorations: bool) -> Result<()> {
+    pub fn center(&self, display: Option<u32>) -> Result<()> {
         with_manager(|mgr| {
-            mgr.push_command(Command::SetDecorations {
+            mgr.push_command(Command::Center {
                 id: self.id,
-                decorations,
+                display,
             });
         });
         Ok(())
     }
 
-    /// Set whether the window is always on top.
+    /// Move the window to the display with index `display`, keeping its
+    /// place in the work area. Maximized and fullscreen windows are
+    /// maximized or made fullscreen on that display.
     #[napi]
-    pub fn set_always_on_top(&self, always_on_top: bool) -> Result<()> {
+    pub fn move_to_display(&self, display: u32) -> Result<()> {
         with_manager(|mgr| {
-            mgr.push_command(Command::SetAlwaysOnTop {
+            mgr.push_command(Command::MoveToDisplay {
                 id: self.id,
-                always_on_top: always_on_top,
+                display,
             });
         });
         Ok(())
     }
 
-    /// Show the window.
+    /// Set whether the window is resizable.
     #[napi]
-    pub fn show(&self) -> Result<()> {
+    pub fn set_resizable(&self, resizable: bool) -> Result<()> {
         with_manager(|mgr| {
-            mgr.push_command(Command::Show { id: self.id });
+            mgr.push_command(Command::SetResizable {
+                id: self.id,
+                resizable,
+            });
         });
         Ok(())
     }
 
-    /// Hide the window.
+    /// Enable or disable the close button.
     #[napi]
-    pub fn hide(&self) -> Result<()> {
+    pub fn set_closable(&self, closable: bool) -> Result<()> {
         with_manager(|mgr| {
-            mgr.push_command(Command::Hide { id: self.id });
+            mgr.push_command(Command::SetClosable {
+                id: self.id,
+                closable,
+            });
         });
         Ok(())
     }
 
-    /// Close and destroy the window.
+    /// Enable or disable the minimize button. Not supported on Linux.
     #[napi]
-    pub fn close(&self) -> Result<()> {
+    pub fn set_minimizable(&self, minimizable: bool) -> Result<()> {
         with_manager(|mgr| {
-            mgr.push_command(Command::Close { id: self.id });
+            mgr.push_command(Command::SetMinimizable {
+                id: self.id,
+                minimizable,
+            });
         });
         Ok(())
     }
 
-    /// Focus the window.
+    /// Enable or disable the maximize (macOS: zoom/fullscreen) button.
+    /// Not supported on Linux.
     #[napi]
-    pub fn focus(&self) -> Result<()> {
+    pub fn set_maximizable(&self, maximizable: bool) -> Result<()> {
         with_manager(|mgr| {
-            mgr.push_command(Command::Focus { id: self.id });
+            mgr.push_command(Command::SetMaximizable {
+                id: self.id,
+                maximizable,
+            });
         });
         Ok(())
     }
 
-    /// Maximize the window.
+    /// Set whether the window has decorations (title bar, borders).
     #[napi]
-    pub fn maximize(&self) -> Result<()> {
+    pub fn set_decorations(&self, decorations: bool) -> Result<()> {
         with_manager(|mgr| {
-            mgr.push_command(Command::Maximize { id: self.id });
+            mgr.push_command(Command::SetDecorations {
+                id: self.id,
+                decorations,
+            });
         });
         Ok(())
     }
 
-    /// Minimize the window.
+    /// Set whether the window is always on top.
     #[napi]
-    pub fn minimize(&self) -> Result<()> {
+    pub fn set_always_on_top(&self, always_on_top: bool) -> Result<()> {
         with_manager(|mgr| {
-            mgr.push_command(Command::Minimize { id: self.id });
+            mgr.push_command(Command::SetAlwaysOnTop {
+                id: self.id,
+                always_on_top: always_on_top,
+            });
         });
         Ok(())
     }
 
-    /// Restore the window from maximized state.
+    /// Set whether the window stays below all normal windows.
     #[napi]
-    pub fn unmaximize(&self) -> Result<()> {
+    pub fn set_always_on_bottom(&self, always_on_bottom: bool) -> Result<()> {
         with_manager(|mgr| {
-            mgr.push_command(Command::Unmaximize { id: self.id });
+            mgr.push_command(Command::SetAlwaysOnBottom {
+                id: self.id,
+                always_on_bottom,
+            });
         });
         Ok(())
     }
 
-    /// Reload the current page in the webview.
+    /// Place the window directly above another window of the app, by ID.
     #[napi]
-    pub fn reload(&self) -> Result<()> {
+    pub fn move_above(&self, other_window_id: u32) -> Result<()> {
+        self.check_stacking_target(other_window_id)?;
         with_manager(|mgr| {
-            mgr.push_command(Command::Reload { id: self.id });
+            mgr.push_command(Command::MoveAbove {
+                id: self.id,
+                other_id: other_window_id,
+            });
         });
         Ok(())
     }
 
-    /// Set the window icon from a PNG or ICO file path.
-    /// On macOS this is silently ignored.
+    /// Place the window directly below another window of the app, by ID.
     #[napi]
-    pub fn set_icon(&self, path: String) -> Result<()> {
+    pub fn move_below(&self, other_window_id: u32) -> Result<()> {
+        self.check_stacking_target(other_window_id)?;
         with_manager(|mgr| {
-            mgr.push_command(Command::SetIcon { id: self.id, path });
+            mgr.push_command(Command::MoveBelow {
+                id: self.id,
+                other_id: other_window_id,
+            });
         });
         Ok(())
     }
 
-    // ---- Event handlers ----
-
-    /// Register a handler for IPC messages from the webview.
-    /// In the webview, call `window.ipc.postMessage(string)` to send messages.
-    /// The callback receives the message string and the source page URL.
-    #[napi(ts_args_type = "callback: (message: string, sourceUrl: string) => void")]
-    pub fn on_message(&self, callback: JsFunction) -> Result<()> {
-        let tsfn: ThreadsafeFunction<(String, String), ErrorStrategy::Fatal> = callback
-            .create_threadsafe_function(0, |ctx: ThreadSafeCallContext<(String, String)>| {
-                let message = ctx.env.create_string(&ctx.value.0)?;
-                let source_url = ctx.env.create_string(&ctx.value.1)?;
-                Ok(vec![message, source_url])
-            })?;
+    /// Move the window to a Windows virtual desktop, by the ID from
+    /// `getVirtualDesktops()`. Windows only.
+    #[napi]
+    pub fn move_to_virtual_desktop(&self, desktop_id: String) -> Result<()> {
+        #[cfg(target_os = "windows")]
+        {
+            if crate::platform::virtual_desktop::parse_id(&desktop_id).is_none() {
+                return Err(napi::Error::from_reason(format!(
+                    "Invalid virtual desktop ID '{}'.",
+                    desktop_id
+                )));
+            }
+            with_manager(|mgr| {
+                mgr.push_command(Command::MoveToVirtualDesktop {
+                    id: self.id,
+                    desktop_id,
+                });
+            });
+            Ok(())
+        }
+        #[cfg(not(target_os = "windows"))]
+        {
+            let _ = desktop_id;
+            Err(napi::Error::from_reason(
+                "Virtual desktops are only available on Windows.",
+            ))
+        }
+    }
 
+    /// Show the window on every macOS Space / Linux workspace / Windows
+    /// virtual desktop.
+    #[napi]
+    pub fn set_visible_on_all_workspaces(&self, visible: bool) -> Result<()> {
         with_manager(|mgr| {
-            if let Some(handlers) = mgr.event_handlers.get_mut(&self.id) {
-                handlers.on_message = Some(tsfn);
-            }
+            mgr.push_command(Command::SetVisibleOnAllWorkspaces {
+                id: self.id,
+                visible,
+            });
         });
         Ok(())
     }
 
-    /// Register a handler for the window close event.
-    #[napi(ts_args_type = "callback: () => void")]
-    pub fn on_close(&self, callback: JsFunction) -> Result<()> {
-        let tsfn: ThreadsafeFunction<(), ErrorStrategy::Fatal> = callback
-            .create_threadsafe_function(0, |ctx: ThreadSafeCallContext<()>| {
-                ctx.env.get_undefined().map(|v| vec![v])
-            })?;
-
+    /// Switch between light and dark native chrome, or follow the OS with
+    /// `"system"`. App-wide on macOS and Linux.
+    #[napi(ts_args_type = "theme: WindowTheme")]
+    pub fn set_theme(&self, theme: String) -> Result<()> {
+        validate_theme(&theme)?;
         with_manager(|mgr| {
-            if let Some(handlers) = mgr.event_handlers.get_mut(&self.id) {
-                handlers.on_close = Some(tsfn);
-            }
+            mgr.push_command(Command::SetTheme { id: self.id, theme });
         });
         Ok(())
     }
 
-    /// Register a handler for window resize events.
-    #[napi(ts_args_type = "callback: (width: number, height: number) => void")]
-    pub fn on_resize(&self, callback: JsFunction) -> Result<()> {
-        let tsfn: ThreadsafeFunction<(f64, f64), ErrorStrategy::Fatal> = callback
-            .create_threadsafe_function(0, |ctx: ThreadSafeCallContext<(f64, f64)>| {
-                let width = ctx.env.create_double(ctx.value.0)?;
-                let height = ctx.env.create_double(ctx.value.1)?;
-                Ok(vec![width, height])
-            })?;
-
+    /// Keep the window out of the taskbar (Windows/Linux) and out of
+    /// window cycling (macOS).
+    #[napi]
+    pub fn set_skip_taskbar(&self, skip: bool) -> Result<()> {
         with_manager(|mgr| {
-            if let Some(handlers) = mgr.event_handlers.get_mut(&self.id) {
-                handlers.on_resize = Some(tsfn);
-            }
+            mgr.push_command(Command::SetSkipTaskbar { id: self.id, skip });
         });
         Ok(())
     }
 
-    /// Register a handler for window move events.
-    #[napi(ts_args_type = "callback: (x: number, y: number) => void")]
-    pub fn on_move(&self, callback: JsFunction) -> Result<()> {
-        let tsfn: ThreadsafeFunction<(f64, f64), ErrorStrategy::Fatal> = callback
-            .create_threadsafe_function(0, |ctx: ThreadSafeCallContext<(f64, f64)>| {
-                let x = ctx.env.create_double(ctx.value.0)?;
-                let y = ctx.env.create_double(ctx.value.1)?;
-                Ok(vec![x, y])
-            })?;
-
+    /// Move the window with the mouse, as if its title bar were dragged.
+    /// Call while the primary button is held down.
+    #[napi]
+    pub fn start_dragging(&self) -> Result<()> {
         with_manager(|mgr| {
-            if let Some(handlers) = mgr.event_handlers.get_mut(&self.id) {
-                handlers.on_move = Some(tsfn);
-            }
+            mgr.push_command(Command::StartDragging { id: self.id });
         });
         Ok(())
     }
 
-    /// Register a handler for window focus events.
-    #[napi(ts_args_type = "callback: () => void")]
-    pub fn on_focus(&self, callback: JsFunction) -> Result<()> {
-        let tsfn: ThreadsafeFunction<(), ErrorStrategy::Fatal> = callback
-            .create_threadsafe_function(0, |ctx: ThreadSafeCallContext<()>| {
-                ctx.env.get_undefined().map(|v| vec![v])
-            })?;
-
+    /// Let mouse input pass through the window to whatever is underneath.
+    #[napi]
+    pub fn set_ignore_cursor_events(&self, ignore: bool) -> Result<()> {
         with_manager(|mgr| {
-            if let Some(handlers) = mgr.event_handlers.get_mut(&self.id) {
-                handlers.on_focus = Some(tsfn);
-            }
+            mgr.push_command(Command::SetIgnoreCursorEvents {
+                id: self.id,
+                ignore,
+            });
+        });
+        Ok(())
+    }
+
+    /// Enter or leave fullscreen. `mode` overrides the window's
+    /// `fullscreenMode` option and is remembered for later calls.
+    #[napi(ts_args_type = "fullscreen: boolean, mode?: 'native' | 'borderless'")]
+    pub fn set_fullscreen(&self, fullscreen: bool, mode: Option<String>) -> Result<()> {
+        if let Some(ref mode) = mode {
+            validate_fullscreen_mode(mode)?;
+        }
+        with_manager(|mgr| {
+            mgr.push_command(Command::SetFullscreen {
+                id: self.id,
+                fullscreen,
+                mode,
+            });
+        });
+        Ok(())
+    }
+
+    /// Show the window.
+    #[napi]
+    pub fn show(&self) -> Result<()> {
+        with_manager(|mgr| {
+            mgr.push_command(Command::Show { id: self.id });
+        });
+        Ok(())
+    }
+
+    /// Hide the window.
+    #[napi]
+    pub fn hide(&self) -> Result<()> {
+        with_manager(|mgr| {
+            mgr.push_command(Command::Hide { id: self.id });
+        });
+        Ok(())
+    }
+
+    /// Close and destroy the window.
+    #[napi]
+    pub fn close(&self) -> Result<()> {
+        with_manager(|mgr| {
+            mgr.push_command(Command::Close { id: self.id });
+        });
+        Ok(())
+    }
+
+    /// Keep the window open when this object is garbage collected. The
+    /// window then lives until it is closed by the user or the app exits.
+    #[napi]
+    pub fn detach(&mut self) -> Result<()> {
+        self.close_on_drop = false;
+        Ok(())
+    }
+
+    /// Close the window now and release this object's hold on it, so
+    /// garbage collection has nothing left to do.
+    #[napi]
+    pub fn dispose(&mut self) -> Result<()> {
+        self.close_on_drop = false;
+        with_manager(|mgr| {
+            mgr.push_command(Command::Close { id: self.id });
+        });
+        Ok(())
+    }
+
+    /// Focus the window.
+    #[napi]
+    pub fn focus(&self) -> Result<()> {
+        with_manager(|mgr| {
+            mgr.push_command(Command::Focus { id: self.id });
+        });
+        Ok(())
+    }
+
+    /// Flash the taskbar button (Windows), bounce the Dock icon (macOS) or
+    /// set the urgency hint (Linux) of a window in the background.
+    /// `critical` keeps it going until the window is focused.
+    #[napi]
+    pub fn request_attention(&self, critical: Option<bool>) -> Result<()> {
+        with_manager(|mgr| {
+            mgr.push_command(Command::RequestAttention {
+                id: self.id,
+                critical: critical.unwrap_or(false),
+            });
+        });
+        Ok(())
+    }
+
+    /// Move keyboard focus into the webview, so key input reaches the page.
+    /// Focusing the window alone does not guarantee this, notably right
+    /// after creation on Windows.
+    #[napi]
+    pub fn focus_webview(&self) -> Result<()> {
+        with_manager(|mgr| {
+            mgr.push_command(Command::FocusWebview { id: self.id });
+        });
+        Ok(())
+    }
+
+    /// Open the system emoji picker for the page's focused input: the
+    /// emoji panel (Win+.) on Windows, the character palette on macOS.
+    /// Focuses the window and webview first. Not supported on Linux.
+    #[napi]
+    pub fn show_emoji_picker(&self) -> Result<()> {
+        #[cfg(target_os = "linux")]
+        {
+            Err(napi::Error::from_reason(
+                "showEmojiPicker() is not supported on Linux.",
+            ))
+        }
+        #[cfg(not(target_os = "linux"))]
+        {
+            with_manager(|mgr| {
+                mgr.push_command(Command::ShowEmojiPicker { id: self.id });
+            });
+            Ok(())
+        }
+    }
+
+    /// Move keyboard focus from the webview back to the window.
+    #[napi]
+    pub fn blur_webview(&self) -> Result<()> {
+        with_manager(|mgr| {
+            mgr.push_command(Command::BlurWebview { id: self.id });
+        });
+        Ok(())
+    }
+
+    /// Move keyboard focus into the webview at the first or last element of
+    /// the page's tab order, as when tabbing in from adjacent native UI.
+    #[napi(ts_args_type = "edge: 'first' | 'last'")]
+    pub fn move_focus_to_webview(&self, edge: String) -> Result<()> {
+        let last = match edge.as_str() {
+            "first" => false,
+            "last" => true,
+            other => {
+                return Err(napi::Error::from_reason(format!(
+                    "Invalid focus edge '{}'. Expected 'first' or 'last'.",
+                    other
+                )))
+            }
+        };
+        with_manager(|mgr| {
+            mgr.push_command(Command::MoveFocusToWebview { id: self.id, last });
+        });
+        Ok(())
+    }
+
+    /// Bring back a window hidden by `minimizeToTray`: show, un-minimize
+    /// and focus it, then fire `onTrayRestore`.
+    #[napi]
+    pub fn restore_from_tray(&self) -> Result<()> {
+        with_manager(|mgr| {
+            mgr.push_command(Command::RestoreFromTray { id: self.id });
+        });
+        Ok(())
+    }
+
+    /// Maximize the window.
+    #[napi]
+    pub fn maximize(&self) -> Result<()> {
+        with_manager(|mgr| {
+            mgr.push_command(Command::Maximize { id: self.id });
+        });
+        Ok(())
+    }
+
+    /// Minimize the window.
+    #[napi]
+    pub fn minimize(&self) -> Result<()> {
+        with_manager(|mgr| {
+            mgr.push_command(Command::Minimize { id: self.id });
+        });
+        Ok(())
+    }
+
+    /// Restore the window from maximized state.
+    #[napi]
+    pub fn unmaximize(&self) -> Result<()> {
+        with_manager(|mgr| {
+            mgr.push_command(Command::Unmaximize { id: self.id });
+        });
+        Ok(())
+    }
+
+    /// Reload the current page in the webview.
+    #[napi]
+    pub fn reload(&self) -> Result<()> {
+        with_manager(|mgr| {
+            mgr.push_command(Command::Reload { id: self.id });
+        });
+        Ok(())
+    }
+
+    /// Set the window icon from a PNG or ICO file path.
+    /// On macOS this is silently ignored.
+    #[napi]
+    pub fn set_icon(&self, path: String) -> Result<()> {
+        with_manager(|mgr| {
+            mgr.push_command(Command::SetIcon { id: self.id, path });
+        });
+        Ok(())
+    }
+
+    /// Set the window icon from PNG or ICO bytes, or from raw RGBA pixels
+    /// when `width` and `height` are given. On macOS this is silently ignored.
+    #[napi]
+    pub fn set_icon_from_buffer(
+        &self,
+        data: Buffer,
+        width: Option<u32>,
+        height: Option<u32>,
+    ) -> Result<()> {
+        let (rgba, width, height) = match (width, height) {
+            (Some(width), Some(height)) => {
+                let expected = width as u64 * height as u64 * 4;
+                if width == 0 || height == 0 || data.len() as u64 != expected {
+                    return Err(napi::Error::from_reason(format!(
+                        "Invalid RGBA icon: expected {}x{}x4 = {} bytes, got {}",
+                        width,
+                        height,
+                        expected,
+                        data.len()
+                    )));
+                }
+                (data.to_vec(), width, height)
+            }
+            (None, None) => {
+                let image = image::load_from_memory(&data).map_err(|e| {
+                    napi::Error::from_reason(format!("Failed to decode icon: {}", e))
+                })?;
+                let rgba = image.into_rgba8();
+                let (width, height) = rgba.dimensions();
+                (rgba.into_raw(), width, height)
+            }
+            _ => {
+                return Err(napi::Error::from_reason(
+                    "setIconFromBuffer() needs both width and height for RGBA data, or neither for PNG/ICO",
+                ));
+            }
+        };
+        with_manager(|mgr| {
+            mgr.push_command(Command::SetIconRgba {
+                id: self.id,
+                rgba,
+                width,
+                height,
+            });
+        });
+        Ok(())
+    }
+
+    /// Show a count badge on the window's taskbar button, or clear it with
+    /// `null` or `0`. Windows draws an overlay icon on this window's button;
+    /// macOS sets the Dock badge; Linux uses the Unity launcher API.
+    #[napi(ts_args_type = "count: number | null")]
+    pub fn set_taskbar_badge(&self, count: Option<u32>) -> Result<()> {
+        with_manager(|mgr| {
+            mgr.push_command(Command::SetTaskbarBadge { id: self.id, count });
+        });
+        Ok(())
+    }
+
+    /// Set an overlay icon (PNG or ICO path) on the window's taskbar button,
+    /// or clear it with `null`. `description` is read by screen readers.
+    /// Windows only; ignored elsewhere.
+    #[napi(ts_args_type = "path: string | null, description?: string")]
+    pub fn set_overlay_icon(
+        &self,
+        path: Option<String>,
+        description: Option<String>,
+    ) -> Result<()> {
+        with_manager(|mgr| {
+            mgr.push_command(Command::SetOverlayIcon {
+                id: self.id,
+                path,
+                description: description.unwrap_or_default(),
+            });
+        });
+        Ok(())
+    }
+
+    /// Show `text` on the Dock icon badge, or clear it with `null` or `""`.
+    /// macOS only; ignored elsewhere.
+    #[napi(ts_args_type = "text: string | null")]
+    pub fn set_badge_label(&self, text: Option<String>) -> Result<()> {
+        with_manager(|mgr| {
+            mgr.push_command(Command::SetBadgeLabel {
+                id: self.id,
+                text: text.filter(|t| !t.is_empty()),
+            });
+        });
+        Ok(())
+    }
+
+    /// Show progress (0–1) on the window's taskbar button, or clear it with
+    /// `null`. `state` defaults to `"normal"`; `"indeterminate"`, `"paused"`
+    /// and `"error"` are drawn as normal outside Windows.
+    #[napi(
+        ts_args_type = "value: number | null, state?: 'normal' | 'indeterminate' | 'paused' | 'error'"
+    )]
+    pub fn set_progress_bar(&self, value: Option<f64>, state: Option<String>) -> Result<()> {
+        const STATES: &[&str] = &["normal", "indeterminate", "paused", "error"];
+        if let Some(value) = value {
+            if !value.is_finite() || !(0.0..=1.0).contains(&value) {
+                return Err(napi::Error::from_reason(
+                    "Progress value must be a number between 0 and 1.",
+                ));
+            }
+        }
+        let state = state.unwrap_or_else(|| "normal".to_string());
+        if !STATES.contains(&state.as_str()) {
+            return Err(napi::Error::from_reason(format!(
+                "Invalid progress state '{}'. Expected one of: {}.",
+                state,
+                STATES.join(", ")
+            )));
+        }
+        with_manager(|mgr| {
+            mgr.push_command(Command::SetProgressBar {
+                id: self.id,
+                progress: value.map(|v| (v * 100.0).round() as u64),
+                state,
+            });
+        });
+        Ok(())
+    }
+
+    /// Attach a classic menu bar to this window, or remove it with `null`.
+    /// Windows and Linux only; ignored on macOS, which has one app-wide menu bar.
+    #[napi(ts_args_type = "menu: WindowMenuItem[] | null")]
+    pub fn set_window_menu(&self, menu: Option<Vec<WindowMenuItem>>) -> Result<()> {
+        let items = menu.unwrap_or_default();
+        validate_menu_items(&items, 0)?;
+        with_manager(|mgr| {
+            mgr.push_command(Command::SetWindowMenu { id: self.id, items });
+        });
+        Ok(())
+    }
+
+    /// Hide the menu bar until Alt is pressed (Windows/Linux).
+    #[napi]
+    pub fn set_auto_hide_menu_bar(&self, hide: bool) -> Result<()> {
+        with_manager(|mgr| {
+            mgr.push_command(Command::SetAutoHideMenuBar {
+                id: self.id,
+                auto_hide: hide,
+            });
+        });
+        Ok(())
+    }
+
+    /// Lower (`"background"`) or restore (`"normal"`) the scheduling priority
+    /// of the webview's content process.
+    #[napi(ts_args_type = "priority: 'normal' | 'background'")]
+    pub fn set_priority(&self, priority: String) -> Result<()> {
+        let background = match priority.as_str() {
+            "normal" => false,
+            "background" => true,
+            other => {
+                return Err(napi::Error::from_reason(format!(
+                    "Invalid priority '{}'. Expected 'normal' or 'background'.",
+                    other
+                )))
+            }
+        };
+        with_manager(|mgr| {
+            mgr.push_command(Command::SetPriority {
+                id: self.id,
+                background,
+            });
+        });
+        Ok(())
+    }
+
+    // ---- Coordinates ----
+
+    /// Convert a point from screen coordinates (logical pixels, as used by
+    /// `setPosition`) to client coordinates relative to the webview's
+    /// top-left corner (CSS pixels at 100% zoom).
+    #[napi]
+    pub fn screen_to_client(&self, x: f64, y: f64) -> Result<Point> {
+        let origin = self.query(|platform| platform.client_origin(self.id))?;
+        Ok(Point {
+            x: x - origin.x,
+            y: y - origin.y,
+        })
+    }
+
+    /// Convert a point from client coordinates (CSS pixels relative to the
+    /// webview's top-left corner) to screen coordinates (logical pixels).
+    #[napi]
+    pub fn client_to_screen(&self, x: f64, y: f64) -> Result<Point> {
+        let origin = self.query(|platform| platform.client_origin(self.id))?;
+        Ok(Point {
+            x: x + origin.x,
+            y: y + origin.y,
+        })
+    }
+
+    /// The window's scale factor: physical pixels per logical pixel on the
+    /// display showing it.
+    #[napi]
+    pub fn get_scale_factor(&self) -> Result<f64> {
+        self.query(|platform| platform.scale_factor(self.id))
+    }
+
+    /// Register a handler for scale factor changes, e.g. when the window
+    /// moves to a display with a different DPI. Receives the new scale.
+    #[napi(ts_args_type = "callback: (scale: number) => void")]
+    pub fn on_scale_factor_changed(&self, callback: JsFunction) -> Result<()> {
+        let tsfn: ThreadsafeFunction<f64, ErrorStrategy::Fatal> = callback
+            .create_threadsafe_function(0, |ctx: ThreadSafeCallContext<f64>| {
+                ctx.env.create_double(ctx.value).map(|v| vec![v])
+            })?;
+
+        with_manager(|mgr| {
+            if let Some(handlers) = mgr.event_handlers.get_mut(&self.id) {
+                handlers.on_scale_factor_changed = Some(tsfn);
+            }
+        });
+        Ok(())
+    }
+
+    /// Convert device-independent (logical) pixels to physical pixels
+    /// using this window's current scale factor.
+    #[napi]
+    pub fn dip_to_physical(&self, x: f64, y: f64) -> Result<Point> {
+        let scale = self.query(|platform| platform.scale_factor(self.id))?;
+        Ok(Point {
+            x: x * scale,
+            y: y * scale,
+        })
+    }
+
+    /// Convert physical pixels to device-independent (logical) pixels
+    /// using this window's current scale factor.
+    #[napi]
+    pub fn physical_to_dip(&self, x: f64, y: f64) -> Result<Point> {
+        let scale = self.query(|platform| platform.scale_factor(self.id))?;
+        Ok(Point {
+            x: x / scale,
+            y: y / scale,
+        })
+    }
+
+    // ---- Geometry ----
+
+    /// Query the window's outer position and inner size in logical pixels.
+    /// Results are delivered asynchronously via the `onBounds` callback.
+    #[napi]
+    pub fn get_bounds(&self) -> Result<()> {
+        with_manager(|mgr| {
+            mgr.push_command(Command::GetBounds { id: self.id });
+        });
+        Ok(())
+    }
+
+    /// Register a handler for window geometry query results.
+    #[napi(ts_args_type = "callback: (bounds: Rect) => void")]
+    pub fn on_bounds(&self, callback: JsFunction) -> Result<()> {
+        let tsfn: ThreadsafeFunction<Rect, ErrorStrategy::Fatal> = callback
+            .create_threadsafe_function(0, |ctx: ThreadSafeCallContext<Rect>| {
+                Ok(vec![ctx.value])
+            })?;
+
+        with_manager(|mgr| {
+            if let Some(handlers) = mgr.event_handlers.get_mut(&self.id) {
+                handlers.on_bounds = Some(tsfn);
+            }
+        });
+        Ok(())
+    }
+
+    /// Query whether the window is maximized, minimized, visible and
+    /// focused. Results are delivered asynchronously via `onWindowState`.
+    #[napi]
+    pub fn get_window_state(&self) -> Result<()> {
+        with_manager(|mgr| {
+            mgr.push_command(Command::GetWindowState { id: self.id });
+        });
+        Ok(())
+    }
+
+    /// Register a handler for window state query results.
+    #[napi(ts_args_type = "callback: (state: WindowState) => void")]
+    pub fn on_window_state(&self, callback: JsFunction) -> Result<()> {
+        let tsfn: ThreadsafeFunction<WindowState, ErrorStrategy::Fatal> = callback
+            .create_threadsafe_function(0, |ctx: ThreadSafeCallContext<WindowState>| {
+                Ok(vec![ctx.value])
+            })?;
+
+        with_manager(|mgr| {
+            if let Some(handlers) = mgr.event_handlers.get_mut(&self.id) {
+                handlers.on_window_state = Some(tsfn);
+            }
+        });
+        Ok(())
+    }
+
+    // ---- Diagnostics ----
+
+    /// The last `limit` commands processed for this window (default: all
+    /// that are kept), oldest first, with timestamps and outcomes.
+    #[napi]
+    pub fn get_command_history(&self, limit: Option<u32>) -> Vec<CommandRecord> {
+        COMMAND_HISTORY.with(|h| {
+            let history = h.borrow();
+            let Some(entries) = history.get(&self.id) else {
+                return Vec::new();
+            };
+            let limit = limit.map_or(entries.len(), |l| l as usize);
+            entries
+                .iter()
+                .skip(entries.len().saturating_sub(limit))
+                .cloned()
+                .collect()
+        })
+    }
+
+    // ---- Event handlers ----
+
+    /// Deliver only the listed events for this window, or every event with
+    /// `null`. Filtered events are dropped in the event loop, before they
+    /// are buffered or cross into JS. Close and page-load events are always
+    /// delivered.
+    #[napi(
+        ts_args_type = "events: Array<'resize' | 'move' | 'focus' | 'blur' | 'message' | 'titleChanged'> | null"
+    )]
+    pub fn set_event_mask(&self, events: Option<Vec<String>>) -> Result<()> {
+        let Some(events) = events else {
+            EVENT_MASKS.with(|m| {
+                m.borrow_mut().remove(&self.id);
+            });
+            return Ok(());
+        };
+        let mut mask = 0;
+        for event in &events {
+            let Some(&(_, bit)) = MASKABLE_EVENTS.iter().find(|(name, _)| name == event) else {
+                let names: Vec<&str> = MASKABLE_EVENTS.iter().map(|(name, _)| *name).collect();
+                return Err(napi::Error::from_reason(format!(
+                    "Unknown event '{}'. Expected one of: {}.",
+                    event,
+                    names.join(", ")
+                )));
+            };
+            mask |= bit;
+        }
+        EVENT_MASKS.with(|m| {
+            m.borrow_mut().insert(self.id, mask);
+        });
+        Ok(())
+    }
+
+    /// Register a handler for IPC messages from the webview.
+    /// In the webview, call `window.ipc.postMessage(string)` to send messages.
+    /// The callback receives the message string and the source page URL.
+    #[napi(ts_args_type = "callback: (message: string, sourceUrl: string) => void")]
+    pub fn on_message(&self, callback: JsFunction) -> Result<()> {
+        let tsfn: ThreadsafeFunction<(String, String), ErrorStrategy::Fatal> = callback
+            .create_threadsafe_function(0, |ctx: ThreadSafeCallContext<(String, String)>| {
+                let message = ctx.env.create_string(&ctx.value.0)?;
+                let source_url = ctx.env.create_string(&ctx.value.1)?;
+                Ok(vec![message, source_url])
+            })?;
+
+        with_manager(|mgr| {
+            if let Some(handlers) = mgr.event_handlers.get_mut(&self.id) {
+                handlers.on_message = Some(tsfn);
+            }
+        });
+        Ok(())
+    }
+
+    /// Register a handler for the window close event.
+    /// Always fires after the native window and webview are destroyed,
+    /// whether closed by `close()`, the OS, or garbage collection.
+    #[napi(ts_args_type = "callback: () => void")]
+    pub fn on_close(&self, callback: JsFunction) -> Result<()> {
+        let tsfn: ThreadsafeFunction<(), ErrorStrategy::Fatal> = callback
+            .create_threadsafe_function(0, |ctx: ThreadSafeCallContext<()>| {
+                ctx.env.get_undefined().map(|v| vec![v])
+            })?;
+
+        with_manager(|mgr| {
+            if let Some(handlers) = mgr.event_handlers.get_mut(&self.id) {
+                handlers.on_close = Some(tsfn);
+            }
+        });
+        Ok(())
+    }
+
+    /// Register a handler for OS close requests (title bar close button,
+    /// Alt+F4, Cmd+W). Once registered, such requests no longer destroy the
+    /// window: the handler decides, and must call `close()` to proceed.
+    /// `close()` itself and garbage collection are not intercepted.
+    #[napi(ts_args_type = "callback: () => void")]
+    pub fn on_close_requested(&self, callback: JsFunction) -> Result<()> {
+        let tsfn: ThreadsafeFunction<(), ErrorStrategy::Fatal> = callback
+            .create_threadsafe_function(0, |ctx: ThreadSafeCallContext<()>| {
+                ctx.env.get_undefined().map(|v| vec![v])
+            })?;
+
+        with_manager(|mgr| {
+            if let Some(handlers) = mgr.event_handlers.get_mut(&self.id) {
+                handlers.on_close_requested = Some(tsfn);
+            }
+        });
+        CLOSE_INTERCEPTED.with(|c| {
+            c.borrow_mut().insert(self.id);
+        });
+        Ok(())
+    }
+
+    /// Register a handler for `restoreFromTray()` bringing back a window
+    /// hidden by `minimizeToTray`.
+    #[napi(ts_args_type = "callback: () => void")]
+    pub fn on_tray_restore(&self, callback: JsFunction) -> Result<()> {
+        let tsfn: ThreadsafeFunction<(), ErrorStrategy::Fatal> = callback
+            .create_threadsafe_function(0, |ctx: ThreadSafeCallContext<()>| {
+                ctx.env.get_undefined().map(|v| vec![v])
+            })?;
+
+        with_manager(|mgr| {
+            if let Some(handlers) = mgr.event_handlers.get_mut(&self.id) {
+                handlers.on_tray_restore = Some(tsfn);
+            }
+        });
+        Ok(())
+    }
+
+    /// Register a handler for window resize events.
+    #[napi(ts_args_type = "callback: (width: number, height: number) => void")]
+    pub fn on_resize(&self, callback: JsFunction) -> Result<()> {
+        let tsfn: ThreadsafeFunction<(f64, f64), ErrorStrategy::Fatal> = callback
+            .create_threadsafe_function(0, |ctx: ThreadSafeCallContext<(f64, f64)>| {
+                let width = ctx.env.create_double(ctx.value.0)?;
+                let height = ctx.env.create_double(ctx.value.1)?;
+                Ok(vec![width, height])
+            })?;
+
+        with_manager(|mgr| {
+            if let Some(handlers) = mgr.event_handlers.get_mut(&self.id) {
+                handlers.on_resize = Some(tsfn);
+            }
+        });
+        Ok(())
+    }
+
+    /// Register a handler for window move events.
+    #[napi(ts_args_type = "callback: (x: number, y: number) => void")]
+    pub fn on_move(&self, callback: JsFunction) -> Result<()> {
+        let tsfn: ThreadsafeFunction<(f64, f64), ErrorStrategy::Fatal> = callback
+            .create_threadsafe_function(0, |ctx: ThreadSafeCallContext<(f64, f64)>| {
+                let x = ctx.env.create_double(ctx.value.0)?;
+                let y = ctx.env.create_double(ctx.value.1)?;
+                Ok(vec![x, y])
+            })?;
+
+        with_manager(|mgr| {
+            if let Some(handlers) = mgr.event_handlers.get_mut(&self.id) {
+                handlers.on_move = Some(tsfn);
+            }
+        });
+        Ok(())
+    }
+
+    /// Register a handler for the end of a resize: fired once the user
+    /// lets go of a resize drag, or after a programmatic resize, with the
+    /// final size.
+    #[napi(ts_args_type = "callback: (width: number, height: number) => void")]
+    pub fn on_resize_end(&self, callback: JsFunction) -> Result<()> {
+        let tsfn: ThreadsafeFunction<(f64, f64), ErrorStrategy::Fatal> = callback
+            .create_threadsafe_function(0, |ctx: ThreadSafeCallContext<(f64, f64)>| {
+                let width = ctx.env.create_double(ctx.value.0)?;
+                let height = ctx.env.create_double(ctx.value.1)?;
+                Ok(vec![width, height])
+            })?;
+
+        with_manager(|mgr| {
+            if let Some(handlers) = mgr.event_handlers.get_mut(&self.id) {
+                handlers.on_resize_end = Some(tsfn);
+            }
+        });
+        Ok(())
+    }
+
+    /// Register a handler for the end of a move, with the final position.
+    #[napi(ts_args_type = "callback: (x: number, y: number) => void")]
+    pub fn on_move_end(&self, callback: JsFunction) -> Result<()> {
+        let tsfn: ThreadsafeFunction<(f64, f64), ErrorStrategy::Fatal> = callback
+            .create_threadsafe_function(0, |ctx: ThreadSafeCallContext<(f64, f64)>| {
+                let x = ctx.env.create_double(ctx.value.0)?;
+                let y = ctx.env.create_double(ctx.value.1)?;
+                Ok(vec![x, y])
+            })?;
+
+        with_manager(|mgr| {
+            if let Some(handlers) = mgr.event_handlers.get_mut(&self.id) {
+                handlers.on_move_end = Some(tsfn);
+            }
+        });
+        Ok(())
+    }
+
+    /// Register a handler for off-screen rescues: fired when the window
+    /// ended up entirely outside every display (e.g. after a monitor was
+    /// disconnected) and was moved back. Receives the new position.
+    #[napi(ts_args_type = "callback: (x: number, y: number) => void")]
+    pub fn on_window_rescued(&self, callback: JsFunction) -> Result<()> {
+        let tsfn: ThreadsafeFunction<(f64, f64), ErrorStrategy::Fatal> = callback
+            .create_threadsafe_function(0, |ctx: ThreadSafeCallContext<(f64, f64)>| {
+                let x = ctx.env.create_double(ctx.value.0)?;
+                let y = ctx.env.create_double(ctx.value.1)?;
+                Ok(vec![x, y])
+            })?;
+
+        with_manager(|mgr| {
+            if let Some(handlers) = mgr.event_handlers.get_mut(&self.id) {
+                handlers.on_window_rescued = Some(tsfn);
+            }
+        });
+        Ok(())
+    }
+
+    /// Register a handler for the cursor moving anywhere on screen, not just
+    /// over this window. Receives logical screen coordinates, at most once
+    /// every `throttleMs` and only when the position changed. The cursor is
+    /// polled from the event pump only once a handler is registered.
+    #[napi(ts_args_type = "throttleMs: number, callback: (x: number, y: number) => void")]
+    pub fn on_global_mouse_move(&self, throttle_ms: f64, callback: JsFunction) -> Result<()> {
+        if !throttle_ms.is_finite() || throttle_ms < 0.0 {
+            return Err(napi::Error::from_reason(
+                "throttleMs must be a non-negative number of milliseconds.",
+            ));
+        }
+        let tsfn: ThreadsafeFunction<(f64, f64), ErrorStrategy::Fatal> = callback
+            .create_threadsafe_function(0, |ctx: ThreadSafeCallContext<(f64, f64)>| {
+                let x = ctx.env.create_double(ctx.value.0)?;
+                let y = ctx.env.create_double(ctx.value.1)?;
+                Ok(vec![x, y])
+            })?;
+
+        with_manager(|mgr| {
+            if let Some(handlers) = mgr.event_handlers.get_mut(&self.id) {
+                handlers.on_global_mouse_move = Some(tsfn);
+            }
+            mgr.push_command(Command::TrackGlobalMouse {
+                id: self.id,
+                throttle_ms,
+            });
+        });
+        Ok(())
+    }
+
+    /// Register a handler for window focus events.
+    #[napi(ts_args_type = "callback: () => void")]
+    pub fn on_focus(&self, callback: JsFunction) -> Result<()> {
+        let tsfn: ThreadsafeFunction<(), ErrorStrategy::Fatal> = callback
+            .create_threadsafe_function(0, |ctx: ThreadSafeCallContext<()>| {
+                ctx.env.get_undefined().map(|v| vec![v])
+            })?;
+
+        with_manager(|mgr| {
+            if let Some(handlers) = mgr.event_handlers.get_mut(&self.id) {
+                handlers.on_focus = Some(tsfn);
+            }
         });
         Ok(())
     }
 
     /// Register a handler for window blur (lost focus) events.
     #[napi(ts_args_type = "callback: () => void")]
-    pub fn on_blur(&self, callback: JsFunction) -> Result<()> {
+    pub fn on_blur(&self, callback: JsFunction) -> Result<()> {
+        let tsfn: ThreadsafeFunction<(), ErrorStrategy::Fatal> = callback
+            .create_threadsafe_function(0, |ctx: ThreadSafeCallContext<()>| {
+                ctx.env.get_undefined().map(|v| vec![v])
+            })?;
+
+        with_manager(|mgr| {
+            if let Some(handlers) = mgr.event_handlers.get_mut(&self.id) {
+                handlers.on_blur = Some(tsfn);
+            }
+        });
+        Ok(())
+    }
+
+    /// Register a handler for the webview gaining (`true`) or losing
+    /// (`false`) keyboard focus, independently of window focus.
+    #[napi(ts_args_type = "callback: (focused: boolean) => void")]
+    pub fn on_webview_focus_changed(&self, callback: JsFunction) -> Result<()> {
+        let tsfn: ThreadsafeFunction<bool, ErrorStrategy::Fatal> = callback
+            .create_threadsafe_function(0, |ctx: ThreadSafeCallContext<bool>| {
+                ctx.env.get_boolean(ctx.value).map(|v| vec![v])
+            })?;
+
+        with_manager(|mgr| {
+            if let Some(handlers) = mgr.event_handlers.get_mut(&self.id) {
+                handlers.on_webview_focus_changed = Some(tsfn);
+            }
+        });
+        Ok(())
+    }
+
+    /// Register a handler for the window entering (`true`) or leaving
+    /// (`false`) the active macOS Space or Windows virtual desktop. Never
+    /// fires on Linux.
+    #[napi(ts_args_type = "callback: (onActiveWorkspace: boolean) => void")]
+    pub fn on_workspace_changed(&self, callback: JsFunction) -> Result<()> {
+        let tsfn: ThreadsafeFunction<bool, ErrorStrategy::Fatal> = callback
+            .create_threadsafe_function(0, |ctx: ThreadSafeCallContext<bool>| {
+                ctx.env.get_boolean(ctx.value).map(|v| vec![v])
+            })?;
+
+        with_manager(|mgr| {
+            if let Some(handlers) = mgr.event_handlers.get_mut(&self.id) {
+                handlers.on_workspace_changed = Some(tsfn);
+            }
+        });
+        Ok(())
+    }
+
+    /// Register a handler for the window's theme changing between light
+    /// and dark, e.g. when the OS appearance changes while `theme` is
+    /// `"system"`.
+    #[napi(ts_args_type = "callback: (theme: 'light' | 'dark') => void")]
+    pub fn on_theme_changed(&self, callback: JsFunction) -> Result<()> {
+        let tsfn: ThreadsafeFunction<String, ErrorStrategy::Fatal> = callback
+            .create_threadsafe_function(0, |ctx: ThreadSafeCallContext<String>| {
+                ctx.env.create_string(&ctx.value).map(|v| vec![v])
+            })?;
+
+        with_manager(|mgr| {
+            if let Some(handlers) = mgr.event_handlers.get_mut(&self.id) {
+                handlers.on_theme_changed = Some(tsfn);
+            }
+        });
+        Ok(())
+    }
+
+    /// Register a handler for Tab (`"next"`) or Shift+Tab (`"previous"`)
+    /// moving keyboard focus out of the page. WebView2 only.
+    #[napi(ts_args_type = "callback: (direction: 'next' | 'previous') => void")]
+    pub fn on_focus_exit_requested(&self, callback: JsFunction) -> Result<()> {
+        let tsfn: ThreadsafeFunction<String, ErrorStrategy::Fatal> = callback
+            .create_threadsafe_function(0, |ctx: ThreadSafeCallContext<String>| {
+                ctx.env.create_string(&ctx.value).map(|v| vec![v])
+            })?;
+
+        with_manager(|mgr| {
+            if let Some(handlers) = mgr.event_handlers.get_mut(&self.id) {
+                handlers.on_focus_exit_requested = Some(tsfn);
+            }
+        });
+        Ok(())
+    }
+
+    /// Register a handler for page load events.
+    #[napi(ts_args_type = "callback: (event: 'started' | 'finished', url: string) => void")]
+    pub fn on_page_load(&self, callback: JsFunction) -> Result<()> {
+        let tsfn: ThreadsafeFunction<(String, String), ErrorStrategy::Fatal> = callback
+            .create_threadsafe_function(0, |ctx: ThreadSafeCallContext<(String, String)>| {
+                let event_type = ctx.env.create_string(&ctx.value.0)?;
+                let url = ctx.env.create_string(&ctx.value.1)?;
+                Ok(vec![event_type, url])
+            })?;
+
+        with_manager(|mgr| {
+            if let Some(handlers) = mgr.event_handlers.get_mut(&self.id) {
+                handlers.on_page_load = Some(tsfn);
+            }
+        });
+        Ok(())
+    }
+
+    /// Register a handler fired once, when the first page load finishes.
+    /// Use it to show a window created with `visible: false` without a
+    /// blank flash.
+    #[napi(ts_args_type = "callback: () => void")]
+    pub fn on_ready_to_show(&self, callback: JsFunction) -> Result<()> {
+        let tsfn: ThreadsafeFunction<(), ErrorStrategy::Fatal> = callback
+            .create_threadsafe_function(0, |ctx: ThreadSafeCallContext<()>| {
+                ctx.env.get_undefined().map(|v| vec![v])
+            })?;
+
+        with_manager(|mgr| {
+            if let Some(handlers) = mgr.event_handlers.get_mut(&self.id) {
+                handlers.on_ready_to_show = Some(tsfn);
+            }
+        });
+        Ok(())
+    }
+
+    /// Register a handler for document title change events.
+    #[napi(ts_args_type = "callback: (title: string) => void")]
+    pub fn on_title_changed(&self, callback: JsFunction) -> Result<()> {
+        let tsfn: ThreadsafeFunction<String, ErrorStrategy::Fatal> = callback
+            .create_threadsafe_function(0, |ctx: ThreadSafeCallContext<String>| {
+                ctx.env.create_string(ctx.value.as_str()).map(|v| vec![v])
+            })?;
+
+        with_manager(|mgr| {
+            if let Some(handlers) = mgr.event_handlers.get_mut(&self.id) {
+                handlers.on_title_changed = Some(tsfn);
+            }
+        });
+        Ok(())
+    }
+
+    /// Register a handler for clicks on the window's menu bar items.
+    /// Receives the `id` of the chosen item.
+    #[napi(ts_args_type = "callback: (id: string) => void")]
+    pub fn on_menu_item_click(&self, callback: JsFunction) -> Result<()> {
+        let tsfn: ThreadsafeFunction<String, ErrorStrategy::Fatal> = callback
+            .create_threadsafe_function(0, |ctx: ThreadSafeCallContext<String>| {
+                ctx.env.create_string(ctx.value.as_str()).map(|v| vec![v])
+            })?;
+
+        with_manager(|mgr| {
+            if let Some(handlers) = mgr.event_handlers.get_mut(&self.id) {
+                handlers.on_menu_item_click = Some(tsfn);
+            }
+        });
+        Ok(())
+    }
+
+    /// Register a handler for the window reload event.
+    #[napi(ts_args_type = "callback: () => void")]
+    pub fn on_reload(&self, callback: JsFunction) -> Result<()> {
         let tsfn: ThreadsafeFunction<(), ErrorStrategy::Fatal> = callback
             .create_threadsafe_function(0, |ctx: ThreadSafeCallContext<()>| {
                 ctx.env.get_undefined().map(|v| vec![v])
@@ -422,120 +1663,1043 @@ impl NativeWindow {
 
         with_manager(|mgr| {
             if let Some(handlers) = mgr.event_handlers.get_mut(&self.id) {
-                handlers.on_blur = Some(tsfn);
+                handlers.on_reload = Some(tsfn);
+            }
+        });
+        Ok(())
+    }
+
+    /// Register a handler for blocked navigation events.
+    /// Fired when a navigation is blocked by the `allowedHosts` restriction.
+    #[napi(ts_args_type = "callback: (url: string) => void")]
+    pub fn on_navigation_blocked(&self, callback: JsFunction) -> Result<()> {
+        let tsfn: ThreadsafeFunction<String, ErrorStrategy::Fatal> = callback
+            .create_threadsafe_function(0, |ctx: ThreadSafeCallContext<String>| {
+                ctx.env.create_string(ctx.value.as_str()).map(|v| vec![v])
+            })?;
+
+        with_manager(|mgr| {
+            if let Some(handlers) = mgr.event_handlers.get_mut(&self.id) {
+                handlers.on_navigation_blocked = Some(tsfn);
+            }
+        });
+        Ok(())
+    }
+
+    /// Register a handler for iframes loading their first document.
+    #[napi(ts_args_type = "callback: (frameId: number, url: string) => void")]
+    pub fn on_frame_created(&self, callback: JsFunction) -> Result<()> {
+        let tsfn: ThreadsafeFunction<(u32, String), ErrorStrategy::Fatal> = callback
+            .create_threadsafe_function(0, |ctx: ThreadSafeCallContext<(u32, String)>| {
+                let frame_id = ctx.env.create_uint32(ctx.value.0)?.into_unknown();
+                let url = ctx.env.create_string(&ctx.value.1)?.into_unknown();
+                Ok(vec![frame_id, url])
+            })?;
+
+        with_manager(|mgr| {
+            if let Some(handlers) = mgr.event_handlers.get_mut(&self.id) {
+                handlers.on_frame_created = Some(tsfn);
+            }
+        });
+        Ok(())
+    }
+
+    /// Register a handler for destroyed iframes.
+    #[napi(ts_args_type = "callback: (frameId: number) => void")]
+    pub fn on_frame_destroyed(&self, callback: JsFunction) -> Result<()> {
+        let tsfn: ThreadsafeFunction<u32, ErrorStrategy::Fatal> = callback
+            .create_threadsafe_function(0, |ctx: ThreadSafeCallContext<u32>| {
+                ctx.env.create_uint32(ctx.value).map(|v| vec![v])
+            })?;
+
+        with_manager(|mgr| {
+            if let Some(handlers) = mgr.event_handlers.get_mut(&self.id) {
+                handlers.on_frame_destroyed = Some(tsfn);
+            }
+        });
+        Ok(())
+    }
+
+    /// Register a handler for throttled navigation events.
+    /// Fired when a navigation is blocked by `maxNavigationsPerMinute`.
+    #[napi(ts_args_type = "callback: (url: string) => void")]
+    pub fn on_navigation_throttled(&self, callback: JsFunction) -> Result<()> {
+        let tsfn: ThreadsafeFunction<String, ErrorStrategy::Fatal> = callback
+            .create_threadsafe_function(0, |ctx: ThreadSafeCallContext<String>| {
+                ctx.env.create_string(ctx.value.as_str()).map(|v| vec![v])
+            })?;
+
+        with_manager(|mgr| {
+            if let Some(handlers) = mgr.event_handlers.get_mut(&self.id) {
+                handlers.on_navigation_throttled = Some(tsfn);
+            }
+        });
+        Ok(())
+    }
+
+    /// Register a handler fired when the `setFallbackHtml()` document
+    /// replaced a page that failed to load.
+    #[napi(ts_args_type = "callback: (originalUrl: string, errorCode: string) => void")]
+    pub fn on_fallback_shown(&self, callback: JsFunction) -> Result<()> {
+        let tsfn: ThreadsafeFunction<(String, String), ErrorStrategy::Fatal> = callback
+            .create_threadsafe_function(0, |ctx: ThreadSafeCallContext<(String, String)>| {
+                let url = ctx.env.create_string(&ctx.value.0)?;
+                let code = ctx.env.create_string(&ctx.value.1)?;
+                Ok(vec![url, code])
+            })?;
+
+        with_manager(|mgr| {
+            if let Some(handlers) = mgr.event_handlers.get_mut(&self.id) {
+                handlers.on_fallback_shown = Some(tsfn);
+            }
+        });
+        Ok(())
+    }
+
+    // ---- Cookie access ----
+
+    /// Query cookies from the native cookie store.
+    /// Results are delivered asynchronously via the `onCookies` callback.
+    /// If `url` is provided, only cookies matching that URL are returned.
+    /// If omitted, all cookies are returned.
+    #[napi]
+    pub fn get_cookies(&self, url: Option<String>) -> Result<()> {
+        with_manager(|mgr| {
+            mgr.push_command(Command::GetCookies { id: self.id, url });
+        });
+        Ok(())
+    }
+
+    /// Register a handler for cookie query results.
+    /// The callback receives a JSON string containing an array of cookie objects.
+    #[napi(ts_args_type = "callback: (cookies: string) => void")]
+    pub fn on_cookies(&self, callback: JsFunction) -> Result<()> {
+        let tsfn: ThreadsafeFunction<String, ErrorStrategy::Fatal> = callback
+            .create_threadsafe_function(0, |ctx: ThreadSafeCallContext<String>| {
+                ctx.env.create_string(ctx.value.as_str()).map(|v| vec![v])
+            })?;
+
+        with_manager(|mgr| {
+            if let Some(handlers) = mgr.event_handlers.get_mut(&self.id) {
+                handlers.on_cookies = Some(tsfn);
+            }
+        });
+        Ok(())
+    }
+
+    // ---- Text search ----
+
+    /// Count case-insensitive occurrences of `text` in the rendered page
+    /// without moving the viewport or changing the selection.
+    /// Results are delivered asynchronously via the `onMatchCount` callback.
+    #[napi]
+    pub fn count_matches(&self, text: String) -> Result<()> {
+        with_manager(|mgr| {
+            mgr.push_command(Command::CountMatches { id: self.id, text });
+        });
+        Ok(())
+    }
+
+    /// Register a handler for text match count results. Receives `null`
+    /// when the page could not be searched.
+    #[napi(ts_args_type = "callback: (count: number | null) => void")]
+    pub fn on_match_count(&self, callback: JsFunction) -> Result<()> {
+        let tsfn: ThreadsafeFunction<Option<f64>, ErrorStrategy::Fatal> = callback
+            .create_threadsafe_function(0, |ctx: ThreadSafeCallContext<Option<f64>>| {
+                let value = match ctx.value {
+                    Some(count) => ctx.env.create_double(count)?.into_unknown(),
+                    None => ctx.env.get_null()?.into_unknown(),
+                };
+                Ok(vec![value])
+            })?;
+
+        with_manager(|mgr| {
+            if let Some(handlers) = mgr.event_handlers.get_mut(&self.id) {
+                handlers.on_match_count = Some(tsfn);
+            }
+        });
+        Ok(())
+    }
+
+    // ---- Accessibility ----
+
+    /// Force the renderer's accessibility tree on or off.
+    /// On Windows this enables the DevTools `Accessibility` domain. On
+    /// macOS/Linux this is a no-op: WebKit builds the tree on demand
+    /// whenever assistive technology queries it.
+    #[napi]
+    pub fn set_accessibility_enabled(&self, enabled: bool) -> Result<()> {
+        with_manager(|mgr| {
+            mgr.push_command(Command::SetAccessibilityEnabled {
+                id: self.id,
+                enabled,
+            });
+        });
+        Ok(())
+    }
+
+    /// Capture a snapshot of the page's accessibility tree.
+    /// Results are delivered asynchronously via the `onAccessibilitySnapshot` callback.
+    #[napi]
+    pub fn get_accessibility_snapshot(&self) -> Result<()> {
+        with_manager(|mgr| {
+            mgr.push_command(Command::GetAccessibilitySnapshot { id: self.id });
+        });
+        Ok(())
+    }
+
+    /// Register a handler for accessibility snapshot results.
+    /// The callback receives a JSON string `{ nodes: [...] }`, or `"null"` on failure.
+    #[napi(ts_args_type = "callback: (snapshot: string) => void")]
+    pub fn on_accessibility_snapshot(&self, callback: JsFunction) -> Result<()> {
+        let tsfn: ThreadsafeFunction<String, ErrorStrategy::Fatal> = callback
+            .create_threadsafe_function(0, |ctx: ThreadSafeCallContext<String>| {
+                ctx.env.create_string(ctx.value.as_str()).map(|v| vec![v])
+            })?;
+
+        with_manager(|mgr| {
+            if let Some(handlers) = mgr.event_handlers.get_mut(&self.id) {
+                handlers.on_accessibility_snapshot = Some(tsfn);
+            }
+        });
+        Ok(())
+    }
+
+    // ---- Performance ----
+
+    /// Read load timings and engine counters for the current page.
+    /// Results are delivered asynchronously via the `onPerformanceMetrics` callback.
+    #[napi]
+    pub fn get_page_performance_metrics(&self) -> Result<()> {
+        with_manager(|mgr| {
+            mgr.push_command(Command::GetPagePerformanceMetrics { id: self.id });
+        });
+        Ok(())
+    }
+
+    /// Register a handler for page performance metrics.
+    /// The callback receives a JSON string `{ timing, metrics }`, or `"null"` on failure.
+    #[napi(ts_args_type = "callback: (json: string) => void")]
+    pub fn on_performance_metrics(&self, callback: JsFunction) -> Result<()> {
+        let tsfn: ThreadsafeFunction<String, ErrorStrategy::Fatal> = callback
+            .create_threadsafe_function(0, |ctx: ThreadSafeCallContext<String>| {
+                ctx.env.create_string(ctx.value.as_str()).map(|v| vec![v])
+            })?;
+
+        with_manager(|mgr| {
+            if let Some(handlers) = mgr.event_handlers.get_mut(&self.id) {
+                handlers.on_performance_metrics = Some(tsfn);
+            }
+        });
+        Ok(())
+    }
+
+    // ---- Reader mode ----
+
+    /// Extract the main article of the page in an isolated script world.
+    /// Results are delivered asynchronously via the `onReadableContent` callback.
+    #[napi]
+    pub fn extract_readable_content(&self) -> Result<()> {
+        with_manager(|mgr| {
+            mgr.push_command(Command::ExtractReadableContent { id: self.id });
+        });
+        Ok(())
+    }
+
+    /// Register a handler for reader-mode extraction results.
+    /// The callback receives a JSON string `{ title, html, text }`, or `"null"` on failure.
+    #[napi(ts_args_type = "callback: (json: string) => void")]
+    pub fn on_readable_content(&self, callback: JsFunction) -> Result<()> {
+        let tsfn: ThreadsafeFunction<String, ErrorStrategy::Fatal> = callback
+            .create_threadsafe_function(0, |ctx: ThreadSafeCallContext<String>| {
+                ctx.env.create_string(ctx.value.as_str()).map(|v| vec![v])
+            })?;
+
+        with_manager(|mgr| {
+            if let Some(handlers) = mgr.event_handlers.get_mut(&self.id) {
+                handlers.on_readable_content = Some(tsfn);
+            }
+        });
+        Ok(())
+    }
+
+    // ---- Emulation ----
+
+    /// Render the page as if the host were in time zone `timezone` (an IANA
+    /// ID such as `"America/New_York"`), or restore the host zone with `null`.
+    #[napi(ts_args_type = "timezone: string | null")]
+    pub fn set_emulated_timezone(&self, timezone: Option<String>) -> Result<()> {
+        if let Some(ref tz) = timezone {
+            validate_emulation_value("time zone", tz, |c| {
+                c.is_ascii_alphanumeric() || "/_+-".contains(c)
+            })?;
+        }
+        with_manager(|mgr| {
+            mgr.push_command(Command::SetEmulatedTimezone {
+                id: self.id,
+                timezone,
+            });
+        });
+        Ok(())
+    }
+
+    /// Render the page with locale `locale` (a BCP 47 tag such as
+    /// `"de-DE"`), or restore the host locale with `null`.
+    #[napi(ts_args_type = "locale: string | null")]
+    pub fn set_emulated_locale(&self, locale: Option<String>) -> Result<()> {
+        if let Some(ref locale) = locale {
+            validate_emulation_value("locale", locale, |c| c.is_ascii_alphanumeric() || c == '-')?;
+        }
+        with_manager(|mgr| {
+            mgr.push_command(Command::SetEmulatedLocale {
+                id: self.id,
+                locale,
+            });
+        });
+        Ok(())
+    }
+
+    /// Set the font used when page CSS names none and the smallest font size
+    /// text is drawn at, or restore the engine defaults with `null`.
+    #[napi(ts_args_type = "overrides: FontOverrides | null")]
+    pub fn set_font_overrides(&self, overrides: Option<FontOverrides>) -> Result<()> {
+        let overrides = overrides.unwrap_or_default();
+        if let Some(ref family) = overrides.standard_family {
+            if family.trim().is_empty() {
+                return Err(napi::Error::from_reason(
+                    "standardFamily must be a non-empty font family name.",
+                ));
+            }
+        }
+        if overrides.minimum_font_size.is_some_and(|size| size > 72) {
+            return Err(napi::Error::from_reason(
+                "minimumFontSize must be at most 72.",
+            ));
+        }
+        with_manager(|mgr| {
+            mgr.push_command(Command::SetFontOverrides {
+                id: self.id,
+                standard_family: overrides.standard_family,
+                minimum_font_size: overrides.minimum_font_size,
+            });
+        });
+        Ok(())
+    }
+
+    /// Scale the page's text to `percent` of its size (100 restores it).
+    /// Linux zooms text only; Windows and macOS zoom the whole page.
+    #[napi]
+    pub fn set_text_zoom(&self, percent: f64) -> Result<()> {
+        if !percent.is_finite() || !(25.0..=500.0).contains(&percent) {
+            return Err(napi::Error::from_reason(
+                "Text zoom must be between 25 and 500 percent.",
+            ));
+        }
+        with_manager(|mgr| {
+            mgr.push_command(Command::SetTextZoom {
+                id: self.id,
+                percent,
+            });
+        });
+        Ok(())
+    }
+
+    /// Throttle or cut off the webview's network, or restore it with
+    /// `null`. Full emulation on Windows; best-effort elsewhere.
+    #[napi(ts_args_type = "conditions: NetworkConditions | null")]
+    pub fn set_network_conditions(&self, conditions: Option<NetworkConditions>) -> Result<()> {
+        if let Some(ref c) = conditions {
+            if !c.latency_ms.map_or(true, |v| v.is_finite() && v >= 0.0) {
+                return Err(napi::Error::from_reason(
+                    "latencyMs must be a non-negative number.",
+                ));
+            }
+            for throughput in [c.download_throughput, c.upload_throughput]
+                .into_iter()
+                .flatten()
+            {
+                if !(throughput.is_finite() && throughput > 0.0) {
+                    return Err(napi::Error::from_reason(
+                        "Throughput must be a positive number of bytes per second.",
+                    ));
+                }
+            }
+        }
+        with_manager(|mgr| {
+            mgr.push_command(Command::SetNetworkConditions {
+                id: self.id,
+                conditions,
+            });
+        });
+        Ok(())
+    }
+
+    // ---- Network capture ----
+
+    /// Start recording the webview's network activity. Stop with
+    /// `stopNetworkCapture()`; the recording is delivered via the
+    /// `onNetworkCapture` callback.
+    #[napi]
+    pub fn start_network_capture(&self) -> Result<()> {
+        with_manager(|mgr| {
+            mgr.push_command(Command::StartNetworkCapture { id: self.id });
+        });
+        Ok(())
+    }
+
+    /// Stop the running network capture and emit its recording.
+    #[napi]
+    pub fn stop_network_capture(&self) -> Result<()> {
+        with_manager(|mgr| {
+            mgr.push_command(Command::StopNetworkCapture { id: self.id });
+        });
+        Ok(())
+    }
+
+    /// Register a handler for network capture results. Receives `null`
+    /// when no capture was running.
+    #[napi(ts_args_type = "callback: (json: string | null) => void")]
+    pub fn on_network_capture(&self, callback: JsFunction) -> Result<()> {
+        let tsfn: ThreadsafeFunction<Option<String>, ErrorStrategy::Fatal> = callback
+            .create_threadsafe_function(0, |ctx: ThreadSafeCallContext<Option<String>>| {
+                let value = match ctx.value {
+                    Some(json) => ctx.env.create_string(&json)?.into_unknown(),
+                    None => ctx.env.get_null()?.into_unknown(),
+                };
+                Ok(vec![value])
+            })?;
+
+        with_manager(|mgr| {
+            if let Some(handlers) = mgr.event_handlers.get_mut(&self.id) {
+                handlers.on_network_capture = Some(tsfn);
             }
         });
         Ok(())
     }
 
-    /// Register a handler for page load events.
-    #[napi(ts_args_type = "callback: (event: 'started' | 'finished', url: string) => void")]
-    pub fn on_page_load(&self, callback: JsFunction) -> Result<()> {
-        let tsfn: ThreadsafeFunction<(String, String), ErrorStrategy::Fatal> = callback
-            .create_threadsafe_function(0, |ctx: ThreadSafeCallContext<(String, String)>| {
-                let event_type = ctx.env.create_string(&ctx.value.0)?;
-                let url = ctx.env.create_string(&ctx.value.1)?;
-                Ok(vec![event_type, url])
+    // ---- Tracing ----
+
+    /// Start recording a Chrome trace of the webview. Empty `categories`
+    /// uses the engine's defaults. Windows (WebView2) only.
+    #[napi]
+    pub fn start_tracing(&self, categories: Vec<String>) -> Result<()> {
+        if categories.iter().any(|c| c.trim().is_empty()) {
+            return Err(napi::Error::from_reason(
+                "Trace categories must be non-empty strings.",
+            ));
+        }
+        #[cfg(target_os = "windows")]
+        {
+            with_manager(|mgr| {
+                mgr.push_command(Command::StartTracing {
+                    id: self.id,
+                    categories,
+                });
+            });
+            Ok(())
+        }
+        #[cfg(not(target_os = "windows"))]
+        {
+            Err(napi::Error::from_reason(
+                "Tracing requires the DevTools protocol and is only available on Windows (WebView2).",
+            ))
+        }
+    }
+
+    /// Stop the running trace and emit it via the `onTrace` callback.
+    #[napi]
+    pub fn stop_tracing(&self) -> Result<()> {
+        with_manager(|mgr| {
+            mgr.push_command(Command::StopTracing { id: self.id });
+        });
+        Ok(())
+    }
+
+    /// Register a handler for completed traces. Receives a JSON array of
+    /// trace event batches, or `null` when no trace was running.
+    #[napi(ts_args_type = "callback: (json: string | null) => void")]
+    pub fn on_trace(&self, callback: JsFunction) -> Result<()> {
+        let tsfn: ThreadsafeFunction<Option<String>, ErrorStrategy::Fatal> = callback
+            .create_threadsafe_function(0, |ctx: ThreadSafeCallContext<Option<String>>| {
+                let value = match ctx.value {
+                    Some(json) => ctx.env.create_string(&json)?.into_unknown(),
+                    None => ctx.env.get_null()?.into_unknown(),
+                };
+                Ok(vec![value])
             })?;
 
         with_manager(|mgr| {
             if let Some(handlers) = mgr.event_handlers.get_mut(&self.id) {
-                handlers.on_page_load = Some(tsfn);
+                handlers.on_trace = Some(tsfn);
             }
         });
         Ok(())
     }
 
-    /// Register a handler for document title change events.
-    #[napi(ts_args_type = "callback: (title: string) => void")]
-    pub fn on_title_changed(&self, callback: JsFunction) -> Result<()> {
+    // ---- Heap snapshots ----
+
+    /// Capture a V8 heap snapshot of the page. Results are delivered
+    /// asynchronously via the `onHeapSnapshot` callback. Windows (WebView2) only.
+    #[napi]
+    pub fn take_heap_snapshot(&self) -> Result<()> {
+        #[cfg(target_os = "windows")]
+        {
+            with_manager(|mgr| {
+                mgr.push_command(Command::TakeHeapSnapshot { id: self.id });
+            });
+            Ok(())
+        }
+        #[cfg(not(target_os = "windows"))]
+        {
+            Err(napi::Error::from_reason(
+                "Heap snapshots require the DevTools protocol and are only available on Windows (WebView2).",
+            ))
+        }
+    }
+
+    /// Register a handler for heap snapshots. Receives a JSON array of
+    /// snapshot chunks, or `null` on failure.
+    #[napi(ts_args_type = "callback: (json: string | null) => void")]
+    pub fn on_heap_snapshot(&self, callback: JsFunction) -> Result<()> {
+        let tsfn: ThreadsafeFunction<Option<String>, ErrorStrategy::Fatal> = callback
+            .create_threadsafe_function(0, |ctx: ThreadSafeCallContext<Option<String>>| {
+                let value = match ctx.value {
+                    Some(json) => ctx.env.create_string(&json)?.into_unknown(),
+                    None => ctx.env.get_null()?.into_unknown(),
+                };
+                Ok(vec![value])
+            })?;
+
+        with_manager(|mgr| {
+            if let Some(handlers) = mgr.event_handlers.get_mut(&self.id) {
+                handlers.on_heap_snapshot = Some(tsfn);
+            }
+        });
+        Ok(())
+    }
+
+    // ---- Frame capture ----
+
+    /// Start streaming composited frames of the webview, at most `fps` per
+    /// second, via the `onFrame` callback until `stopFrameCapture()`.
+    /// `format` is `"jpeg"` (default) or `"png"`. Windows (WebView2) only.
+    #[napi(ts_args_type = "fps: number, format?: 'jpeg' | 'png'")]
+    pub fn start_frame_capture(&self, fps: f64, format: Option<String>) -> Result<()> {
+        if !fps.is_finite() || fps <= 0.0 {
+            return Err(napi::Error::from_reason("fps must be a positive number."));
+        }
+        let format = format.unwrap_or_else(|| "jpeg".to_string());
+        if format != "jpeg" && format != "png" {
+            return Err(napi::Error::from_reason(format!(
+                "Invalid frame format \"{}\". Expected \"jpeg\" or \"png\".",
+                format
+            )));
+        }
+        #[cfg(target_os = "windows")]
+        {
+            with_manager(|mgr| {
+                mgr.push_command(Command::StartFrameCapture {
+                    id: self.id,
+                    fps,
+                    format,
+                });
+            });
+            Ok(())
+        }
+        #[cfg(not(target_os = "windows"))]
+        {
+            Err(napi::Error::from_reason(
+                "Frame capture requires the DevTools protocol and is only available on Windows (WebView2).",
+            ))
+        }
+    }
+
+    /// Stop the running frame capture.
+    #[napi]
+    pub fn stop_frame_capture(&self) -> Result<()> {
+        with_manager(|mgr| {
+            mgr.push_command(Command::StopFrameCapture { id: self.id });
+        });
+        Ok(())
+    }
+
+    /// Register a handler for captured frames. Receives the JSON
+    /// `Page.screencastFrame` params (base64 `data` and `metadata`).
+    #[napi(ts_args_type = "callback: (json: string) => void")]
+    pub fn on_frame(&self, callback: JsFunction) -> Result<()> {
         let tsfn: ThreadsafeFunction<String, ErrorStrategy::Fatal> = callback
             .create_threadsafe_function(0, |ctx: ThreadSafeCallContext<String>| {
-                ctx.env.create_string(ctx.value.as_str()).map(|v| vec![v])
+                ctx.env.create_string(&ctx.value).map(|v| vec![v])
             })?;
 
         with_manager(|mgr| {
             if let Some(handlers) = mgr.event_handlers.get_mut(&self.id) {
-                handlers.on_title_changed = Some(tsfn);
+                handlers.on_frame = Some(tsfn);
             }
         });
         Ok(())
     }
 
-    /// Register a handler for the window reload event.
-    #[napi(ts_args_type = "callback: () => void")]
-    pub fn on_reload(&self, callback: JsFunction) -> Result<()> {
-        let tsfn: ThreadsafeFunction<(), ErrorStrategy::Fatal> = callback
-            .create_threadsafe_function(0, |ctx: ThreadSafeCallContext<()>| {
-                ctx.env.get_undefined().map(|v| vec![v])
+    // ---- Screenshots ----
+
+    /// Capture the whole window, including the native title bar and
+    /// borders, as a PNG. Results are delivered asynchronously via the
+    /// `onWindowCapture` callback.
+    #[napi]
+    pub fn capture_window(&self) -> Result<()> {
+        with_manager(|mgr| {
+            mgr.push_command(Command::CaptureWindow { id: self.id });
+        });
+        Ok(())
+    }
+
+    /// Snapshot the visible area of the page, without the native frame, as
+    /// a PNG. Results are delivered asynchronously via the `onPageCapture`
+    /// callback.
+    #[napi]
+    pub fn capture_page(&self) -> Result<()> {
+        with_manager(|mgr| {
+            mgr.push_command(Command::CapturePage { id: self.id });
+        });
+        Ok(())
+    }
+
+    /// Start capturing the audio played by the webview. Audio is delivered
+    /// via the `onAudioCapture` callback until `stopAudioCapture()`.
+    #[napi]
+    pub fn start_audio_capture(&self) -> Result<()> {
+        with_manager(|mgr| {
+            mgr.push_command(Command::StartAudioCapture { id: self.id });
+        });
+        Ok(())
+    }
+
+    /// Stop the running audio capture.
+    #[napi]
+    pub fn stop_audio_capture(&self) -> Result<()> {
+        with_manager(|mgr| {
+            mgr.push_command(Command::StopAudioCapture { id: self.id });
+        });
+        Ok(())
+    }
+
+    /// Register a handler for captured audio: 16-bit stereo PCM at 48 kHz.
+    /// Receives `pcm === null` once the capture ends, with `error` set if
+    /// it failed.
+    #[napi(ts_args_type = "callback: (pcm: Buffer | null, error: string | null) => void")]
+    pub fn on_audio_capture(&self, callback: JsFunction) -> Result<()> {
+        let tsfn: ThreadsafeFunction<(Option<Vec<u8>>, Option<String>), ErrorStrategy::Fatal> =
+            callback.create_threadsafe_function(
+                0,
+                |ctx: ThreadSafeCallContext<(Option<Vec<u8>>, Option<String>)>| {
+                    let (pcm, error) = ctx.value;
+                    let pcm = match pcm {
+                        Some(pcm) => ctx
+                            .env
+                            .create_buffer_with_data(pcm)?
+                            .into_raw()
+                            .into_unknown(),
+                        None => ctx.env.get_null()?.into_unknown(),
+                    };
+                    let error = match error {
+                        Some(error) => ctx.env.create_string(&error)?.into_unknown(),
+                        None => ctx.env.get_null()?.into_unknown(),
+                    };
+                    Ok(vec![pcm, error])
+                },
+            )?;
+
+        with_manager(|mgr| {
+            if let Some(handlers) = mgr.event_handlers.get_mut(&self.id) {
+                handlers.on_audio_capture = Some(tsfn);
+            }
+        });
+        Ok(())
+    }
+
+    /// Capture the window like `captureWindow()`, scaled down to fit
+    /// `maxWidth` x `maxHeight` physical pixels. Results are delivered
+    /// asynchronously via the `onThumbnail` callback.
+    #[napi]
+    pub fn get_thumbnail(&self, max_width: u32, max_height: u32) -> Result<()> {
+        if max_width == 0 || max_height == 0 {
+            return Err(napi::Error::from_reason(
+                "Thumbnail size must be at least 1x1.",
+            ));
+        }
+        with_manager(|mgr| {
+            mgr.push_command(Command::GetThumbnail {
+                id: self.id,
+                max_width,
+                max_height,
+            });
+        });
+        Ok(())
+    }
+
+    /// Let the user pick a color anywhere on screen with the system
+    /// eyedropper (macOS) or a magnifier overlay (Windows). Results are
+    /// delivered asynchronously via the `onColorPicked` callback. Not
+    /// supported on Linux.
+    #[napi]
+    pub fn pick_color_from_screen(&self) -> Result<()> {
+        if !crate::capabilities::capabilities().supports_color_picker {
+            return Err(napi::Error::from_reason(
+                "pickColorFromScreen() is not supported on this platform.",
+            ));
+        }
+        with_manager(|mgr| {
+            mgr.push_command(Command::PickColor { id: self.id });
+        });
+        Ok(())
+    }
+
+    /// Open the native share sheet anchored to this window:
+    /// `NSSharingServicePicker` on macOS, the Share UI on Windows.
+    /// Not supported on Linux.
+    #[napi]
+    pub fn share(&self, options: ShareOptions) -> Result<()> {
+        if !crate::capabilities::capabilities().supports_share {
+            return Err(napi::Error::from_reason(
+                "share() is not supported on this platform.",
+            ));
+        }
+        if options.text.is_none()
+            && options.url.is_none()
+            && options.files.iter().flatten().next().is_none()
+        {
+            return Err(napi::Error::from_reason(
+                "share() requires at least one of text, url or files.",
+            ));
+        }
+        if let Some(path) = options
+            .files
+            .iter()
+            .flatten()
+            .find(|p| !std::path::Path::new(p).is_absolute())
+        {
+            return Err(napi::Error::from_reason(format!(
+                "share() file paths must be absolute: {}",
+                path
+            )));
+        }
+        with_manager(|mgr| {
+            mgr.push_command(Command::Share {
+                id: self.id,
+                options,
+            });
+        });
+        Ok(())
+    }
+
+    /// Register a handler for `pickColorFromScreen()` results.
+    #[napi(ts_args_type = "callback: (color: PickedColor | null) => void")]
+    pub fn on_color_picked(&self, callback: JsFunction) -> Result<()> {
+        let tsfn: ThreadsafeFunction<Option<PickedColor>, ErrorStrategy::Fatal> = callback
+            .create_threadsafe_function(0, |ctx: ThreadSafeCallContext<Option<PickedColor>>| {
+                Ok(vec![ctx.value])
             })?;
 
         with_manager(|mgr| {
             if let Some(handlers) = mgr.event_handlers.get_mut(&self.id) {
-                handlers.on_reload = Some(tsfn);
+                handlers.on_color_picked = Some(tsfn);
             }
         });
         Ok(())
     }
 
-    /// Register a handler for blocked navigation events.
-    /// Fired when a navigation is blocked by the `allowedHosts` restriction.
-    #[napi(ts_args_type = "callback: (url: string) => void")]
-    pub fn on_navigation_blocked(&self, callback: JsFunction) -> Result<()> {
-        let tsfn: ThreadsafeFunction<String, ErrorStrategy::Fatal> = callback
-            .create_threadsafe_function(0, |ctx: ThreadSafeCallContext<String>| {
-                ctx.env.create_string(ctx.value.as_str()).map(|v| vec![v])
+    /// Register a handler for window thumbnail results.
+    #[napi(ts_args_type = "callback: (png: Buffer | null) => void")]
+    pub fn on_thumbnail(&self, callback: JsFunction) -> Result<()> {
+        let tsfn: ThreadsafeFunction<Option<Vec<u8>>, ErrorStrategy::Fatal> = callback
+            .create_threadsafe_function(0, |ctx: ThreadSafeCallContext<Option<Vec<u8>>>| {
+                let value = match ctx.value {
+                    Some(png) => ctx
+                        .env
+                        .create_buffer_with_data(png)?
+                        .into_raw()
+                        .into_unknown(),
+                    None => ctx.env.get_null()?.into_unknown(),
+                };
+                Ok(vec![value])
             })?;
 
         with_manager(|mgr| {
             if let Some(handlers) = mgr.event_handlers.get_mut(&self.id) {
-                handlers.on_navigation_blocked = Some(tsfn);
+                handlers.on_thumbnail = Some(tsfn);
             }
         });
         Ok(())
     }
 
-    // ---- Cookie access ----
+    /// Register a handler for window screenshot results.
+    #[napi(ts_args_type = "callback: (png: Buffer | null) => void")]
+    pub fn on_window_capture(&self, callback: JsFunction) -> Result<()> {
+        let tsfn: ThreadsafeFunction<Option<Vec<u8>>, ErrorStrategy::Fatal> = callback
+            .create_threadsafe_function(0, |ctx: ThreadSafeCallContext<Option<Vec<u8>>>| {
+                let value = match ctx.value {
+                    Some(png) => ctx
+                        .env
+                        .create_buffer_with_data(png)?
+                        .into_raw()
+                        .into_unknown(),
+                    None => ctx.env.get_null()?.into_unknown(),
+                };
+                Ok(vec![value])
+            })?;
 
-    /// Query cookies from the native cookie store.
-    /// Results are delivered asynchronously via the `onCookies` callback.
-    /// If `url` is provided, only cookies matching that URL are returned.
-    /// If omitted, all cookies are returned.
-    #[napi]
-    pub fn get_cookies(&self, url: Option<String>) -> Result<()> {
         with_manager(|mgr| {
-            mgr.push_command(Command::GetCookies { id: self.id, url });
+            if let Some(handlers) = mgr.event_handlers.get_mut(&self.id) {
+                handlers.on_window_capture = Some(tsfn);
+            }
         });
         Ok(())
     }
 
-    /// Register a handler for cookie query results.
-    /// The callback receives a JSON string containing an array of cookie objects.
-    #[napi(ts_args_type = "callback: (cookies: string) => void")]
-    pub fn on_cookies(&self, callback: JsFunction) -> Result<()> {
-        let tsfn: ThreadsafeFunction<String, ErrorStrategy::Fatal> = callback
-            .create_threadsafe_function(0, |ctx: ThreadSafeCallContext<String>| {
-                ctx.env.create_string(ctx.value.as_str()).map(|v| vec![v])
+    /// Register a handler for page snapshot results.
+    #[napi(ts_args_type = "callback: (png: Buffer | null) => void")]
+    pub fn on_page_capture(&self, callback: JsFunction) -> Result<()> {
+        let tsfn: ThreadsafeFunction<Option<Vec<u8>>, ErrorStrategy::Fatal> = callback
+            .create_threadsafe_function(0, |ctx: ThreadSafeCallContext<Option<Vec<u8>>>| {
+                let value = match ctx.value {
+                    Some(png) => ctx
+                        .env
+                        .create_buffer_with_data(png)?
+                        .into_raw()
+                        .into_unknown(),
+                    None => ctx.env.get_null()?.into_unknown(),
+                };
+                Ok(vec![value])
             })?;
 
         with_manager(|mgr| {
             if let Some(handlers) = mgr.event_handlers.get_mut(&self.id) {
-                handlers.on_cookies = Some(tsfn);
+                handlers.on_page_capture = Some(tsfn);
             }
         });
         Ok(())
     }
 }
 
+// ── Synchronous queries ─────────────────────────────────────────
+
+impl NativeWindow {
+    /// Run a synchronous query against the backend, which answers `None`
+    /// for windows it does not have.
+    ///
+    /// Windows are created during the next `pumpEvents()` after construction,
+    /// so synchronous queries fail until then (and after the window closes).
+    fn query<R>(&self, f: impl FnOnce(&dyn PlatformBackend) -> Option<R>) -> Result<R> {
+        with_manager(|mgr| {
+            mgr.platform.as_deref().and_then(f).ok_or_else(|| {
+                napi::Error::from_reason(format!(
+                    "Window {} is not available (not yet created or already closed)",
+                    self.id
+                ))
+            })
+        })
+    }
+
+    /// Reject `moveAbove()`/`moveBelow()` relative to the window itself.
+    fn check_stacking_target(&self, other_window_id: u32) -> Result<()> {
+        if other_window_id == self.id {
+            return Err(napi::Error::from_reason(
+                "A window cannot be ordered relative to itself",
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Materials accepted by the `vibrancy` option.
+const VIBRANCY_MATERIALS: &[&str] = &[
+    "titlebar",
+    "selection",
+    "menu",
+    "popover",
+    "sidebar",
+    "header",
+    "sheet",
+    "window",
+    "hud",
+    "fullscreen-ui",
+    "tooltip",
+    "content",
+    "under-window",
+    "under-page",
+];
+
+/// Reject anything but `"light"`, `"dark"` and `"system"`.
+fn validate_theme(theme: &str) -> Result<()> {
+    if !matches!(theme, "light" | "dark" | "system") {
+        return Err(napi::Error::from_reason(format!(
+            "Invalid theme '{}'. Expected 'light', 'dark', or 'system'.",
+            theme
+        )));
+    }
+    Ok(())
+}
+
+/// Normalize `allowedSchemes` (lowercase, no trailing colon) and reject
+/// invalid or reserved schemes.
+fn validate_allowed_schemes(schemes: &mut [String]) -> Result<()> {
+    for scheme in schemes.iter_mut() {
+        *scheme = scheme.trim_end_matches(':').to_lowercase();
+        if !crate::platform::protocol_client::is_valid_scheme(scheme) {
+            return Err(napi::Error::from_reason(format!(
+                "Invalid URL scheme '{}' in allowedSchemes.",
+                scheme
+            )));
+        }
+        if crate::window_manager::RESERVED_SCHEMES.contains(&scheme.as_str()) {
+            return Err(napi::Error::from_reason(format!(
+                "The {}: scheme cannot be listed in allowedSchemes.",
+                scheme
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Reject anything but the two supported fullscreen modes.
+fn validate_fullscreen_mode(mode: &str) -> Result<()> {
+    if mode != "native" && mode != "borderless" {
+        return Err(napi::Error::from_reason(format!(
+            "Invalid fullscreen mode '{}'. Expected 'native' or 'borderless'.",
+            mode
+        )));
+    }
+    Ok(())
+}
+
+/// Reject empty, overlong, or oddly-charactered time zone / locale IDs
+/// before they reach CDP or a page script.
+fn validate_emulation_value(kind: &str, value: &str, allowed: impl Fn(char) -> bool) -> Result<()> {
+    if value.is_empty() || value.len() > 64 || !value.chars().all(allowed) {
+        return Err(napi::Error::from_reason(format!(
+            "Invalid {} '{}'.",
+            kind, value
+        )));
+    }
+    Ok(())
+}
+
+/// Most URLs accepted by a single `preconnect()` / `prefetch()` call.
+const MAX_RESOURCE_HINTS: usize = 64;
+
+/// Check that resource hint targets are http(s) URLs and normalize them —
+/// to their origin when `origins_only` is set.
+fn validate_resource_hints(
+    method: &str,
+    urls: Vec<String>,
+    origins_only: bool,
+) -> Result<Vec<String>> {
+    if urls.len() > MAX_RESOURCE_HINTS {
+        return Err(napi::Error::from_reason(format!(
+            "{}() accepts at most {} URLs.",
+            method, MAX_RESOURCE_HINTS
+        )));
+    }
+    urls.iter()
+        .map(|raw| {
+            let parsed = url::Url::parse(raw)
+                .ok()
+                .filter(|u| u.scheme() == "http" || u.scheme() == "https")
+                .ok_or_else(|| {
+                    napi::Error::from_reason(format!(
+                        "Invalid {}() URL '{}'. Expected an http(s) URL.",
+                        method, raw
+                    ))
+                })?;
+            Ok(if origins_only {
+                parsed.origin().ascii_serialization()
+            } else {
+                parsed.to_string()
+            })
+        })
+        .collect()
+}
+
+/// Deepest submenu nesting accepted by `setWindowMenu()`.
+const MAX_MENU_DEPTH: usize = 8;
+
+/// Check item types and submenu nesting before queuing a menu.
+fn validate_menu_items(items: &[WindowMenuItem], depth: usize) -> Result<()> {
+    if depth >= MAX_MENU_DEPTH {
+        return Err(napi::Error::from_reason(format!(
+            "Window menu is nested more than {} levels deep.",
+            MAX_MENU_DEPTH
+        )));
+    }
+    for item in items {
+        match item.kind.as_deref() {
+            None | Some("normal") => {
+                if item.label.is_none() {
+                    return Err(napi::Error::from_reason(
+                        "Window menu items need a label (or type: 'separator').",
+                    ));
+                }
+            }
+            Some("separator") => {}
+            Some(other) => {
+                return Err(napi::Error::from_reason(format!(
+                    "Invalid menu item type '{}'. Expected 'normal' or 'separator'.",
+                    other
+                )));
+            }
+        }
+        if let Some(ref submenu) = item.submenu {
+            validate_menu_items(submenu, depth + 1)?;
+        }
+    }
+    Ok(())
+}
+
 // ── Drop ────────────────────────────────────────────────────────
 
 /// Enqueue a close command when a `NativeWindow` is garbage-collected
 /// without an explicit `close()` call, preventing event handler and
-/// security config leaks in the thread-local maps.
+/// security config leaks in the thread-local maps. Skipped for windows
+/// created with `persistAfterGc`, detached, or already disposed.
 impl Drop for NativeWindow {
     fn drop(&mut self) {
+        if !self.close_on_drop {
+            return;
+        }
         with_manager(|mgr| {
             mgr.push_command(Command::Close { id: self.id });
         });
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schemes(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn allowed_schemes_are_normalized() {
+        let mut list = schemes(&["File:", "zoommtg"]);
+        assert!(validate_allowed_schemes(&mut list).is_ok());
+        assert_eq!(list, schemes(&["file", "zoommtg"]));
+    }
+
+    #[test]
+    fn allowed_schemes_reject_reserved_schemes() {
+        for reserved in ["javascript", "http", "HTTPS:", "nativewindow", "about"] {
+            let mut list = schemes(&[reserved]);
+            assert!(validate_allowed_schemes(&mut list).is_err(), "{reserved}");
+        }
+    }
+
+    #[test]
+    fn allowed_schemes_reject_invalid_schemes() {
+        for invalid in ["", "1file", "fi le"] {
+            let mut list = schemes(&[invalid]);
+            assert!(validate_allowed_schemes(&mut list).is_err(), "{invalid:?}");
+        }
+    }
+}
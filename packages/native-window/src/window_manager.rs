@@ -1,10 +1,16 @@
 use std::cell::RefCell;
-use std::collections::{HashMap, HashSet};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::time::{SystemTime, UNIX_EPOCH};
 
+use napi_derive::napi;
 use tao::event_loop::EventLoop;
 
-use crate::events::WindowEventHandlers;
-use crate::options::WindowOptions;
+use crate::events::{AppEventHandlers, WindowEventHandlers};
+use crate::options::{
+    InputEvent, NetworkConditions, Rect, ShareOptions, WindowLayout, WindowMenuItem, WindowOptions,
+    WindowState,
+};
+use crate::runtime::ProfileFallback;
 
 // ── Permission flags ───────────────────────────────────────────
 
@@ -35,28 +41,601 @@ pub fn get_permissions(window_id: u32) -> PermissionFlags {
 
 /// Commands that can be sent to the window manager for execution during pump.
 pub enum Command {
-    CreateWindow { id: u32, options: WindowOptions },
-    LoadURL { id: u32, url: String },
-    LoadHTML { id: u32, html: String },
-    EvaluateJS { id: u32, script: String },
-    SetTitle { id: u32, title: String },
-    SetSize { id: u32, width: f64, height: f64 },
-    SetMinSize { id: u32, width: f64, height: f64 },
-    SetMaxSize { id: u32, width: f64, height: f64 },
-    SetPosition { id: u32, x: f64, y: f64 },
-    SetResizable { id: u32, resizable: bool },
-    SetDecorations { id: u32, decorations: bool },
-    SetAlwaysOnTop { id: u32, always_on_top: bool },
-    Show { id: u32 },
-    Hide { id: u32 },
-    Close { id: u32 },
-    Focus { id: u32 },
-    Maximize { id: u32 },
-    Minimize { id: u32 },
-    Unmaximize { id: u32 },
-    Reload { id: u32 },
-    GetCookies { id: u32, url: Option<String> },
-    SetIcon { id: u32, path: String },
+    CreateWindow {
+        id: u32,
+        options: WindowOptions,
+    },
+    LoadURL {
+        id: u32,
+        url: String,
+    },
+    LoadHTML {
+        id: u32,
+        html: String,
+        /// Document path under the custom protocol origin, e.g. `/settings`.
+        path: String,
+    },
+    EvaluateJS {
+        id: u32,
+        script: String,
+    },
+    EvaluateJSWithUserGesture {
+        id: u32,
+        script: String,
+    },
+    SetTitle {
+        id: u32,
+        title: String,
+    },
+    SetTitleTemplate {
+        id: u32,
+        template: Option<String>,
+    },
+    SetSize {
+        id: u32,
+        width: f64,
+        height: f64,
+    },
+    SetMinSize {
+        id: u32,
+        width: f64,
+        height: f64,
+    },
+    SetMaxSize {
+        id: u32,
+        width: f64,
+        height: f64,
+    },
+    /// `ratio` is width / height; `None` removes the constraint.
+    SetAspectRatio {
+        id: u32,
+        ratio: Option<f64>,
+    },
+    SetPosition {
+        id: u32,
+        x: f64,
+        y: f64,
+    },
+    SnapTo {
+        id: u32,
+        position: String,
+        display: Option<u32>,
+    },
+    /// Center in a display's work area; `None` is the current display.
+    Center {
+        id: u32,
+        display: Option<u32>,
+    },
+    MoveToDisplay {
+        id: u32,
+        display: u32,
+    },
+    SetResizable {
+        id: u32,
+        resizable: bool,
+    },
+    SetClosable {
+        id: u32,
+        closable: bool,
+    },
+    SetMinimizable {
+        id: u32,
+        minimizable: bool,
+    },
+    SetMaximizable {
+        id: u32,
+        maximizable: bool,
+    },
+    SetDecorations {
+        id: u32,
+        decorations: bool,
+    },
+    SetAlwaysOnTop {
+        id: u32,
+        always_on_top: bool,
+    },
+    SetAlwaysOnBottom {
+        id: u32,
+        always_on_bottom: bool,
+    },
+    /// Place the window directly above `other_id` in the z-order.
+    MoveAbove {
+        id: u32,
+        other_id: u32,
+    },
+    /// Place the window directly below `other_id` in the z-order.
+    MoveBelow {
+        id: u32,
+        other_id: u32,
+    },
+    SetSkipTaskbar {
+        id: u32,
+        skip: bool,
+    },
+    SetVisibleOnAllWorkspaces {
+        id: u32,
+        visible: bool,
+    },
+    /// `theme` is "light", "dark" or "system".
+    SetTheme {
+        id: u32,
+        theme: String,
+    },
+    /// `desktop_id` is a virtual desktop GUID (Windows).
+    MoveToVirtualDesktop {
+        id: u32,
+        desktop_id: String,
+    },
+    StartDragging {
+        id: u32,
+    },
+    /// Start polling the global cursor position for `onGlobalMouseMove()`.
+    TrackGlobalMouse {
+        id: u32,
+        throttle_ms: f64,
+    },
+    SetIgnoreCursorEvents {
+        id: u32,
+        ignore: bool,
+    },
+    SetFullscreen {
+        id: u32,
+        fullscreen: bool,
+        mode: Option<String>,
+    },
+    Show {
+        id: u32,
+    },
+    Hide {
+        id: u32,
+    },
+    Close {
+        id: u32,
+    },
+    Focus {
+        id: u32,
+    },
+    /// `critical` keeps flashing / bouncing until the window is focused.
+    RequestAttention {
+        id: u32,
+        critical: bool,
+    },
+    /// Move keyboard focus into the webview.
+    FocusWebview {
+        id: u32,
+    },
+    /// Move keyboard focus from the webview back to the window.
+    BlurWebview {
+        id: u32,
+    },
+    ShowEmojiPicker {
+        id: u32,
+    },
+    /// Focus the first (`last == false`) or last element of the page's tab order.
+    MoveFocusToWebview {
+        id: u32,
+        last: bool,
+    },
+    /// Show, un-minimize and focus a window hidden by `minimizeToTray`.
+    RestoreFromTray {
+        id: u32,
+    },
+    Maximize {
+        id: u32,
+    },
+    Minimize {
+        id: u32,
+    },
+    Unmaximize {
+        id: u32,
+    },
+    Reload {
+        id: u32,
+    },
+    GetCookies {
+        id: u32,
+        url: Option<String>,
+    },
+    SetIcon {
+        id: u32,
+        path: String,
+    },
+    /// Decoded by `setIconFromBuffer()`.
+    SetIconRgba {
+        id: u32,
+        rgba: Vec<u8>,
+        width: u32,
+        height: u32,
+    },
+    SetTaskbarBadge {
+        id: u32,
+        count: Option<u32>,
+    },
+    /// Windows taskbar overlay icon from a file; `None` clears it.
+    SetOverlayIcon {
+        id: u32,
+        path: Option<String>,
+        description: String,
+    },
+    /// macOS Dock badge text; `None` clears it.
+    SetBadgeLabel {
+        id: u32,
+        text: Option<String>,
+    },
+    /// Taskbar progress in percent; `None` clears it.
+    SetProgressBar {
+        id: u32,
+        progress: Option<u64>,
+        state: String,
+    },
+    /// Replace the window's menu bar; empty removes it.
+    SetWindowMenu {
+        id: u32,
+        items: Vec<WindowMenuItem>,
+    },
+    SetAutoHideMenuBar {
+        id: u32,
+        auto_hide: bool,
+    },
+    CountMatches {
+        id: u32,
+        text: String,
+    },
+    SendInputEvent {
+        id: u32,
+        event: InputEvent,
+    },
+    SetAccessibilityEnabled {
+        id: u32,
+        enabled: bool,
+    },
+    GetAccessibilitySnapshot {
+        id: u32,
+    },
+    GetPagePerformanceMetrics {
+        id: u32,
+    },
+    ExtractReadableContent {
+        id: u32,
+    },
+    /// Empty `categories` uses the engine's default set.
+    StartTracing {
+        id: u32,
+        categories: Vec<String>,
+    },
+    StopTracing {
+        id: u32,
+    },
+    TakeHeapSnapshot {
+        id: u32,
+    },
+    /// `format` is `"jpeg"` or `"png"`; frames beyond `fps` per second are dropped.
+    StartFrameCapture {
+        id: u32,
+        fps: f64,
+        format: String,
+    },
+    StopFrameCapture {
+        id: u32,
+    },
+    UnregisterServiceWorkers {
+        id: u32,
+    },
+    /// `None` removes the fallback document.
+    SetFallbackHtml {
+        id: u32,
+        html: Option<String>,
+    },
+    /// `background` lowers the webview's process priority; `false` restores it.
+    SetPriority {
+        id: u32,
+        background: bool,
+    },
+    /// `rel` is `"preconnect"` or `"prefetch"`; `urls` are validated http(s) URLs.
+    AddResourceHints {
+        id: u32,
+        rel: &'static str,
+        urls: Vec<String>,
+    },
+    /// IANA time zone ID; `None` restores the host zone.
+    SetEmulatedTimezone {
+        id: u32,
+        timezone: Option<String>,
+    },
+    /// BCP 47 locale; `None` restores the host locale.
+    SetEmulatedLocale {
+        id: u32,
+        locale: Option<String>,
+    },
+    /// `None` fields restore the engine defaults.
+    SetFontOverrides {
+        id: u32,
+        standard_family: Option<String>,
+        minimum_font_size: Option<u32>,
+    },
+    SetTextZoom {
+        id: u32,
+        percent: f64,
+    },
+    /// `None` removes throttling and offline mode.
+    SetNetworkConditions {
+        id: u32,
+        conditions: Option<NetworkConditions>,
+    },
+    StartAudioCapture {
+        id: u32,
+    },
+    StopAudioCapture {
+        id: u32,
+    },
+    StartNetworkCapture {
+        id: u32,
+    },
+    StopNetworkCapture {
+        id: u32,
+    },
+    CaptureWindow {
+        id: u32,
+    },
+    CapturePage {
+        id: u32,
+    },
+    /// Sizes are physical pixels.
+    GetThumbnail {
+        id: u32,
+        max_width: u32,
+        max_height: u32,
+    },
+    PickColor {
+        id: u32,
+    },
+    Share {
+        id: u32,
+        options: ShareOptions,
+    },
+    GetBounds {
+        id: u32,
+    },
+    GetWindowState {
+        id: u32,
+    },
+    /// Bounds, visibility and stacking of several windows, applied at once.
+    ApplyLayout {
+        layout: Vec<WindowLayout>,
+    },
+}
+
+impl Command {
+    /// The window a command targets; `0` (never a window ID) for commands
+    /// spanning several windows.
+    pub fn window_id(&self) -> u32 {
+        match self {
+            Command::CreateWindow { id, .. }
+            | Command::LoadURL { id, .. }
+            | Command::LoadHTML { id, .. }
+            | Command::EvaluateJS { id, .. }
+            | Command::EvaluateJSWithUserGesture { id, .. }
+            | Command::SetTitle { id, .. }
+            | Command::SetTitleTemplate { id, .. }
+            | Command::SetSize { id, .. }
+            | Command::SetMinSize { id, .. }
+            | Command::SetMaxSize { id, .. }
+            | Command::SetAspectRatio { id, .. }
+            | Command::SetPosition { id, .. }
+            | Command::SnapTo { id, .. }
+            | Command::Center { id, .. }
+            | Command::MoveToDisplay { id, .. }
+            | Command::SetResizable { id, .. }
+            | Command::SetClosable { id, .. }
+            | Command::SetMinimizable { id, .. }
+            | Command::SetMaximizable { id, .. }
+            | Command::SetDecorations { id, .. }
+            | Command::SetAlwaysOnTop { id, .. }
+            | Command::SetAlwaysOnBottom { id, .. }
+            | Command::MoveAbove { id, .. }
+            | Command::MoveBelow { id, .. }
+            | Command::SetSkipTaskbar { id, .. }
+            | Command::SetVisibleOnAllWorkspaces { id, .. }
+            | Command::SetTheme { id, .. }
+            | Command::MoveToVirtualDesktop { id, .. }
+            | Command::StartDragging { id }
+            | Command::TrackGlobalMouse { id, .. }
+            | Command::SetIgnoreCursorEvents { id, .. }
+            | Command::SetFullscreen { id, .. }
+            | Command::Show { id }
+            | Command::Hide { id }
+            | Command::Close { id }
+            | Command::Focus { id }
+            | Command::RequestAttention { id, .. }
+            | Command::FocusWebview { id }
+            | Command::ShowEmojiPicker { id }
+            | Command::BlurWebview { id }
+            | Command::MoveFocusToWebview { id, .. }
+            | Command::RestoreFromTray { id }
+            | Command::Maximize { id }
+            | Command::Minimize { id }
+            | Command::Unmaximize { id }
+            | Command::Reload { id }
+            | Command::GetCookies { id, .. }
+            | Command::SetIcon { id, .. }
+            | Command::SetIconRgba { id, .. }
+            | Command::SetTaskbarBadge { id, .. }
+            | Command::SetOverlayIcon { id, .. }
+            | Command::SetBadgeLabel { id, .. }
+            | Command::SetProgressBar { id, .. }
+            | Command::SetWindowMenu { id, .. }
+            | Command::SetAutoHideMenuBar { id, .. }
+            | Command::CountMatches { id, .. }
+            | Command::SendInputEvent { id, .. }
+            | Command::SetAccessibilityEnabled { id, .. }
+            | Command::GetAccessibilitySnapshot { id }
+            | Command::GetPagePerformanceMetrics { id }
+            | Command::ExtractReadableContent { id }
+            | Command::StartTracing { id, .. }
+            | Command::StopTracing { id }
+            | Command::TakeHeapSnapshot { id }
+            | Command::StartFrameCapture { id, .. }
+            | Command::StopFrameCapture { id }
+            | Command::UnregisterServiceWorkers { id }
+            | Command::SetFallbackHtml { id, .. }
+            | Command::AddResourceHints { id, .. }
+            | Command::SetPriority { id, .. }
+            | Command::SetEmulatedTimezone { id, .. }
+            | Command::SetFontOverrides { id, .. }
+            | Command::SetTextZoom { id, .. }
+            | Command::SetEmulatedLocale { id, .. }
+            | Command::SetNetworkConditions { id, .. }
+            | Command::StartAudioCapture { id }
+            | Command::StopAudioCapture { id }
+            | Command::StartNetworkCapture { id }
+            | Command::StopNetworkCapture { id }
+            | Command::CaptureWindow { id }
+            | Command::CapturePage { id }
+            | Command::GetThumbnail { id, .. }
+            | Command::PickColor { id }
+            | Command::Share { id, .. }
+            | Command::GetBounds { id }
+            | Command::GetWindowState { id } => *id,
+            Command::ApplyLayout { .. } => 0,
+        }
+    }
+
+    /// Name of the command for diagnostics, e.g. `"loadUrl"`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Command::CreateWindow { .. } => "createWindow",
+            Command::LoadURL { .. } => "loadUrl",
+            Command::LoadHTML { .. } => "loadHtml",
+            Command::EvaluateJS { .. } => "evaluateJs",
+            Command::EvaluateJSWithUserGesture { .. } => "evaluateJsWithUserGesture",
+            Command::SetTitle { .. } => "setTitle",
+            Command::SetTitleTemplate { .. } => "setTitleTemplate",
+            Command::SetSize { .. } => "setSize",
+            Command::SetMinSize { .. } => "setMinSize",
+            Command::SetMaxSize { .. } => "setMaxSize",
+            Command::SetAspectRatio { .. } => "setAspectRatio",
+            Command::SetPosition { .. } => "setPosition",
+            Command::SnapTo { .. } => "snapTo",
+            Command::Center { .. } => "center",
+            Command::MoveToDisplay { .. } => "moveToDisplay",
+            Command::SetResizable { .. } => "setResizable",
+            Command::SetClosable { .. } => "setClosable",
+            Command::SetMinimizable { .. } => "setMinimizable",
+            Command::SetMaximizable { .. } => "setMaximizable",
+            Command::SetDecorations { .. } => "setDecorations",
+            Command::SetAlwaysOnTop { .. } => "setAlwaysOnTop",
+            Command::SetAlwaysOnBottom { .. } => "setAlwaysOnBottom",
+            Command::MoveAbove { .. } => "moveAbove",
+            Command::MoveBelow { .. } => "moveBelow",
+            Command::SetSkipTaskbar { .. } => "setSkipTaskbar",
+            Command::SetVisibleOnAllWorkspaces { .. } => "setVisibleOnAllWorkspaces",
+            Command::SetTheme { .. } => "setTheme",
+            Command::MoveToVirtualDesktop { .. } => "moveToVirtualDesktop",
+            Command::StartDragging { .. } => "startDragging",
+            Command::TrackGlobalMouse { .. } => "onGlobalMouseMove",
+            Command::SetIgnoreCursorEvents { .. } => "setIgnoreCursorEvents",
+            Command::SetFullscreen { .. } => "setFullscreen",
+            Command::Show { .. } => "show",
+            Command::Hide { .. } => "hide",
+            Command::Close { .. } => "close",
+            Command::Focus { .. } => "focus",
+            Command::RequestAttention { .. } => "requestAttention",
+            Command::FocusWebview { .. } => "focusWebview",
+            Command::ShowEmojiPicker { .. } => "showEmojiPicker",
+            Command::BlurWebview { .. } => "blurWebview",
+            Command::MoveFocusToWebview { .. } => "moveFocusToWebview",
+            Command::RestoreFromTray { .. } => "restoreFromTray",
+            Command::Maximize { .. } => "maximize",
+            Command::Minimize { .. } => "minimize",
+            Command::Unmaximize { .. } => "unmaximize",
+            Command::Reload { .. } => "reload",
+            Command::GetCookies { .. } => "getCookies",
+            Command::SetIcon { .. } => "setIcon",
+            Command::SetIconRgba { .. } => "setIconFromBuffer",
+            Command::SetTaskbarBadge { .. } => "setTaskbarBadge",
+            Command::SetOverlayIcon { .. } => "setOverlayIcon",
+            Command::SetBadgeLabel { .. } => "setBadgeLabel",
+            Command::SetProgressBar { .. } => "setProgressBar",
+            Command::SetWindowMenu { .. } => "setWindowMenu",
+            Command::SetAutoHideMenuBar { .. } => "setAutoHideMenuBar",
+            Command::CountMatches { .. } => "countMatches",
+            Command::SendInputEvent { .. } => "sendInputEvent",
+            Command::SetAccessibilityEnabled { .. } => "setAccessibilityEnabled",
+            Command::GetAccessibilitySnapshot { .. } => "getAccessibilitySnapshot",
+            Command::GetPagePerformanceMetrics { .. } => "getPagePerformanceMetrics",
+            Command::ExtractReadableContent { .. } => "extractReadableContent",
+            Command::StartTracing { .. } => "startTracing",
+            Command::StopTracing { .. } => "stopTracing",
+            Command::TakeHeapSnapshot { .. } => "takeHeapSnapshot",
+            Command::StartFrameCapture { .. } => "startFrameCapture",
+            Command::StopFrameCapture { .. } => "stopFrameCapture",
+            Command::UnregisterServiceWorkers { .. } => "unregisterServiceWorkers",
+            Command::SetFallbackHtml { .. } => "setFallbackHtml",
+            Command::SetPriority { .. } => "setPriority",
+            Command::AddResourceHints { rel, .. } => rel,
+            Command::SetEmulatedTimezone { .. } => "setEmulatedTimezone",
+            Command::SetFontOverrides { .. } => "setFontOverrides",
+            Command::SetTextZoom { .. } => "setTextZoom",
+            Command::SetEmulatedLocale { .. } => "setEmulatedLocale",
+            Command::SetNetworkConditions { .. } => "setNetworkConditions",
+            Command::StartAudioCapture { .. } => "startAudioCapture",
+            Command::StopAudioCapture { .. } => "stopAudioCapture",
+            Command::StartNetworkCapture { .. } => "startNetworkCapture",
+            Command::StopNetworkCapture { .. } => "stopNetworkCapture",
+            Command::CaptureWindow { .. } => "captureWindow",
+            Command::CapturePage { .. } => "capturePage",
+            Command::GetThumbnail { .. } => "getThumbnail",
+            Command::PickColor { .. } => "pickColorFromScreen",
+            Command::Share { .. } => "share",
+            Command::GetBounds { .. } => "getBounds",
+            Command::GetWindowState { .. } => "getWindowState",
+            Command::ApplyLayout { .. } => "applyLayout",
+        }
+    }
+}
+
+// ── Command history ────────────────────────────────────────────
+
+/// A command processed for a window, as returned by `getCommandHistory()`.
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct CommandRecord {
+    /// Command name, e.g. `"loadUrl"`.
+    pub command: String,
+    /// When the command was processed (or dropped), in milliseconds since
+    /// the Unix epoch.
+    pub timestamp: f64,
+    /// `"ok"`, `"error"`, `"windowNotFound"` (the native window did not
+    /// exist) or `"dropped"` (the command queue was full).
+    #[napi(ts_type = "'ok' | 'error' | 'windowNotFound' | 'dropped'")]
+    pub outcome: String,
+    /// Error message when `outcome` is `"error"`.
+    pub error: Option<String>,
+}
+
+/// Append a command outcome to a window's history. Commands spanning
+/// several windows (ID `0`) are not recorded.
+pub fn record_command(window_id: u32, command: &str, outcome: &str, error: Option<String>) {
+    if window_id == 0 {
+        return;
+    }
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs_f64() * 1000.0)
+        .unwrap_or(0.0);
+    COMMAND_HISTORY.with(|h| {
+        let mut history = h.borrow_mut();
+        let entries = history.entry(window_id).or_default();
+        if entries.len() >= MAX_COMMAND_HISTORY {
+            entries.pop_front();
+        }
+        entries.push_back(CommandRecord {
+            command: command.to_string(),
+            timestamp,
+            outcome: outcome.to_string(),
+            error,
+        });
+    });
 }
 
 /// Global window manager state. Lives in thread_local storage.
@@ -64,13 +643,22 @@ pub struct WindowManager {
     pub next_id: u32,
     pub command_queue: Vec<Command>,
     pub event_handlers: HashMap<u32, WindowEventHandlers>,
+    pub app_handlers: AppEventHandlers,
     pub initialized: bool,
-    pub platform: Option<super::platform::Platform>,
+    /// Reject window options the platform would ignore (`init({ strictOptions })`).
+    pub strict_options: bool,
+    /// The windowing backend created by `init()`.
+    pub platform: Option<Box<dyn super::platform::PlatformBackend>>,
 }
 
-/// Maximum number of commands in the queue before logging a warning.
-/// Commands are still accepted to avoid silently dropping operations.
-const MAX_COMMAND_QUEUE: usize = 10_000;
+/// Maximum number of commands in the queue; further commands are dropped
+/// and logged. Commands held for a window still waiting for WebView2 are
+/// never dropped: a warning is logged once this many are held.
+pub const MAX_COMMAND_QUEUE: usize = 10_000;
+
+/// Commands kept per window for `getCommandHistory()`; older ones are
+/// discarded first.
+pub const MAX_COMMAND_HISTORY: usize = 200;
 
 /// Maximum entries in any single PENDING_* event buffer.
 /// Events are dropped when the buffer reaches this size.
@@ -82,7 +670,9 @@ impl WindowManager {
             next_id: 1,
             command_queue: Vec::new(),
             event_handlers: HashMap::new(),
+            app_handlers: AppEventHandlers::new(),
             initialized: false,
+            strict_options: false,
             platform: None,
         }
     }
@@ -110,6 +700,7 @@ impl WindowManager {
                 self.command_queue.len(),
                 MAX_COMMAND_QUEUE
             );
+            record_command(cmd.window_id(), cmd.name(), "dropped", None);
             return;
         }
         self.command_queue.push(cmd);
@@ -119,6 +710,16 @@ impl WindowManager {
         std::mem::take(&mut self.command_queue)
     }
 
+    /// Remove and return the queued commands for one window, keeping the
+    /// others queued in order.
+    pub fn drain_window_commands(&mut self, window_id: u32) -> Vec<Command> {
+        let (matching, rest) = std::mem::take(&mut self.command_queue)
+            .into_iter()
+            .partition(|cmd| cmd.window_id() == window_id);
+        self.command_queue = rest;
+        matching
+    }
+
     /// Remove event handlers and security config for a closed window to prevent memory leaks.
     #[allow(dead_code)]
     pub fn remove_event_handlers(&mut self, id: u32) {
@@ -129,12 +730,33 @@ impl WindowManager {
         ALLOWED_HOSTS_MAP.with(|h| {
             h.borrow_mut().remove(&id);
         });
+        ALLOWED_SCHEMES_MAP.with(|s| {
+            s.borrow_mut().remove(&id);
+        });
         PERMISSIONS_MAP.with(|p| {
             p.borrow_mut().remove(&id);
         });
+        SERVICE_WORKER_MAP.with(|m| {
+            m.borrow_mut().remove(&id);
+        });
         HTML_CONTENT_MAP.with(|m| {
             m.borrow_mut().remove(&id);
         });
+        FALLBACK_HTML_MAP.with(|m| {
+            m.borrow_mut().remove(&id);
+        });
+        CLOSE_INTERCEPTED.with(|c| {
+            c.borrow_mut().remove(&id);
+        });
+        MINIMIZE_TO_TRAY.with(|t| {
+            t.borrow_mut().remove(&id);
+        });
+        EVENT_MASKS.with(|m| {
+            m.borrow_mut().remove(&id);
+        });
+        COMMAND_HISTORY.with(|h| {
+            h.borrow_mut().remove(&id);
+        });
     }
 }
 
@@ -151,6 +773,10 @@ thread_local! {
     /// Stored outside MANAGER so navigation handlers can read them
     /// while MANAGER is mutably borrowed by pump_events.
     pub static ALLOWED_HOSTS_MAP: RefCell<HashMap<u32, Vec<String>>> = RefCell::new(HashMap::new());
+    /// Per-window `allowedSchemes`, lowercased without the trailing colon.
+    /// Stored outside MANAGER so navigation handlers can read them
+    /// while MANAGER is mutably borrowed by pump_events.
+    pub static ALLOWED_SCHEMES_MAP: RefCell<HashMap<u32, Vec<String>>> = RefCell::new(HashMap::new());
     /// Per-window permission flags for platform callbacks.
     /// Stored outside MANAGER so permission handlers can read them
     /// while MANAGER is mutably borrowed by pump_events.
@@ -158,6 +784,24 @@ thread_local! {
     /// Buffer for IPC messages deferred during pump_events.
     /// Each entry: (window_id, message, source_url).
     pub static PENDING_MESSAGES: RefCell<Vec<(u32, String, String)>> = RefCell::new(Vec::new());
+    /// Windows with an `onCloseRequested` handler. OS close requests for
+    /// these are reported instead of destroying the window. Stored outside
+    /// MANAGER so the event loop can read it while MANAGER is borrowed.
+    pub static CLOSE_INTERCEPTED: RefCell<HashSet<u32>> = RefCell::new(HashSet::new());
+    /// Windows created with `minimizeToTray`: minimizing or an OS close
+    /// request hides them instead. Stored outside MANAGER so the event loop
+    /// can read it while MANAGER is borrowed.
+    pub static MINIMIZE_TO_TRAY: RefCell<HashSet<u32>> = RefCell::new(HashSet::new());
+    /// Per-window ring buffer of processed commands, newest last.
+    pub static COMMAND_HISTORY: RefCell<HashMap<u32, VecDeque<CommandRecord>>> = RefCell::new(HashMap::new());
+    /// Per-window `setEventMask()` filters as `EVENT_*` bit sets. Windows
+    /// without an entry receive every event. Stored outside MANAGER so the
+    /// event loop can read it while MANAGER is borrowed.
+    pub static EVENT_MASKS: RefCell<HashMap<u32, u32>> = RefCell::new(HashMap::new());
+    /// Buffer for `restoreFromTray()` restores deferred during pump_events.
+    pub static PENDING_TRAY_RESTORES: RefCell<Vec<u32>> = RefCell::new(Vec::new());
+    /// Buffer for intercepted OS close requests deferred during pump_events.
+    pub static PENDING_CLOSE_REQUESTS: RefCell<Vec<u32>> = RefCell::new(Vec::new());
     /// Buffer for window close events deferred during pump_events.
     pub static PENDING_CLOSES: RefCell<Vec<u32>> = RefCell::new(Vec::new());
     /// Buffer for reload events triggered by keyboard shortcuts during pump_events.
@@ -168,24 +812,119 @@ thread_local! {
     /// Buffer for move callback events deferred during pump_events.
     /// Each entry: (window_id, x, y).
     pub static PENDING_MOVES: RefCell<Vec<(u32, f64, f64)>> = RefCell::new(Vec::new());
+    /// Buffer for finished resizes: (window_id, width, height), the final size.
+    pub static PENDING_RESIZE_ENDS: RefCell<Vec<(u32, f64, f64)>> = RefCell::new(Vec::new());
+    /// Buffer for finished moves: (window_id, x, y), the final position.
+    pub static PENDING_MOVE_ENDS: RefCell<Vec<(u32, f64, f64)>> = RefCell::new(Vec::new());
+    /// Buffer for scale factor changes deferred during pump_events: (window_id, scale).
+    pub static PENDING_SCALE_FACTOR_CHANGES: RefCell<Vec<(u32, f64)>> = RefCell::new(Vec::new());
     /// Buffer for focus events deferred during pump_events.
     pub static PENDING_FOCUSES: RefCell<Vec<u32>> = RefCell::new(Vec::new());
     /// Buffer for blur events deferred during pump_events.
     pub static PENDING_BLURS: RefCell<Vec<u32>> = RefCell::new(Vec::new());
+    /// Buffer for webview keyboard focus changes: (window_id, focused).
+    pub static PENDING_WEBVIEW_FOCUS: RefCell<Vec<(u32, bool)>> = RefCell::new(Vec::new());
+    /// Buffer for windows entering or leaving the active macOS Space:
+    /// (window_id, on_active_workspace).
+    pub static PENDING_WORKSPACE_CHANGES: RefCell<Vec<(u32, bool)>> = RefCell::new(Vec::new());
+    /// Buffer for window theme changes: (window_id, theme).
+    /// theme is "light" or "dark".
+    pub static PENDING_THEME_CHANGES: RefCell<Vec<(u32, &'static str)>> = RefCell::new(Vec::new());
+    /// Buffer for global cursor moves: (window_id, x, y) in logical screen
+    /// coordinates.
+    pub static PENDING_GLOBAL_MOUSE_MOVES: RefCell<Vec<(u32, f64, f64)>> = RefCell::new(Vec::new());
+    /// Buffer for Tab moving focus out of the webview: (window_id, direction).
+    /// direction is "next" or "previous".
+    pub static PENDING_FOCUS_EXITS: RefCell<Vec<(u32, &'static str)>> = RefCell::new(Vec::new());
     /// Buffer for page load events deferred during pump_events: (window_id, event_type, url).
     /// event_type is "started" or "finished".
     pub static PENDING_PAGE_LOADS: RefCell<Vec<(u32, String, String)>> = RefCell::new(Vec::new());
+    /// Buffer for ready-to-show events (first completed page load per window).
+    pub static PENDING_READY_TO_SHOW: RefCell<Vec<u32>> = RefCell::new(Vec::new());
     /// Buffer for navigation-blocked events deferred during pump_events: (window_id, url).
     pub static PENDING_NAVIGATION_BLOCKED: RefCell<Vec<(u32, String)>> = RefCell::new(Vec::new());
+    /// Buffer for iframe lifecycle events: (window_id, frame_id, url), where
+    /// `Some(url)` is a created frame and `None` a destroyed one.
+    pub static PENDING_FRAME_EVENTS: RefCell<Vec<(u32, u32, Option<String>)>> = RefCell::new(Vec::new());
+    /// Buffer for navigations blocked by `maxNavigationsPerMinute`: (window_id, url).
+    pub static PENDING_NAVIGATION_THROTTLED: RefCell<Vec<(u32, String)>> = RefCell::new(Vec::new());
+    /// Buffer for fallback pages shown after failed navigations:
+    /// (window_id, original_url, error_code).
+    pub static PENDING_FALLBACKS_SHOWN: RefCell<Vec<(u32, String, String)>> = RefCell::new(Vec::new());
     /// Buffer for document title change events deferred during pump_events: (window_id, title).
     pub static PENDING_TITLE_CHANGES: RefCell<Vec<(u32, String)>> = RefCell::new(Vec::new());
+    /// Buffer for window menu bar clicks deferred during pump_events: (window_id, item_id).
+    pub static PENDING_MENU_CLICKS: RefCell<Vec<(u32, String)>> = RefCell::new(Vec::new());
     /// Buffer for cookie query results deferred during pump_events: (window_id, json).
     pub static PENDING_COOKIES: RefCell<Vec<(u32, String)>> = RefCell::new(Vec::new());
-    /// Per-window stored HTML content for the custom protocol handler.
+    /// Buffer for text match count results deferred during pump_events:
+    /// (window_id, count), with `None` when counting failed.
+    pub static PENDING_MATCH_COUNTS: RefCell<Vec<(u32, Option<f64>)>> = RefCell::new(Vec::new());
+    /// Buffer for accessibility snapshot results deferred during pump_events: (window_id, json).
+    /// json is `"null"` when the snapshot could not be captured.
+    pub static PENDING_ACCESSIBILITY_SNAPSHOTS: RefCell<Vec<(u32, String)>> = RefCell::new(Vec::new());
+    /// Buffer for page performance metrics deferred during pump_events: (window_id, json).
+    /// json is `"null"` when the metrics could not be read.
+    pub static PENDING_PERFORMANCE_METRICS: RefCell<Vec<(u32, String)>> = RefCell::new(Vec::new());
+    /// Buffer for reader-mode extraction results deferred during pump_events: (window_id, json).
+    /// json is `"null"` when the page could not be read.
+    pub static PENDING_READABLE_CONTENTS: RefCell<Vec<(u32, String)>> = RefCell::new(Vec::new());
+    /// Buffer for completed traces deferred during pump_events: (window_id, json).
+    /// json is a JSON array of `Tracing.dataCollected` params, or `None` when
+    /// no trace was running or it could not be stopped.
+    pub static PENDING_TRACES: RefCell<Vec<(u32, Option<String>)>> = RefCell::new(Vec::new());
+    /// Buffer for heap snapshots deferred during pump_events: (window_id, json).
+    /// json is a JSON array of `HeapProfiler.addHeapSnapshotChunk` params, or
+    /// `None` when the snapshot failed.
+    pub static PENDING_HEAP_SNAPSHOTS: RefCell<Vec<(u32, Option<String>)>> = RefCell::new(Vec::new());
+    /// Buffer for screencast frames deferred during pump_events: (window_id, json).
+    /// json is the `Page.screencastFrame` params. Only the latest frame of a
+    /// window is kept.
+    pub static PENDING_FRAMES: RefCell<Vec<(u32, String)>> = RefCell::new(Vec::new());
+    /// Buffer for window screenshots deferred during pump_events: (window_id, png).
+    /// png is `None` when the capture failed.
+    pub static PENDING_WINDOW_CAPTURES: RefCell<Vec<(u32, Option<Vec<u8>>)>> = RefCell::new(Vec::new());
+    /// Buffer for page snapshots deferred during pump_events: (window_id, png).
+    /// png is `None` when the snapshot failed.
+    pub static PENDING_PAGE_CAPTURES: RefCell<Vec<(u32, Option<Vec<u8>>)>> = RefCell::new(Vec::new());
+    /// Buffer for window thumbnails deferred during pump_events: (window_id, png).
+    /// png is `None` when the capture failed.
+    pub static PENDING_THUMBNAILS: RefCell<Vec<(u32, Option<Vec<u8>>)>> = RefCell::new(Vec::new());
+    /// Buffer for `pickColorFromScreen()` results: (window_id, color).
+    /// color is `None` when the user cancelled.
+    pub static PENDING_COLOR_PICKS: RefCell<Vec<(u32, Option<crate::options::PickedColor>)>> =
+        RefCell::new(Vec::new());
+    /// Buffer for captured webview audio: (window_id, pcm, error).
+    /// pcm is `None` when the capture ended, with `error` set if it failed.
+    pub static PENDING_AUDIO_CAPTURES: RefCell<Vec<(u32, Option<Vec<u8>>, Option<String>)>> = RefCell::new(Vec::new());
+    /// Buffer for network capture results deferred during pump_events: (window_id, json).
+    /// json is `None` when no capture was running or it could not be read.
+    pub static PENDING_NETWORK_CAPTURES: RefCell<Vec<(u32, Option<String>)>> = RefCell::new(Vec::new());
+    /// Buffer for off-screen window rescues: (window_id, x, y) new logical position.
+    pub static PENDING_RESCUES: RefCell<Vec<(u32, f64, f64)>> = RefCell::new(Vec::new());
+    /// Buffer for window geometry query results: (window_id, bounds) with the
+    /// outer position and inner size in logical pixels.
+    pub static PENDING_BOUNDS: RefCell<Vec<(u32, Rect)>> = RefCell::new(Vec::new());
+    /// Buffer for window state query results: (window_id, state).
+    pub static PENDING_WINDOW_STATES: RefCell<Vec<(u32, WindowState)>> = RefCell::new(Vec::new());
+    /// Buffer for WebView2 temporary profile fallbacks (app-level, Windows only).
+    pub static PENDING_PROFILE_FALLBACKS: RefCell<Vec<ProfileFallback>> = RefCell::new(Vec::new());
+    /// Per-window last-known-good outer bounds (physical x, y, width, height):
+    /// the most recent bounds that were visible on some display. Used to put
+    /// a window back after its display disappears.
+    pub static LAST_GOOD_BOUNDS: RefCell<HashMap<u32, (i32, i32, i32, i32)>> = RefCell::new(HashMap::new());
+    /// Per-window stored HTML documents for the custom protocol handler,
+    /// keyed by path (`"/"` unless `loadHtml()` was given one).
     /// When loadHtml() is called, the HTML is stored here and the webview
     /// navigates to the custom protocol URL which reads from this map.
     /// macOS/Linux: `nativewindow://localhost/`, Windows: `https://nativewindow.localhost/`.
-    pub static HTML_CONTENT_MAP: RefCell<HashMap<u32, String>> = RefCell::new(HashMap::new());
+    pub static HTML_CONTENT_MAP: RefCell<HashMap<u32, HashMap<String, String>>> = RefCell::new(HashMap::new());
+    /// Per-window service worker scripts served by the custom protocol at
+    /// [`SERVICE_WORKER_PATH`], set via `setServiceWorker()`.
+    pub static SERVICE_WORKER_MAP: RefCell<HashMap<u32, String>> = RefCell::new(HashMap::new());
+    /// Per-window fallback documents from `setFallbackHtml()`, served in
+    /// place of a page that failed to load.
+    pub static FALLBACK_HTML_MAP: RefCell<HashMap<u32, String>> = RefCell::new(HashMap::new());
     /// Set of window IDs that have already been warned about missing trustedOrigins.
     static ORIGIN_WARNED: RefCell<HashSet<u32>> = RefCell::new(HashSet::new());
 }
@@ -200,25 +939,109 @@ where
 
 // ── HTML content storage for custom protocol ───────────────────
 
-/// Store HTML content for a window's custom protocol handler.
-pub fn set_html_content(window_id: u32, html: String) {
+/// Store an HTML document at `path` for a window's custom protocol handler.
+/// Other documents of the window are kept.
+pub fn set_html_content(window_id: u32, path: &str, html: String) {
     HTML_CONTENT_MAP.with(|m| {
-        m.borrow_mut().insert(window_id, html);
+        m.borrow_mut()
+            .entry(window_id)
+            .or_default()
+            .insert(html_document_path(path).to_string(), html);
     });
 }
 
-/// Retrieve stored HTML content for a window's custom protocol handler.
-pub fn get_html_content(window_id: u32) -> Option<String> {
-    HTML_CONTENT_MAP.with(|m| m.borrow().get(&window_id).cloned())
+/// Retrieve the HTML document stored at `path` for a window's custom
+/// protocol handler.
+pub fn get_html_content(window_id: u32, path: &str) -> Option<String> {
+    HTML_CONTENT_MAP.with(|m| {
+        m.borrow()
+            .get(&window_id)
+            .and_then(|docs| docs.get(html_document_path(path)))
+            .cloned()
+    })
+}
+
+/// Canonical form of a document path: `/settings/` and `/settings` are the
+/// same document.
+fn html_document_path(path: &str) -> &str {
+    match path.trim_end_matches('/') {
+        "" => "/",
+        trimmed => trimmed,
+    }
 }
 
-/// Remove stored HTML content for a window (called on close or loadUrl).
+/// Check a `loadHtml()` document path: absolute, URL-safe characters only,
+/// no `..` segments, and not the service worker path.
+pub fn validate_html_path(path: &str) -> Result<(), String> {
+    if !path.starts_with('/') {
+        return Err(format!("Document path must start with '/': {}", path));
+    }
+    if path.len() > 1024 {
+        return Err("Document path is too long (max 1024 characters)".to_string());
+    }
+    if let Some(c) = path
+        .chars()
+        .find(|c| !(c.is_ascii_alphanumeric() || matches!(c, '/' | '-' | '_' | '.' | '~')))
+    {
+        return Err(format!(
+            "Invalid character {:?} in document path: {}",
+            c, path
+        ));
+    }
+    if path.split('/').any(|segment| segment == "..") {
+        return Err(format!("Document path must not contain '..': {}", path));
+    }
+    if path == SERVICE_WORKER_PATH {
+        return Err(format!("{} is reserved for setServiceWorker()", path));
+    }
+    Ok(())
+}
+
+/// Remove all stored HTML documents for a window (called on close or loadUrl).
 pub fn remove_html_content(window_id: u32) {
     HTML_CONTENT_MAP.with(|m| {
         m.borrow_mut().remove(&window_id);
     });
 }
 
+/// Path under the custom protocol origin that serves a window's service
+/// worker script.
+pub const SERVICE_WORKER_PATH: &str = "/service-worker.js";
+
+/// Store (or with `None`, remove) the service worker script for a window.
+pub fn set_service_worker_script(window_id: u32, script: Option<String>) {
+    SERVICE_WORKER_MAP.with(|m| match script {
+        Some(script) => {
+            m.borrow_mut().insert(window_id, script);
+        }
+        None => {
+            m.borrow_mut().remove(&window_id);
+        }
+    });
+}
+
+/// Retrieve the stored service worker script for a window.
+pub fn get_service_worker_script(window_id: u32) -> Option<String> {
+    SERVICE_WORKER_MAP.with(|m| m.borrow().get(&window_id).cloned())
+}
+
+/// Store (or with `None`, remove) the fallback document for a window.
+pub fn set_fallback_html(window_id: u32, html: Option<String>) {
+    FALLBACK_HTML_MAP.with(|m| match html {
+        Some(html) => {
+            m.borrow_mut().insert(window_id, html);
+        }
+        None => {
+            m.borrow_mut().remove(&window_id);
+        }
+    });
+}
+
+/// Retrieve the fallback document for a window.
+pub fn get_fallback_html(window_id: u32) -> Option<String> {
+    FALLBACK_HTML_MAP.with(|m| m.borrow().get(&window_id).cloned())
+}
+
 /// The custom protocol scheme used by `loadHtml()` content.
 const CUSTOM_SCHEME: &str = "nativewindow";
 
@@ -290,6 +1113,31 @@ pub fn is_origin_trusted(window_id: u32, source_url: &str) -> bool {
     })
 }
 
+// ── Event mask ─────────────────────────────────────────────────
+
+pub const EVENT_RESIZE: u32 = 1 << 0;
+pub const EVENT_MOVE: u32 = 1 << 1;
+pub const EVENT_FOCUS: u32 = 1 << 2;
+pub const EVENT_BLUR: u32 = 1 << 3;
+pub const EVENT_MESSAGE: u32 = 1 << 4;
+pub const EVENT_TITLE_CHANGED: u32 = 1 << 5;
+
+/// Events `setEventMask()` can filter, by their JS name. Lifecycle events
+/// (close, page load, ready-to-show) are always delivered.
+pub const MASKABLE_EVENTS: [(&str, u32); 6] = [
+    ("resize", EVENT_RESIZE),
+    ("move", EVENT_MOVE),
+    ("focus", EVENT_FOCUS),
+    ("blur", EVENT_BLUR),
+    ("message", EVENT_MESSAGE),
+    ("titleChanged", EVENT_TITLE_CHANGED),
+];
+
+/// Whether `event` (an `EVENT_*` bit) should be buffered for a window.
+pub fn is_event_enabled(window_id: u32, event: u32) -> bool {
+    EVENT_MASKS.with(|m| !matches!(m.borrow().get(&window_id), Some(mask) if mask & event == 0))
+}
+
 // ── Navigation host restriction ────────────────────────────────
 
 /// Extract the host (without port) from a URL string using the `url` crate.
@@ -325,35 +1173,65 @@ pub fn is_host_allowed(window_id: u32, url: &str) -> bool {
     ALLOWED_HOSTS_MAP.with(|h| {
         let map = h.borrow();
         if let Some(hosts) = map.get(&window_id) {
-            if hosts.is_empty() {
-                return true;
-            }
-            match extract_host(url) {
-                Some(host) => {
-                    let host_lower = host.to_lowercase();
-                    hosts.iter().any(|pattern| {
-                        let p = pattern.to_lowercase();
-                        if let Some(suffix) = p.strip_prefix('*') {
-                            // "*.example.com" → suffix = ".example.com"
-                            // Match: host ends with ".example.com"
-                            //    OR: host equals "example.com" (strip leading dot)
-                            host_lower.ends_with(suffix)
-                                || suffix
-                                    .strip_prefix('.')
-                                    .map_or(false, |bare| host_lower == bare)
-                        } else {
-                            host_lower == p
-                        }
-                    })
-                }
-                None => false, // No host extractable = blocked
-            }
+            hosts.is_empty() || host_matches(hosts, url)
         } else {
             true // No allowed_hosts configured = allow all
         }
     })
 }
 
+/// Check if a URL's host matches one of `patterns`, with the wildcard
+/// rules of [`is_host_allowed`]. URLs without a host never match.
+pub fn host_matches(patterns: &[String], url: &str) -> bool {
+    match extract_host(url) {
+        Some(host) => {
+            let host_lower = host.to_lowercase();
+            patterns.iter().any(|pattern| {
+                let p = pattern.to_lowercase();
+                if let Some(suffix) = p.strip_prefix('*') {
+                    // "*.example.com" → suffix = ".example.com"
+                    // Match: host ends with ".example.com"
+                    //    OR: host equals "example.com" (strip leading dot)
+                    host_lower.ends_with(suffix)
+                        || suffix
+                            .strip_prefix('.')
+                            .map_or(false, |bare| host_lower == bare)
+                } else {
+                    host_lower == p
+                }
+            })
+        }
+        None => false, // No host extractable = blocked
+    }
+}
+
+/// Schemes `allowedSchemes` may not list. `javascript:` is never allowed,
+/// and the others are already permitted and governed by `allowedHosts`,
+/// which must not be switched off by listing them.
+pub const RESERVED_SCHEMES: &[&str] = &["javascript", "http", "https", "nativewindow", "about"];
+
+/// Whether a URL has no host (`file:///…`, `mailto:`). Only such URLs of an
+/// `allowedSchemes` scheme skip `allowedHosts`; any URL with a host is
+/// still matched against it.
+pub fn is_hostless(url: &str) -> bool {
+    extract_host(url).is_none()
+}
+
+/// Check if a URL's scheme is in the window's `allowedSchemes` list.
+/// Allowed schemes are exempt from the `javascript:`/`file:`/`data:`/`blob:`
+/// deny-list only; see [`is_hostless`] for `allowedHosts`.
+pub fn is_scheme_allowed(window_id: u32, url: &str) -> bool {
+    let Some((scheme, _)) = url.trim().split_once(':') else {
+        return false;
+    };
+    let scheme = scheme.to_lowercase();
+    ALLOWED_SCHEMES_MAP.with(|s| {
+        s.borrow()
+            .get(&window_id)
+            .is_some_and(|schemes| schemes.contains(&scheme))
+    })
+}
+
 // ── JSON helpers ────────────────────────────────────────────────
 
 /// Escape a string for safe embedding as a JSON string value in JavaScript.
@@ -385,3 +1263,40 @@ pub fn json_escape(s: &str) -> String {
     out.push('"');
     out
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn allow_schemes(window_id: u32, schemes: &[&str]) {
+        ALLOWED_SCHEMES_MAP.with(|s| {
+            s.borrow_mut()
+                .insert(window_id, schemes.iter().map(|s| s.to_string()).collect());
+        });
+    }
+
+    #[test]
+    fn scheme_allowed_matches_listed_schemes_case_insensitively() {
+        allow_schemes(1, &["file", "zoommtg"]);
+        assert!(is_scheme_allowed(1, "file:///tmp/report.html"));
+        assert!(is_scheme_allowed(1, "  ZoomMtg://zoom.us/join?confno=1"));
+        assert!(!is_scheme_allowed(1, "data:text/html,hi"));
+        assert!(!is_scheme_allowed(1, "https://example.com"));
+        assert!(!is_scheme_allowed(1, "no-scheme"));
+    }
+
+    #[test]
+    fn scheme_allowed_is_per_window() {
+        allow_schemes(2, &["file"]);
+        assert!(is_scheme_allowed(2, "file:///tmp"));
+        assert!(!is_scheme_allowed(3, "file:///tmp"));
+    }
+
+    #[test]
+    fn hostless_only_for_urls_without_a_host() {
+        assert!(is_hostless("file:///tmp/report.html"));
+        assert!(is_hostless("mailto:me@example.com"));
+        assert!(!is_hostless("zoommtg://zoom.us/join"));
+        assert!(!is_hostless("https://example.com"));
+    }
+}